@@ -64,6 +64,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         skip_sudo: true,
         command_timeout: 10,
         verbose: false,
+        ..Default::default()
     };
     println!(
         "   Created config with timeout: {} seconds",
@@ -25,7 +25,8 @@ limitations under the License.
 
 use hardware_report::{
     create_service, create_service_with_config, new_domain::HardwareReport, validate_system,
-    ContainerConfigBuilder, FileRepository, FileSystemRepository, ReportConfig, ServerInfo,
+    ContainerConfigBuilder, FileRepository, FileSystemRepository, ProgressCallback, ReportConfig,
+    ServerInfo, SubsystemProgress,
 };
 use std::error::Error;
 use std::path::Path;
@@ -89,6 +90,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
         skip_sudo: !has_privileges,
         command_timeout: 30,
         verbose: false,
+        progress: Some(ProgressCallback::new(|event| {
+            if let SubsystemProgress::Started(subsystem) = event {
+                println!("   collecting {subsystem}...");
+            }
+        })),
+        ..Default::default()
     };
 
     let start_time = Instant::now();
@@ -162,6 +169,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         skip_sudo: true,
         command_timeout: 15,
         verbose: true,
+        ..Default::default()
     };
 
     let _custom_service =
@@ -14,6 +14,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod recording;
 pub mod unix;
 
+pub use recording::*;
 pub use unix::*;
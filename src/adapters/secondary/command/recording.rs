@@ -0,0 +1,93 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Command executor decorator that records raw command output
+
+use crate::domain::CommandError;
+use crate::ports::{CommandExecutor, CommandOutput, SystemCommand};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Command executor that records the stdout of every successful command
+///
+/// Wraps another executor and keeps a map of command line to stdout so the
+/// raw tool output can be attached to a report for debugging parsers.
+pub struct RecordingCommandExecutor {
+    inner: Arc<dyn CommandExecutor>,
+    sources: Mutex<HashMap<String, String>>,
+}
+
+impl RecordingCommandExecutor {
+    /// Create a new recording executor around an existing executor
+    pub fn new(inner: Arc<dyn CommandExecutor>) -> Self {
+        Self {
+            inner,
+            sources: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Snapshot of the recorded command outputs, keyed by command line
+    pub fn raw_sources(&self) -> HashMap<String, String> {
+        self.sources
+            .lock()
+            .map(|sources| sources.clone())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, command: &SystemCommand, output: &CommandOutput) {
+        if !output.success {
+            return;
+        }
+        let key = std::iter::once(command.program.as_str())
+            .chain(command.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Ok(mut sources) = self.sources.lock() {
+            sources.insert(key, output.stdout.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for RecordingCommandExecutor {
+    async fn execute(&self, command: &SystemCommand) -> Result<CommandOutput, CommandError> {
+        let output = self.inner.execute(command).await?;
+        self.record(command, &output);
+        Ok(output)
+    }
+
+    async fn execute_with_privileges(
+        &self,
+        command: &SystemCommand,
+    ) -> Result<CommandOutput, CommandError> {
+        let output = self.inner.execute_with_privileges(command).await?;
+        self.record(command, &output);
+        Ok(output)
+    }
+
+    async fn is_command_available(&self, command_name: &str) -> Result<bool, CommandError> {
+        self.inner.is_command_available(command_name).await
+    }
+
+    async fn get_command_path(&self, command_name: &str) -> Result<Option<String>, CommandError> {
+        self.inner.get_command_path(command_name).await
+    }
+
+    async fn has_elevated_privileges(&self) -> Result<bool, CommandError> {
+        self.inner.has_elevated_privileges().await
+    }
+}
@@ -16,6 +16,8 @@ limitations under the License.
 
 //! File-based data publisher for saving reports to local files
 
+use super::markdown::to_markdown;
+use super::openmetrics::to_openmetrics;
 use crate::domain::{HardwareReport, PublishError};
 use crate::ports::FileRepository;
 use async_trait::async_trait;
@@ -78,14 +80,62 @@ impl FileRepository for FileSystemRepository {
         Ok(())
     }
 
+    async fn save_openmetrics(
+        &self,
+        report: &HardwareReport,
+        path: &Path,
+    ) -> Result<(), PublishError> {
+        let metrics = to_openmetrics(report);
+
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                PublishError::NetworkFailed(format!("Failed to create directory: {e}"))
+            })?;
+        }
+
+        fs::write(path, metrics).await.map_err(|e| {
+            PublishError::NetworkFailed(format!("Failed to write OpenMetrics file: {e}"))
+        })?;
+
+        Ok(())
+    }
+
+    async fn save_markdown(
+        &self,
+        report: &HardwareReport,
+        path: &Path,
+    ) -> Result<(), PublishError> {
+        let markdown = to_markdown(report);
+
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                PublishError::NetworkFailed(format!("Failed to create directory: {e}"))
+            })?;
+        }
+
+        fs::write(path, markdown).await.map_err(|e| {
+            PublishError::NetworkFailed(format!("Failed to write Markdown file: {e}"))
+        })?;
+
+        Ok(())
+    }
+
     async fn load_json(&self, path: &Path) -> Result<HardwareReport, PublishError> {
         let json_string = fs::read_to_string(path)
             .await
             .map_err(|e| PublishError::NetworkFailed(format!("Failed to read JSON file: {e}")))?;
 
-        serde_json::from_str(&json_string).map_err(|e| {
+        let report: HardwareReport = serde_json::from_str(&json_string).map_err(|e| {
             PublishError::SerializationFailed(format!("JSON deserialization failed: {e}"))
-        })
+        })?;
+
+        if let Some(warning) = report.compatibility_warning() {
+            log::warn!("{}: {warning}", path.display());
+        }
+
+        Ok(report)
     }
 
     async fn load_toml(&self, path: &Path) -> Result<HardwareReport, PublishError> {
@@ -93,9 +143,15 @@ impl FileRepository for FileSystemRepository {
             .await
             .map_err(|e| PublishError::NetworkFailed(format!("Failed to read TOML file: {e}")))?;
 
-        toml::from_str(&toml_string).map_err(|e| {
+        let report: HardwareReport = toml::from_str(&toml_string).map_err(|e| {
             PublishError::SerializationFailed(format!("TOML deserialization failed: {e}"))
-        })
+        })?;
+
+        if let Some(warning) = report.compatibility_warning() {
+            log::warn!("{}: {warning}", path.display());
+        }
+
+        Ok(report)
     }
 
     async fn file_exists(&self, path: &Path) -> Result<bool, PublishError> {
@@ -162,6 +218,7 @@ mod tests {
 
     fn create_test_report() -> HardwareReport {
         HardwareReport {
+            report_version: crate::domain::REPORT_VERSION,
             summary: SystemSummary {
                 system_info: SystemInfo {
                     uuid: "test-uuid".to_string(),
@@ -171,6 +228,7 @@ mod tests {
                 },
                 total_memory: "16GB".to_string(),
                 memory_config: "DDR4 @ 3200MHz".to_string(),
+                memory_running_at_rated_speed: true,
                 total_storage: "1TB".to_string(),
                 total_storage_tb: 1.0,
                 filesystems: vec![],
@@ -184,6 +242,7 @@ mod tests {
                     manufacturer: "Test Corp".to_string(),
                     type_: "Desktop".to_string(),
                     serial: "test-chassis".to_string(),
+                    airflow: None,
                 },
                 motherboard: crate::domain::MotherboardInfo {
                     manufacturer: "Test Corp".to_string(),
@@ -194,8 +253,14 @@ mod tests {
                     location: "System".to_string(),
                     type_: "Motherboard".to_string(),
                 },
+                dmi_data_complete: true,
                 total_gpus: 1,
+                total_mig_instances: 0,
                 total_nics: 1,
+                pcie_lanes_in_use: 0,
+                pcie_lanes_total: None,
+                tpm: Default::default(),
+                numa_balancing: None,
                 numa_topology: HashMap::new(),
                 cpu_topology: crate::domain::CpuTopology {
                     total_cores: 8,
@@ -205,15 +270,21 @@ mod tests {
                     threads_per_core: 2,
                     numa_nodes: 1,
                     cpu_model: "Test CPU".to_string(),
+                    ..Default::default()
                 },
+                cpu_counts: crate::domain::CpuCounts::new(1, 8, 2),
                 cpu_summary: "Test CPU (1 Socket, 8 Cores/Socket, 2 Threads/Core, 1 NUMA Node)"
                     .to_string(),
             },
             hostname: "test-host".to_string(),
+            detected_hostname: None,
+            machine_id: "test-host".to_string(),
+            machine_id_source: "hostname".to_string(),
             fqdn: "test-host.example.com".to_string(),
             os_ip: vec![],
             bmc_ip: None,
             bmc_mac: None,
+            bmc_ip_source: None,
             hardware: HardwareInfo {
                 cpu: crate::domain::CpuInfo {
                     model: "Test CPU".to_string(),
@@ -228,14 +299,47 @@ mod tests {
                     type_: "DDR4".to_string(),
                     speed: "3200 MHz".to_string(),
                     modules: vec![],
+                    transparent_hugepages: None,
+                    hugepage_pools: Vec::new(),
+                    nvdimm_namespaces: Vec::new(),
+                    ecc_enabled: None,
+                },
+                storage: crate::domain::StorageInfo {
+                    devices: vec![],
+                    enclosures: vec![],
+                },
+                gpus: crate::domain::GpuInfo {
+                    devices: vec![],
+                    gpus_homogeneous: true,
+                    recent_xid_errors: None,
+                    nvlink: Vec::new(),
                 },
-                storage: crate::domain::StorageInfo { devices: vec![] },
-                gpus: crate::domain::GpuInfo { devices: vec![] },
+                accelerators: Vec::new(),
+                unbound_pci_devices: Vec::new(),
             },
             network: NetworkInfo {
                 interfaces: vec![],
                 infiniband: None,
+                fc_hbas: Vec::new(),
             },
+            gpu_nic_affinity: None,
+            tool_versions: HashMap::new(),
+            bios_settings: HashMap::new(),
+            firmware_components: Vec::new(),
+            boot_mode: None,
+            operational: None,
+            kernel_hardware_errors: None,
+            sel_recent_events: None,
+            configuration_deviations: Vec::new(),
+            time_sync: None,
+            watchdog: None,
+            power: None,
+            sensors: Vec::new(),
+            container_support: None,
+            container_limits: None,
+            gpu_cgroup_allocations: None,
+            cloud: None,
+            raw_sources: None,
         }
     }
 
@@ -333,6 +437,42 @@ mod tests {
         assert_eq!(toml_report.hostname, report.hostname);
     }
 
+    #[tokio::test]
+    async fn test_report_version_present() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_report.json");
+
+        let repository = FileSystemRepository::new();
+        repository
+            .save_json(&create_test_report(), &file_path)
+            .await
+            .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file_path).unwrap()).unwrap();
+        assert_eq!(
+            json["report_version"],
+            serde_json::json!(crate::domain::REPORT_VERSION)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_future_report_version() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("future_report.json");
+
+        let repository = FileSystemRepository::new();
+        let mut report = create_test_report();
+        report.report_version = crate::domain::REPORT_VERSION + 1;
+        repository.save_json(&report, &file_path).await.unwrap();
+
+        // A newer schema is loaded with a warning rather than rejected
+        let loaded_report = repository.load_json(&file_path).await.unwrap();
+        assert_eq!(loaded_report.hostname, report.hostname);
+        assert!(loaded_report.compatibility_warning().is_some());
+        assert!(create_test_report().compatibility_warning().is_none());
+    }
+
     #[tokio::test]
     async fn test_create_directory() {
         let temp_dir = tempdir().unwrap();
@@ -351,4 +491,108 @@ mod tests {
         // Verify file was created
         assert!(nested_path.exists());
     }
+
+    #[tokio::test]
+    async fn test_save_markdown() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("report.md");
+        let repository = FileSystemRepository::new();
+
+        let mut report = create_test_report();
+        report.hardware.gpus.devices = (0..2)
+            .map(|index| crate::domain::GpuDevice {
+                index,
+                name: "NVIDIA H100 80GB HBM3".to_string(),
+                memory: "80.0 GB".to_string(),
+                pci_bus_id: Some(format!("0000:{:02x}:00.0", 0x18 + index)),
+                numa_node: Some(0),
+                ..Default::default()
+            })
+            .collect();
+
+        repository.save_markdown(&report, &file_path).await.unwrap();
+        let markdown = std::fs::read_to_string(&file_path).unwrap();
+
+        assert!(markdown.starts_with("# test-host\n"));
+        let gpu_section = markdown
+            .split("## GPUs\n\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\n\n").next())
+            .unwrap();
+        let lines: Vec<&str> = gpu_section.lines().collect();
+        assert_eq!(lines[0], "| Index | Model | Memory | PCI Bus | NUMA Node |");
+        assert_eq!(lines.len(), 2 + report.hardware.gpus.devices.len());
+        assert_eq!(
+            lines[2],
+            "| 0 | NVIDIA H100 80GB HBM3 | 80.0 GB | 0000:18:00.0 | 0 |"
+        );
+        assert!(markdown.contains("## NUMA\n\nNone detected.\n"));
+    }
+
+    #[tokio::test]
+    async fn test_save_openmetrics() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("report.om");
+        let repository = FileSystemRepository::new();
+
+        let mut report = create_test_report();
+        report.hardware.memory.total = "2.0 TiB".to_string();
+        repository
+            .save_openmetrics(&report, &file_path)
+            .await
+            .unwrap();
+        let metrics = std::fs::read_to_string(&file_path).unwrap();
+
+        assert!(metrics.ends_with("# EOF\n"));
+        assert!(metrics.contains("# UNIT hardware_report_total_memory_bytes bytes\n"));
+        // Memory is converted with the same parser as storage sizes
+        assert!(metrics.contains(
+            "hardware_report_total_memory_bytes{hostname=\"test-host\",serial=\"test-serial\"} 2199023255552\n"
+        ));
+
+        // Check the exposition against the OpenMetrics text grammar
+        let is_valid_name = |name: &str| {
+            name.chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+        };
+        let mut families: HashMap<String, String> = HashMap::new();
+        let lines: Vec<&str> = metrics.lines().collect();
+        for line in &lines[..lines.len() - 1] {
+            if let Some(descriptor) = line.strip_prefix("# ") {
+                let parts: Vec<&str> = descriptor.splitn(3, ' ').collect();
+                assert_eq!(parts.len(), 3, "malformed descriptor: {line}");
+                assert!(is_valid_name(parts[1]), "invalid name: {line}");
+                match parts[0] {
+                    "TYPE" => {
+                        families.insert(parts[1].to_string(), parts[2].to_string());
+                    }
+                    "UNIT" => {
+                        assert!(families.contains_key(parts[1]));
+                        assert!(parts[1].ends_with(&format!("_{}", parts[2])));
+                    }
+                    "HELP" => assert!(families.contains_key(parts[1])),
+                    other => panic!("unexpected descriptor {other}"),
+                }
+            } else {
+                let name = line.split(['{', ' ']).next().unwrap();
+                assert!(is_valid_name(name), "invalid sample name: {line}");
+                let family = families
+                    .iter()
+                    .find(|(family, type_)| match type_.as_str() {
+                        "info" => name == format!("{family}_info"),
+                        "counter" => name == format!("{family}_total"),
+                        _ => name == family.as_str(),
+                    })
+                    .map(|(family, _)| family.clone());
+                assert!(family.is_some(), "sample without family: {line}");
+                let value = line.rsplit(' ').next().unwrap();
+                assert!(value.parse::<f64>().is_ok(), "invalid value: {line}");
+            }
+        }
+        assert_eq!(lines.last(), Some(&"# EOF"));
+    }
 }
@@ -160,6 +160,7 @@ mod tests {
 
     fn create_test_report() -> HardwareReport {
         HardwareReport {
+            report_version: crate::domain::REPORT_VERSION,
             summary: SystemSummary {
                 system_info: SystemInfo {
                     uuid: "test-uuid".to_string(),
@@ -169,6 +170,7 @@ mod tests {
                 },
                 total_memory: "16GB".to_string(),
                 memory_config: "DDR4 @ 3200MHz".to_string(),
+                memory_running_at_rated_speed: true,
                 total_storage: "1TB".to_string(),
                 total_storage_tb: 1.0,
                 filesystems: vec![],
@@ -182,6 +184,7 @@ mod tests {
                     manufacturer: "Test Corp".to_string(),
                     type_: "Desktop".to_string(),
                     serial: "test-chassis".to_string(),
+                    airflow: None,
                 },
                 motherboard: crate::domain::MotherboardInfo {
                     manufacturer: "Test Corp".to_string(),
@@ -192,8 +195,14 @@ mod tests {
                     location: "System".to_string(),
                     type_: "Motherboard".to_string(),
                 },
+                dmi_data_complete: true,
                 total_gpus: 1,
+                total_mig_instances: 0,
                 total_nics: 1,
+                pcie_lanes_in_use: 0,
+                pcie_lanes_total: None,
+                tpm: Default::default(),
+                numa_balancing: None,
                 numa_topology: HashMap::new(),
                 cpu_topology: crate::domain::CpuTopology {
                     total_cores: 8,
@@ -203,15 +212,21 @@ mod tests {
                     threads_per_core: 2,
                     numa_nodes: 1,
                     cpu_model: "Test CPU".to_string(),
+                    ..Default::default()
                 },
+                cpu_counts: crate::domain::CpuCounts::new(1, 8, 2),
                 cpu_summary: "Test CPU (1 Socket, 8 Cores/Socket, 2 Threads/Core, 1 NUMA Node)"
                     .to_string(),
             },
             hostname: "test-host".to_string(),
+            detected_hostname: None,
+            machine_id: "test-host".to_string(),
+            machine_id_source: "hostname".to_string(),
             fqdn: "test-host.example.com".to_string(),
             os_ip: vec![],
             bmc_ip: None,
             bmc_mac: None,
+            bmc_ip_source: None,
             hardware: HardwareInfo {
                 cpu: crate::domain::CpuInfo {
                     model: "Test CPU".to_string(),
@@ -226,14 +241,47 @@ mod tests {
                     type_: "DDR4".to_string(),
                     speed: "3200 MHz".to_string(),
                     modules: vec![],
+                    transparent_hugepages: None,
+                    hugepage_pools: Vec::new(),
+                    nvdimm_namespaces: Vec::new(),
+                    ecc_enabled: None,
+                },
+                storage: crate::domain::StorageInfo {
+                    devices: vec![],
+                    enclosures: vec![],
+                },
+                gpus: crate::domain::GpuInfo {
+                    devices: vec![],
+                    gpus_homogeneous: true,
+                    recent_xid_errors: None,
+                    nvlink: Vec::new(),
                 },
-                storage: crate::domain::StorageInfo { devices: vec![] },
-                gpus: crate::domain::GpuInfo { devices: vec![] },
+                accelerators: Vec::new(),
+                unbound_pci_devices: Vec::new(),
             },
             network: NetworkInfo {
                 interfaces: vec![],
                 infiniband: None,
+                fc_hbas: Vec::new(),
             },
+            gpu_nic_affinity: None,
+            tool_versions: HashMap::new(),
+            bios_settings: HashMap::new(),
+            firmware_components: Vec::new(),
+            boot_mode: None,
+            operational: None,
+            kernel_hardware_errors: None,
+            sel_recent_events: None,
+            configuration_deviations: Vec::new(),
+            time_sync: None,
+            watchdog: None,
+            power: None,
+            sensors: Vec::new(),
+            container_support: None,
+            container_limits: None,
+            gpu_cgroup_allocations: None,
+            cloud: None,
+            raw_sources: None,
         }
     }
 
@@ -0,0 +1,207 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Markdown rendering of hardware reports for wiki and runbook pages
+
+use crate::domain::HardwareReport;
+use std::fmt::Write;
+
+/// Write a Markdown table; empty tables are replaced by a "None" line
+fn write_table(out: &mut String, headers: &[&str], rows: Vec<Vec<String>>) {
+    if rows.is_empty() {
+        out.push_str("None detected.\n\n");
+        return;
+    }
+    writeln!(out, "| {} |", headers.join(" | ")).unwrap();
+    writeln!(out, "|{}", "---|".repeat(headers.len())).unwrap();
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| escape_cell(cell)).collect();
+        writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+    }
+    out.push('\n');
+}
+
+/// Keep cell text from breaking the table layout
+fn escape_cell(value: &str) -> String {
+    let value = value.replace('|', "\\|").replace('\n', " ");
+    if value.is_empty() {
+        "-".to_string()
+    } else {
+        value
+    }
+}
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+/// Render a hardware report as Markdown
+///
+/// Produces a title followed by CPU, memory, GPU, network, storage and NUMA
+/// sections, each with a table.
+///
+/// # Arguments
+/// * `report` - The hardware report to render
+///
+/// # Returns
+/// * Markdown document
+pub fn to_markdown(report: &HardwareReport) -> String {
+    let hardware = &report.hardware;
+    let summary = &report.summary;
+    let mut out = String::new();
+
+    writeln!(out, "# {}\n", report.hostname).unwrap();
+    writeln!(
+        out,
+        "{} {} (serial `{}`), BIOS {} {}\n",
+        summary.system_info.product_manufacturer,
+        summary.system_info.product_name,
+        summary.system_info.serial,
+        summary.bios.vendor,
+        summary.bios.version,
+    )
+    .unwrap();
+
+    out.push_str("## CPU\n\n");
+    let topology = &summary.cpu_topology;
+    write_table(
+        &mut out,
+        &[
+            "Model",
+            "Sockets",
+            "Cores/Socket",
+            "Threads/Core",
+            "NUMA Nodes",
+        ],
+        vec![vec![
+            topology.cpu_model.clone(),
+            topology.sockets.to_string(),
+            topology.cores_per_socket.to_string(),
+            topology.threads_per_core.to_string(),
+            topology.numa_nodes.to_string(),
+        ]],
+    );
+
+    out.push_str("## Memory\n\n");
+    writeln!(
+        out,
+        "{} total, {}\n",
+        hardware.memory.total, summary.memory_config
+    )
+    .unwrap();
+    write_table(
+        &mut out,
+        &["Slot", "Size", "Type", "Speed", "Manufacturer"],
+        hardware
+            .memory
+            .modules
+            .iter()
+            .map(|module| {
+                vec![
+                    module.location.clone(),
+                    module.size.clone(),
+                    module.type_.clone(),
+                    module.speed.clone(),
+                    module.manufacturer.clone(),
+                ]
+            })
+            .collect(),
+    );
+
+    out.push_str("## GPUs\n\n");
+    write_table(
+        &mut out,
+        &["Index", "Model", "Memory", "PCI Bus", "NUMA Node"],
+        hardware
+            .gpus
+            .devices
+            .iter()
+            .map(|gpu| {
+                vec![
+                    gpu.index.to_string(),
+                    gpu.name.clone(),
+                    gpu.memory.clone(),
+                    or_dash(gpu.pci_bus_id.as_deref()),
+                    or_dash(gpu.numa_node),
+                ]
+            })
+            .collect(),
+    );
+
+    out.push_str("## Network Interfaces\n\n");
+    write_table(
+        &mut out,
+        &["Name", "MAC", "Model", "Speed", "MTU", "NUMA Node"],
+        report
+            .network
+            .interfaces
+            .iter()
+            .filter(|iface| !iface.is_virtual)
+            .map(|iface| {
+                vec![
+                    iface.name.clone(),
+                    iface.mac.clone(),
+                    iface.model.clone(),
+                    or_dash(iface.speed.as_deref()),
+                    iface.mtu.to_string(),
+                    or_dash(iface.numa_node),
+                ]
+            })
+            .collect(),
+    );
+
+    out.push_str("## Storage\n\n");
+    write_table(
+        &mut out,
+        &["Device", "Type", "Model", "Size", "Serial"],
+        hardware
+            .storage
+            .devices
+            .iter()
+            .map(|device| {
+                vec![
+                    device.name.clone(),
+                    device.type_.clone(),
+                    device.model.clone(),
+                    device.size.clone(),
+                    or_dash(device.serial_number.as_deref()),
+                ]
+            })
+            .collect(),
+    );
+
+    out.push_str("## NUMA\n\n");
+    let mut nodes: Vec<_> = summary.numa_topology.values().collect();
+    nodes.sort_by_key(|node| node.id);
+    write_table(
+        &mut out,
+        &["Node", "CPUs", "Memory"],
+        nodes
+            .into_iter()
+            .map(|node| {
+                vec![
+                    node.id.to_string(),
+                    node.cpu_ranges.clone(),
+                    node.memory.clone(),
+                ]
+            })
+            .collect(),
+    );
+
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
@@ -16,6 +16,10 @@ limitations under the License.
 
 pub mod file;
 pub mod http;
+pub mod markdown;
+pub mod openmetrics;
 
 pub use file::*;
 pub use http::*;
+pub use markdown::*;
+pub use openmetrics::*;
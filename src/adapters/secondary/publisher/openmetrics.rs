@@ -0,0 +1,292 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Prometheus and OpenMetrics text exposition of hardware reports
+//!
+//! Both formats share one set of metric families. The Prometheus output is
+//! meant for node_exporter's textfile collector; the OpenMetrics output adds
+//! `# UNIT` metadata and the trailing `# EOF` for strict OpenMetrics parsers.
+
+use crate::domain::HardwareReport;
+use std::fmt::Write;
+
+/// Text exposition format to render
+#[derive(Clone, Copy, PartialEq)]
+enum Exposition {
+    Prometheus,
+    OpenMetrics,
+}
+
+/// Metric family being written, with its samples
+struct MetricFamily<'a> {
+    name: &'a str,
+    type_: &'a str,
+    unit: Option<&'a str>,
+    help: &'a str,
+    samples: Vec<(Vec<(&'a str, String)>, String)>,
+}
+
+impl MetricFamily<'_> {
+    fn write(&self, out: &mut String, exposition: Exposition, common_labels: &[(&str, String)]) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        // Info and counter samples carry a suffix after the family name
+        let suffix = match self.type_ {
+            "info" => "_info",
+            "counter" => "_total",
+            _ => "",
+        };
+        match exposition {
+            Exposition::OpenMetrics => {
+                writeln!(out, "# TYPE {} {}", self.name, self.type_).unwrap();
+                if let Some(unit) = self.unit {
+                    writeln!(out, "# UNIT {} {}", self.name, unit).unwrap();
+                }
+                writeln!(out, "# HELP {} {}", self.name, self.help).unwrap();
+            }
+            Exposition::Prometheus => {
+                // The Prometheus text format names families after their samples
+                // and has no info type
+                let type_ = if self.type_ == "info" {
+                    "gauge"
+                } else {
+                    self.type_
+                };
+                writeln!(out, "# HELP {}{suffix} {}", self.name, self.help).unwrap();
+                writeln!(out, "# TYPE {}{suffix} {type_}", self.name).unwrap();
+            }
+        }
+
+        for (labels, value) in &self.samples {
+            out.push_str(self.name);
+            out.push_str(suffix);
+            let labels: Vec<String> = common_labels
+                .iter()
+                .chain(labels)
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label_value(value)))
+                .collect();
+            if !labels.is_empty() {
+                write!(out, "{{{}}}", labels.join(",")).unwrap();
+            }
+            writeln!(out, " {value}").unwrap();
+        }
+    }
+}
+
+/// Escape a label value; both formats use the same escapes
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render a hardware report in the Prometheus text format
+///
+/// Write the output to a `.prom` file in node_exporter's textfile collector
+/// directory. Every sample carries `hostname` and `serial` labels.
+///
+/// # Arguments
+/// * `report` - The hardware report to render
+///
+/// # Returns
+/// * Prometheus text exposition
+pub fn to_prometheus(report: &HardwareReport) -> String {
+    render(report, Exposition::Prometheus)
+}
+
+/// Render a hardware report in the OpenMetrics text format
+///
+/// Families with a unit carry a `# UNIT` line and a name ending in that unit,
+/// and the exposition ends with the mandatory `# EOF` marker. Every sample
+/// carries `hostname` and `serial` labels.
+///
+/// # Arguments
+/// * `report` - The hardware report to render
+///
+/// # Returns
+/// * OpenMetrics text exposition
+pub fn to_openmetrics(report: &HardwareReport) -> String {
+    render(report, Exposition::OpenMetrics)
+}
+
+fn render(report: &HardwareReport, exposition: Exposition) -> String {
+    let hardware = &report.hardware;
+    let topology = &report.summary.cpu_topology;
+    let common_labels = [
+        ("hostname", report.hostname.clone()),
+        ("serial", report.summary.system_info.serial.clone()),
+    ];
+
+    let families = [
+        MetricFamily {
+            name: "hardware_report_host",
+            type_: "info",
+            unit: None,
+            help: "Host identity of the hardware report.",
+            samples: vec![(
+                vec![
+                    ("machine_id", report.machine_id.clone()),
+                    ("cpu_model", topology.cpu_model.clone()),
+                ],
+                "1".to_string(),
+            )],
+        },
+        MetricFamily {
+            name: "hardware_report_cpu_cores",
+            type_: "gauge",
+            unit: None,
+            help: "Physical CPU cores.",
+            samples: vec![(vec![], topology.total_cores.to_string())],
+        },
+        MetricFamily {
+            name: "hardware_report_cpu_threads",
+            type_: "gauge",
+            unit: None,
+            help: "Logical CPU threads.",
+            samples: vec![(vec![], topology.total_threads.to_string())],
+        },
+        MetricFamily {
+            name: "hardware_report_total_memory_bytes",
+            type_: "gauge",
+            unit: Some("bytes"),
+            help: "Total installed memory.",
+            samples: vec![(
+                vec![],
+                // Same parser as storage `size_bytes` so the byte metrics agree
+                crate::ServerInfo::parse_storage_size(&hardware.memory.total)
+                    .unwrap_or_default()
+                    .to_string(),
+            )],
+        },
+        MetricFamily {
+            name: "hardware_report_storage_bytes",
+            type_: "gauge",
+            unit: Some("bytes"),
+            help: "Total capacity of physical storage devices.",
+            samples: vec![(
+                vec![],
+                hardware
+                    .storage
+                    .devices
+                    .iter()
+                    .map(|device| device.size_bytes)
+                    .sum::<u64>()
+                    .to_string(),
+            )],
+        },
+        MetricFamily {
+            name: "hardware_report_storage_device_size_bytes",
+            type_: "gauge",
+            unit: Some("bytes"),
+            help: "Capacity of each storage device.",
+            samples: hardware
+                .storage
+                .devices
+                .iter()
+                .map(|device| {
+                    (
+                        vec![("device", device.name.clone())],
+                        device.size_bytes.to_string(),
+                    )
+                })
+                .collect(),
+        },
+        MetricFamily {
+            name: "hardware_report_gpu_count",
+            type_: "gauge",
+            unit: None,
+            help: "Number of GPUs.",
+            samples: vec![(vec![], hardware.gpus.devices.len().to_string())],
+        },
+        MetricFamily {
+            name: "hardware_report_gpu_memory_bytes",
+            type_: "gauge",
+            unit: Some("bytes"),
+            help: "Total memory of each GPU.",
+            samples: hardware
+                .gpus
+                .devices
+                .iter()
+                .map(|gpu| {
+                    (
+                        vec![("gpu", gpu.index.to_string()), ("uuid", gpu.uuid.clone())],
+                        (gpu.memory_total_mb * 1024 * 1024).to_string(),
+                    )
+                })
+                .collect(),
+        },
+        MetricFamily {
+            name: "hardware_report_gpu_temperature_celsius",
+            type_: "gauge",
+            unit: Some("celsius"),
+            help: "GPU core temperature.",
+            samples: hardware
+                .gpus
+                .devices
+                .iter()
+                .filter_map(|gpu| {
+                    gpu.temperature_c
+                        .map(|temp| (vec![("gpu", gpu.index.to_string())], temp.to_string()))
+                })
+                .collect(),
+        },
+        MetricFamily {
+            name: "hardware_report_gpu_pcie_replays",
+            type_: "counter",
+            unit: None,
+            help: "PCIe link replays reported by the GPU driver.",
+            samples: hardware
+                .gpus
+                .devices
+                .iter()
+                .filter_map(|gpu| {
+                    gpu.pcie_replay_counter
+                        .map(|count| (vec![("gpu", gpu.index.to_string())], count.to_string()))
+                })
+                .collect(),
+        },
+        MetricFamily {
+            name: "hardware_report_nic_speed_bits",
+            type_: "gauge",
+            unit: Some("bits"),
+            help: "Negotiated network interface link speed in bits per second.",
+            samples: report
+                .network
+                .interfaces
+                .iter()
+                .filter_map(|nic| {
+                    let mbps = nic.speed_mbps?;
+                    Some((
+                        vec![("interface", nic.name.clone())],
+                        (u64::from(mbps) * 1_000_000).to_string(),
+                    ))
+                })
+                .collect(),
+        },
+    ];
+
+    let mut out = String::new();
+    for family in &families {
+        family.write(&mut out, exposition, &common_labels);
+    }
+    if exposition == Exposition::OpenMetrics {
+        out.push_str("# EOF\n");
+    }
+    out
+}
@@ -0,0 +1,208 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Cloud provider instance metadata client
+
+use crate::domain::{
+    parse_azure_instance_metadata, parse_ec2_identity_document, parse_gcp_instance_metadata,
+    CloudInfo,
+};
+use reqwest::{Client, RequestBuilder};
+use std::time::Duration;
+
+/// Link-local address shared by the AWS, GCP and Azure metadata services
+const METADATA_ENDPOINT: &str = "http://169.254.169.254";
+
+/// Client for the instance metadata services of AWS, GCP and Azure
+pub struct CloudMetadataClient {
+    client: Client,
+    base_url: String,
+}
+
+impl CloudMetadataClient {
+    /// Create a metadata client
+    ///
+    /// # Arguments
+    /// * `base_url` - Metadata service base URL
+    /// * `timeout` - Per-request timeout; keep short so non-cloud hosts are not delayed
+    pub fn new(base_url: &str, timeout: Duration) -> Result<Self, reqwest::Error> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout)
+            .no_proxy()
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Create a client for the standard metadata endpoint
+    pub fn with_defaults() -> Result<Self, reqwest::Error> {
+        Self::new(METADATA_ENDPOINT, Duration::from_secs(1))
+    }
+
+    /// Query each provider's metadata service in turn
+    ///
+    /// # Returns
+    /// * Cloud info of the first provider that answers, `None` off-cloud
+    pub async fn fetch(&self) -> Option<CloudInfo> {
+        if let Some(cloud) = self.fetch_aws().await {
+            return Some(cloud);
+        }
+        if let Some(cloud) = self.fetch_gcp().await {
+            return Some(cloud);
+        }
+        self.fetch_azure().await
+    }
+
+    async fn fetch_aws(&self) -> Option<CloudInfo> {
+        // IMDSv2 needs a session token; fall back to IMDSv1 when it is refused
+        let token = self
+            .send(
+                self.client
+                    .put(format!("{}/latest/api/token", self.base_url))
+                    .header("X-aws-ec2-metadata-token-ttl-seconds", "60"),
+            )
+            .await;
+
+        let mut request = self.client.get(format!(
+            "{}/latest/dynamic/instance-identity/document",
+            self.base_url
+        ));
+        if let Some(token) = token {
+            request = request.header("X-aws-ec2-metadata-token", token);
+        }
+        let document = self.send(request).await?;
+        parse_ec2_identity_document(&document).ok()
+    }
+
+    async fn fetch_gcp(&self) -> Option<CloudInfo> {
+        let request = self
+            .client
+            .get(format!(
+                "{}/computeMetadata/v1/instance/?recursive=true",
+                self.base_url
+            ))
+            .header("Metadata-Flavor", "Google");
+        let metadata = self.send(request).await?;
+        parse_gcp_instance_metadata(&metadata).ok()
+    }
+
+    async fn fetch_azure(&self) -> Option<CloudInfo> {
+        let request = self
+            .client
+            .get(format!(
+                "{}/metadata/instance?api-version=2021-02-01",
+                self.base_url
+            ))
+            .header("Metadata", "true");
+        let metadata = self.send(request).await?;
+        parse_azure_instance_metadata(&metadata).ok()
+    }
+
+    /// Send a request, returning the body only for a successful response
+    async fn send(&self, request: RequestBuilder) -> Option<String> {
+        let response = request.send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const EC2_DOCUMENT: &str = r#"{
+  "accountId" : "123456789012",
+  "architecture" : "x86_64",
+  "availabilityZone" : "us-east-1a",
+  "imageId" : "ami-0abcdef1234567890",
+  "instanceId" : "i-1234567890abcdef0",
+  "instanceType" : "p5.48xlarge",
+  "privateIp" : "10.0.0.12",
+  "region" : "us-east-1",
+  "version" : "2017-09-30"
+}"#;
+
+    /// Serve canned IMDSv2 responses on a local port
+    async fn spawn_mock_imds() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+
+                let (status, body) = if request.starts_with("PUT /latest/api/token") {
+                    ("200 OK", "mock-token")
+                } else if request.starts_with("GET /latest/dynamic/instance-identity/document")
+                    && request.contains("x-aws-ec2-metadata-token: mock-token")
+                {
+                    ("200 OK", EC2_DOCUMENT)
+                } else {
+                    ("404 Not Found", "")
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ec2_instance_document() {
+        let base_url = spawn_mock_imds().await;
+        let client = CloudMetadataClient::new(&base_url, Duration::from_secs(2)).unwrap();
+
+        let cloud = client.fetch().await.unwrap();
+
+        assert_eq!(
+            cloud,
+            CloudInfo {
+                provider: "aws".to_string(),
+                instance_type: Some("p5.48xlarge".to_string()),
+                region: Some("us-east-1".to_string()),
+                availability_zone: Some("us-east-1a".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_none_off_cloud() {
+        // Nothing listens on this port, so every provider fails to connect
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client =
+            CloudMetadataClient::new(&format!("http://{address}"), Duration::from_millis(200))
+                .unwrap();
+
+        assert_eq!(client.fetch().await, None);
+    }
+}
@@ -34,36 +34,126 @@ limitations under the License.
 //! 3. Fallback: sysinfo crate (cross-platform)
 
 use crate::domain::{
-    combine_cpu_info, determine_memory_speed, determine_memory_type, parse_dmidecode_bios_info,
-    parse_dmidecode_chassis_info, parse_dmidecode_cpu, parse_dmidecode_memory,
-    parse_dmidecode_system_info, parse_free_output, parse_hostname_output, parse_ip_output,
-    parse_lscpu_output, BiosInfo, ChassisInfo, CpuInfo, GpuDevice, GpuInfo, GpuVendor, MemoryInfo,
-    MotherboardInfo, NetworkInfo, NetworkInterface, NetworkInterfaceType, NumaNode, StorageDevice,
-    StorageInfo, StorageType, SystemError, SystemInfo,
+    are_gpus_homogeneous, aspm_state_from_link_flags, combine_cpu_info, cpu_steal_percent,
+    determine_memory_speed, determine_memory_type, dmidecode_types_for_keyword,
+    filter_dmidecode_sections, find_raid_virtual_disk, format_cpu_list, infer_numa_memory_tiers,
+    is_container_interface, is_nvme_overheating, is_resizable_bar_enabled, merge_gpu_devices,
+    normalize_bios_settings, normalize_pci_address, nvme_namespace_controller, parse_aer_counters,
+    parse_bios_attributes_json, parse_cgroup_cpu_max, parse_cgroup_gpu_devices, parse_cgroup_limit,
+    parse_cgroup_throttled_periods, parse_chronyc_tracking, parse_cmdline_cpu_list,
+    parse_cmdline_hugepages, parse_container_from_cgroup, parse_cpu_driver, parse_cpu_list,
+    parse_dcmi_power_reading, parse_dmidecode_bios_info, parse_dmidecode_chassis_info,
+    parse_dmidecode_cpu, parse_dmidecode_memory, parse_dmidecode_memory_ecc,
+    parse_dmidecode_system_info, parse_ethtool_channels, parse_ethtool_ring, parse_fc_wwn,
+    parse_free_output, parse_hostname_output, parse_ip_output, parse_ipmitool_lan_print,
+    parse_ipmitool_sel_elist, parse_ipmitool_watchdog_get, parse_kernel_hardware_errors,
+    parse_lscpu_output, parse_lspci_accelerators, parse_lspci_aspm, parse_lspci_intel_gpus,
+    parse_megacli_ldinfo, parse_meminfo_hugepages, parse_ndctl_namespaces, parse_node_distances,
+    parse_node_meminfo_total, parse_numa_balancing, parse_nvidia_smi_mig_list,
+    parse_nvidia_smi_nvlink, parse_nvidia_smi_query, parse_nvidia_smi_topo_matrix,
+    parse_nvme_id_ctrl_temperature_thresholds, parse_pci_resource_bar1_size,
+    parse_pcie_link_speed_gen, parse_pcie_link_width, parse_pcie_path, parse_proc_stat_cpu,
+    parse_racadm_bios_attributes, parse_smartctl_rotation_info, parse_smt_active,
+    parse_smt_control, parse_storcli_virtual_disks_json, parse_sum_bios_config,
+    parse_sysfs_counter, parse_sysfs_partition, parse_sysfs_scheduler,
+    parse_systemctl_failed_units, parse_timedatectl_timesync, parse_tool_version, parse_tpm_info,
+    parse_transparent_hugepages, parse_turbo_enabled, parse_xid_errors, parse_xpu_smi_discovery,
+    rapl_power_watts, Accelerator, BiosInfo, BmcNetwork, Capabilities, ChassisInfo, CloudInfo,
+    ContainerLimits, ContainerRuntimeInfo, CpuInfo, Enclosure, EnclosureSlot, FcHba,
+    FirmwareComponent, GpuCgroupAllocation, GpuDevice, GpuInfo, GpuVendor, HugepagePool,
+    KernelError, LogicalCpu, MemoryInfo, MotherboardInfo, NetworkInfo, NetworkInterface,
+    NetworkInterfaceType, NumaNode, NvLinkConnection, NvdimmNamespace, Partition, PciDevice,
+    PowerInfo, RaidVirtualDisk, RedfishConfig, SelEvent, SensorReading, StorageDevice, StorageInfo,
+    StorageType, SystemError, SystemInfo, TimeSyncInfo, TpmInfo, WatchdogInfo, XidError, UNKNOWN,
 };
 
 use crate::domain::parsers::storage::{
-    is_virtual_device, parse_lsblk_json, parse_sysfs_rotational, parse_sysfs_size,
+    is_virtual_device, parse_lsblk_json, parse_nvme_smart_log_json, parse_pci_storage_class,
+    parse_sysfs_rotational, parse_sysfs_size,
 };
 
-use crate::ports::{CommandExecutor, SystemCommand, SystemInfoProvider};
+use super::{probe_subsystems, CloudMetadataClient, RedfishClient};
+use crate::adapters::RecordingCommandExecutor;
+use crate::ports::{CommandExecutor, CommandOutput, SystemCommand, SystemInfoProvider};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// storcli-compatible RAID tools, in the order they are tried
+const STORCLI_COMMANDS: &[&str] = &["storcli64", "storcli", "perccli64", "perccli"];
+
+/// MegaCli builds, tried when no storcli-compatible tool answers
+const MEGACLI_COMMANDS: &[&str] = &["MegaCli64", "MegaCli"];
+
+/// Number of System Event Log entries read from the BMC
+const SEL_RECENT_EVENT_COUNT: usize = 20;
+
 /// Linux system information provider using standard system commands
 pub struct LinuxSystemInfoProvider {
     command_executor: Arc<dyn CommandExecutor>,
+    /// Skip slow per-device probes (smartctl, extra GPU queries, tool versions)
+    fast_mode: bool,
+    /// Records raw command output when raw capture is enabled
+    raw_recorder: Option<Arc<RecordingCommandExecutor>>,
+    /// Pre-captured `dmidecode` dump to read instead of running dmidecode
+    dmidecode_source: Option<PathBuf>,
+    /// Detected container environment; enables the minimal collection profile
+    container: Option<String>,
+    /// BMC to read the Redfish firmware inventory from
+    redfish: Option<RedfishConfig>,
 }
 
 impl LinuxSystemInfoProvider {
     /// Create a new Linux system information provider
     pub fn new(command_executor: Arc<dyn CommandExecutor>) -> Self {
-        Self { command_executor }
+        Self {
+            command_executor,
+            fast_mode: false,
+            raw_recorder: None,
+            dmidecode_source: None,
+            container: None,
+            redfish: None,
+        }
+    }
+
+    /// Skip slow per-device probes; the fields they populate stay `None`
+    pub fn with_fast_mode(mut self, fast_mode: bool) -> Self {
+        self.fast_mode = fast_mode;
+        self
+    }
+
+    /// Record the stdout of every command run for inclusion in the report
+    pub fn with_raw_capture(mut self, enabled: bool) -> Self {
+        if enabled && self.raw_recorder.is_none() {
+            let recorder = Arc::new(RecordingCommandExecutor::new(self.command_executor.clone()));
+            self.command_executor = recorder.clone();
+            self.raw_recorder = Some(recorder);
+        }
+        self
+    }
+
+    /// Detect whether we run inside a container and, if so, switch to the
+    /// minimal profile: dmidecode and ipmitool are never run, and only
+    /// cgroup limits plus whatever sysfs is visible are collected
+    pub fn with_container_detection(mut self) -> Self {
+        self.container = self.detect_container(Path::new("/"));
+        self
+    }
+
+    /// Read the firmware inventory from this BMC's Redfish API
+    pub fn with_redfish(mut self, redfish: Option<RedfishConfig>) -> Self {
+        self.redfish = redfish;
+        self
+    }
+
+    /// Read DMI tables from a saved `dmidecode` dump instead of the live system
+    pub fn with_dmidecode_source(mut self, source: Option<PathBuf>) -> Self {
+        self.dmidecode_source = source;
+        self
     }
 
     /// Check if required commands are available
@@ -151,6 +241,11 @@ impl LinuxSystemInfoProvider {
             // Determine device type
             let device_type = StorageType::from_device(&device_name, is_rotational);
 
+            let io_scheduler = self
+                .read_sysfs_file(&device_path.join("queue/scheduler"))
+                .ok()
+                .and_then(|content| parse_sysfs_scheduler(&content));
+
             // Read optional fields
             let model = self
                 .read_sysfs_file(&device_path.join("device/model"))
@@ -180,9 +275,11 @@ impl LinuxSystemInfoProvider {
                 StorageType::Nvme => "NVMe".to_string(),
                 StorageType::Emmc => "eMMC".to_string(),
                 StorageType::Hdd | StorageType::Ssd => "SATA".to_string(),
-                _ => "Unknown".to_string(),
+                _ => UNKNOWN.to_string(),
             };
 
+            let (controller, behind_raid) = self.read_storage_controller(&device_path);
+
             let mut device = StorageDevice {
                 name: device_name.clone(),
                 device_path: format!("/dev/{}", device_name),
@@ -195,6 +292,9 @@ impl LinuxSystemInfoProvider {
                 interface,
                 is_rotational,
                 detection_method: "sysfs".to_string(),
+                controller,
+                behind_raid,
+                io_scheduler,
                 ..Default::default()
             };
 
@@ -212,16 +312,7 @@ impl LinuxSystemInfoProvider {
         existing_serial: Option<String>,
         existing_firmware: Option<String>,
     ) -> (Option<String>, Option<String>) {
-        // Extract controller name: "nvme0n1" -> "nvme0"
-        let controller = if let Some(stripped) = device_name.strip_prefix("nvme") {
-            if let Some(pos) = stripped.find('n') {
-                &device_name[..4 + pos]
-            } else {
-                device_name
-            }
-        } else {
-            device_name
-        };
+        let controller = nvme_namespace_controller(device_name).unwrap_or(device_name);
 
         let nvme_path = PathBuf::from("/sys/class/nvme").join(controller);
 
@@ -369,17 +460,31 @@ impl LinuxSystemInfoProvider {
             .map(|s| s.trim() == "1")
             .ok();
 
+        self.read_interface_statistics(&iface_path.join("statistics"), iface);
+
+        // Bond members, e.g. "ens1f0 ens1f1"
+        if let Ok(slaves) = self.read_sysfs_file(&iface_path.join("bonding/slaves")) {
+            iface.bond_members = slaves.split_whitespace().map(str::to_string).collect();
+        }
+
         // Virtual interface detection
         let device_path = iface_path.join("device");
         iface.is_virtual = !device_path.exists()
             || iface.name.starts_with("lo")
-            || iface.name.starts_with("veth")
             || iface.name.starts_with("br")
-            || iface.name.starts_with("docker")
-            || iface.name.starts_with("virbr");
+            || iface.name.starts_with("virbr")
+            || is_container_interface(&iface.name);
 
         // Driver information (only for physical interfaces)
         if !iface.is_virtual {
+            (iface.aer_correctable_errors, iface.aer_uncorrectable_errors) =
+                self.read_pci_aer_counters(&device_path);
+            (iface.pcie_path, iface.numa_node) = self.read_pcie_locality(&device_path);
+            iface.aspm_state = self.read_aspm_state(&device_path);
+            (iface.pcie_gen, iface.pcie_width) = self.read_pcie_link(&device_path, "current");
+            (iface.pcie_max_gen, iface.pcie_max_width) = self.read_pcie_link(&device_path, "max");
+            iface.rdma_device = Self::read_rdma_device(&device_path);
+
             let driver_link = device_path.join("driver");
             if let Ok(driver_path) = fs::read_link(&driver_link) {
                 if let Some(driver_name) = driver_path.file_name() {
@@ -416,305 +521,1548 @@ impl LinuxSystemInfoProvider {
             NetworkInterfaceType::Ethernet
         };
     }
-}
-
-#[async_trait]
-impl SystemInfoProvider for LinuxSystemInfoProvider {
-    async fn get_cpu_info(&self) -> Result<CpuInfo, SystemError> {
-        let lscpu_cmd = SystemCommand::new("lscpu").timeout(Duration::from_secs(10));
-        let lscpu_output = self
-            .command_executor
-            .execute(&lscpu_cmd)
-            .await
-            .map_err(|e| SystemError::CommandFailed {
-                command: "lscpu".to_string(),
-                exit_code: None,
-                stderr: e.to_string(),
-            })?;
-
-        let lscpu_info =
-            parse_lscpu_output(&lscpu_output.stdout).map_err(SystemError::ParseError)?;
 
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "processor"])
-            .timeout(Duration::from_secs(10));
+    /// Read raw BIOS attributes from the first vendor tool that answers
+    ///
+    /// Tries Dell `racadm`, HPE `ilorest`, then Supermicro `sum`.
+    async fn read_vendor_bios_attributes(&self) -> HashMap<String, String> {
+        let mut attributes = HashMap::new();
+        for group in ["BIOS.SysProfileSettings", "BIOS.ProcSettings"] {
+            let racadm_cmd = SystemCommand::new("racadm")
+                .args(&["get", group])
+                .timeout(Duration::from_secs(30));
+            if let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&racadm_cmd)
+                .await
+            {
+                if output.success {
+                    attributes.extend(parse_racadm_bios_attributes(&output.stdout));
+                }
+            }
+        }
+        if !attributes.is_empty() {
+            return attributes;
+        }
 
-        match self
+        let ilorest_cmd = SystemCommand::new("ilorest")
+            .args(&["--nologo", "get", "--json", "--selector", "Bios."])
+            .timeout(Duration::from_secs(60));
+        if let Ok(output) = self
             .command_executor
-            .execute_with_privileges(&dmidecode_cmd)
+            .execute_with_privileges(&ilorest_cmd)
             .await
         {
-            Ok(dmidecode_output) if dmidecode_output.success => {
-                if let Ok(dmidecode_info) = parse_dmidecode_cpu(&dmidecode_output.stdout) {
-                    Ok(combine_cpu_info(lscpu_info, dmidecode_info))
-                } else {
-                    Ok(lscpu_info)
+            if let Ok(parsed) = parse_bios_attributes_json(&output.stdout) {
+                if output.success && !parsed.is_empty() {
+                    return parsed;
                 }
             }
-            _ => Ok(lscpu_info),
         }
-    }
-
-    async fn get_memory_info(&self) -> Result<MemoryInfo, SystemError> {
-        let free_cmd = SystemCommand::new("free")
-            .args(&["-b"])
-            .timeout(Duration::from_secs(5));
-        let free_output = self
-            .command_executor
-            .execute(&free_cmd)
-            .await
-            .map_err(|e| SystemError::CommandFailed {
-                command: "free".to_string(),
-                exit_code: None,
-                stderr: e.to_string(),
-            })?;
-
-        let total_memory =
-            parse_free_output(&free_output.stdout).map_err(SystemError::ParseError)?;
 
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "memory"])
-            .timeout(Duration::from_secs(10));
-
-        let (modules, type_, speed) = match self
+        // sum only writes the configuration to a file
+        let dump_path = std::env::temp_dir().join("hardware_report_sum_bios.xml");
+        let dump_arg = dump_path.display().to_string();
+        let sum_cmd = SystemCommand::new("sum")
+            .args(&[
+                "-c",
+                "GetCurrentBiosCfg",
+                "--file",
+                &dump_arg,
+                "--overwrite",
+            ])
+            .timeout(Duration::from_secs(120));
+        if let Ok(output) = self
             .command_executor
-            .execute_with_privileges(&dmidecode_cmd)
+            .execute_with_privileges(&sum_cmd)
             .await
         {
-            Ok(dmidecode_output) if dmidecode_output.success => {
-                match parse_dmidecode_memory(&dmidecode_output.stdout) {
-                    Ok(modules) if !modules.is_empty() => {
-                        let type_ = determine_memory_type(&modules);
-                        let speed = determine_memory_speed(&modules);
-                        (modules, type_, speed)
-                    }
-                    _ => (Vec::new(), "Unknown".to_string(), "Unknown".to_string()),
+            if output.success {
+                if let Ok(content) = fs::read_to_string(&dump_path) {
+                    attributes = parse_sum_bios_config(&content);
                 }
+                let _ = fs::remove_file(&dump_path);
             }
-            _ => (Vec::new(), "Unknown".to_string(), "Unknown".to_string()),
-        };
-
-        Ok(MemoryInfo {
-            total: total_memory,
-            type_,
-            speed,
-            modules,
-        })
+        }
+        attributes
     }
 
-    async fn get_storage_info(&self) -> Result<StorageInfo, SystemError> {
-        let mut devices = Vec::new();
+    /// Add rotation rate and form factor from `smartctl -i`
+    ///
+    /// NVMe drives do not report either field, so they are skipped.
+    async fn enrich_storage_smart_info(&self, devices: &mut [StorageDevice]) {
+        for device in devices
+            .iter_mut()
+            .filter(|d| d.device_type != StorageType::Nvme)
+        {
+            let smart_cmd = SystemCommand::new("smartctl")
+                .args(&["-i", &device.device_path])
+                .timeout(Duration::from_secs(10));
 
-        // Try sysfs first
-        if let Ok(sysfs_devices) = self.detect_storage_sysfs().await {
-            log::debug!("sysfs detected {} storage devices", sysfs_devices.len());
-            devices = sysfs_devices;
-        } else {
-            log::warn!("sysfs storage detection failed, trying next method");
+            let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&smart_cmd)
+                .await
+            else {
+                continue;
+            };
+            if !output.success {
+                continue;
+            }
+
+            let (rotation_rpm, form_factor) = parse_smartctl_rotation_info(&output.stdout);
+            device.rotation_rpm = rotation_rpm;
+            device.form_factor = form_factor;
         }
+    }
 
-        // Enrich with lsblk
-        if let Ok(lsblk_devices) = self.detect_storage_lsblk().await {
-            log::debug!(
-                "lsblk found {} devices for additional info",
-                lsblk_devices.len()
-            );
-            self.merge_storage_info(&mut devices, lsblk_devices);
+    /// Add RAID level and member drive count to devices behind a RAID controller
+    ///
+    /// Fields stay `None` when no storcli, perccli or MegaCli build is installed.
+    async fn enrich_raid_virtual_disks(&self, devices: &mut [StorageDevice]) {
+        if !devices.iter().any(|d| d.behind_raid) {
+            return;
         }
 
-        // Fallback to sysinfo
-        if devices.is_empty() {
-            log::warn!("No devices from sysfs/lsblk, trying sysinfo as fallback");
-            if let Ok(sysinfo_devices) = self.detect_storage_sysinfo() {
-                devices = sysinfo_devices;
+        let disks = match self.read_raid_virtual_disks().await {
+            Some(disks) if !disks.is_empty() => disks,
+            _ => return,
+        };
+
+        for device in devices.iter_mut().filter(|d| d.behind_raid) {
+            let scsi_target =
+                fs::canonicalize(Path::new("/sys/block").join(&device.name).join("device"))
+                    .ok()
+                    .and_then(|path| {
+                        // The device directory is named "host:channel:target:lun"
+                        let address = path.file_name()?.to_string_lossy().to_string();
+                        address.split(':').nth(2)?.parse().ok()
+                    });
+
+            if let Some(disk) = find_raid_virtual_disk(&disks, &device.device_path, scsi_target) {
+                device.raid_level = Some(disk.raid_level.clone());
+                device.physical_disk_count = Some(disk.physical_disk_count);
             }
         }
+    }
 
-        // Filter virtual devices and sort
-        devices.retain(|d| d.device_type != StorageType::Virtual);
-
-        for device in &mut devices {
-            if device.size_gb == 0.0 && device.size_bytes > 0 {
-                device.calculate_size_fields();
+    /// Read controller virtual disks with storcli, falling back to MegaCli
+    ///
+    /// Dell's perccli is a rebranded storcli with the same syntax and JSON
+    /// output; vendor packages install the 64-bit builds under their own names.
+    async fn read_raid_virtual_disks(&self) -> Option<Vec<RaidVirtualDisk>> {
+        for tool in STORCLI_COMMANDS {
+            let storcli_cmd = SystemCommand::new(tool)
+                .args(&["/call/vall", "show", "all", "j"])
+                .timeout(Duration::from_secs(30));
+            let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&storcli_cmd)
+                .await
+            else {
+                continue;
+            };
+            if output.success {
+                if let Ok(disks) = parse_storcli_virtual_disks_json(&output.stdout) {
+                    return Some(disks);
+                }
             }
-            device.set_device_path();
         }
 
-        devices.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(StorageInfo { devices })
+        for tool in MEGACLI_COMMANDS {
+            let megacli_cmd = SystemCommand::new(tool)
+                .args(&["-LDInfo", "-Lall", "-aAll"])
+                .timeout(Duration::from_secs(30));
+            if let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&megacli_cmd)
+                .await
+            {
+                if output.success {
+                    return Some(parse_megacli_ldinfo(&output.stdout));
+                }
+            }
+        }
+        None
     }
 
-    async fn get_gpu_info(&self) -> Result<GpuInfo, SystemError> {
-        let nvidia_cmd = SystemCommand::new("nvidia-smi")
-            .args(&[
-                "--query-gpu=index,name,uuid,memory.total",
-                "--format=csv,noheader,nounits",
-            ])
-            .timeout(Duration::from_secs(10));
+    /// Add the NVMe SMART/health log from `nvme smart-log` when the nvme CLI is present
+    ///
+    /// Composite temperature thresholds come from `nvme id-ctrl` and decide
+    /// whether the drive is flagged as overheating.
+    async fn enrich_nvme_health(&self, devices: &mut [StorageDevice]) {
+        if !matches!(
+            self.command_executor.is_command_available("nvme").await,
+            Ok(true)
+        ) {
+            return;
+        }
 
-        let mut devices = Vec::new();
+        for device in devices
+            .iter_mut()
+            .filter(|d| d.device_type == StorageType::Nvme)
+        {
+            let smart_log_cmd = SystemCommand::new("nvme")
+                .args(&["smart-log", &device.device_path, "-o", "json"])
+                .timeout(Duration::from_secs(10));
 
-        if let Ok(nvidia_output) = self.command_executor.execute(&nvidia_cmd).await {
-            if nvidia_output.success {
-                for (index, line) in nvidia_output.stdout.lines().enumerate() {
-                    let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-                    if parts.len() >= 4 {
-                        let memory_mb: u64 = parts[3].parse().unwrap_or(0);
-                        devices.push(GpuDevice {
-                            index: index as u32,
-                            name: parts[1].to_string(),
-                            uuid: parts[2].to_string(),
-                            memory: format!("{} MB", parts[3]),
-                            memory_total_mb: memory_mb,
-                            pci_id: String::new(),
-                            vendor: "NVIDIA".to_string(),
-                            vendor_enum: GpuVendor::Nvidia,
-                            numa_node: None,
-                            detection_method: "nvidia-smi".to_string(),
-                            ..Default::default()
-                        });
-                    }
-                }
+            let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&smart_log_cmd)
+                .await
+            else {
+                continue;
+            };
+            if !output.success {
+                continue;
             }
-        }
 
-        // Fallback to lspci
-        if devices.is_empty() {
-            let lspci_cmd = SystemCommand::new("lspci")
-                .args(&["-nn"])
-                .timeout(Duration::from_secs(5));
+            let Ok(mut health) = parse_nvme_smart_log_json(&output.stdout) else {
+                continue;
+            };
 
-            if let Ok(lspci_output) = self.command_executor.execute(&lspci_cmd).await {
-                if lspci_output.success {
-                    let mut gpu_index = 0;
-                    for line in lspci_output.stdout.lines() {
-                        if line.to_lowercase().contains("vga") || line.to_lowercase().contains("3d")
-                        {
-                            devices.push(GpuDevice {
-                                index: gpu_index,
-                                name: line.to_string(),
-                                uuid: format!("pci-gpu-{gpu_index}"),
-                                memory: "Unknown".to_string(),
-                                pci_id: String::new(),
-                                vendor: "Unknown".to_string(),
-                                vendor_enum: GpuVendor::Unknown,
-                                numa_node: None,
-                                detection_method: "lspci".to_string(),
-                                ..Default::default()
-                            });
-                            gpu_index += 1;
-                        }
+            let id_ctrl_cmd = SystemCommand::new("nvme")
+                .args(&["id-ctrl", &device.device_path, "-o", "json"])
+                .timeout(Duration::from_secs(10));
+            if let Ok(output) = self
+                .command_executor
+                .execute_with_privileges(&id_ctrl_cmd)
+                .await
+            {
+                if output.success {
+                    if let Ok((warning, critical)) =
+                        parse_nvme_id_ctrl_temperature_thresholds(&output.stdout)
+                    {
+                        health.warning_temperature_c = warning;
+                        health.critical_temperature_c = critical;
                     }
                 }
             }
-        }
 
-        Ok(GpuInfo { devices })
+            device.overheating = is_nvme_overheating(&health);
+            device.nvme_health = Some(health);
+        }
     }
 
-    async fn get_network_info(&self) -> Result<NetworkInfo, SystemError> {
-        let ip_cmd = SystemCommand::new("ip")
-            .args(&["addr", "show"])
+    /// Add ring sizes and combined queue count from `ethtool -g` and `ethtool -l`
+    async fn enrich_ethtool_queues(&self, iface: &mut NetworkInterface) {
+        let ring_cmd = SystemCommand::new("ethtool")
+            .args(&["-g", &iface.name])
             .timeout(Duration::from_secs(5));
-        let ip_output = self.command_executor.execute(&ip_cmd).await.map_err(|e| {
-            SystemError::CommandFailed {
-                command: "ip".to_string(),
-                exit_code: None,
-                stderr: e.to_string(),
+        if let Ok(output) = self.command_executor.execute(&ring_cmd).await {
+            if output.success {
+                (iface.rx_ring, iface.tx_ring) = parse_ethtool_ring(&output.stdout);
             }
-        })?;
+        }
 
-        let mut interfaces = parse_ip_output(&ip_output.stdout).map_err(SystemError::ParseError)?;
+        let channels_cmd = SystemCommand::new("ethtool")
+            .args(&["-l", &iface.name])
+            .timeout(Duration::from_secs(5));
+        if let Ok(output) = self.command_executor.execute(&channels_cmd).await {
+            if output.success {
+                iface.combined_queues = parse_ethtool_channels(&output.stdout);
+            }
+        }
+    }
 
-        // Enrich with sysfs data
-        for iface in &mut interfaces {
-            self.enrich_network_interface_sysfs(iface);
+    /// Detect Intel discrete GPUs with xpu-smi, plus an lspci scan for cards
+    /// the tool does not report or when it is not installed
+    async fn detect_intel_gpus(&self) -> Vec<GpuDevice> {
+        let mut devices = Vec::new();
+
+        if matches!(
+            self.command_executor.is_command_available("xpu-smi").await,
+            Ok(true)
+        ) {
+            let xpu_cmd = SystemCommand::new("xpu-smi")
+                .args(&["discovery", "-j"])
+                .timeout(Duration::from_secs(10));
+            if let Ok(output) = self.command_executor.execute(&xpu_cmd).await {
+                if output.success {
+                    devices = parse_xpu_smi_discovery(&output.stdout).unwrap_or_default();
+                }
+            }
         }
 
-        Ok(NetworkInfo {
-            interfaces,
-            infiniband: None,
-        })
+        let lspci_cmd = SystemCommand::new("lspci")
+            .args(&["-D", "-nn", "-d", "8086:"])
+            .timeout(Duration::from_secs(5));
+        if let Ok(output) = self.command_executor.execute(&lspci_cmd).await {
+            if output.success {
+                merge_gpu_devices(&mut devices, parse_lspci_intel_gpus(&output.stdout));
+            }
+        }
+
+        devices
     }
 
-    async fn get_bios_info(&self) -> Result<BiosInfo, SystemError> {
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "bios"])
-            .timeout(Duration::from_secs(10));
-        let dmidecode_output = self
-            .command_executor
-            .execute_with_privileges(&dmidecode_cmd)
-            .await
-            .map_err(|e| SystemError::CommandFailed {
-                command: "dmidecode".to_string(),
-                exit_code: None,
-                stderr: e.to_string(),
-            })?;
+    /// List persistent memory namespaces; empty when ndctl is not installed
+    async fn read_nvdimm_namespaces(&self) -> Vec<NvdimmNamespace> {
+        if !matches!(
+            self.command_executor.is_command_available("ndctl").await,
+            Ok(true)
+        ) {
+            return Vec::new();
+        }
 
-        parse_dmidecode_bios_info(&dmidecode_output.stdout).map_err(SystemError::ParseError)
+        let ndctl_cmd = SystemCommand::new("ndctl")
+            .args(&["list", "-N"])
+            .timeout(Duration::from_secs(10));
+        match self.command_executor.execute(&ndctl_cmd).await {
+            Ok(output) if output.success => {
+                parse_ndctl_namespaces(&output.stdout).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        }
     }
 
-    async fn get_chassis_info(&self) -> Result<ChassisInfo, SystemError> {
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "chassis"])
-            .timeout(Duration::from_secs(10));
-        let dmidecode_output = self
-            .command_executor
+    /// Add temperatures, retired pages and virtualization modes from `nvidia-smi -q`
+    ///
+    /// GPU sections are matched to devices by PCI bus ID.
+    async fn enrich_gpu_query_details(&self, devices: &mut [GpuDevice]) {
+        let query_cmd = SystemCommand::new("nvidia-smi")
+            .args(&["-q"])
+            .timeout(Duration::from_secs(30));
+
+        let Ok(output) = self.command_executor.execute(&query_cmd).await else {
+            return;
+        };
+        if !output.success {
+            return;
+        }
+
+        for reading in parse_nvidia_smi_query(&output.stdout) {
+            let Some(device) = devices.iter_mut().find(|device| {
+                device.pci_bus_id.is_some() && device.pci_bus_id == reading.pci_bus_id
+            }) else {
+                continue;
+            };
+            device.temperature_c = reading.temperature_c;
+            device.temperature_memory_c = reading.temperature_memory_c;
+            device.temperature_slowdown_c = reading.temperature_slowdown_c;
+            device.temperature_shutdown_c = reading.temperature_shutdown_c;
+            device.retired_pages_single_bit = reading.retired_pages_single_bit;
+            device.retired_pages_double_bit = reading.retired_pages_double_bit;
+            device.retired_pages_pending = reading.retired_pages_pending;
+            device.virtualization_mode = reading.virtualization_mode;
+        }
+    }
+
+    /// Add MIG instances from `nvidia-smi -L`
+    ///
+    /// Only run when `nvidia-smi -q` reported a GPU in MIG mode.
+    async fn enrich_gpu_mig_instances(&self, devices: &mut [GpuDevice]) {
+        if !devices
+            .iter()
+            .any(|device| device.virtualization_mode.as_deref() == Some("mig"))
+        {
+            return;
+        }
+
+        let list_cmd = SystemCommand::new("nvidia-smi")
+            .args(&["-L"])
+            .timeout(Duration::from_secs(10));
+
+        let Ok(output) = self.command_executor.execute(&list_cmd).await else {
+            return;
+        };
+        if !output.success {
+            return;
+        }
+
+        let mut instances = parse_nvidia_smi_mig_list(&output.stdout);
+        for device in devices.iter_mut() {
+            device.mig_instances = instances.remove(&device.index).unwrap_or_default();
+        }
+    }
+
+    /// Add NVLink state and error counters from `nvidia-smi nvlink`
+    async fn enrich_gpu_nvlinks(&self, devices: &mut [GpuDevice]) {
+        let status_cmd = SystemCommand::new("nvidia-smi")
+            .args(&["nvlink", "-s"])
+            .timeout(Duration::from_secs(10));
+        let Ok(status_output) = self.command_executor.execute(&status_cmd).await else {
+            return;
+        };
+        if !status_output.success {
+            return;
+        }
+        // GPUs without NVLink print no links; skip the error counter call
+        if !matches!(parse_nvidia_smi_nvlink(&status_output.stdout, ""), Ok(links) if !links.is_empty())
+        {
+            return;
+        }
+
+        let errors_cmd = SystemCommand::new("nvidia-smi")
+            .args(&["nvlink", "-e"])
+            .timeout(Duration::from_secs(10));
+        let errors_stdout = match self.command_executor.execute(&errors_cmd).await {
+            Ok(output) if output.success => output.stdout,
+            _ => String::new(),
+        };
+
+        let Ok(mut links) = parse_nvidia_smi_nvlink(&status_output.stdout, &errors_stdout) else {
+            return;
+        };
+        for device in devices.iter_mut() {
+            if let Some(device_links) = links.remove(&device.index) {
+                device.nvlinks = device_links;
+            }
+        }
+    }
+
+    /// Map GPU-to-GPU NVLink connections from `nvidia-smi topo -m`
+    ///
+    /// Only run when `nvidia-smi nvlink -s` reported an active link, so PCIe-only
+    /// hosts skip the extra call and report no connections.
+    async fn read_gpu_nvlink_topology(&self, devices: &[GpuDevice]) -> Vec<NvLinkConnection> {
+        let has_active_link = devices
+            .iter()
+            .flat_map(|device| &device.nvlinks)
+            .any(|link| link.state == "up");
+        if !has_active_link {
+            return Vec::new();
+        }
+
+        let topo_cmd = SystemCommand::new("nvidia-smi")
+            .args(&["topo", "-m"])
+            .timeout(Duration::from_secs(10));
+        match self.command_executor.execute(&topo_cmd).await {
+            Ok(output) if output.success => parse_nvidia_smi_topo_matrix(&output.stdout),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Read upstream PCIe bridges and NUMA node for a PCI device directory
+    fn read_pcie_locality(&self, pci_path: &Path) -> (Vec<String>, Option<i32>) {
+        let pcie_path = fs::canonicalize(pci_path)
+            .map(|resolved| parse_pcie_path(&resolved.to_string_lossy()))
+            .unwrap_or_default();
+        let numa_node = self
+            .read_sysfs_file(&pci_path.join("numa_node"))
+            .ok()
+            .and_then(|node| node.trim().parse::<i32>().ok())
+            .filter(|node| *node >= 0);
+        (pcie_path, numa_node)
+    }
+
+    /// Read a kernel watchdog's driver, timeout and state from sysfs
+    fn read_kernel_watchdog(&self, watchdog_path: &Path) -> Option<WatchdogInfo> {
+        let read = |name: &str| {
+            self.read_sysfs_file(&watchdog_path.join(name))
+                .ok()
+                .map(|content| content.trim().to_string())
+        };
+        // Every registered watchdog has an identity
+        let identity = read("identity")?;
+        Some(WatchdogInfo {
+            device: identity,
+            timeout_s: read("timeout").and_then(|timeout| timeout.parse().ok()),
+            active: read("state").as_deref() == Some("active"),
+        })
+    }
+
+    /// Read the PCIe generation and lane width of a PCI device
+    ///
+    /// `link` is "current" for the negotiated link or "max" for the device's capability.
+    fn read_pcie_link(&self, pci_path: &Path, link: &str) -> (Option<u8>, Option<u8>) {
+        let generation = self
+            .read_sysfs_file(&pci_path.join(format!("{link}_link_speed")))
+            .ok()
+            .and_then(|content| parse_pcie_link_speed_gen(&content));
+        let width = self
+            .read_sysfs_file(&pci_path.join(format!("{link}_link_width")))
+            .ok()
+            .and_then(|content| parse_pcie_link_width(&content));
+        (generation, width)
+    }
+
+    /// Read the enabled ASPM states from a PCI device's `link/` attributes
+    fn read_aspm_state(&self, pci_path: &Path) -> Option<String> {
+        let read_flag = |file: &str| {
+            self.read_sysfs_file(&pci_path.join("link").join(file))
+                .ok()
+                .map(|flag| flag.trim() == "1")
+        };
+        aspm_state_from_link_flags(read_flag("l0s_aspm"), read_flag("l1_aspm"))
+    }
+
+    /// Read the ASPM state from `lspci -vv` link control for one device
+    async fn lspci_aspm_state(&self, pci_address: &str) -> Option<String> {
+        let lspci_cmd = SystemCommand::new("lspci")
+            .args(&["-vv", "-s", pci_address])
+            .timeout(Duration::from_secs(5));
+        // Link control registers are only shown to root
+        let output = self
+            .command_executor
+            .execute_with_privileges(&lspci_cmd)
+            .await
+            .ok()
+            .filter(|output| output.success)?;
+        parse_lspci_aspm(&output.stdout)
+    }
+
+    /// First RDMA device registered under a NIC's PCI device, e.g. "mlx5_0"
+    fn read_rdma_device(pci_path: &Path) -> Option<String> {
+        let mut devices: Vec<String> = fs::read_dir(pci_path.join("infiniband"))
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        devices.sort();
+        devices.into_iter().next()
+    }
+
+    /// Read PCIe AER correctable and uncorrectable error totals for a PCI device
+    fn read_pci_aer_counters(&self, pci_path: &Path) -> (Option<u64>, Option<u64>) {
+        let read_counter = |file: &str| {
+            self.read_sysfs_file(&pci_path.join(file))
+                .ok()
+                .and_then(|content| parse_aer_counters(&content))
+        };
+
+        let correctable = read_counter("aer_dev_correctable");
+        let uncorrectable = match (
+            read_counter("aer_dev_fatal"),
+            read_counter("aer_dev_nonfatal"),
+        ) {
+            (None, None) => None,
+            (fatal, nonfatal) => Some(fatal.unwrap_or(0) + nonfatal.unwrap_or(0)),
+        };
+
+        (correctable, uncorrectable)
+    }
+
+    /// Find the PCI storage controller a block device is attached to
+    ///
+    /// Walks up the resolved `device` link until a PCI device with a mass
+    /// storage class is found. Returns the controller description and whether
+    /// it is a RAID controller.
+    fn read_storage_controller(&self, block_device_path: &Path) -> (Option<String>, bool) {
+        let Ok(device_path) = fs::canonicalize(block_device_path.join("device")) else {
+            return (None, false);
+        };
+
+        device_path
+            .ancestors()
+            .find_map(|ancestor| {
+                let class = self.read_sysfs_file(&ancestor.join("class")).ok()?;
+                let kind = parse_pci_storage_class(&class)?;
+                let pci_address = ancestor.file_name()?.to_string_lossy();
+                Some((Some(format!("{kind} ({pci_address})")), kind == "RAID"))
+            })
+            .unwrap_or((None, false))
+    }
+
+    /// Read online, isolated and nohz_full CPUs plus turbo, SMT and cpufreq/cpuidle
+    /// driver state from the kernel command line and sysfs
+    fn read_cpu_sysfs_state(&self, cmdline_path: &Path, cpu_sysfs_path: &Path, cpu: &mut CpuInfo) {
+        let cmdline = self.read_sysfs_file(cmdline_path).unwrap_or_default();
+
+        let mut isolated = parse_cmdline_cpu_list(&cmdline, "isolcpus");
+        if let Ok(content) = self.read_sysfs_file(&cpu_sysfs_path.join("isolated")) {
+            isolated.extend(parse_cpu_list(&content).unwrap_or_default());
+        }
+        isolated.sort_unstable();
+        isolated.dedup();
+
+        cpu.isolated_cpus = isolated;
+        cpu.nohz_full_cpus = parse_cmdline_cpu_list(&cmdline, "nohz_full");
+        cpu.online_cpus = self
+            .read_sysfs_file(&cpu_sysfs_path.join("online"))
+            .ok()
+            .and_then(|content| parse_cpu_list(&content).ok())
+            .unwrap_or_default();
+        cpu.offline_cpus = self
+            .read_sysfs_file(&cpu_sysfs_path.join("offline"))
+            .ok()
+            .and_then(|content| parse_cpu_list(&content).ok())
+            .unwrap_or_default();
+
+        // Intel exposes intel_pstate/no_turbo, AMD and acpi-cpufreq expose cpufreq/boost
+        let no_turbo = self
+            .read_sysfs_file(&cpu_sysfs_path.join("intel_pstate/no_turbo"))
+            .ok();
+        let boost = self
+            .read_sysfs_file(&cpu_sysfs_path.join("cpufreq/boost"))
+            .ok();
+        cpu.turbo_enabled = parse_turbo_enabled(no_turbo.as_deref(), boost.as_deref());
+
+        cpu.smt_control = self
+            .read_sysfs_file(&cpu_sysfs_path.join("smt/control"))
+            .ok()
+            .and_then(|content| parse_smt_control(&content));
+        cpu.smt_active = self
+            .read_sysfs_file(&cpu_sysfs_path.join("smt/active"))
+            .ok()
+            .and_then(|content| parse_smt_active(&content));
+
+        cpu.cpufreq_driver = self
+            .read_sysfs_file(&cpu_sysfs_path.join("cpu0/cpufreq/scaling_driver"))
+            .ok()
+            .and_then(|content| parse_cpu_driver(&content));
+        cpu.scaling_governor = self
+            .read_sysfs_file(&cpu_sysfs_path.join("cpu0/cpufreq/scaling_governor"))
+            .ok()
+            .and_then(|content| parse_cpu_driver(&content));
+        cpu.cpuidle_driver = self
+            .read_sysfs_file(&cpu_sysfs_path.join("cpuidle/current_driver"))
+            .ok()
+            .and_then(|content| parse_cpu_driver(&content));
+
+        cpu.logical_cpus = self.read_logical_cpus(cpu_sysfs_path);
+    }
+
+    /// Read the core and socket of each logical CPU from `cpuN/topology`
+    fn read_logical_cpus(&self, cpu_sysfs_path: &Path) -> Vec<LogicalCpu> {
+        let Ok(entries) = fs::read_dir(cpu_sysfs_path) else {
+            return Vec::new();
+        };
+
+        let mut logical_cpus: Vec<LogicalCpu> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let cpu = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("cpu")?
+                    .parse()
+                    .ok()?;
+                let topology = entry.path().join("topology");
+                let read_id = |name: &str| {
+                    self.read_sysfs_file(&topology.join(name))
+                        .ok()
+                        .and_then(|content| content.trim().parse().ok())
+                };
+                Some(LogicalCpu {
+                    cpu,
+                    core: read_id("core_id")?,
+                    socket: read_id("physical_package_id")?,
+                })
+            })
+            .collect();
+
+        logical_cpus.sort_by_key(|logical| logical.cpu);
+        logical_cpus
+    }
+
+    /// Compare hugepages requested on the kernel command line with the allocated pools
+    ///
+    /// Allocation is read per page size from `hugepages_root`, falling back to
+    /// `HugePages_Total` in meminfo for the default size.
+    fn read_hugepage_pools(
+        &self,
+        cmdline_path: &Path,
+        meminfo_path: &Path,
+        hugepages_root: &Path,
+    ) -> Vec<HugepagePool> {
+        let Ok(cmdline) = self.read_sysfs_file(cmdline_path) else {
+            return Vec::new();
+        };
+        let default_pool = self
+            .read_sysfs_file(meminfo_path)
+            .ok()
+            .and_then(|meminfo| parse_meminfo_hugepages(&meminfo));
+        let default_size_kb = default_pool.map_or(2048, |(size_kb, _)| size_kb);
+
+        let mut pools = parse_cmdline_hugepages(&cmdline, default_size_kb);
+        for pool in &mut pools {
+            let nr_path = hugepages_root
+                .join(format!("hugepages-{}kB", pool.page_size_kb))
+                .join("nr_hugepages");
+            pool.allocated = self
+                .read_sysfs_file(&nr_path)
+                .ok()
+                .and_then(|nr| nr.trim().parse().ok())
+                .or_else(|| {
+                    default_pool
+                        .filter(|(size_kb, _)| *size_kb == pool.page_size_kb)
+                        .map(|(_, total)| total)
+                });
+        }
+        pools
+    }
+
+    /// Run `dmidecode -t {type_arg}`, or extract those types from the saved dump
+    async fn run_dmidecode(&self, type_arg: &str) -> Result<CommandOutput, SystemError> {
+        if let Some(source) = &self.dmidecode_source {
+            let dump = fs::read_to_string(source).map_err(|e| SystemError::IoErrorWithPath {
+                path: source.display().to_string(),
+                message: e.to_string(),
+            })?;
+            return Ok(CommandOutput {
+                stdout: filter_dmidecode_sections(&dump, &dmidecode_types_for_keyword(type_arg)),
+                stderr: String::new(),
+                exit_code: Some(0),
+                success: true,
+            });
+        }
+
+        if self.container.is_some() {
+            return Err(SystemError::NotAvailable {
+                resource: "dmidecode inside a container".to_string(),
+            });
+        }
+
+        let dmidecode_cmd = SystemCommand::new("dmidecode")
+            .args(&["-t", type_arg])
+            .timeout(Duration::from_secs(10));
+        self.command_executor
             .execute_with_privileges(&dmidecode_cmd)
             .await
             .map_err(|e| SystemError::CommandFailed {
                 command: "dmidecode".to_string(),
                 exit_code: None,
                 stderr: e.to_string(),
+            })
+    }
+
+    /// Read the kernel log for this boot from dmesg, falling back to the journal
+    async fn read_kernel_log(&self) -> Result<String, SystemError> {
+        // dmesg may be restricted (kernel.dmesg_restrict), so fall back to the journal
+        let dmesg_cmd = SystemCommand::new("dmesg").timeout(Duration::from_secs(10));
+        if let Ok(output) = self
+            .command_executor
+            .execute_with_privileges(&dmesg_cmd)
+            .await
+        {
+            if output.success {
+                return Ok(output.stdout);
+            }
+        }
+
+        let journal_cmd = SystemCommand::new("journalctl")
+            .args(&["-k", "-b", "--no-pager", "-q"])
+            .timeout(Duration::from_secs(30));
+        let output = self
+            .command_executor
+            .execute_with_privileges(&journal_cmd)
+            .await
+            .map_err(|e| SystemError::CommandFailed {
+                command: "journalctl -k".to_string(),
+                exit_code: None,
+                stderr: e.to_string(),
             })?;
+        if !output.success {
+            return Err(SystemError::CommandFailed {
+                command: "journalctl -k".to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+            });
+        }
+        Ok(output.stdout)
+    }
 
-        parse_dmidecode_chassis_info(&dmidecode_output.stdout).map_err(SystemError::ParseError)
+    /// Detect firmware boot mode from the presence of `firmware/efi` in sysfs
+    fn detect_boot_mode(&self, sysfs_root: &Path) -> &'static str {
+        if sysfs_root.join("firmware/efi").is_dir() {
+            "UEFI"
+        } else {
+            "Legacy"
+        }
     }
 
-    async fn get_motherboard_info(&self) -> Result<MotherboardInfo, SystemError> {
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "2"])
-            .timeout(Duration::from_secs(10));
-        let _dmidecode_output = self
+    /// Read partitions of every block device under `sys_block`, keyed by device name
+    ///
+    /// Partitions are the device subdirectories carrying a `partition` attribute.
+    fn read_partitions(&self, sys_block: &Path) -> HashMap<String, Vec<Partition>> {
+        let mut partitions = HashMap::new();
+        let Ok(entries) = fs::read_dir(sys_block) else {
+            return partitions;
+        };
+
+        for entry in entries.flatten() {
+            let device_name = entry.file_name().to_string_lossy().to_string();
+            let device_path = sys_block.join(&device_name);
+            let physical_block_size = self
+                .read_sysfs_file(&device_path.join("queue/physical_block_size"))
+                .unwrap_or_default();
+            let Ok(children) = fs::read_dir(&device_path) else {
+                continue;
+            };
+
+            let mut device_partitions: Vec<Partition> = children
+                .flatten()
+                .map(|child| child.path())
+                .filter(|path| path.join("partition").is_file())
+                .filter_map(|path| {
+                    let name = path.file_name()?.to_string_lossy().to_string();
+                    let start = self.read_sysfs_file(&path.join("start")).ok()?;
+                    let alignment_offset = self
+                        .read_sysfs_file(&path.join("alignment_offset"))
+                        .unwrap_or_default();
+                    parse_sysfs_partition(&name, &start, &alignment_offset, &physical_block_size)
+                })
+                .collect();
+            if !device_partitions.is_empty() {
+                device_partitions.sort_by_key(|p| p.start_sector);
+                partitions.insert(device_name, device_partitions);
+            }
+        }
+
+        partitions
+    }
+
+    /// Read automatic NUMA balancing from `kernel/numa_balancing` under a procfs sysctl root
+    fn read_numa_balancing(&self, sysctl_root: &Path) -> Option<bool> {
+        self.read_sysfs_file(&sysctl_root.join("kernel/numa_balancing"))
+            .ok()
+            .and_then(|content| parse_numa_balancing(&content))
+    }
+
+    /// Detect the container environment the collector itself runs in
+    ///
+    /// Mirrors `systemd-detect-virt --container`: the systemd marker file,
+    /// the docker/podman marker files, then PID 1's cgroup path.
+    fn detect_container(&self, root: &Path) -> Option<String> {
+        if let Ok(container) = self.read_sysfs_file(&root.join("run/systemd/container")) {
+            let container = container.trim();
+            if !container.is_empty() {
+                return Some(container.to_string());
+            }
+        }
+        if root.join(".dockerenv").exists() {
+            return Some("docker".to_string());
+        }
+        if root.join("run/.containerenv").exists() {
+            return Some("podman".to_string());
+        }
+        self.read_sysfs_file(&root.join("proc/1/cgroup"))
+            .ok()
+            .and_then(|cgroup| parse_container_from_cgroup(&cgroup))
+    }
+
+    /// Walk a cgroup v1 devices hierarchy and collect cgroups granted GPU device nodes
+    fn read_gpu_cgroup_allocations(&self, devices_root: &Path) -> Vec<GpuCgroupAllocation> {
+        let mut allocations = Vec::new();
+        let mut pending = vec![devices_root.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            if let Ok(list) = self.read_sysfs_file(&dir.join("devices.list")) {
+                let devices = parse_cgroup_gpu_devices(&list);
+                if !devices.is_empty() {
+                    let relative = dir.strip_prefix(devices_root).unwrap_or(&dir);
+                    allocations.push(GpuCgroupAllocation {
+                        cgroup: format!("/{}", relative.display()),
+                        devices,
+                    });
+                }
+            }
+
+            if let Ok(entries) = fs::read_dir(&dir) {
+                pending.extend(
+                    entries
+                        .flatten()
+                        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                        .map(|entry| entry.path()),
+                );
+            }
+        }
+
+        allocations.sort_by(|a, b| a.cgroup.cmp(&b.cgroup));
+        allocations
+    }
+
+    /// Read CPU and memory limits from the cgroup hierarchy mounted at `cgroup_root`
+    fn read_container_limits(&self, container: &str, cgroup_root: &Path) -> ContainerLimits {
+        let read = |file: &str| self.read_sysfs_file(&cgroup_root.join(file)).ok();
+
+        if cgroup_root.join("cgroup.controllers").exists() {
+            return ContainerLimits {
+                container: container.to_string(),
+                cgroup_version: 2,
+                cpu_quota_cores: read("cpu.max").and_then(|max| parse_cgroup_cpu_max(&max)),
+                cpu_shares: read("cpu.weight").and_then(|weight| weight.trim().parse().ok()),
+                memory_limit_bytes: read("memory.max").and_then(|max| parse_cgroup_limit(&max)),
+                cpu_throttled_periods: read("cpu.stat")
+                    .and_then(|stat| parse_cgroup_throttled_periods(&stat)),
+            };
+        }
+
+        let quota = read("cpu/cpu.cfs_quota_us").and_then(|q| q.trim().parse::<i64>().ok());
+        let period = read("cpu/cpu.cfs_period_us").and_then(|p| p.trim().parse::<i64>().ok());
+        ContainerLimits {
+            container: container.to_string(),
+            cgroup_version: 1,
+            cpu_quota_cores: match (quota, period) {
+                (Some(quota), Some(period)) if quota > 0 && period > 0 => {
+                    Some(quota as f64 / period as f64)
+                }
+                _ => None,
+            },
+            cpu_shares: read("cpu/cpu.shares").and_then(|shares| shares.trim().parse().ok()),
+            memory_limit_bytes: read("memory/memory.limit_in_bytes")
+                .and_then(|limit| parse_cgroup_limit(&limit)),
+            cpu_throttled_periods: read("cpu/cpu.stat")
+                .and_then(|stat| parse_cgroup_throttled_periods(&stat)),
+        }
+    }
+
+    /// Detect the container runtime from its socket, falling back to its binary
+    ///
+    /// CRI-O and containerd are checked before Docker, since Docker hosts also
+    /// run containerd but Kubernetes nodes talk to the CRI runtime directly.
+    async fn detect_container_runtime(&self, root: &Path) -> Option<String> {
+        let runtimes = [
+            ("cri-o", "run/crio/crio.sock", "crio"),
+            ("containerd", "run/containerd/containerd.sock", "containerd"),
+            ("docker", "run/docker.sock", "dockerd"),
+        ];
+
+        for (name, socket, _) in runtimes {
+            if root.join(socket).exists() {
+                return Some(name.to_string());
+            }
+        }
+        for (name, _, binary) in runtimes {
+            if let Ok(true) = self.command_executor.is_command_available(binary).await {
+                return Some(name.to_string());
+            }
+        }
+        None
+    }
+
+    /// List PCI devices with no bound driver from `/sys/bus/pci/devices`
+    ///
+    /// Only storage (0x01), network (0x02), display (0x03) and accelerator
+    /// (0x12) classes are reported; unbound bridges and platform functions
+    /// are normal and would only add noise.
+    fn read_unbound_pci_devices(&self, pci_root: &Path) -> Vec<PciDevice> {
+        let Ok(entries) = fs::read_dir(pci_root) else {
+            return Vec::new();
+        };
+
+        let mut devices: Vec<PciDevice> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.join("driver").exists() {
+                    return None;
+                }
+                let class = self.read_sysfs_file(&path.join("class")).ok()?;
+                let class = class.trim().to_string();
+                let base_class = class.trim_start_matches("0x").get(..2)?;
+                if !matches!(base_class, "01" | "02" | "03" | "12") {
+                    return None;
+                }
+                let read_id = |name: &str| {
+                    self.read_sysfs_file(&path.join(name))
+                        .map(|id| id.trim().trim_start_matches("0x").to_string())
+                        .unwrap_or_default()
+                };
+                Some(PciDevice {
+                    address: entry.file_name().to_string_lossy().into_owned(),
+                    vendor_id: read_id("vendor"),
+                    device_id: read_id("device"),
+                    class,
+                })
+            })
+            .collect();
+        devices.sort_by(|a, b| a.address.cmp(&b.address));
+        devices
+    }
+
+    /// Sample the RAPL package energy counters as (domain, energy_uj, max_energy_range_uj)
+    ///
+    /// Only top-level `intel-rapl:N` domains are read; their `intel-rapl:N:M`
+    /// subdomains are already included in the package total.
+    fn read_rapl_energy(&self, powercap_root: &Path) -> Vec<(String, u64, u64)> {
+        let Ok(entries) = fs::read_dir(powercap_root) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let index = name.strip_prefix("intel-rapl:")?;
+                if index.contains(':') {
+                    return None;
+                }
+                let path = entry.path();
+                let read_counter = |file: &str| {
+                    self.read_sysfs_file(&path.join(file))
+                        .ok()
+                        .and_then(|content| parse_sysfs_counter(&content))
+                };
+                Some((
+                    name,
+                    read_counter("energy_uj")?,
+                    read_counter("max_energy_range_uj")?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Average CPU package power over a short interval from the RAPL counters
+    ///
+    /// RAPL is a cumulative energy counter, so it needs two samples; the rate
+    /// uses the measured time between them rather than the nominal sleep.
+    async fn sample_rapl_watts(&self, powercap_root: &Path) -> Option<f64> {
+        let first = self.read_rapl_energy(powercap_root);
+        if first.is_empty() {
+            return None;
+        }
+        let sampled_at = Instant::now();
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let interval = sampled_at.elapsed();
+        let second = self.read_rapl_energy(powercap_root);
+
+        Some(
+            first
+                .iter()
+                .filter_map(|(domain, first_uj, max_uj)| {
+                    let (_, second_uj, _) = second.iter().find(|(d, _, _)| d == domain)?;
+                    rapl_power_watts(*first_uj, *second_uj, *max_uj, interval)
+                })
+                .sum(),
+        )
+    }
+
+    /// Read NUMA topology from sysfs and infer memory tiers
+    fn read_numa_topology(&self, node_root: &Path) -> HashMap<String, NumaNode> {
+        let mut nodes = HashMap::new();
+
+        let Ok(entries) = fs::read_dir(node_root) else {
+            return nodes;
+        };
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(id) = file_name
+                .to_str()
+                .and_then(|name| name.strip_prefix("node"))
+                .and_then(|id| id.parse::<i32>().ok())
+            else {
+                continue;
+            };
+
+            let node_path = entry.path();
+            let cpus = self
+                .read_sysfs_file(&node_path.join("cpulist"))
+                .ok()
+                .and_then(|content| parse_cpu_list(&content).ok())
+                .unwrap_or_default();
+            let memory = self
+                .read_sysfs_file(&node_path.join("meminfo"))
+                .ok()
+                .and_then(|content| parse_node_meminfo_total(&content))
+                .map(|kb| format!("{} MB", kb / 1024))
+                .unwrap_or_else(|| UNKNOWN.to_string());
+            let distances = self
+                .read_sysfs_file(&node_path.join("distance"))
+                .map(|content| parse_node_distances(&content))
+                .unwrap_or_default();
+
+            nodes.insert(
+                id.to_string(),
+                NumaNode {
+                    id,
+                    cpu_ranges: format_cpu_list(&cpus),
+                    cpus,
+                    memory,
+                    devices: Vec::new(),
+                    distances,
+                    memory_tier: None,
+                },
+            );
+        }
+
+        infer_numa_memory_tiers(&mut nodes);
+        nodes
+    }
+
+    /// Read SES enclosures and their slot-to-disk mapping from `enclosure` in sysfs
+    ///
+    /// The class is populated by the `ses` kernel driver; without it (or
+    /// without SES-capable enclosures) the result is empty.
+    fn read_enclosures(&self, class_root: &Path) -> Vec<Enclosure> {
+        let Ok(entries) = fs::read_dir(class_root.join("enclosure")) else {
+            return Vec::new();
+        };
+
+        let read_attr = |path: PathBuf| {
+            self.read_sysfs_file(&path)
+                .map(|s| s.trim().to_string())
+                .ok()
+                .filter(|s| !s.is_empty())
+        };
+
+        let mut enclosures: Vec<Enclosure> = entries
+            .flatten()
+            .map(|entry| {
+                let enclosure_path = entry.path();
+                let mut slots: Vec<EnclosureSlot> = fs::read_dir(&enclosure_path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|component| component.path().join("status").exists())
+                    .map(|component| {
+                        let slot_path = component.path();
+                        // The linked SCSI device lists its block device under `block/`
+                        let disk = fs::read_dir(slot_path.join("device/block"))
+                            .ok()
+                            .and_then(|mut blocks| blocks.next())
+                            .and_then(|block| block.ok())
+                            .map(|block| block.file_name().to_string_lossy().into_owned());
+                        EnclosureSlot {
+                            slot: component.file_name().to_string_lossy().into_owned(),
+                            disk,
+                        }
+                    })
+                    .collect();
+                slots.sort_by(|a, b| a.slot.cmp(&b.slot));
+
+                Enclosure {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    id: read_attr(enclosure_path.join("id")),
+                    vendor: read_attr(enclosure_path.join("device/vendor")),
+                    model: read_attr(enclosure_path.join("device/model")),
+                    slots,
+                }
+            })
+            .collect();
+
+        enclosures.sort_by(|a, b| a.name.cmp(&b.name));
+        enclosures
+    }
+
+    /// Read Fibre Channel HBA ports from `fc_host` and `fc_remote_ports` in sysfs
+    fn read_fc_hbas(&self, class_root: &Path) -> Vec<FcHba> {
+        let Ok(entries) = fs::read_dir(class_root.join("fc_host")) else {
+            return Vec::new();
+        };
+
+        let read_attr = |path: PathBuf| {
+            self.read_sysfs_file(&path)
+                .map(|s| s.trim().to_string())
+                .ok()
+                .filter(|s| !s.is_empty() && s != "unknown")
+        };
+
+        let mut hbas: Vec<FcHba> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let host_path = entry.path();
+                let port_name = self
+                    .read_sysfs_file(&host_path.join("port_name"))
+                    .ok()
+                    .and_then(|content| parse_fc_wwn(&content))?;
+
+                let remote_ports = self.read_fc_remote_ports(class_root, &name);
+
+                Some(FcHba {
+                    port_name,
+                    node_name: read_attr(host_path.join("node_name"))
+                        .and_then(|content| parse_fc_wwn(&content)),
+                    speed: read_attr(host_path.join("speed")),
+                    port_state: read_attr(host_path.join("port_state")),
+                    symbolic_name: read_attr(host_path.join("symbolic_name")),
+                    remote_ports,
+                    name,
+                })
+            })
+            .collect();
+
+        hbas.sort_by(|a, b| a.name.cmp(&b.name));
+        hbas
+    }
+
+    /// Read World Wide Port Names of remote ports discovered by an FC host
+    fn read_fc_remote_ports(&self, class_root: &Path, host_name: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(class_root.join("fc_remote_ports")) else {
+            return Vec::new();
+        };
+
+        // Remote ports are named rport-{host}:{bus}-{id}
+        let prefix = format!("rport-{}:", host_name.trim_start_matches("host"));
+        let mut remote_ports: Vec<String> = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+            .filter_map(|entry| {
+                self.read_sysfs_file(&entry.path().join("port_name"))
+                    .ok()
+                    .and_then(|content| parse_fc_wwn(&content))
+            })
+            .collect();
+
+        remote_ports.sort();
+        remote_ports
+    }
+
+    /// Read a snapshot of interface counters from a sysfs statistics directory
+    fn read_interface_statistics(&self, stats_path: &Path, iface: &mut NetworkInterface) {
+        let read_counter = |name: &str| {
+            self.read_sysfs_file(&stats_path.join(name))
+                .ok()
+                .and_then(|content| parse_sysfs_counter(&content))
+        };
+
+        iface.rx_bytes = read_counter("rx_bytes");
+        iface.tx_bytes = read_counter("tx_bytes");
+        iface.rx_errors = read_counter("rx_errors");
+        iface.tx_errors = read_counter("tx_errors");
+        iface.rx_dropped = read_counter("rx_dropped");
+        iface.tx_dropped = read_counter("tx_dropped");
+    }
+}
+
+#[async_trait]
+impl SystemInfoProvider for LinuxSystemInfoProvider {
+    async fn get_cpu_info(&self) -> Result<CpuInfo, SystemError> {
+        let lscpu_cmd = SystemCommand::new("lscpu").timeout(Duration::from_secs(10));
+        let lscpu_output = self
             .command_executor
-            .execute_with_privileges(&dmidecode_cmd)
+            .execute(&lscpu_cmd)
             .await
             .map_err(|e| SystemError::CommandFailed {
-                command: "dmidecode".to_string(),
+                command: "lscpu".to_string(),
                 exit_code: None,
                 stderr: e.to_string(),
             })?;
 
-        Ok(MotherboardInfo {
-            manufacturer: "Unknown".to_string(),
-            product_name: "Unknown".to_string(),
-            version: "Unknown".to_string(),
-            serial: "Unknown".to_string(),
-            features: "Unknown".to_string(),
-            location: "Unknown".to_string(),
-            type_: "Motherboard".to_string(),
-        })
+        let lscpu_info =
+            parse_lscpu_output(&lscpu_output.stdout).map_err(SystemError::ParseError)?;
+
+        let mut cpu_info = match self.run_dmidecode("processor").await {
+            Ok(dmidecode_output) if dmidecode_output.success => {
+                match parse_dmidecode_cpu(&dmidecode_output.stdout) {
+                    Ok(dmidecode_info) => combine_cpu_info(lscpu_info, dmidecode_info),
+                    Err(_) => lscpu_info,
+                }
+            }
+            _ => lscpu_info,
+        };
+
+        self.read_cpu_sysfs_state(
+            Path::new("/proc/cmdline"),
+            Path::new("/sys/devices/system/cpu"),
+            &mut cpu_info,
+        );
+
+        // Steal time only exists under a hypervisor and needs two samples
+        if !self.fast_mode && cpu_info.flags.iter().any(|flag| flag == "hypervisor") {
+            let proc_stat = Path::new("/proc/stat");
+            let sample = || {
+                self.read_sysfs_file(proc_stat)
+                    .ok()
+                    .and_then(|content| parse_proc_stat_cpu(&content))
+            };
+            if let Some(first) = sample() {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                cpu_info.cpu_steal_percent =
+                    sample().and_then(|second| cpu_steal_percent(first, second));
+            }
+        }
+
+        Ok(cpu_info)
     }
 
-    async fn get_system_info(&self) -> Result<SystemInfo, SystemError> {
-        let dmidecode_cmd = SystemCommand::new("dmidecode")
-            .args(&["-t", "system"])
-            .timeout(Duration::from_secs(10));
-        let dmidecode_output = self
+    async fn get_memory_info(&self) -> Result<MemoryInfo, SystemError> {
+        let free_cmd = SystemCommand::new("free")
+            .args(&["-b"])
+            .timeout(Duration::from_secs(5));
+        let free_output = self
             .command_executor
-            .execute_with_privileges(&dmidecode_cmd)
+            .execute(&free_cmd)
             .await
             .map_err(|e| SystemError::CommandFailed {
-                command: "dmidecode".to_string(),
+                command: "free".to_string(),
                 exit_code: None,
                 stderr: e.to_string(),
             })?;
 
+        let total_memory =
+            parse_free_output(&free_output.stdout).map_err(SystemError::ParseError)?;
+
+        let mut ecc_enabled = None;
+        let (modules, type_, speed) = match self.run_dmidecode("memory").await {
+            Ok(dmidecode_output) if dmidecode_output.success => {
+                ecc_enabled = parse_dmidecode_memory_ecc(&dmidecode_output.stdout);
+                match parse_dmidecode_memory(&dmidecode_output.stdout) {
+                    Ok(modules) if !modules.is_empty() => {
+                        let type_ = determine_memory_type(&modules);
+                        let speed = determine_memory_speed(&modules);
+                        (modules, type_, speed)
+                    }
+                    _ => (Vec::new(), UNKNOWN.to_string(), UNKNOWN.to_string()),
+                }
+            }
+            _ => (Vec::new(), UNKNOWN.to_string(), UNKNOWN.to_string()),
+        };
+
+        let transparent_hugepages = self
+            .read_sysfs_file(Path::new("/sys/kernel/mm/transparent_hugepage/enabled"))
+            .ok()
+            .and_then(|content| parse_transparent_hugepages(&content));
+        let hugepage_pools = self.read_hugepage_pools(
+            Path::new("/proc/cmdline"),
+            Path::new("/proc/meminfo"),
+            Path::new("/sys/kernel/mm/hugepages"),
+        );
+
+        Ok(MemoryInfo {
+            total: total_memory,
+            type_,
+            speed,
+            modules,
+            transparent_hugepages,
+            hugepage_pools,
+            nvdimm_namespaces: self.read_nvdimm_namespaces().await,
+            ecc_enabled,
+        })
+    }
+
+    async fn get_storage_info(&self) -> Result<StorageInfo, SystemError> {
+        let mut devices = Vec::new();
+
+        // Try sysfs first
+        if let Ok(sysfs_devices) = self.detect_storage_sysfs().await {
+            log::debug!("sysfs detected {} storage devices", sysfs_devices.len());
+            devices = sysfs_devices;
+        } else {
+            log::warn!("sysfs storage detection failed, trying next method");
+        }
+
+        // Enrich with lsblk
+        if let Ok(lsblk_devices) = self.detect_storage_lsblk().await {
+            log::debug!(
+                "lsblk found {} devices for additional info",
+                lsblk_devices.len()
+            );
+            self.merge_storage_info(&mut devices, lsblk_devices);
+        }
+
+        // Fallback to sysinfo
+        if devices.is_empty() {
+            log::warn!("No devices from sysfs/lsblk, trying sysinfo as fallback");
+            if let Ok(sysinfo_devices) = self.detect_storage_sysinfo() {
+                devices = sysinfo_devices;
+            }
+        }
+
+        // Filter virtual devices and sort
+        devices.retain(|d| d.device_type != StorageType::Virtual);
+
+        for device in &mut devices {
+            if device.size_gb == 0.0 && device.size_bytes > 0 {
+                device.calculate_size_fields();
+            }
+            device.set_device_path();
+        }
+
+        if !self.fast_mode {
+            self.enrich_storage_smart_info(&mut devices).await;
+            self.enrich_nvme_health(&mut devices).await;
+            self.enrich_raid_virtual_disks(&mut devices).await;
+        }
+
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(StorageInfo {
+            devices,
+            enclosures: self.read_enclosures(Path::new("/sys/class")),
+        })
+    }
+
+    async fn get_gpu_info(&self) -> Result<GpuInfo, SystemError> {
+        let nvidia_cmd = SystemCommand::new("nvidia-smi")
+            .args(&[
+                "--query-gpu=index,name,uuid,memory.total,pci.bus_id,pcie.replay.counter,power.limit,power.default_limit,pstate",
+                "--format=csv,noheader,nounits",
+            ])
+            .timeout(Duration::from_secs(10));
+
+        let mut devices = Vec::new();
+
+        if let Ok(nvidia_output) = self.command_executor.execute(&nvidia_cmd).await {
+            if nvidia_output.success {
+                for (index, line) in nvidia_output.stdout.lines().enumerate() {
+                    let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+                    if parts.len() >= 4 {
+                        let memory_mb: u64 = parts[3].parse().unwrap_or(0);
+                        // Unsupported fields read "[N/A]" or "[Not Supported]"
+                        let field = |i: usize| parts.get(i).filter(|v| !v.starts_with('['));
+                        devices.push(GpuDevice {
+                            index: index as u32,
+                            name: parts[1].to_string(),
+                            uuid: parts[2].to_string(),
+                            memory: format!("{} MB", parts[3]),
+                            memory_total_mb: memory_mb,
+                            pci_id: String::new(),
+                            pci_bus_id: parts.get(4).map(|id| normalize_pci_address(id)),
+                            vendor: "NVIDIA".to_string(),
+                            vendor_enum: GpuVendor::Nvidia,
+                            numa_node: None,
+                            detection_method: "nvidia-smi".to_string(),
+                            pcie_replay_counter: field(5).and_then(|v| v.parse().ok()),
+                            power_limit_watts: field(6).and_then(|v| v.parse().ok()),
+                            default_power_limit_watts: field(7).and_then(|v| v.parse().ok()),
+                            performance_state: field(8).map(|v| v.to_string()),
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+        }
+
+        if devices.is_empty() {
+            devices = self.detect_intel_gpus().await;
+        }
+
+        for device in &mut devices {
+            if let Some(bus_id) = &device.pci_bus_id {
+                let pci_path = Path::new("/sys/bus/pci/devices").join(bus_id);
+                (
+                    device.aer_correctable_errors,
+                    device.aer_uncorrectable_errors,
+                ) = self.read_pci_aer_counters(&pci_path);
+                (device.pcie_path, device.numa_node) = self.read_pcie_locality(&pci_path);
+                device.aspm_state = self.read_aspm_state(&pci_path);
+                (device.pcie_gen, device.pcie_width) = self.read_pcie_link(&pci_path, "current");
+                (device.pcie_max_gen, device.pcie_max_width) =
+                    self.read_pcie_link(&pci_path, "max");
+                device.resizable_bar_enabled = self
+                    .read_sysfs_file(&pci_path.join("resource"))
+                    .ok()
+                    .and_then(|resource| parse_pci_resource_bar1_size(&resource))
+                    .and_then(|bar1| is_resizable_bar_enabled(bar1, device.memory_total_mb));
+                if device.aspm_state.is_none() && !self.fast_mode {
+                    device.aspm_state = self.lspci_aspm_state(bus_id).await;
+                }
+            }
+        }
+
+        let mut nvlink = Vec::new();
+        if !devices.is_empty() && !self.fast_mode {
+            self.enrich_gpu_query_details(&mut devices).await;
+            self.enrich_gpu_mig_instances(&mut devices).await;
+            self.enrich_gpu_nvlinks(&mut devices).await;
+            nvlink = self.read_gpu_nvlink_topology(&devices).await;
+        }
+
+        // Fallback to lspci
+        if devices.is_empty() {
+            let lspci_cmd = SystemCommand::new("lspci")
+                .args(&["-nn"])
+                .timeout(Duration::from_secs(5));
+
+            if let Ok(lspci_output) = self.command_executor.execute(&lspci_cmd).await {
+                if lspci_output.success {
+                    let mut gpu_index = 0;
+                    for line in lspci_output.stdout.lines() {
+                        if line.to_lowercase().contains("vga") || line.to_lowercase().contains("3d")
+                        {
+                            devices.push(GpuDevice {
+                                index: gpu_index,
+                                name: line.to_string(),
+                                uuid: format!("pci-gpu-{gpu_index}"),
+                                memory: UNKNOWN.to_string(),
+                                pci_id: String::new(),
+                                vendor: UNKNOWN.to_string(),
+                                vendor_enum: GpuVendor::Unknown,
+                                numa_node: None,
+                                detection_method: "lspci".to_string(),
+                                ..Default::default()
+                            });
+                            gpu_index += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(GpuInfo {
+            gpus_homogeneous: are_gpus_homogeneous(&devices),
+            recent_xid_errors: None,
+            nvlink,
+            devices,
+        })
+    }
+
+    async fn get_network_info(&self) -> Result<NetworkInfo, SystemError> {
+        let ip_cmd = SystemCommand::new("ip")
+            .args(&["addr", "show"])
+            .timeout(Duration::from_secs(5));
+        let ip_output = self.command_executor.execute(&ip_cmd).await.map_err(|e| {
+            SystemError::CommandFailed {
+                command: "ip".to_string(),
+                exit_code: None,
+                stderr: e.to_string(),
+            }
+        })?;
+
+        let mut interfaces = parse_ip_output(&ip_output.stdout).map_err(SystemError::ParseError)?;
+
+        let has_ethtool = matches!(
+            self.command_executor.is_command_available("ethtool").await,
+            Ok(true)
+        );
+
+        // Enrich with sysfs data
+        for iface in &mut interfaces {
+            self.enrich_network_interface_sysfs(iface);
+            if iface.is_virtual {
+                continue;
+            }
+            if has_ethtool {
+                self.enrich_ethtool_queues(iface).await;
+            }
+            if iface.aspm_state.is_some() || self.fast_mode {
+                continue;
+            }
+            // Kernels without CONFIG_PCIEASPM have no link/ attributes
+            let device_path = PathBuf::from("/sys/class/net")
+                .join(&iface.name)
+                .join("device");
+            if let Some(address) = fs::canonicalize(&device_path).ok().and_then(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            }) {
+                iface.aspm_state = self.lspci_aspm_state(&address).await;
+            }
+        }
+        Ok(NetworkInfo {
+            interfaces,
+            infiniband: None,
+            fc_hbas: self.read_fc_hbas(Path::new("/sys/class")),
+        })
+    }
+
+    async fn get_bios_info(&self) -> Result<BiosInfo, SystemError> {
+        let dmidecode_output = self.run_dmidecode("bios").await?;
+
+        parse_dmidecode_bios_info(&dmidecode_output.stdout).map_err(SystemError::ParseError)
+    }
+
+    async fn get_chassis_info(&self) -> Result<ChassisInfo, SystemError> {
+        let dmidecode_output = self.run_dmidecode("chassis").await?;
+
+        parse_dmidecode_chassis_info(&dmidecode_output.stdout).map_err(SystemError::ParseError)
+    }
+
+    async fn get_motherboard_info(&self) -> Result<MotherboardInfo, SystemError> {
+        let _dmidecode_output = self.run_dmidecode("2").await?;
+
+        Ok(MotherboardInfo {
+            manufacturer: UNKNOWN.to_string(),
+            product_name: UNKNOWN.to_string(),
+            version: UNKNOWN.to_string(),
+            serial: UNKNOWN.to_string(),
+            features: UNKNOWN.to_string(),
+            location: UNKNOWN.to_string(),
+            type_: "Motherboard".to_string(),
+        })
+    }
+
+    async fn get_system_info(&self) -> Result<SystemInfo, SystemError> {
+        let dmidecode_output = self.run_dmidecode("system").await?;
+
         parse_dmidecode_system_info(&dmidecode_output.stdout).map_err(SystemError::ParseError)
     }
 
     async fn get_numa_topology(&self) -> Result<HashMap<String, NumaNode>, SystemError> {
-        Ok(HashMap::new())
+        Ok(self.read_numa_topology(Path::new("/sys/devices/system/node")))
     }
 
     async fn get_hostname(&self) -> Result<String, SystemError> {
@@ -785,4 +2133,1205 @@ impl SystemInfoProvider for LinuxSystemInfoProvider {
     async fn get_missing_dependencies(&self) -> Result<Vec<String>, SystemError> {
         Ok(self.check_required_commands().await)
     }
+
+    async fn get_tool_versions(&self) -> Result<HashMap<String, String>, SystemError> {
+        if self.fast_mode {
+            return Ok(HashMap::new());
+        }
+
+        let tools = [
+            ("dmidecode", "--version"),
+            ("nvidia-smi", "--version"),
+            ("lspci", "--version"),
+            ("ipmitool", "-V"),
+        ];
+
+        let mut versions = HashMap::new();
+        for (tool, flag) in tools {
+            if self.container.is_some() && matches!(tool, "dmidecode" | "ipmitool") {
+                continue;
+            }
+            let cmd = SystemCommand::new(tool)
+                .args(&[flag])
+                .timeout(Duration::from_secs(5));
+            let Ok(output) = self.command_executor.execute(&cmd).await else {
+                continue;
+            };
+            // Some tools print their version to stderr
+            if let Some(version) =
+                parse_tool_version(&output.stdout).or_else(|| parse_tool_version(&output.stderr))
+            {
+                versions.insert(tool.to_string(), version);
+            }
+        }
+
+        Ok(versions)
+    }
+
+    async fn get_failed_units(&self) -> Result<Vec<String>, SystemError> {
+        let cmd = SystemCommand::new("systemctl")
+            .args(&["--failed", "--plain", "--no-legend"])
+            .timeout(Duration::from_secs(10));
+
+        // Non-systemd systems have no systemctl; report no failed units
+        match self.command_executor.execute(&cmd).await {
+            Ok(output) if output.success => Ok(parse_systemctl_failed_units(&output.stdout)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_boot_mode(&self) -> Result<String, SystemError> {
+        Ok(self.detect_boot_mode(Path::new("/sys")).to_string())
+    }
+
+    async fn get_tpm_info(&self) -> Result<TpmInfo, SystemError> {
+        // No /sys/class/tpm just means no TPM driver is bound
+        let version_major = self
+            .read_sysfs_file(Path::new("/sys/class/tpm/tpm0/tpm_version_major"))
+            .ok();
+        let dmidecode_output = match self.run_dmidecode("43").await {
+            Ok(output) if output.success => output.stdout,
+            _ => String::new(),
+        };
+        Ok(parse_tpm_info(version_major.as_deref(), &dmidecode_output))
+    }
+
+    async fn get_numa_balancing(&self) -> Result<Option<bool>, SystemError> {
+        Ok(self.read_numa_balancing(Path::new("/proc/sys")))
+    }
+
+    async fn get_bios_settings(&self) -> Result<HashMap<String, String>, SystemError> {
+        if self.fast_mode {
+            return Ok(HashMap::new());
+        }
+        let attributes = self.read_vendor_bios_attributes().await;
+        Ok(normalize_bios_settings(&attributes))
+    }
+
+    async fn get_os_machine_id(&self) -> Result<String, SystemError> {
+        let path = Path::new("/etc/machine-id");
+        self.read_sysfs_file(path)
+            .map(|content| content.trim().to_string())
+            .map_err(|e| SystemError::IoErrorWithPath {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn get_partitions(&self) -> Result<HashMap<String, Vec<Partition>>, SystemError> {
+        Ok(self.read_partitions(Path::new("/sys/block")))
+    }
+
+    async fn get_xid_errors(&self) -> Result<Vec<XidError>, SystemError> {
+        Ok(parse_xid_errors(&self.read_kernel_log().await?))
+    }
+
+    async fn get_kernel_hardware_errors(&self) -> Result<Vec<KernelError>, SystemError> {
+        Ok(parse_kernel_hardware_errors(&self.read_kernel_log().await?))
+    }
+
+    async fn get_accelerators(&self) -> Result<Vec<Accelerator>, SystemError> {
+        let lspci_cmd = SystemCommand::new("lspci")
+            .args(&["-Dnnmm"])
+            .timeout(Duration::from_secs(10));
+        let output = self
+            .command_executor
+            .execute(&lspci_cmd)
+            .await
+            .map_err(|e| SystemError::CommandFailed {
+                command: "lspci".to_string(),
+                exit_code: None,
+                stderr: e.to_string(),
+            })?;
+
+        Ok(parse_lspci_accelerators(&output.stdout))
+    }
+
+    async fn get_unbound_pci_devices(&self) -> Result<Vec<PciDevice>, SystemError> {
+        Ok(self.read_unbound_pci_devices(Path::new("/sys/bus/pci/devices")))
+    }
+
+    async fn get_container_runtime(&self) -> Result<ContainerRuntimeInfo, SystemError> {
+        let container_runtime = self.detect_container_runtime(Path::new("/")).await;
+
+        // Present alone is not enough; `info` fails when the driver is not reachable
+        let toolkit_cmd = SystemCommand::new("nvidia-container-cli")
+            .args(&["info"])
+            .timeout(Duration::from_secs(10));
+        let nvidia_container_toolkit_present = matches!(self.command_executor.execute(&toolkit_cmd).await, Ok(output) if output.success);
+
+        Ok(ContainerRuntimeInfo {
+            container_runtime,
+            nvidia_container_toolkit_present,
+        })
+    }
+
+    async fn get_container_limits(&self) -> Result<ContainerLimits, SystemError> {
+        match &self.container {
+            Some(container) => {
+                Ok(self.read_container_limits(container, Path::new("/sys/fs/cgroup")))
+            }
+            None => Err(SystemError::NotAvailable {
+                resource: "container cgroup limits".to_string(),
+            }),
+        }
+    }
+
+    async fn get_gpu_cgroup_allocations(&self) -> Result<Vec<GpuCgroupAllocation>, SystemError> {
+        // cgroup v2 enforces device access with eBPF programs, which expose no allowlist
+        let devices_root = Path::new("/sys/fs/cgroup/devices");
+        if !devices_root.join("devices.list").exists() {
+            return Err(SystemError::NotAvailable {
+                resource: "cgroup v1 devices controller".to_string(),
+            });
+        }
+        Ok(self.read_gpu_cgroup_allocations(devices_root))
+    }
+
+    async fn get_bmc_network(&self) -> Result<BmcNetwork, SystemError> {
+        if self.container.is_some() {
+            return Err(SystemError::NotAvailable {
+                resource: "BMC (ipmitool is skipped inside a container)".to_string(),
+            });
+        }
+
+        let lan_cmd = SystemCommand::new("ipmitool")
+            .args(&["lan", "print"])
+            .timeout(Duration::from_secs(10));
+        match self
+            .command_executor
+            .execute_with_privileges(&lan_cmd)
+            .await
+        {
+            Ok(output) if output.success => Ok(parse_ipmitool_lan_print(&output.stdout)),
+            _ => Err(SystemError::NotAvailable {
+                resource: "ipmitool lan print".to_string(),
+            }),
+        }
+    }
+
+    async fn get_sel_events(&self) -> Result<Vec<SelEvent>, SystemError> {
+        if self.container.is_some() {
+            return Err(SystemError::NotAvailable {
+                resource: "BMC (ipmitool is skipped inside a container)".to_string(),
+            });
+        }
+
+        let count = SEL_RECENT_EVENT_COUNT.to_string();
+        let sel_cmd = SystemCommand::new("ipmitool")
+            .args(&["sel", "elist", "last", &count])
+            .timeout(Duration::from_secs(30));
+        match self
+            .command_executor
+            .execute_with_privileges(&sel_cmd)
+            .await
+        {
+            Ok(output) if output.success => Ok(parse_ipmitool_sel_elist(&output.stdout)),
+            _ => Err(SystemError::NotAvailable {
+                resource: "ipmitool sel elist".to_string(),
+            }),
+        }
+    }
+
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, SystemError> {
+        // Sensor readings are only collected out of band, over Redfish
+        Err(SystemError::NotAvailable {
+            resource: "BMC sensors".to_string(),
+        })
+    }
+
+    async fn get_firmware_inventory(&self) -> Result<Vec<FirmwareComponent>, SystemError> {
+        let config = self
+            .redfish
+            .as_ref()
+            .ok_or_else(|| SystemError::NotAvailable {
+                resource: "Redfish endpoint".to_string(),
+            })?;
+        let client = RedfishClient::new(config, Duration::from_secs(30)).map_err(|e| {
+            SystemError::NotAvailable {
+                resource: format!("Redfish client: {e}"),
+            }
+        })?;
+        client
+            .firmware_inventory()
+            .await
+            .map_err(SystemError::IoError)
+    }
+
+    async fn get_cloud_info(&self) -> Result<Option<CloudInfo>, SystemError> {
+        let client =
+            CloudMetadataClient::with_defaults().map_err(|e| SystemError::NotAvailable {
+                resource: format!("cloud metadata client: {e}"),
+            })?;
+        Ok(client.fetch().await)
+    }
+
+    async fn get_time_sync(&self) -> Result<TimeSyncInfo, SystemError> {
+        // Prefer chrony, then fall back to systemd-timesyncd
+        let chronyc_cmd = SystemCommand::new("chronyc")
+            .args(&["tracking"])
+            .timeout(Duration::from_secs(5));
+        if let Ok(output) = self.command_executor.execute(&chronyc_cmd).await {
+            if output.success {
+                return Ok(parse_chronyc_tracking(&output.stdout));
+            }
+        }
+
+        let timedatectl_cmd = SystemCommand::new("timedatectl")
+            .args(&["show-timesync"])
+            .timeout(Duration::from_secs(5));
+        match self.command_executor.execute(&timedatectl_cmd).await {
+            Ok(output) if output.success => Ok(parse_timedatectl_timesync(&output.stdout)),
+            _ => Err(SystemError::NotAvailable {
+                resource: "chronyc or timedatectl".to_string(),
+            }),
+        }
+    }
+
+    async fn get_watchdog(&self) -> Result<WatchdogInfo, SystemError> {
+        let kernel_watchdog = self.read_kernel_watchdog(Path::new("/sys/class/watchdog/watchdog0"));
+        if kernel_watchdog
+            .as_ref()
+            .is_some_and(|watchdog| watchdog.active)
+            || self.fast_mode
+        {
+            return kernel_watchdog.ok_or_else(|| SystemError::NotAvailable {
+                resource: "/sys/class/watchdog/watchdog0".to_string(),
+            });
+        }
+
+        // The BMC watchdog runs independently of any kernel driver
+        let ipmi_watchdog = if self.container.is_none() {
+            let watchdog_cmd = SystemCommand::new("ipmitool")
+                .args(&["mc", "watchdog", "get"])
+                .timeout(Duration::from_secs(10));
+            match self
+                .command_executor
+                .execute_with_privileges(&watchdog_cmd)
+                .await
+            {
+                Ok(output) if output.success => parse_ipmitool_watchdog_get(&output.stdout),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        match (kernel_watchdog, ipmi_watchdog) {
+            (_, Some(ipmi)) if ipmi.active => Ok(ipmi),
+            (Some(kernel), _) => Ok(kernel),
+            (None, Some(ipmi)) => Ok(ipmi),
+            (None, None) => Err(SystemError::NotAvailable {
+                resource: "watchdog".to_string(),
+            }),
+        }
+    }
+
+    async fn get_power_info(&self) -> Result<PowerInfo, SystemError> {
+        // The BMC reading covers the whole system, including GPUs and NICs
+        let system_watts = if self.container.is_some() {
+            None
+        } else {
+            let dcmi_cmd = SystemCommand::new("ipmitool")
+                .args(&["dcmi", "power", "reading"])
+                .timeout(Duration::from_secs(10));
+            match self
+                .command_executor
+                .execute_with_privileges(&dcmi_cmd)
+                .await
+            {
+                Ok(output) if output.success => parse_dcmi_power_reading(&output.stdout),
+                _ => None,
+            }
+        };
+
+        let cpu_package_watts = if self.fast_mode {
+            None
+        } else {
+            self.sample_rapl_watts(Path::new("/sys/class/powercap"))
+                .await
+        };
+
+        if system_watts.is_none() && cpu_package_watts.is_none() {
+            return Err(SystemError::NotAvailable {
+                resource: "ipmitool dcmi or RAPL".to_string(),
+            });
+        }
+        Ok(PowerInfo {
+            power_draw_watts: system_watts,
+            source: system_watts.map(|_| "dcmi".to_string()),
+            cpu_package_watts,
+            power_supplies: Vec::new(),
+        })
+    }
+
+    async fn get_raw_sources(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(self
+            .raw_recorder
+            .as_ref()
+            .map(|recorder| recorder.raw_sources())
+            .unwrap_or_default())
+    }
+
+    async fn probe_capabilities(&self) -> Result<Capabilities, SystemError> {
+        let subsystems: &[(&str, &[&str])] = &[
+            ("cpu", &["lscpu"]),
+            ("memory", &["dmidecode", "free"]),
+            ("storage", &["lsblk"]),
+            ("smart", &["smartctl"]),
+            ("nvme", &["nvme"]),
+            ("gpu", &["nvidia-smi"]),
+            ("network", &["ip"]),
+            ("dmi", &["dmidecode"]),
+            ("accelerators", &["lspci"]),
+        ];
+
+        Ok(Capabilities {
+            adapter: "linux".to_string(),
+            has_privileges: self.has_required_privileges().await.unwrap_or(false),
+            subsystems: probe_subsystems(self.command_executor.as_ref(), subsystems).await,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::UnixCommandExecutor;
+    use crate::domain::CommandError;
+    use tempfile::tempdir;
+
+    fn create_test_provider() -> LinuxSystemInfoProvider {
+        LinuxSystemInfoProvider::new(Arc::new(UnixCommandExecutor::with_defaults()))
+    }
+
+    /// Command executor that records invocations and returns canned output
+    #[derive(Default)]
+    struct MockCommandExecutor {
+        invocations: std::sync::Mutex<Vec<String>>,
+        unavailable_tools: Vec<&'static str>,
+    }
+
+    impl MockCommandExecutor {
+        fn invocations(&self) -> Vec<String> {
+            self.invocations.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl CommandExecutor for MockCommandExecutor {
+        async fn execute(&self, command: &SystemCommand) -> Result<CommandOutput, CommandError> {
+            let invocation = format!("{} {}", command.program, command.args.join(" "));
+            self.invocations.lock().unwrap().push(invocation.clone());
+
+            let stdout = if invocation.starts_with("nvidia-smi --query-gpu") {
+                "0, NVIDIA H100 80GB HBM3, GPU-0, 81559, 00000000:2A:00.0, 17, 500.00, 700.00, P0\n"
+                    .to_string()
+            } else if invocation == "nvidia-smi -q" {
+                // The first section belongs to a GPU the CSV query did not list
+                "GPU 00000000:18:00.0\n    Temperature\n        GPU Current Temp : 51 C\n\
+                 GPU 00000000:2A:00.0\n    Temperature\n        GPU Current Temp : 34 C\n    \
+                 Retired Pages\n        Single Bit ECC : 2\n"
+                    .to_string()
+            } else if invocation.starts_with("lscpu") {
+                "Architecture: x86_64\nModel name: Test CPU\n".to_string()
+            } else if invocation == "ipmitool dcmi power reading" {
+                "    Instantaneous power reading:                  3412 Watts\n".to_string()
+            } else {
+                String::new()
+            };
+            Ok(CommandOutput {
+                stdout,
+                stderr: String::new(),
+                exit_code: Some(0),
+                success: true,
+            })
+        }
+
+        async fn execute_with_privileges(
+            &self,
+            command: &SystemCommand,
+        ) -> Result<CommandOutput, CommandError> {
+            self.execute(command).await
+        }
+
+        async fn is_command_available(&self, command_name: &str) -> Result<bool, CommandError> {
+            Ok(!self.unavailable_tools.contains(&command_name))
+        }
+
+        async fn get_command_path(
+            &self,
+            command_name: &str,
+        ) -> Result<Option<String>, CommandError> {
+            Ok(Some(format!("/usr/bin/{command_name}")))
+        }
+
+        async fn has_elevated_privileges(&self) -> Result<bool, CommandError> {
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_read_interface_statistics() {
+        let temp_dir = tempdir().unwrap();
+        let stats_path = temp_dir.path().join("statistics");
+        fs::create_dir_all(&stats_path).unwrap();
+        for (counter, value) in [
+            ("rx_bytes", "987654321\n"),
+            ("tx_bytes", "123456789\n"),
+            ("rx_errors", "42\n"),
+            ("tx_errors", "0\n"),
+            ("rx_dropped", "7\n"),
+        ] {
+            fs::write(stats_path.join(counter), value).unwrap();
+        }
+
+        let provider = create_test_provider();
+        let mut iface = NetworkInterface {
+            name: "eth0".to_string(),
+            ..Default::default()
+        };
+        provider.read_interface_statistics(&stats_path, &mut iface);
+
+        assert_eq!(iface.rx_bytes, Some(987654321));
+        assert_eq!(iface.tx_bytes, Some(123456789));
+        assert_eq!(iface.rx_errors, Some(42));
+        assert_eq!(iface.tx_errors, Some(0));
+        assert_eq!(iface.rx_dropped, Some(7));
+        assert_eq!(iface.tx_dropped, None);
+    }
+
+    #[test]
+    fn test_read_numa_topology_memory_tiers() {
+        let temp_dir = tempdir().unwrap();
+        let node_root = temp_dir.path();
+
+        // DDR node with CPUs and an on-package HBM node without CPUs
+        for (node, cpulist, mem_kb, distance) in [
+            ("node0", "0-55\n", 536870912, "10 13\n"),
+            ("node1", "\n", 67108864, "13 10\n"),
+        ] {
+            let node_path = node_root.join(node);
+            fs::create_dir_all(&node_path).unwrap();
+            fs::write(node_path.join("cpulist"), cpulist).unwrap();
+            fs::write(
+                node_path.join("meminfo"),
+                format!("Node 0 MemTotal:       {mem_kb} kB\n"),
+            )
+            .unwrap();
+            fs::write(node_path.join("distance"), distance).unwrap();
+        }
+        fs::create_dir_all(node_root.join("power")).unwrap();
+
+        let provider = create_test_provider();
+        let nodes = provider.read_numa_topology(node_root);
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes["0"].cpus.len(), 56);
+        assert_eq!(nodes["0"].memory, "524288 MB");
+        assert_eq!(nodes["0"].memory_tier.as_deref(), Some("DDR"));
+        assert!(nodes["1"].cpus.is_empty());
+        assert_eq!(nodes["1"].distances.get("0"), Some(&13));
+        assert_eq!(nodes["1"].memory_tier.as_deref(), Some("HBM"));
+    }
+
+    #[test]
+    fn test_read_storage_controller_behind_raid() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        // Disk exposed by a MegaRAID SAS controller (class 0x010400)
+        let controller_path = root.join("devices/pci0000:3a/0000:3b:00.0");
+        let scsi_device_path = controller_path.join("host0/target0:2:0/0:2:0:0");
+        fs::create_dir_all(&scsi_device_path).unwrap();
+        fs::write(controller_path.join("class"), "0x010400\n").unwrap();
+
+        let block_path = root.join("block/sda");
+        fs::create_dir_all(&block_path).unwrap();
+        std::os::unix::fs::symlink(&scsi_device_path, block_path.join("device")).unwrap();
+
+        let provider = create_test_provider();
+        let (controller, behind_raid) = provider.read_storage_controller(&block_path);
+
+        assert_eq!(controller.as_deref(), Some("RAID (0000:3b:00.0)"));
+        assert!(behind_raid);
+        assert_eq!(parse_pci_storage_class("0x010802\n"), Some("NVMe"));
+        assert_eq!(parse_pci_storage_class("0x020000\n"), None);
+    }
+
+    #[test]
+    fn test_detect_boot_mode() {
+        let temp_dir = tempdir().unwrap();
+        let sysfs_root = temp_dir.path();
+        fs::create_dir_all(sysfs_root.join("firmware")).unwrap();
+
+        let provider = create_test_provider();
+        assert_eq!(provider.detect_boot_mode(sysfs_root), "Legacy");
+
+        fs::create_dir_all(sysfs_root.join("firmware/efi")).unwrap();
+        assert_eq!(provider.detect_boot_mode(sysfs_root), "UEFI");
+    }
+
+    #[test]
+    fn test_read_partitions() {
+        let temp_dir = tempdir().unwrap();
+        let sys_block = temp_dir.path();
+        fs::create_dir_all(sys_block.join("sda/queue")).unwrap();
+        fs::write(sys_block.join("sda/queue/physical_block_size"), "4096\n").unwrap();
+        for (name, start, offset) in [("sda2", "1050624", "0"), ("sda1", "63", "3584")] {
+            let part = sys_block.join("sda").join(name);
+            fs::create_dir_all(&part).unwrap();
+            fs::write(part.join("partition"), "1\n").unwrap();
+            fs::write(part.join("start"), format!("{start}\n")).unwrap();
+            fs::write(part.join("alignment_offset"), format!("{offset}\n")).unwrap();
+        }
+        fs::create_dir_all(sys_block.join("loop0/queue")).unwrap();
+
+        let provider = create_test_provider();
+        let partitions = provider.read_partitions(sys_block);
+
+        assert_eq!(partitions.len(), 1);
+        let names: Vec<&str> = partitions["sda"].iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["sda1", "sda2"]);
+        assert!(partitions["sda"][0].misaligned);
+        assert!(!partitions["sda"][1].misaligned);
+    }
+
+    #[test]
+    fn test_read_numa_balancing() {
+        let temp_dir = tempdir().unwrap();
+        let sysctl_root = temp_dir.path();
+        let provider = create_test_provider();
+        assert_eq!(provider.read_numa_balancing(sysctl_root), None);
+
+        fs::create_dir_all(sysctl_root.join("kernel")).unwrap();
+        fs::write(sysctl_root.join("kernel/numa_balancing"), "0\n").unwrap();
+        assert_eq!(provider.read_numa_balancing(sysctl_root), Some(false));
+
+        fs::write(sysctl_root.join("kernel/numa_balancing"), "1\n").unwrap();
+        assert_eq!(provider.read_numa_balancing(sysctl_root), Some(true));
+    }
+
+    #[test]
+    fn test_read_cpu_sysfs_state() {
+        let temp_dir = tempdir().unwrap();
+        let cmdline_path = temp_dir.path().join("cmdline");
+        let cpu_sysfs_path = temp_dir.path().join("cpu");
+        fs::create_dir_all(&cpu_sysfs_path).unwrap();
+        fs::write(&cmdline_path, "ro quiet isolcpus=2-7,10 nohz_full=2-7\n").unwrap();
+        fs::write(cpu_sysfs_path.join("isolated"), "2-7,10-11\n").unwrap();
+        fs::write(cpu_sysfs_path.join("online"), "0-4,6,10-127\n").unwrap();
+        fs::write(cpu_sysfs_path.join("offline"), "5,7-9\n").unwrap();
+
+        let provider = create_test_provider();
+        let mut cpu = CpuInfo::default();
+        provider.read_cpu_sysfs_state(&cmdline_path, &cpu_sysfs_path, &mut cpu);
+
+        assert_eq!(cpu.isolated_cpus, vec![2, 3, 4, 5, 6, 7, 10, 11]);
+        assert_eq!(cpu.nohz_full_cpus, vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(cpu.online_cpus.len(), 124);
+        assert_eq!(cpu.offline_cpus, vec![5, 7, 8, 9]);
+        assert_eq!(cpu.turbo_enabled, None);
+        assert_eq!(cpu.cpufreq_driver, None);
+        assert!(cpu.logical_cpus.is_empty());
+
+        for (logical, core, socket) in [(0, 0, 0), (1, 4, 1), (64, 0, 0)] {
+            let topology = cpu_sysfs_path.join(format!("cpu{logical}/topology"));
+            fs::create_dir_all(&topology).unwrap();
+            fs::write(topology.join("core_id"), format!("{core}\n")).unwrap();
+            fs::write(topology.join("physical_package_id"), format!("{socket}\n")).unwrap();
+        }
+        provider.read_cpu_sysfs_state(&cmdline_path, &cpu_sysfs_path, &mut cpu);
+        assert_eq!(
+            cpu.logical_cpus,
+            vec![
+                LogicalCpu {
+                    cpu: 0,
+                    core: 0,
+                    socket: 0,
+                },
+                LogicalCpu {
+                    cpu: 1,
+                    core: 4,
+                    socket: 1,
+                },
+                LogicalCpu {
+                    cpu: 64,
+                    core: 0,
+                    socket: 0,
+                },
+            ]
+        );
+
+        fs::create_dir_all(cpu_sysfs_path.join("cpu0/cpufreq")).unwrap();
+        fs::create_dir_all(cpu_sysfs_path.join("cpuidle")).unwrap();
+        fs::write(
+            cpu_sysfs_path.join("cpu0/cpufreq/scaling_driver"),
+            "amd-pstate-epp\n",
+        )
+        .unwrap();
+        fs::write(cpu_sysfs_path.join("cpuidle/current_driver"), "acpi_idle\n").unwrap();
+        provider.read_cpu_sysfs_state(&cmdline_path, &cpu_sysfs_path, &mut cpu);
+        assert_eq!(cpu.cpufreq_driver.as_deref(), Some("amd-pstate-epp"));
+        assert_eq!(cpu.cpuidle_driver.as_deref(), Some("acpi_idle"));
+
+        fs::create_dir_all(cpu_sysfs_path.join("cpufreq")).unwrap();
+        fs::write(cpu_sysfs_path.join("cpufreq/boost"), "1\n").unwrap();
+        provider.read_cpu_sysfs_state(&cmdline_path, &cpu_sysfs_path, &mut cpu);
+        assert_eq!(cpu.turbo_enabled, Some(true));
+
+        fs::create_dir_all(cpu_sysfs_path.join("intel_pstate")).unwrap();
+        fs::write(cpu_sysfs_path.join("intel_pstate/no_turbo"), "1\n").unwrap();
+        provider.read_cpu_sysfs_state(&cmdline_path, &cpu_sysfs_path, &mut cpu);
+        assert_eq!(cpu.turbo_enabled, Some(false));
+    }
+
+    #[test]
+    fn test_container_limits_cgroup_v2() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("proc/1")).unwrap();
+        fs::write(root.join("proc/1/cgroup"), "0::/docker/3f2a9c1b\n").unwrap();
+
+        let cgroup_root = root.join("sys/fs/cgroup");
+        fs::create_dir_all(&cgroup_root).unwrap();
+        fs::write(cgroup_root.join("cgroup.controllers"), "cpu memory pids\n").unwrap();
+        fs::write(cgroup_root.join("cpu.max"), "400000 100000\n").unwrap();
+        fs::write(cgroup_root.join("cpu.weight"), "100\n").unwrap();
+        fs::write(cgroup_root.join("memory.max"), "17179869184\n").unwrap();
+
+        let provider = create_test_provider();
+        let container = provider.detect_container(root).unwrap();
+        assert_eq!(container, "docker");
+
+        let limits = provider.read_container_limits(&container, &cgroup_root);
+        assert_eq!(
+            limits,
+            ContainerLimits {
+                container: "docker".to_string(),
+                cgroup_version: 2,
+                cpu_quota_cores: Some(4.0),
+                cpu_shares: Some(100),
+                memory_limit_bytes: Some(17_179_869_184),
+                cpu_throttled_periods: None,
+            }
+        );
+
+        fs::write(cgroup_root.join("memory.max"), "max\n").unwrap();
+        assert_eq!(
+            provider
+                .read_container_limits(&container, &cgroup_root)
+                .memory_limit_bytes,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_container_profile_skips_dmidecode() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let mut provider = LinuxSystemInfoProvider::new(executor.clone());
+        provider.container = Some("docker".to_string());
+
+        assert!(provider.run_dmidecode("system").await.is_err());
+        assert!(executor.invocations().is_empty());
+    }
+
+    #[test]
+    fn test_hugepage_shortfall() {
+        let temp_dir = tempdir().unwrap();
+        let cmdline = temp_dir.path().join("cmdline");
+        let meminfo = temp_dir.path().join("meminfo");
+        fs::write(
+            &cmdline,
+            "BOOT_IMAGE=/vmlinuz ro default_hugepagesz=1G hugepagesz=1G hugepages=128\n",
+        )
+        .unwrap();
+        // Fragmented memory left the kernel 8 pages short
+        fs::write(
+            &meminfo,
+            "MemTotal:       2113486848 kB\nHugePages_Total:     120\nHugePages_Free:      120\nHugepagesize:    1048576 kB\n",
+        )
+        .unwrap();
+
+        let provider = create_test_provider();
+        let pools =
+            provider.read_hugepage_pools(&cmdline, &meminfo, &temp_dir.path().join("hugepages"));
+        assert_eq!(
+            pools,
+            vec![HugepagePool {
+                page_size_kb: 1_048_576,
+                requested: 128,
+                allocated: Some(120),
+            }]
+        );
+
+        // The per-size sysfs pool takes precedence over meminfo
+        let pool_dir = temp_dir.path().join("hugepages/hugepages-1048576kB");
+        fs::create_dir_all(&pool_dir).unwrap();
+        fs::write(pool_dir.join("nr_hugepages"), "128\n").unwrap();
+        let pools =
+            provider.read_hugepage_pools(&cmdline, &meminfo, &temp_dir.path().join("hugepages"));
+        assert_eq!(pools[0].allocated, Some(128));
+    }
+
+    #[test]
+    fn test_read_pcie_locality() {
+        let temp_dir = tempdir().unwrap();
+        let nic = temp_dir
+            .path()
+            .join("pci0000:00/0000:00:01.0/0000:01:00.0/0000:02:04.0/0000:03:00.0");
+        fs::create_dir_all(nic.join("infiniband/mlx5_0")).unwrap();
+        fs::write(nic.join("numa_node"), "1\n").unwrap();
+
+        let provider = create_test_provider();
+        let (pcie_path, numa_node) = provider.read_pcie_locality(&nic);
+        assert_eq!(
+            pcie_path,
+            vec!["0000:00:01.0", "0000:01:00.0", "0000:02:04.0"]
+        );
+        assert_eq!(numa_node, Some(1));
+        assert_eq!(
+            LinuxSystemInfoProvider::read_rdma_device(&nic).as_deref(),
+            Some("mlx5_0")
+        );
+
+        assert_eq!(provider.read_aspm_state(&nic), None);
+        fs::create_dir_all(nic.join("link")).unwrap();
+        fs::write(nic.join("link/l0s_aspm"), "0\n").unwrap();
+        fs::write(nic.join("link/l1_aspm"), "1\n").unwrap();
+        assert_eq!(provider.read_aspm_state(&nic).as_deref(), Some("L1"));
+
+        // Firmware without NUMA affinity reports -1
+        fs::write(nic.join("numa_node"), "-1\n").unwrap();
+        assert_eq!(provider.read_pcie_locality(&nic).1, None);
+        assert_eq!(
+            LinuxSystemInfoProvider::read_rdma_device(&temp_dir.path().join("missing")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_read_enclosures() {
+        let temp_dir = tempdir().unwrap();
+        let enclosure = temp_dir.path().join("enclosure/0:0:8:0");
+        fs::create_dir_all(enclosure.join("device")).unwrap();
+        fs::write(enclosure.join("id"), "0x500304801f3c4a3f\n").unwrap();
+        fs::write(enclosure.join("device/vendor"), "LSI     \n").unwrap();
+        fs::write(enclosure.join("device/model"), "SAS3x28         \n").unwrap();
+
+        for (slot, disk) in [
+            ("Slot00", Some("sdb")),
+            ("Slot01", Some("sda")),
+            ("Slot02", None),
+        ] {
+            let slot_path = enclosure.join(slot);
+            fs::create_dir_all(&slot_path).unwrap();
+            fs::write(slot_path.join("status"), "OK\n").unwrap();
+            if let Some(disk) = disk {
+                fs::create_dir_all(slot_path.join("device/block").join(disk)).unwrap();
+            }
+        }
+
+        let provider = create_test_provider();
+        let enclosures = provider.read_enclosures(temp_dir.path());
+
+        assert_eq!(enclosures.len(), 1);
+        assert_eq!(enclosures[0].id.as_deref(), Some("0x500304801f3c4a3f"));
+        assert_eq!(enclosures[0].model.as_deref(), Some("SAS3x28"));
+        assert_eq!(
+            enclosures[0].slots,
+            vec![
+                EnclosureSlot {
+                    slot: "Slot00".to_string(),
+                    disk: Some("sdb".to_string()),
+                },
+                EnclosureSlot {
+                    slot: "Slot01".to_string(),
+                    disk: Some("sda".to_string()),
+                },
+                EnclosureSlot {
+                    slot: "Slot02".to_string(),
+                    disk: None,
+                },
+            ]
+        );
+
+        // No ses driver loaded
+        assert!(provider
+            .read_enclosures(&temp_dir.path().join("missing"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_read_fc_hbas() {
+        let temp_dir = tempdir().unwrap();
+        let class_root = temp_dir.path();
+
+        // Dual-port 32G HBA, one port online with a target and one port down
+        for (host, port_name, state, speed) in [
+            ("host1", "0x21000024ff7b1a2c", "Online", "32 Gbit"),
+            ("host2", "0x21000024ff7b1a2d", "Linkdown", "unknown"),
+        ] {
+            let host_path = class_root.join("fc_host").join(host);
+            fs::create_dir_all(&host_path).unwrap();
+            fs::write(host_path.join("port_name"), format!("{port_name}\n")).unwrap();
+            fs::write(host_path.join("node_name"), "0x20000024ff7b1a2c\n").unwrap();
+            fs::write(host_path.join("port_state"), format!("{state}\n")).unwrap();
+            fs::write(host_path.join("speed"), format!("{speed}\n")).unwrap();
+            fs::write(
+                host_path.join("symbolic_name"),
+                "QLE2772 FW:v9.08.02 DVR:v10.02.08.200-k\n",
+            )
+            .unwrap();
+        }
+        let rport_path = class_root.join("fc_remote_ports/rport-1:0-0");
+        fs::create_dir_all(&rport_path).unwrap();
+        fs::write(rport_path.join("port_name"), "0x500a098188a6b2c1\n").unwrap();
+
+        let provider = create_test_provider();
+        let hbas = provider.read_fc_hbas(class_root);
+
+        assert_eq!(hbas.len(), 2);
+        assert_eq!(hbas[0].name, "host1");
+        assert_eq!(hbas[0].port_name, "21:00:00:24:ff:7b:1a:2c");
+        assert_eq!(hbas[0].speed.as_deref(), Some("32 Gbit"));
+        assert_eq!(hbas[0].port_state.as_deref(), Some("Online"));
+        assert_eq!(hbas[0].remote_ports, vec!["50:0a:09:81:88:a6:b2:c1"]);
+        assert_eq!(hbas[1].port_state.as_deref(), Some("Linkdown"));
+        assert_eq!(hbas[1].speed, None);
+        assert!(hbas[1].remote_ports.is_empty());
+
+        assert!(provider
+            .read_fc_hbas(&class_root.join("missing"))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fast_mode_skips_slow_probes() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor.clone()).with_fast_mode(true);
+
+        let gpus = provider.get_gpu_info().await.unwrap();
+        let tool_versions = provider.get_tool_versions().await.unwrap();
+
+        assert_eq!(gpus.devices.len(), 1);
+        assert_eq!(gpus.devices[0].temperature_c, None);
+        assert!(tool_versions.is_empty());
+
+        let invocations = executor.invocations();
+        assert_eq!(invocations.len(), 1);
+        assert!(invocations.iter().all(|cmd| !cmd.starts_with("smartctl")));
+        assert!(invocations.iter().all(|cmd| cmd != "nvidia-smi -q"));
+
+        // Without fast mode the full query runs
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor.clone());
+        provider.get_gpu_info().await.unwrap();
+        assert!(executor
+            .invocations()
+            .iter()
+            .any(|cmd| cmd == "nvidia-smi -q"));
+    }
+
+    #[tokio::test]
+    async fn test_gpu_details_matched_by_bus_id() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor.clone());
+
+        let gpus = provider.get_gpu_info().await.unwrap();
+
+        let gpu = &gpus.devices[0];
+        assert_eq!(gpu.pci_bus_id.as_deref(), Some("0000:2a:00.0"));
+        assert_eq!(gpu.temperature_c, Some(34));
+        assert_eq!(gpu.retired_pages_single_bit, Some(2));
+        assert_eq!(gpu.pcie_replay_counter, Some(17));
+        assert_eq!(gpu.power_limit_watts, Some(500.0));
+        assert_eq!(gpu.default_power_limit_watts, Some(700.0));
+        assert_eq!(gpu.performance_state.as_deref(), Some("P0"));
+
+        // One CSV query, one full query and the NVLink status; no MIG listing
+        // or NVLink error counters without MIG mode or links
+        let nvidia_smi: Vec<String> = executor
+            .invocations()
+            .into_iter()
+            .filter(|cmd| cmd.starts_with("nvidia-smi"))
+            .collect();
+        assert_eq!(nvidia_smi.len(), 3);
+        assert_eq!(nvidia_smi[1], "nvidia-smi -q");
+        assert_eq!(nvidia_smi[2], "nvidia-smi nvlink -s");
+    }
+
+    #[tokio::test]
+    async fn test_raid_tool_candidates() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor.clone());
+
+        // The mock answers every tool with empty output, which is not storcli JSON
+        assert_eq!(provider.read_raid_virtual_disks().await, Some(Vec::new()));
+        let tools: Vec<String> = executor
+            .invocations()
+            .iter()
+            .map(|cmd| cmd.split(' ').next().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            tools,
+            vec!["storcli64", "storcli", "perccli64", "perccli", "MegaCli64"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_power_info_prefers_system_reading() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor).with_fast_mode(true);
+
+        let power = provider.get_power_info().await.unwrap();
+        assert_eq!(power.power_draw_watts, Some(3412.0));
+        assert_eq!(power.source.as_deref(), Some("dcmi"));
+        // RAPL sampling sleeps, so fast mode skips it
+        assert_eq!(power.cpu_package_watts, None);
+
+        let temp_dir = tempdir().unwrap();
+        let package = temp_dir.path().join("intel-rapl:0");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("energy_uj"), "1000000\n").unwrap();
+        fs::write(package.join("max_energy_range_uj"), "262143328850\n").unwrap();
+        assert_eq!(provider.sample_rapl_watts(temp_dir.path()).await, Some(0.0));
+        assert_eq!(
+            provider
+                .sample_rapl_watts(&temp_dir.path().join("missing"))
+                .await,
+            None
+        );
+    }
+
+    #[test]
+    fn test_read_pci_aer_counters() {
+        let temp_dir = tempdir().unwrap();
+        let pci_path = temp_dir.path();
+        fs::write(
+            pci_path.join("aer_dev_correctable"),
+            "RxErr 5\nBadTLP 2\nTOTAL_ERR_COR 7\n",
+        )
+        .unwrap();
+        fs::write(pci_path.join("aer_dev_fatal"), "DLP 0\nTOTAL_ERR_FATAL 0\n").unwrap();
+        fs::write(
+            pci_path.join("aer_dev_nonfatal"),
+            "MalfTLP 1\nTOTAL_ERR_NONFATAL 1\n",
+        )
+        .unwrap();
+
+        let provider = create_test_provider();
+        assert_eq!(provider.read_pci_aer_counters(pci_path), (Some(7), Some(1)));
+        assert_eq!(
+            provider.read_pci_aer_counters(&pci_path.join("missing")),
+            (None, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_capture_records_lscpu_output() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor).with_raw_capture(true);
+
+        let _ = provider.get_cpu_info().await;
+
+        let raw = provider.get_raw_sources().await.unwrap();
+        assert_eq!(
+            raw.get("lscpu").map(String::as_str),
+            Some("Architecture: x86_64\nModel name: Test CPU\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_sources_empty_without_capture() {
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider = LinuxSystemInfoProvider::new(executor);
+
+        let _ = provider.get_cpu_info().await;
+
+        assert!(provider.get_raw_sources().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_probe_capabilities_reports_missing_tools() {
+        let executor = Arc::new(MockCommandExecutor {
+            unavailable_tools: vec!["nvidia-smi", "smartctl"],
+            ..Default::default()
+        });
+        let provider = LinuxSystemInfoProvider::new(executor.clone());
+
+        let capabilities = provider.probe_capabilities().await.unwrap();
+
+        assert_eq!(capabilities.adapter, "linux");
+        assert!(capabilities.has_privileges);
+        let available = |name: &str| {
+            capabilities
+                .subsystems
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.available)
+        };
+        assert_eq!(available("cpu"), Some(true));
+        assert_eq!(available("gpu"), Some(false));
+        assert_eq!(available("smart"), Some(false));
+        let gpu = capabilities.subsystems.iter().find(|s| s.name == "gpu");
+        assert_eq!(gpu.unwrap().missing_tools, vec!["nvidia-smi"]);
+
+        // Probing never runs collection commands
+        assert!(executor.invocations().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dmidecode_source_dump() {
+        let temp_dir = tempdir().unwrap();
+        let dump_path = temp_dir.path().join("dmidecode.txt");
+        fs::write(
+            &dump_path,
+            "# dmidecode 3.3\n\
+             Getting SMBIOS data from sysfs.\n\
+             SMBIOS 3.3.0 present.\n\
+             \n\
+             Handle 0x0000, DMI type 0, 26 bytes\n\
+             BIOS Information\n\
+             \tVendor: American Megatrends International, LLC.\n\
+             \tVersion: 2.1a\n\
+             \tRelease Date: 02/10/2024\n\
+             \n\
+             Handle 0x0001, DMI type 1, 27 bytes\n\
+             System Information\n\
+             \tManufacturer: Supermicro\n\
+             \tProduct Name: SYS-821GE-TNHR\n\
+             \tVersion: 0123456789\n\
+             \tSerial Number: S123456X4A12345\n\
+             \tUUID: 4c4c4544-0042-3510-8044-b4c04f4e3233\n\
+             \n\
+             Handle 0x0002, DMI type 2, 15 bytes\n\
+             Base Board Information\n\
+             \tManufacturer: Supermicro\n\
+             \tSerial Number: OM23AS000123\n\
+             \n\
+             Handle 0x0003, DMI type 3, 22 bytes\n\
+             Chassis Information\n\
+             \tManufacturer: Supermicro\n\
+             \tType: Rack Mount Chassis\n\
+             \tSerial Number: C8170LM23NW0001\n\
+             \n\
+             Handle 0x0004, DMI type 127, 4 bytes\n\
+             End Of Table\n",
+        )
+        .unwrap();
+
+        let executor = Arc::new(MockCommandExecutor::default());
+        let provider =
+            LinuxSystemInfoProvider::new(executor.clone()).with_dmidecode_source(Some(dump_path));
+
+        let bios = provider.get_bios_info().await.unwrap();
+        assert_eq!(bios.vendor, "American Megatrends International, LLC.");
+        assert_eq!(bios.version, "2.1a");
+        assert_eq!(bios.release_date, "02/10/2024");
+
+        let system = provider.get_system_info().await.unwrap();
+        assert_eq!(system.product_name, "SYS-821GE-TNHR");
+        assert_eq!(system.serial, "S123456X4A12345");
+        assert_eq!(system.uuid, "4c4c4544-0042-3510-8044-b4c04f4e3233");
+
+        let chassis = provider.get_chassis_info().await.unwrap();
+        assert_eq!(chassis.type_, "Rack Mount Chassis");
+        assert_eq!(chassis.serial, "C8170LM23NW0001");
+
+        // The dump replaces dmidecode entirely
+        assert!(executor.invocations().is_empty());
+    }
+
+    #[test]
+    fn test_read_unbound_pci_devices() {
+        let temp_dir = tempdir().unwrap();
+        let write_device = |address: &str, vendor: &str, device: &str, class: &str| {
+            let dir = temp_dir.path().join(address);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("vendor"), format!("{vendor}\n")).unwrap();
+            fs::write(dir.join("device"), format!("{device}\n")).unwrap();
+            fs::write(dir.join("class"), format!("{class}\n")).unwrap();
+            dir
+        };
+
+        // GPU with no driver bound
+        write_device("0000:3b:00.0", "0x10de", "0x2330", "0x030200");
+        // NIC bound to mlx5_core
+        let nic = write_device("0000:5e:00.0", "0x15b3", "0x101b", "0x020000");
+        fs::create_dir_all(nic.join("driver")).unwrap();
+        // Unbound host bridge is ignored
+        write_device("0000:00:00.0", "0x8086", "0x09a2", "0x060000");
+
+        let provider = create_test_provider();
+        let devices = provider.read_unbound_pci_devices(temp_dir.path());
+        assert_eq!(
+            devices,
+            vec![PciDevice {
+                address: "0000:3b:00.0".to_string(),
+                vendor_id: "10de".to_string(),
+                device_id: "2330".to_string(),
+                class: "0x030200".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detect_containerd_and_nvidia_toolkit() {
+        let temp_dir = tempdir().unwrap();
+        let executor = Arc::new(MockCommandExecutor {
+            unavailable_tools: vec!["crio"],
+            ..Default::default()
+        });
+        let provider = LinuxSystemInfoProvider::new(executor.clone());
+
+        // Binary fallback when no runtime socket is present
+        assert_eq!(
+            provider.detect_container_runtime(temp_dir.path()).await,
+            Some("containerd".to_string())
+        );
+
+        // A runtime socket takes precedence over installed binaries
+        fs::create_dir_all(temp_dir.path().join("run")).unwrap();
+        fs::write(temp_dir.path().join("run/docker.sock"), "").unwrap();
+        assert_eq!(
+            provider.detect_container_runtime(temp_dir.path()).await,
+            Some("docker".to_string())
+        );
+
+        let info = provider.get_container_runtime().await.unwrap();
+        assert!(info.nvidia_container_toolkit_present);
+        assert!(executor
+            .invocations()
+            .contains(&"nvidia-container-cli info".to_string()));
+    }
+
+    #[test]
+    fn test_read_gpu_cgroup_allocations() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let provider = create_test_provider();
+
+        // Root and unrelated cgroups are unrestricted
+        fs::write(root.join("devices.list"), "a *:* rwm\n").unwrap();
+        fs::create_dir_all(root.join("system.slice")).unwrap();
+        fs::write(root.join("system.slice/devices.list"), "a *:* rwm\n").unwrap();
+
+        let container = root.join("kubepods/pod1234/abcd");
+        fs::create_dir_all(&container).unwrap();
+        fs::write(
+            container.join("devices.list"),
+            "c 1:3 rwm\nc 195:255 rw\nc 195:0 rw\n",
+        )
+        .unwrap();
+
+        let allocations = provider.read_gpu_cgroup_allocations(root);
+        assert_eq!(
+            allocations,
+            vec![GpuCgroupAllocation {
+                cgroup: "/kubepods/pod1234/abcd".to_string(),
+                devices: vec!["/dev/nvidia0".to_string()],
+            }]
+        );
+    }
 }
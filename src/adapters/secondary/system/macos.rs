@@ -16,10 +16,15 @@ limitations under the License.
 
 //! macOS system information provider
 
+use super::{probe_subsystems, CloudMetadataClient};
+use crate::adapters::RecordingCommandExecutor;
 use crate::domain::{
-    parse_hostname_output, parse_macos_cpu_info, parse_macos_memory_info, parse_macos_network_info,
-    parse_macos_storage_info, BiosInfo, ChassisInfo, CpuInfo, GpuInfo, MemoryInfo, MotherboardInfo,
-    NetworkInfo, NumaNode, StorageInfo, SystemError, SystemInfo,
+    are_gpus_homogeneous, parse_hostname_output, parse_macos_cpu_info, parse_macos_memory_info,
+    parse_macos_network_info, parse_macos_storage_info, Accelerator, BiosInfo, BmcNetwork,
+    Capabilities, ChassisInfo, CloudInfo, ContainerLimits, ContainerRuntimeInfo, CpuInfo,
+    FirmwareComponent, GpuCgroupAllocation, GpuInfo, KernelError, MemoryInfo, MotherboardInfo,
+    NetworkInfo, NumaNode, Partition, PciDevice, PowerInfo, SelEvent, SensorReading, StorageInfo,
+    SystemError, SystemInfo, TimeSyncInfo, TpmInfo, WatchdogInfo, XidError, UNKNOWN,
 };
 use crate::ports::{CommandExecutor, SystemCommand, SystemInfoProvider};
 use async_trait::async_trait;
@@ -30,12 +35,27 @@ use std::time::Duration;
 /// macOS system information provider using system_profiler and other macOS tools
 pub struct MacOSSystemInfoProvider {
     command_executor: Arc<dyn CommandExecutor>,
+    /// Records raw command output when raw capture is enabled
+    raw_recorder: Option<Arc<RecordingCommandExecutor>>,
 }
 
 impl MacOSSystemInfoProvider {
     /// Create a new macOS system information provider
     pub fn new(command_executor: Arc<dyn CommandExecutor>) -> Self {
-        Self { command_executor }
+        Self {
+            command_executor,
+            raw_recorder: None,
+        }
+    }
+
+    /// Record the stdout of every command run for inclusion in the report
+    pub fn with_raw_capture(mut self, enabled: bool) -> Self {
+        if enabled && self.raw_recorder.is_none() {
+            let recorder = Arc::new(RecordingCommandExecutor::new(self.command_executor.clone()));
+            self.command_executor = recorder.clone();
+            self.raw_recorder = Some(recorder);
+        }
+        self
     }
 
     /// Check if required commands are available
@@ -104,7 +124,10 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
 
         let devices = parse_macos_storage_info(&output.stdout).map_err(SystemError::ParseError)?;
 
-        Ok(StorageInfo { devices })
+        Ok(StorageInfo {
+            devices,
+            enclosures: Vec::new(),
+        })
     }
 
     async fn get_gpu_info(&self) -> Result<GpuInfo, SystemError> {
@@ -150,7 +173,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 } else if trimmed.contains("M1 Pro") {
                     "16 cores"
                 } else {
-                    "Unknown"
+                    UNKNOWN
                 };
 
                 devices.push(crate::domain::GpuDevice {
@@ -173,7 +196,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 index: 0,
                 name: "Integrated Graphics".to_string(),
                 uuid: "macOS-GPU-0".to_string(),
-                memory: "Unknown".to_string(),
+                memory: UNKNOWN.to_string(),
                 pci_id: "Apple Fabric (Integrated)".to_string(),
                 vendor: "Apple".to_string(),
                 numa_node: None,
@@ -181,7 +204,12 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
             });
         }
 
-        Ok(GpuInfo { devices })
+        Ok(GpuInfo {
+            gpus_homogeneous: are_gpus_homogeneous(&devices),
+            recent_xid_errors: None,
+            nvlink: Vec::new(),
+            devices,
+        })
     }
 
     async fn get_network_info(&self) -> Result<NetworkInfo, SystemError> {
@@ -202,6 +230,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
         Ok(NetworkInfo {
             interfaces,
             infiniband: None, // macOS doesn't typically have InfiniBand
+            fc_hbas: Vec::new(),
         })
     }
 
@@ -221,8 +250,8 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
             })?;
 
         let vendor = "Apple Inc.".to_string();
-        let mut version = "Unknown".to_string();
-        let mut release_date = "Unknown".to_string();
+        let mut version = UNKNOWN.to_string();
+        let mut release_date = UNKNOWN.to_string();
 
         for line in output.stdout.lines() {
             let trimmed = line.trim();
@@ -230,14 +259,14 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 version = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             } else if trimmed.starts_with("OS Loader Version:") {
                 release_date = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             }
@@ -267,7 +296,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
 
         let manufacturer = "Apple Inc.".to_string();
         let mut type_ = "Laptop".to_string();
-        let mut serial = "Unknown".to_string();
+        let mut serial = UNKNOWN.to_string();
 
         for line in output.stdout.lines() {
             let trimmed = line.trim();
@@ -283,7 +312,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 serial = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             }
@@ -293,6 +322,7 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
             manufacturer,
             type_,
             serial,
+            airflow: None,
         })
     }
 
@@ -311,9 +341,9 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
             })?;
 
         let manufacturer = "Apple Inc.".to_string();
-        let mut product_name = "Unknown Product".to_string();
-        let mut version = "Unknown Version".to_string();
-        let mut serial = "Unknown S/N".to_string();
+        let mut product_name = UNKNOWN.to_string();
+        let mut version = UNKNOWN.to_string();
+        let mut serial = UNKNOWN.to_string();
 
         for line in output.stdout.lines() {
             let trimmed = line.trim();
@@ -321,21 +351,21 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 product_name = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown Product")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             } else if trimmed.starts_with("System Firmware Version:") {
                 version = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown Version")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             } else if trimmed.starts_with("Serial Number (system):") {
                 serial = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown S/N")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             }
@@ -366,9 +396,9 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 stderr: e.to_string(),
             })?;
 
-        let mut uuid = "Unknown".to_string();
-        let mut serial = "Unknown".to_string();
-        let mut product_name = "Unknown".to_string();
+        let mut uuid = UNKNOWN.to_string();
+        let mut serial = UNKNOWN.to_string();
+        let mut product_name = UNKNOWN.to_string();
         let manufacturer = "Apple Inc.".to_string();
 
         for line in output.stdout.lines() {
@@ -377,18 +407,18 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
                 uuid = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             } else if trimmed.starts_with("Serial Number (system):") {
                 serial = trimmed
                     .split(':')
                     .nth(1)
-                    .unwrap_or("Unknown")
+                    .unwrap_or(UNKNOWN)
                     .trim()
                     .to_string();
             } else if trimmed.starts_with("Model Name:") {
-                let model = trimmed.split(':').nth(1).unwrap_or("Unknown").trim();
+                let model = trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim();
                 let chip = output
                     .stdout
                     .lines()
@@ -479,4 +509,172 @@ impl SystemInfoProvider for MacOSSystemInfoProvider {
     async fn get_missing_dependencies(&self) -> Result<Vec<String>, SystemError> {
         Ok(self.check_required_commands().await)
     }
+
+    async fn get_tool_versions(&self) -> Result<HashMap<String, String>, SystemError> {
+        // Collection relies on bundled system utilities without version flags
+        Ok(HashMap::new())
+    }
+
+    async fn get_failed_units(&self) -> Result<Vec<String>, SystemError> {
+        // macOS uses launchd rather than systemd
+        Ok(Vec::new())
+    }
+
+    async fn get_boot_mode(&self) -> Result<String, SystemError> {
+        // Macs boot through EFI (Intel) or iBoot (Apple Silicon), never legacy BIOS
+        Ok("UEFI".to_string())
+    }
+
+    async fn get_tpm_info(&self) -> Result<TpmInfo, SystemError> {
+        // Macs use the Secure Enclave / T2 chip rather than a TPM
+        Ok(TpmInfo::default())
+    }
+
+    async fn get_numa_balancing(&self) -> Result<Option<bool>, SystemError> {
+        // macOS has no NUMA balancing
+        Ok(None)
+    }
+
+    async fn get_bios_settings(&self) -> Result<HashMap<String, String>, SystemError> {
+        // Mac firmware exposes no configurable BIOS settings
+        Ok(HashMap::new())
+    }
+
+    async fn get_os_machine_id(&self) -> Result<String, SystemError> {
+        // The hardware UUID is already reported as the system UUID on macOS
+        Err(SystemError::NotAvailable {
+            resource: "/etc/machine-id".to_string(),
+        })
+    }
+
+    async fn get_partitions(&self) -> Result<HashMap<String, Vec<Partition>>, SystemError> {
+        // APFS containers have no sysfs alignment attributes
+        Ok(HashMap::new())
+    }
+
+    async fn get_xid_errors(&self) -> Result<Vec<XidError>, SystemError> {
+        // No NVIDIA driver on macOS
+        Ok(Vec::new())
+    }
+
+    async fn get_accelerators(&self) -> Result<Vec<Accelerator>, SystemError> {
+        // Apple silicon neural engines are not discrete PCI devices
+        Ok(Vec::new())
+    }
+
+    async fn get_unbound_pci_devices(&self) -> Result<Vec<PciDevice>, SystemError> {
+        // macOS has no sysfs driver bindings to inspect
+        Ok(Vec::new())
+    }
+
+    async fn get_kernel_hardware_errors(&self) -> Result<Vec<KernelError>, SystemError> {
+        // The unified log has no equivalent of the Linux kernel error messages
+        Err(SystemError::NotAvailable {
+            resource: "kernel log".to_string(),
+        })
+    }
+
+    async fn get_container_runtime(&self) -> Result<ContainerRuntimeInfo, SystemError> {
+        let docker_available = self
+            .command_executor
+            .is_command_available("docker")
+            .await
+            .unwrap_or(false);
+
+        // Docker Desktop runs containers in a Linux VM without GPU passthrough
+        Ok(ContainerRuntimeInfo {
+            container_runtime: docker_available.then(|| "docker".to_string()),
+            nvidia_container_toolkit_present: false,
+        })
+    }
+
+    async fn get_container_limits(&self) -> Result<ContainerLimits, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "cgroups".to_string(),
+        })
+    }
+
+    async fn get_gpu_cgroup_allocations(&self) -> Result<Vec<GpuCgroupAllocation>, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "cgroups".to_string(),
+        })
+    }
+
+    async fn get_bmc_network(&self) -> Result<BmcNetwork, SystemError> {
+        // Macs have no BMC
+        Err(SystemError::NotAvailable {
+            resource: "BMC".to_string(),
+        })
+    }
+
+    async fn get_sel_events(&self) -> Result<Vec<SelEvent>, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "BMC".to_string(),
+        })
+    }
+
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "BMC sensors".to_string(),
+        })
+    }
+
+    async fn get_firmware_inventory(&self) -> Result<Vec<FirmwareComponent>, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "Redfish".to_string(),
+        })
+    }
+
+    async fn get_cloud_info(&self) -> Result<Option<CloudInfo>, SystemError> {
+        let client =
+            CloudMetadataClient::with_defaults().map_err(|e| SystemError::NotAvailable {
+                resource: format!("cloud metadata client: {e}"),
+            })?;
+        Ok(client.fetch().await)
+    }
+
+    async fn get_watchdog(&self) -> Result<WatchdogInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "watchdog".to_string(),
+        })
+    }
+
+    async fn get_time_sync(&self) -> Result<TimeSyncInfo, SystemError> {
+        // timed exposes no query interface comparable to chronyc
+        Err(SystemError::NotAvailable {
+            resource: "time sync status".to_string(),
+        })
+    }
+
+    async fn get_power_info(&self) -> Result<PowerInfo, SystemError> {
+        // powermetrics needs root and a sampling run; not collected for now
+        Err(SystemError::NotAvailable {
+            resource: "power draw".to_string(),
+        })
+    }
+
+    async fn get_raw_sources(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(self
+            .raw_recorder
+            .as_ref()
+            .map(|recorder| recorder.raw_sources())
+            .unwrap_or_default())
+    }
+
+    async fn probe_capabilities(&self) -> Result<Capabilities, SystemError> {
+        let subsystems: &[(&str, &[&str])] = &[
+            ("cpu", &["system_profiler", "sysctl"]),
+            ("memory", &["system_profiler"]),
+            ("storage", &["system_profiler"]),
+            ("gpu", &["system_profiler"]),
+            ("network", &["system_profiler", "ifconfig"]),
+            ("dmi", &["ioreg"]),
+        ];
+
+        Ok(Capabilities {
+            adapter: "macos".to_string(),
+            has_privileges: self.has_required_privileges().await.unwrap_or(false),
+            subsystems: probe_subsystems(self.command_executor.as_ref(), subsystems).await,
+        })
+    }
 }
@@ -14,8 +14,43 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod cloud;
 pub mod linux;
 pub mod macos;
+pub mod out_of_band;
+pub mod redfish;
 
+pub use cloud::*;
 pub use linux::*;
 pub use macos::*;
+pub use out_of_band::*;
+pub use redfish::*;
+
+use crate::domain::SubsystemCapability;
+use crate::ports::CommandExecutor;
+
+/// Check tool availability for each subsystem without running any collection
+///
+/// # Arguments
+/// * `executor` - Command executor used to look up tools
+/// * `subsystems` - Subsystem names paired with the tools they need
+pub(crate) async fn probe_subsystems(
+    executor: &dyn CommandExecutor,
+    subsystems: &[(&str, &[&str])],
+) -> Vec<SubsystemCapability> {
+    let mut capabilities = Vec::with_capacity(subsystems.len());
+    for (name, tools) in subsystems {
+        let mut missing_tools = Vec::new();
+        for tool in tools.iter() {
+            if !executor.is_command_available(tool).await.unwrap_or(false) {
+                missing_tools.push(tool.to_string());
+            }
+        }
+        capabilities.push(SubsystemCapability {
+            name: name.to_string(),
+            available: missing_tools.is_empty(),
+            missing_tools,
+        });
+    }
+    capabilities
+}
@@ -0,0 +1,488 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Out-of-band system information provider that reads only from the BMC
+
+use super::RedfishClient;
+use crate::domain::{
+    parse_redfish_bios_attributes, parse_redfish_bios_info, parse_redfish_bmc_network,
+    parse_redfish_chassis_info, parse_redfish_cpu_summary, parse_redfish_host_watchdog,
+    parse_redfish_hostname, parse_redfish_member_links, parse_redfish_memory_summary,
+    parse_redfish_power, parse_redfish_system_info, parse_redfish_thermal, parse_redfish_tpm_info,
+    Accelerator, BiosInfo, BmcNetwork, Capabilities, ChassisInfo, CloudInfo, ContainerLimits,
+    ContainerRuntimeInfo, CpuInfo, FirmwareComponent, GpuCgroupAllocation, GpuInfo, KernelError,
+    MemoryInfo, MotherboardInfo, NetworkInfo, NumaNode, Partition, PciDevice, PowerInfo,
+    RedfishConfig, SelEvent, SensorReading, StorageInfo, SubsystemCapability, SystemError,
+    SystemInfo, TimeSyncInfo, TpmInfo, WatchdogInfo, XidError, UNKNOWN,
+};
+use crate::ports::SystemInfoProvider;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const SYSTEMS_PATH: &str = "/redfish/v1/Systems";
+const CHASSIS_PATH: &str = "/redfish/v1/Chassis";
+const MANAGERS_PATH: &str = "/redfish/v1/Managers";
+
+/// System information provider for hosts reachable only through their BMC
+///
+/// Every value comes from Redfish and no command is run, so it works for
+/// dead-on-arrival hosts without an OS. What only the host OS can see
+/// (storage, GPUs, NICs, NUMA, filesystems) is reported empty.
+pub struct OutOfBandSystemInfoProvider {
+    redfish: RedfishClient,
+}
+
+impl OutOfBandSystemInfoProvider {
+    /// Create a provider for a BMC
+    ///
+    /// # Arguments
+    /// * `config` - BMC endpoint and credentials
+    /// * `timeout` - Per-request timeout
+    pub fn new(config: &RedfishConfig, timeout: Duration) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            redfish: RedfishClient::new(config, timeout)?,
+        })
+    }
+
+    /// GET a resource below the first member of a collection
+    async fn member_resource(&self, collection: &str, suffix: &str) -> Result<String, SystemError> {
+        let member = self
+            .redfish
+            .first_member(collection)
+            .await
+            .map_err(SystemError::IoError)?;
+        self.redfish
+            .get(&format!("{member}{suffix}"))
+            .await
+            .map_err(SystemError::IoError)
+    }
+
+    async fn computer_system(&self) -> Result<String, SystemError> {
+        self.member_resource(SYSTEMS_PATH, "").await
+    }
+}
+
+fn in_band_only(resource: &str) -> SystemError {
+    SystemError::NotAvailable {
+        resource: format!("{resource} (out-of-band collection)"),
+    }
+}
+
+#[async_trait]
+impl SystemInfoProvider for OutOfBandSystemInfoProvider {
+    async fn get_cpu_info(&self) -> Result<CpuInfo, SystemError> {
+        parse_redfish_cpu_summary(&self.computer_system().await?).map_err(SystemError::ParseError)
+    }
+
+    async fn get_memory_info(&self) -> Result<MemoryInfo, SystemError> {
+        parse_redfish_memory_summary(&self.computer_system().await?)
+            .map_err(SystemError::ParseError)
+    }
+
+    async fn get_storage_info(&self) -> Result<StorageInfo, SystemError> {
+        Ok(StorageInfo {
+            devices: Vec::new(),
+            enclosures: Vec::new(),
+        })
+    }
+
+    async fn get_gpu_info(&self) -> Result<GpuInfo, SystemError> {
+        Ok(GpuInfo {
+            devices: Vec::new(),
+            gpus_homogeneous: true,
+            recent_xid_errors: None,
+            nvlink: Vec::new(),
+        })
+    }
+
+    async fn get_network_info(&self) -> Result<NetworkInfo, SystemError> {
+        Ok(NetworkInfo {
+            interfaces: Vec::new(),
+            infiniband: None,
+            fc_hbas: Vec::new(),
+        })
+    }
+
+    async fn get_bios_info(&self) -> Result<BiosInfo, SystemError> {
+        parse_redfish_bios_info(&self.computer_system().await?).map_err(SystemError::ParseError)
+    }
+
+    async fn get_chassis_info(&self) -> Result<ChassisInfo, SystemError> {
+        let chassis = self.member_resource(CHASSIS_PATH, "").await?;
+        parse_redfish_chassis_info(&chassis).map_err(SystemError::ParseError)
+    }
+
+    async fn get_motherboard_info(&self) -> Result<MotherboardInfo, SystemError> {
+        Ok(MotherboardInfo {
+            manufacturer: UNKNOWN.to_string(),
+            product_name: UNKNOWN.to_string(),
+            version: UNKNOWN.to_string(),
+            serial: UNKNOWN.to_string(),
+            features: UNKNOWN.to_string(),
+            location: UNKNOWN.to_string(),
+            type_: "Motherboard".to_string(),
+        })
+    }
+
+    async fn get_system_info(&self) -> Result<SystemInfo, SystemError> {
+        parse_redfish_system_info(&self.computer_system().await?).map_err(SystemError::ParseError)
+    }
+
+    async fn get_numa_topology(&self) -> Result<HashMap<String, NumaNode>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_hostname(&self) -> Result<String, SystemError> {
+        let system = self.computer_system().await?;
+        if let Some(hostname) = parse_redfish_hostname(&system) {
+            return Ok(hostname);
+        }
+
+        // The BMC only learns the host name from a running OS; fall back to the serial
+        let system_info = parse_redfish_system_info(&system).map_err(SystemError::ParseError)?;
+        Ok(system_info.serial)
+    }
+
+    async fn get_fqdn(&self) -> Result<String, SystemError> {
+        self.get_hostname().await
+    }
+
+    async fn get_filesystems(&self) -> Result<Vec<String>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    async fn has_required_privileges(&self) -> Result<bool, SystemError> {
+        // Read access is governed by the BMC credentials
+        Ok(true)
+    }
+
+    async fn get_missing_dependencies(&self) -> Result<Vec<String>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_tool_versions(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_failed_units(&self) -> Result<Vec<String>, SystemError> {
+        Err(in_band_only("systemd units"))
+    }
+
+    async fn get_boot_mode(&self) -> Result<String, SystemError> {
+        Err(in_band_only("boot mode"))
+    }
+
+    async fn get_tpm_info(&self) -> Result<TpmInfo, SystemError> {
+        Ok(parse_redfish_tpm_info(&self.computer_system().await?))
+    }
+
+    async fn get_numa_balancing(&self) -> Result<Option<bool>, SystemError> {
+        Err(in_band_only("/proc/sys/kernel/numa_balancing"))
+    }
+
+    async fn get_bios_settings(&self) -> Result<HashMap<String, String>, SystemError> {
+        let bios = self.member_resource(SYSTEMS_PATH, "/Bios").await?;
+        parse_redfish_bios_attributes(&bios).map_err(SystemError::ParseError)
+    }
+
+    async fn get_os_machine_id(&self) -> Result<String, SystemError> {
+        Err(in_band_only("/etc/machine-id"))
+    }
+
+    async fn get_partitions(&self) -> Result<HashMap<String, Vec<Partition>>, SystemError> {
+        Err(in_band_only("partition table"))
+    }
+
+    async fn get_xid_errors(&self) -> Result<Vec<XidError>, SystemError> {
+        Err(in_band_only("kernel log"))
+    }
+
+    async fn get_kernel_hardware_errors(&self) -> Result<Vec<KernelError>, SystemError> {
+        Err(in_band_only("kernel log"))
+    }
+
+    async fn get_accelerators(&self) -> Result<Vec<Accelerator>, SystemError> {
+        Err(in_band_only("accelerators"))
+    }
+
+    async fn get_unbound_pci_devices(&self) -> Result<Vec<PciDevice>, SystemError> {
+        Err(in_band_only("PCI devices"))
+    }
+
+    async fn get_container_runtime(&self) -> Result<ContainerRuntimeInfo, SystemError> {
+        Err(in_band_only("container runtime"))
+    }
+
+    async fn get_container_limits(&self) -> Result<ContainerLimits, SystemError> {
+        Err(in_band_only("cgroups"))
+    }
+
+    async fn get_gpu_cgroup_allocations(&self) -> Result<Vec<GpuCgroupAllocation>, SystemError> {
+        Err(in_band_only("cgroups"))
+    }
+
+    async fn get_bmc_network(&self) -> Result<BmcNetwork, SystemError> {
+        let interface = self
+            .member_resource(MANAGERS_PATH, "/EthernetInterfaces")
+            .await
+            .and_then(|collection| {
+                parse_redfish_member_links(&collection)
+                    .map_err(SystemError::ParseError)?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| SystemError::NotAvailable {
+                        resource: "BMC Ethernet interface".to_string(),
+                    })
+            })?;
+        let resource = self
+            .redfish
+            .get(&interface)
+            .await
+            .map_err(SystemError::IoError)?;
+        parse_redfish_bmc_network(&resource).map_err(SystemError::ParseError)
+    }
+
+    async fn get_sel_events(&self) -> Result<Vec<SelEvent>, SystemError> {
+        Err(in_band_only("ipmitool SEL"))
+    }
+
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, SystemError> {
+        let thermal = self.member_resource(CHASSIS_PATH, "/Thermal").await?;
+        parse_redfish_thermal(&thermal).map_err(SystemError::ParseError)
+    }
+
+    async fn get_firmware_inventory(&self) -> Result<Vec<FirmwareComponent>, SystemError> {
+        self.redfish
+            .firmware_inventory()
+            .await
+            .map_err(SystemError::IoError)
+    }
+
+    async fn get_cloud_info(&self) -> Result<Option<CloudInfo>, SystemError> {
+        Err(in_band_only("cloud metadata"))
+    }
+
+    async fn get_time_sync(&self) -> Result<TimeSyncInfo, SystemError> {
+        Err(in_band_only("time sync"))
+    }
+
+    async fn get_watchdog(&self) -> Result<WatchdogInfo, SystemError> {
+        parse_redfish_host_watchdog(&self.computer_system().await?).ok_or_else(|| {
+            SystemError::NotAvailable {
+                resource: "Redfish HostWatchdogTimer".to_string(),
+            }
+        })
+    }
+
+    async fn get_power_info(&self) -> Result<PowerInfo, SystemError> {
+        let power = self.member_resource(CHASSIS_PATH, "/Power").await?;
+        parse_redfish_power(&power).map_err(SystemError::ParseError)
+    }
+
+    async fn get_raw_sources(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    async fn probe_capabilities(&self) -> Result<Capabilities, SystemError> {
+        let available = self.redfish.get(SYSTEMS_PATH).await.is_ok();
+        Ok(Capabilities {
+            adapter: "redfish".to_string(),
+            has_privileges: available,
+            subsystems: vec![SubsystemCapability {
+                name: "bmc".to_string(),
+                available,
+                missing_tools: Vec::new(),
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::ServiceContainer;
+    use crate::domain::ReportConfig;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Resources of a Supermicro-style BMC whose host never booted an OS
+    fn bmc_resource(path: &str) -> Option<&'static str> {
+        Some(match path {
+            "/redfish/v1/Systems" => r#"{"Members": [{"@odata.id": "/redfish/v1/Systems/1"}]}"#,
+            "/redfish/v1/Systems/1" => {
+                r#"{
+  "Id": "1",
+  "Manufacturer": "Supermicro",
+  "Model": "SYS-821GE-TNHR",
+  "SerialNumber": "S612345X4A01234",
+  "UUID": "00000000-0000-0000-0000-3CECEF123456",
+  "HostName": "",
+  "BiosVersion": "2.1",
+  "PowerState": "Off",
+  "ProcessorSummary": {"Count": 2, "Model": "Intel(R) Xeon(R) Platinum 8480+", "CoreCount": 112, "LogicalProcessorCount": 224},
+  "MemorySummary": {"TotalSystemMemoryGiB": 2048},
+  "TrustedModules": [{"InterfaceType": "TPM2_0", "Status": {"State": "Enabled"}}]
+}"#
+            }
+            "/redfish/v1/Systems/1/Bios" => {
+                r#"{"Attributes": {"Hyper-Threading": "Enabled", "SR-IOVSupport": "Enabled", "PCIeARISupport": 1}}"#
+            }
+            "/redfish/v1/Chassis" => r#"{"Members": [{"@odata.id": "/redfish/v1/Chassis/1"}]}"#,
+            "/redfish/v1/Chassis/1" => {
+                r#"{"Manufacturer": "Supermicro", "ChassisType": "RackMount", "SerialNumber": "C8210AM33A00123"}"#
+            }
+            "/redfish/v1/Chassis/1/Power" => {
+                r#"{
+  "PowerControl": [{"PowerConsumedWatts": 412}],
+  "PowerSupplies": [
+    {"Name": "PS1", "PowerCapacityWatts": 3000, "Status": {"State": "Enabled", "Health": "OK"}},
+    {"Name": "PS2", "PowerCapacityWatts": 3000, "Status": {"State": "Absent", "Health": "Critical"}}
+  ]
+}"#
+            }
+            "/redfish/v1/Chassis/1/Thermal" => {
+                r#"{
+  "Temperatures": [
+    {"Name": "CPU1 Temp", "ReadingCelsius": 38},
+    {"Name": "CPU2 Temp", "ReadingCelsius": null}
+  ],
+  "Fans": [{"Name": "FAN1", "Reading": 4200, "ReadingUnits": "RPM"}]
+}"#
+            }
+            "/redfish/v1/Managers" => r#"{"Members": [{"@odata.id": "/redfish/v1/Managers/1"}]}"#,
+            "/redfish/v1/Managers/1/EthernetInterfaces" => {
+                r#"{"Members": [{"@odata.id": "/redfish/v1/Managers/1/EthernetInterfaces/1"}]}"#
+            }
+            "/redfish/v1/Managers/1/EthernetInterfaces/1" => {
+                r#"{"MACAddress": "3C:EC:EF:12:34:56", "IPv4Addresses": [{"Address": "10.0.12.34", "AddressOrigin": "DHCP"}]}"#
+            }
+            "/redfish/v1/UpdateService/FirmwareInventory?$expand=.($levels=1)" => {
+                r#"{"Members": [{"Id": "BMC", "Name": "BMC", "Version": "01.03.12", "Updateable": true}]}"#
+            }
+            _ => return None,
+        })
+    }
+
+    async fn spawn_mock_bmc() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let (status, body) = match bmc_resource(path) {
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", ""),
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_out_of_band_report_from_redfish_only() {
+        let config = ReportConfig {
+            out_of_band_only: true,
+            // In-band only, so these are skipped rather than failing the report
+            collect_xid_errors: true,
+            collect_kernel_errors: true,
+            collect_container_runtime: true,
+            collect_operational: true,
+            collect_cloud_metadata: true,
+            collect_partitions: true,
+            redfish: Some(RedfishConfig {
+                endpoint: spawn_mock_bmc().await,
+                username: "ADMIN".to_string(),
+                password: "ADMIN".to_string(),
+                skip_tls_verify: false,
+            }),
+            ..Default::default()
+        };
+        let service = ServiceContainer::with_defaults()
+            .create_hardware_reporting_service(Some(config.clone()))
+            .unwrap();
+
+        let report = service.generate_report(config).await.unwrap();
+
+        // No OS, so the serial stands in for the host name
+        assert_eq!(report.hostname, "S612345X4A01234");
+        assert_eq!(report.summary.system_info.product_name, "SYS-821GE-TNHR");
+        assert_eq!(report.summary.chassis.type_, "RackMount");
+        assert_eq!(report.summary.bios.version, "2.1");
+        assert!(report.summary.tpm.present);
+        assert_eq!(report.summary.tpm.version.as_deref(), Some("2.0"));
+        assert_eq!(report.summary.total_memory, "2.0 TiB");
+        assert_eq!(report.hardware.cpu.sockets, 2);
+        assert_eq!(report.hardware.cpu.cores, 56);
+        assert_eq!(report.hardware.cpu.threads, 2);
+        assert_eq!(report.bmc_ip.as_deref(), Some("10.0.12.34"));
+        assert_eq!(report.bmc_mac.as_deref(), Some("3c:ec:ef:12:34:56"));
+        assert_eq!(report.bmc_ip_source.as_deref(), Some("dhcp"));
+        assert_eq!(report.bios_settings["PCIeARISupport"], "1");
+        assert_eq!(report.firmware_components[0].version, "01.03.12");
+        assert_eq!(report.hardware.gpus.recent_xid_errors, None);
+        assert_eq!(report.kernel_hardware_errors, None);
+        assert!(report.container_support.is_none());
+        assert!(report.operational.is_none());
+        assert!(report.cloud.is_none());
+
+        let power = report.power.unwrap();
+        assert_eq!(power.power_draw_watts, Some(412.0));
+        assert_eq!(power.power_supplies.len(), 2);
+        assert_eq!(power.power_supplies[1].health.as_deref(), Some("Critical"));
+
+        // CPU2 has no reading and is left out
+        assert_eq!(
+            report.sensors,
+            vec![
+                SensorReading {
+                    name: "CPU1 Temp".to_string(),
+                    reading: 38.0,
+                    units: "Cel".to_string(),
+                },
+                SensorReading {
+                    name: "FAN1".to_string(),
+                    reading: 4200.0,
+                    units: "RPM".to_string(),
+                },
+            ]
+        );
+        assert!(report.hardware.storage.devices.is_empty());
+        assert!(report.tool_versions.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_band_requires_redfish_endpoint() {
+        let config = ReportConfig {
+            out_of_band_only: true,
+            ..Default::default()
+        };
+        assert!(ServiceContainer::with_defaults()
+            .create_hardware_reporting_service(Some(config))
+            .is_err());
+    }
+}
@@ -0,0 +1,191 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Redfish BMC client
+
+use crate::domain::{
+    parse_redfish_firmware_component, parse_redfish_firmware_inventory, parse_redfish_member_links,
+    FirmwareComponent, RedfishConfig,
+};
+use reqwest::Client;
+use std::time::Duration;
+
+/// Firmware inventory collection under the Redfish service root
+const FIRMWARE_INVENTORY_PATH: &str = "/redfish/v1/UpdateService/FirmwareInventory";
+
+/// Client for a BMC's Redfish API
+pub struct RedfishClient {
+    client: Client,
+    base_url: String,
+    username: String,
+    password: String,
+}
+
+impl RedfishClient {
+    /// Create a Redfish client
+    ///
+    /// # Arguments
+    /// * `config` - BMC endpoint and credentials
+    /// * `timeout` - Per-request timeout
+    pub fn new(config: &RedfishConfig, timeout: Duration) -> Result<Self, reqwest::Error> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .danger_accept_invalid_certs(config.skip_tls_verify)
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: config.endpoint.trim_end_matches('/').to_string(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    /// Read the firmware inventory
+    ///
+    /// Asks for an expanded collection first; BMCs that ignore `$expand`
+    /// return bare links, which are then fetched one by one.
+    ///
+    /// # Returns
+    /// * Every component that reports a version
+    pub async fn firmware_inventory(&self) -> Result<Vec<FirmwareComponent>, String> {
+        let collection = self
+            .get(&format!("{FIRMWARE_INVENTORY_PATH}?$expand=.($levels=1)"))
+            .await?;
+        let components = parse_redfish_firmware_inventory(&collection)?;
+        if !components.is_empty() {
+            return Ok(components);
+        }
+
+        let mut components = Vec::new();
+        for link in parse_redfish_member_links(&collection)? {
+            let resource = self.get(&link).await?;
+            if let Ok(component) = parse_redfish_firmware_component(&resource) {
+                components.push(component);
+            }
+        }
+        Ok(components)
+    }
+
+    /// Path of the first member of a Redfish collection
+    ///
+    /// Single-node servers expose exactly one system, chassis and manager.
+    ///
+    /// # Arguments
+    /// * `collection` - Collection path (e.g., "/redfish/v1/Systems")
+    pub async fn first_member(&self, collection: &str) -> Result<String, String> {
+        let body = self.get(collection).await?;
+        parse_redfish_member_links(&body)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Redfish collection {collection} has no members"))
+    }
+
+    /// GET a Redfish path, returning the body of a successful response
+    pub async fn get(&self, path: &str) -> Result<String, String> {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.base_url))
+            .basic_auth(&self.username, Some(&self.password))
+            .send()
+            .await
+            .map_err(|e| format!("Redfish request to {path} failed: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Redfish request to {path} returned {}",
+                response.status()
+            ));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| format!("Redfish response from {path} unreadable: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve a firmware inventory without `$expand` support
+    async fn spawn_mock_bmc() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+
+                // "root:calvin" in basic auth
+                let (status, body) = if !request.contains("Basic cm9vdDpjYWx2aW4=") {
+                    ("401 Unauthorized", "")
+                } else if request.starts_with("GET /redfish/v1/UpdateService/FirmwareInventory?") {
+                    (
+                        "200 OK",
+                        r#"{"Members": [{"@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/BMC"}]}"#,
+                    )
+                } else if request
+                    .starts_with("GET /redfish/v1/UpdateService/FirmwareInventory/BMC ")
+                {
+                    (
+                        "200 OK",
+                        r#"{"Id": "BMC", "Name": "BMC", "Version": "01.02.03", "Updateable": true}"#,
+                    )
+                } else {
+                    ("404 Not Found", "")
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_firmware_inventory_follows_member_links() {
+        let config = RedfishConfig {
+            endpoint: spawn_mock_bmc().await,
+            username: "root".to_string(),
+            password: "calvin".to_string(),
+            skip_tls_verify: false,
+        };
+        let client = RedfishClient::new(&config, Duration::from_secs(2)).unwrap();
+
+        assert_eq!(
+            client.firmware_inventory().await.unwrap(),
+            vec![FirmwareComponent {
+                name: "BMC".to_string(),
+                version: "01.02.03".to_string(),
+                updateable: true,
+            }]
+        );
+
+        let wrong_password = RedfishConfig {
+            password: "wrong".to_string(),
+            ..config
+        };
+        let client = RedfishClient::new(&wrong_password, Duration::from_secs(2)).unwrap();
+        assert!(client.firmware_inventory().await.is_err());
+    }
+}
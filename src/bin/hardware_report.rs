@@ -14,17 +14,48 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use hardware_report::new_domain::format_cpu_list;
 use hardware_report::posting::post_data;
-use hardware_report::ServerInfo;
+use hardware_report::{CollectOptions, ServerInfo};
+use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::error::Error;
-use std::process::Command;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use structopt::StructOpt;
+use tokio::sync::Semaphore;
 
-#[derive(Debug)]
+/// Command run on each remote host in `--hosts` mode; the report arrives on stdout
+const REMOTE_REPORT_COMMAND: &[&str] = &[
+    "hardware_report",
+    "--noout",
+    "--format",
+    "json",
+    "--output",
+    "-",
+];
+
+#[derive(Debug, Clone, Copy)]
 enum FileFormat {
     Toml,
     Json,
+    Yaml,
+    Prometheus,
+    OpenMetrics,
+}
+
+impl FileFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Toml => "toml",
+            FileFormat::Json => "json",
+            FileFormat::Yaml => "yaml",
+            FileFormat::Prometheus => "prom",
+            FileFormat::OpenMetrics => "om",
+        }
+    }
 }
 
 impl std::str::FromStr for FileFormat {
@@ -34,7 +65,12 @@ impl std::str::FromStr for FileFormat {
         match s.to_uppercase().as_str() {
             "TOML" => Ok(FileFormat::Toml),
             "JSON" => Ok(FileFormat::Json),
-            _ => Err("File format must be either 'toml' or 'json'".to_string()),
+            "YAML" | "YML" => Ok(FileFormat::Yaml),
+            "PROMETHEUS" => Ok(FileFormat::Prometheus),
+            "OPENMETRICS" => Ok(FileFormat::OpenMetrics),
+            _ => Err(format!(
+                "unknown format '{s}'; expected 'toml', 'json', 'yaml', 'prometheus' or 'openmetrics'"
+            )),
         }
     }
 }
@@ -44,6 +80,9 @@ impl std::fmt::Display for FileFormat {
         match self {
             FileFormat::Toml => write!(f, "TOML"),
             FileFormat::Json => write!(f, "JSON"),
+            FileFormat::Yaml => write!(f, "YAML"),
+            FileFormat::Prometheus => write!(f, "Prometheus"),
+            FileFormat::OpenMetrics => write!(f, "OpenMetrics"),
         }
     }
 }
@@ -71,9 +110,15 @@ struct Opt {
     #[structopt(long)]
     system_identifier: Option<String>,
 
-    /// Output file format (toml or json)
-    #[structopt(long, default_value = "toml")]
-    _file_format: FileFormat,
+    /// Write a single report in this format (toml, json, yaml, prometheus or
+    /// openmetrics) instead of both the TOML and JSON files
+    #[structopt(long)]
+    format: Option<FileFormat>,
+
+    /// Path for the single report ("-" for stdout); implies --format toml
+    /// when no format is given
+    #[structopt(long)]
+    output: Option<String>,
 
     /// Save POST payload to specified file for debugging (only works with --post)
     #[structopt(long)]
@@ -86,6 +131,62 @@ struct Opt {
     /// No summary output to console
     #[structopt(long)]
     noout: bool,
+
+    /// Hostname to record in the report instead of the detected one
+    #[structopt(long)]
+    hostname_override: Option<String>,
+
+    /// Print what can be collected on this host as JSON and exit without collecting
+    #[structopt(long)]
+    capabilities: bool,
+
+    /// Exit without writing or posting if the hardware fingerprint matches the one
+    /// stored in this state file; otherwise update it after a successful run
+    #[structopt(long, parse(from_os_str))]
+    skip_unchanged: Option<PathBuf>,
+
+    /// Regex selecting data interfaces (e.g., "^ens"); differing MTUs are
+    /// recorded as a `data_mtu_mismatch` deviation in the report
+    #[structopt(long)]
+    data_interfaces: Option<Regex>,
+
+    /// Write only the static hardware subset to the output files, omitting IPs
+    /// and other values that change between runs
+    #[structopt(long)]
+    stable_only: bool,
+
+    /// Validate the serialized report against the report JSON Schema before
+    /// writing or posting it, failing on any mismatch
+    #[structopt(long)]
+    self_validate: bool,
+
+    /// Collect from every host listed in this file (one per line) over SSH,
+    /// writing one report per host instead of collecting locally
+    #[structopt(long, parse(from_os_str))]
+    hosts: Option<PathBuf>,
+
+    /// Maximum number of hosts collected at the same time with --hosts
+    #[structopt(long, default_value = "16")]
+    max_parallel: usize,
+
+    /// SSH client used with --hosts; hardware_report must be on the remote PATH
+    #[structopt(long, default_value = "ssh")]
+    ssh_command: String,
+
+    /// Skip slow per-device probes such as smartctl; the fields they populate
+    /// are left empty
+    #[structopt(long)]
+    fast: bool,
+}
+
+impl Opt {
+    /// Options for local collection
+    fn collect_options(&self) -> CollectOptions {
+        CollectOptions {
+            fast: self.fast,
+            data_interfaces: self.data_interfaces.clone(),
+        }
+    }
 }
 
 fn parse_label(s: &str) -> Result<(String, String), String> {
@@ -97,173 +198,541 @@ fn parse_label(s: &str) -> Result<(String, String), String> {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    let opt = Opt::from_args();
+/// Check whether the fingerprint matches the one recorded in the state file
+///
+/// A missing or unreadable state file counts as changed.
+fn is_unchanged(state_file: &Path, fingerprint: &str) -> bool {
+    std::fs::read_to_string(state_file)
+        .map(|stored| stored.trim() == fingerprint)
+        .unwrap_or(false)
+}
 
-    if opt.system_identifier.is_some() && !opt.post {
-        return Err("`--system-identifier` is only valid when posting (`--post`)".into());
+/// Record the fingerprint of a successful run in the state file
+fn record_fingerprint(state_file: &Path, fingerprint: &str) -> std::io::Result<()> {
+    std::fs::write(state_file, format!("{fingerprint}\n"))
+}
+
+/// Replace characters that are unsafe in file names with '_'
+fn sanitize_filename(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+}
+
+/// Read host names from a `--hosts` file, skipping blank lines and `#` comments
+fn read_host_list(path: &Path) -> std::io::Result<Vec<String>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Run hardware_report on a remote host over SSH and parse the report it prints
+async fn collect_from_host(
+    ssh_command: &str,
+    host: &str,
+    fast: bool,
+) -> Result<ServerInfo, String> {
+    let output = tokio::process::Command::new(ssh_command)
+        // "--" stops a host starting with '-' from being read as an ssh option
+        .args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10", "--", host])
+        .args(REMOTE_REPORT_COMMAND)
+        .args(fast.then_some("--fast"))
+        .output()
+        .await
+        .map_err(|e| format!("failed to run {ssh_command}: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} ({})", output.status, stderr.trim()));
     }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("invalid report: {e}"))
+}
 
-    // Collect server information
-    let server_info = ServerInfo::collect()?;
-
-    // Generate summary output for console only if no_summary is false
-    if !opt.noout {
-        println!("System Summary:");
-        println!("==============");
-        println!("Hostname: {}", server_info.hostname);
-        println!("FQDN: {}", server_info.fqdn);
-        println!("System UUID: {}", server_info.summary.system_info.uuid);
-        println!("System Serial: {}", server_info.summary.system_info.serial);
-        println!("CPU: {}", server_info.summary.cpu_summary);
-        println!(
-            "Total: {} Cores, {} Threads",
-            server_info.summary.cpu_topology.total_cores,
-            server_info.summary.cpu_topology.total_threads
-        );
+/// Collect from every host concurrently, at most `max_parallel` at a time
+///
+/// Results are in the same order as `hosts`.
+async fn collect_from_hosts(
+    hosts: &[String],
+    ssh_command: &str,
+    max_parallel: usize,
+    fast: bool,
+) -> Vec<Result<ServerInfo, String>> {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let tasks: Vec<_> = hosts
+        .iter()
+        .map(|host| {
+            let semaphore = Arc::clone(&semaphore);
+            let ssh_command = ssh_command.to_string();
+            let host = host.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+                collect_from_host(&ssh_command, &host, fast).await
+            })
+        })
+        .collect();
 
-        // Fix memory output format - add the missing format specifier
-        println!(
-            "Memory: {} {} @ {}",
-            server_info.hardware.memory.total,
-            server_info.hardware.memory.type_,
-            server_info.hardware.memory.speed
-        );
+    let mut results = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        results.push(task.await.unwrap_or_else(|e| Err(e.to_string())));
+    }
+    results
+}
 
-        println!(
-            "Storage: {} (Total: {:.2} TB)",
-            server_info.summary.total_storage, server_info.summary.total_storage_tb
-        );
+/// Write a host's report to `<dir>/<host>_hardware_report.<ext>` in each format
+///
+/// Files are keyed by the host name from the hosts file rather than the
+/// chassis serial, which whitebox nodes often leave as a shared placeholder.
+fn write_host_report(
+    host: &str,
+    server_info: &ServerInfo,
+    dir: &Path,
+    formats: &[FileFormat],
+    stable_only: bool,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let safe_filename = sanitize_filename(host);
+    let mut written = Vec::new();
+    for format in formats {
+        let report = render_server_info(server_info, format, stable_only)?;
+        let path = dir.join(format!(
+            "{safe_filename}_hardware_report.{}",
+            format.extension()
+        ));
+        std::fs::write(&path, report)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Reject host lists where two entries would write to the same report file
+fn check_unique_report_names(hosts: &[String]) -> Result<(), String> {
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for host in hosts {
+        if let Some(previous) = seen.insert(sanitize_filename(host), host) {
+            return Err(format!(
+                "hosts '{previous}' and '{host}' would write to the same report file"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `--hosts` mode: collect from each listed host and print a per-host summary
+async fn run_hosts(opt: &Opt, hosts_file: &Path) -> Result<(), Box<dyn Error>> {
+    let hosts = read_host_list(hosts_file)?;
+    check_unique_report_names(&hosts)?;
+    let formats = opt
+        .format
+        .map_or(vec![FileFormat::Toml, FileFormat::Json], |format| {
+            vec![format]
+        });
+
+    let results = collect_from_hosts(&hosts, &opt.ssh_command, opt.max_parallel, opt.fast).await;
+
+    let mut failed = 0;
+    println!("Collection summary:");
+    for (host, result) in hosts.iter().zip(results) {
+        let written = result.and_then(|server_info| {
+            if opt.self_validate {
+                check_report_schema(&server_info).map_err(|e| e.to_string())?;
+            }
+            write_host_report(
+                host,
+                &server_info,
+                Path::new("."),
+                &formats,
+                opt.stable_only,
+            )
+            .map_err(|e| e.to_string())
+        });
+        match written {
+            Ok(paths) => {
+                let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+                println!("  {host}: ok ({})", paths.join(", "));
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  {host}: failed: {e}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} of {} hosts failed", hosts.len()).into());
+    }
+    Ok(())
+}
 
-        // Calculate total storage - show clean disk sizes
-        let disk_sizes: Vec<String> = server_info
-            .hardware
-            .storage
-            .devices
-            .iter()
-            .map(|device| {
-                // Extract clean size from macOS format or use as-is for Linux
-                if device.size.contains("TB (") {
-                    // Extract "2.0 TB" from "2.0 TB (2001111162880 Bytes) (exactly...)"
-                    device
-                        .size
-                        .split(" (")
-                        .next()
-                        .unwrap_or(&device.size)
-                        .to_string()
+/// `--self-validate`: reject a report whose serialized form violates the schema
+fn check_report_schema(server_info: &ServerInfo) -> Result<(), Box<dyn Error>> {
+    hardware_report::schema::validate_report(server_info).map_err(|errors| {
+        format!(
+            "report does not match its JSON Schema:\n  {}",
+            errors.join("\n  ")
+        )
+        .into()
+    })
+}
+
+/// Render collected server information in the requested format
+///
+/// `stable_only` selects the static subset for the data formats; the metric
+/// expositions carry no addresses and always render the full report.
+fn render_server_info(
+    server_info: &ServerInfo,
+    format: &FileFormat,
+    stable_only: bool,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        FileFormat::Prometheus => Ok(hardware_report::export_prometheus(server_info)),
+        FileFormat::OpenMetrics => Ok(hardware_report::export_openmetrics(server_info)),
+        _ if stable_only => render_report(&server_info.stable_subset(), format),
+        _ => render_report(server_info, format),
+    }
+}
+
+/// Serialize a report in the requested data format
+fn render_report<T: Serialize>(report: &T, format: &FileFormat) -> Result<String, Box<dyn Error>> {
+    Ok(match format {
+        FileFormat::Toml => toml::to_string_pretty(report)?,
+        FileFormat::Json => serde_json::to_string_pretty(report)?,
+        FileFormat::Yaml => to_yaml(&serde_json::to_value(report)?),
+        FileFormat::Prometheus | FileFormat::OpenMetrics => {
+            return Err(format!("{format} output is rendered from the full report").into())
+        }
+    })
+}
+
+/// Render a JSON value as a block-style YAML document
+///
+/// Strings and keys are emitted as double-quoted scalars, which YAML reads
+/// with the same escapes as JSON.
+fn to_yaml(value: &serde_json::Value) -> String {
+    let mut out = String::from("---\n");
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => write_yaml_block(&mut out, value, 0),
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            write_yaml_block(&mut out, value, 0)
+        }
+        scalar => writeln!(out, "{}", yaml_scalar(scalar)).unwrap(),
+    }
+    out
+}
+
+fn is_yaml_block(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => !map.is_empty(),
+        serde_json::Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn yaml_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(_) => "{}".to_string(),
+        serde_json::Value::Array(_) => "[]".to_string(),
+        // JSON scalars (null, booleans, numbers, quoted strings) are valid YAML
+        scalar => scalar.to_string(),
+    }
+}
+
+fn write_yaml_block(out: &mut String, value: &serde_json::Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, item) in map {
+                let key = serde_json::Value::String(key.clone());
+                if is_yaml_block(item) {
+                    writeln!(out, "{pad}{key}:").unwrap();
+                    write_yaml_block(out, item, indent + 2);
                 } else {
-                    device.size.clone()
+                    writeln!(out, "{pad}{key}: {}", yaml_scalar(item)).unwrap();
                 }
-            })
-            .collect();
-        if !disk_sizes.is_empty() {
-            println!("Available Disks: {}", disk_sizes.join(" + "));
+            }
         }
-
-        // Get BIOS/Firmware information (platform-specific)
-        if cfg!(target_os = "macos") {
-            println!(
-                "BIOS: {} {} ({})",
-                server_info.summary.bios.vendor,
-                server_info.summary.bios.version,
-                server_info.summary.bios.release_date
-            );
-            println!(
-                "Chassis: {} {} (S/N: {})",
-                server_info.summary.chassis.manufacturer,
-                server_info.summary.chassis.type_,
-                server_info.summary.chassis.serial
-            );
-        } else {
-            // Linux - use dmidecode
-            let output = Command::new("dmidecode").args(["-t", "bios"]).output()?;
-            let bios_str = String::from_utf8(output.stdout)?;
-            println!(
-                "BIOS: {} {} ({})",
-                ServerInfo::extract_dmidecode_value(&bios_str, "Vendor")?,
-                ServerInfo::extract_dmidecode_value(&bios_str, "Version")?,
-                ServerInfo::extract_dmidecode_value(&bios_str, "Release Date")?
-            );
-
-            // Get chassis information from dmidecode
-            let output = Command::new("dmidecode").args(["-t", "chassis"]).output()?;
-            let chassis_str = String::from_utf8(output.stdout)?;
-            println!(
-                "Chassis: {} {} (S/N: {})",
-                ServerInfo::extract_dmidecode_value(&chassis_str, "Manufacturer")?,
-                ServerInfo::extract_dmidecode_value(&chassis_str, "Type")?,
-                ServerInfo::extract_dmidecode_value(&chassis_str, "Serial Number")?
-            );
+        serde_json::Value::Array(items) => {
+            for item in items {
+                if is_yaml_block(item) {
+                    // Put the item's first line on the "- " line
+                    let mut nested = String::new();
+                    write_yaml_block(&mut nested, item, indent + 2);
+                    out.push_str(&pad);
+                    out.push_str("- ");
+                    out.push_str(&nested[indent + 2..]);
+                } else {
+                    writeln!(out, "{pad}- {}", yaml_scalar(item)).unwrap();
+                }
+            }
         }
+        scalar => writeln!(out, "{pad}{}", yaml_scalar(scalar)).unwrap(),
+    }
+}
 
-        // Get motherboard information from server_info
-        println!(
-            "Motherboard: {} {} v{} (S/N: {})",
-            server_info.summary.motherboard.manufacturer,
-            server_info.summary.motherboard.product_name,
-            server_info.summary.motherboard.version,
-            server_info.summary.motherboard.serial
-        );
+/// Render the console summary from collected server information
+fn render_summary(server_info: &ServerInfo) -> String {
+    let mut out = String::new();
 
-        println!("\nNetwork Interfaces:");
-        for nic in &server_info.network.interfaces {
-            let numa_info = if cfg!(target_os = "macos") || nic.numa_node.is_none() {
-                String::new() // No NUMA info on macOS or when not detected
+    writeln!(out, "System Summary:").unwrap();
+    writeln!(out, "==============").unwrap();
+    writeln!(out, "Hostname: {}", server_info.hostname).unwrap();
+    if let Some(detected) = &server_info.detected_hostname {
+        writeln!(out, "Detected Hostname: {detected}").unwrap();
+    }
+    writeln!(out, "FQDN: {}", server_info.fqdn).unwrap();
+    if !server_info.machine_id.is_empty() {
+        writeln!(
+            out,
+            "Machine ID: {} ({})",
+            server_info.machine_id, server_info.machine_id_source
+        )
+        .unwrap();
+    }
+    writeln!(out, "System UUID: {}", server_info.summary.system_info.uuid).unwrap();
+    writeln!(
+        out,
+        "System Serial: {}",
+        server_info.summary.system_info.serial
+    )
+    .unwrap();
+    writeln!(out, "CPU: {}", server_info.summary.cpu_summary).unwrap();
+    writeln!(
+        out,
+        "Total: {} Cores, {} Threads",
+        server_info.summary.cpu_topology.total_cores,
+        server_info.summary.cpu_topology.total_threads
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Memory: {} {} @ {}",
+        server_info.hardware.memory.total,
+        server_info.hardware.memory.type_,
+        server_info.hardware.memory.speed
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Storage: {} (Total: {:.2} TB)",
+        server_info.summary.total_storage, server_info.summary.total_storage_tb
+    )
+    .unwrap();
+
+    // Show clean disk sizes
+    let disk_sizes: Vec<String> = server_info
+        .hardware
+        .storage
+        .devices
+        .iter()
+        .map(|device| {
+            // Extract clean size from macOS format or use as-is for Linux
+            if device.size.contains("TB (") {
+                // Extract "2.0 TB" from "2.0 TB (2001111162880 Bytes) (exactly...)"
+                device
+                    .size
+                    .split(" (")
+                    .next()
+                    .unwrap_or(&device.size)
+                    .to_string()
             } else {
-                format!(
-                    " [NUMA: {}]",
-                    nic.numa_node
-                        .map_or("Unknown".to_string(), |n| n.to_string())
+                device.size.clone()
+            }
+        })
+        .collect();
+    if !disk_sizes.is_empty() {
+        writeln!(out, "Available Disks: {}", disk_sizes.join(" + ")).unwrap();
+    }
+
+    writeln!(
+        out,
+        "BIOS: {} {} ({})",
+        server_info.summary.bios.vendor,
+        server_info.summary.bios.version,
+        server_info.summary.bios.release_date
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Chassis: {} {} (S/N: {})",
+        server_info.summary.chassis.manufacturer,
+        server_info.summary.chassis.type_,
+        server_info.summary.chassis.serial
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Motherboard: {} {} v{} (S/N: {})",
+        server_info.summary.motherboard.manufacturer,
+        server_info.summary.motherboard.product_name,
+        server_info.summary.motherboard.version,
+        server_info.summary.motherboard.serial
+    )
+    .unwrap();
+
+    writeln!(out, "\nNetwork Interfaces:").unwrap();
+    for nic in &server_info.network.interfaces {
+        let numa_info = if cfg!(target_os = "macos") || nic.numa_node.is_none() {
+            String::new() // No NUMA info on macOS or when not detected
+        } else {
+            format!(
+                " [NUMA: {}]",
+                nic.numa_node
+                    .map_or("Unknown".to_string(), |n| n.to_string())
+            )
+        };
+
+        let pci_info = if cfg!(target_os = "macos") && nic.pci_id == "Unknown" {
+            String::new() // Hide PCI ID on macOS when not available
+        } else {
+            format!(" ({})", nic.pci_id)
+        };
+
+        writeln!(
+            out,
+            "  {} - {} {}{} [Speed: {}]{}",
+            nic.name,
+            nic.vendor,
+            nic.model,
+            pci_info,
+            nic.speed.as_deref().unwrap_or("Unknown"),
+            numa_info
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "\nGPUs:").unwrap();
+    for gpu in &server_info.hardware.gpus.devices {
+        let numa_info = if cfg!(target_os = "macos") || gpu.numa_node.is_none() {
+            String::new() // No NUMA info on macOS or when not detected
+        } else {
+            format!(
+                " [NUMA: {}]",
+                gpu.numa_node
+                    .map_or("Unknown".to_string(), |n| n.to_string())
+            )
+        };
+
+        let pci_info = if cfg!(target_os = "macos") && gpu.pci_id == "Unknown" {
+            String::new() // Hide PCI ID on macOS when not available
+        } else {
+            format!(" ({})", gpu.pci_id)
+        };
+
+        let memory_info = if gpu.memory != "Unknown" {
+            format!(" [{}]", gpu.memory)
+        } else {
+            String::new()
+        };
+
+        writeln!(
+            out,
+            "  {} - {}{}{}{}",
+            gpu.name, gpu.vendor, memory_info, pci_info, numa_info
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// Render the NUMA topology section
+fn render_numa_topology(server_info: &ServerInfo) -> String {
+    let mut out = String::new();
+    if server_info.summary.numa_topology.is_empty() {
+        return out;
+    }
+
+    writeln!(out, "\nNUMA Topology:").unwrap();
+    for (node_id, node) in &server_info.summary.numa_topology {
+        writeln!(out, "  Node {node_id}:").unwrap();
+        writeln!(out, "    Memory: {}", node.memory).unwrap();
+        writeln!(out, "    CPUs: {}", format_cpu_list(&node.cpus)).unwrap();
+
+        if !node.devices.is_empty() {
+            writeln!(out, "    Devices:").unwrap();
+            for device in &node.devices {
+                writeln!(
+                    out,
+                    "      {} - {} (PCI ID: {})",
+                    device.type_, device.name, device.pci_id
                 )
-            };
+                .unwrap();
+            }
+        }
 
-            let pci_info = if cfg!(target_os = "macos") && nic.pci_id == "Unknown" {
-                String::new() // Hide PCI ID on macOS when not available
-            } else {
-                format!(" ({})", nic.pci_id)
-            };
-
-            println!(
-                "  {} - {} {}{} [Speed: {}]{}",
-                nic.name,
-                nic.vendor,
-                nic.model,
-                pci_info,
-                nic.speed.as_deref().unwrap_or("Unknown"),
-                numa_info
-            );
+        writeln!(out, "    Distances:").unwrap();
+        let mut distances: Vec<_> = node.distances.iter().collect();
+        distances.sort_by_key(|&(k, _)| k);
+        for (to_node, distance) in distances {
+            writeln!(out, "      To Node {to_node}: {distance}").unwrap();
         }
+    }
 
-        println!("\nGPUs:");
-        for gpu in &server_info.hardware.gpus.devices {
-            let numa_info = if cfg!(target_os = "macos") || gpu.numa_node.is_none() {
-                String::new() // No NUMA info on macOS or when not detected
-            } else {
-                format!(
-                    " [NUMA: {}]",
-                    gpu.numa_node
-                        .map_or("Unknown".to_string(), |n| n.to_string())
-                )
-            };
+    out
+}
 
-            let pci_info = if cfg!(target_os = "macos") && gpu.pci_id == "Unknown" {
-                String::new() // Hide PCI ID on macOS when not available
-            } else {
-                format!(" ({})", gpu.pci_id)
-            };
+/// Render the filesystems section
+fn render_filesystems(server_info: &ServerInfo) -> String {
+    let mut out = String::new();
+    writeln!(out, "\nFilesystems:").unwrap();
+    for filesystem in &server_info.summary.filesystems {
+        writeln!(out, "  {filesystem}").unwrap();
+    }
+    out
+}
 
-            let memory_info = if gpu.memory != "Unknown" {
-                format!(" [{}]", gpu.memory)
-            } else {
-                String::new()
-            };
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+
+    if opt.system_identifier.is_some() && !opt.post {
+        return Err("`--system-identifier` is only valid when posting (`--post`)".into());
+    }
+
+    if opt.capabilities {
+        let capabilities = hardware_report::probe_capabilities().await?;
+        println!("{}", serde_json::to_string_pretty(&capabilities)?);
+        return Ok(());
+    }
 
-            println!(
-                "  {} - {}{}{}{}",
-                gpu.name, gpu.vendor, memory_info, pci_info, numa_info
-            );
+    if let Some(hosts_file) = &opt.hosts {
+        if opt.output.is_some() || opt.post {
+            return Err("`--hosts` writes one report per host and cannot be combined with `--output` or `--post`".into());
         }
+        return run_hosts(&opt, hosts_file).await;
+    }
+
+    let to_stdout = opt.output.as_deref() == Some("-");
+
+    // Collect server information
+    let mut server_info = ServerInfo::collect_with(&opt.collect_options())?;
+    if let Some(hostname) = &opt.hostname_override {
+        server_info.apply_hostname_override(hostname);
+    }
+
+    if opt.self_validate {
+        check_report_schema(&server_info)?;
+    }
+
+    let fingerprint = server_info.hardware_fingerprint();
+    if let Some(state_file) = &opt.skip_unchanged {
+        if is_unchanged(state_file, &fingerprint) {
+            println!("Hardware unchanged since last run ({fingerprint}); skipping");
+            return Ok(());
+        }
+    }
+
+    // Generate summary output for console only if no_summary is false; the
+    // report itself owns stdout when writing there
+    if !opt.noout && !to_stdout {
+        print!("{}", render_summary(&server_info));
 
         // On macOS, show display information summary
         if cfg!(target_os = "macos") {
@@ -292,81 +761,45 @@ async fn main() -> Result<(), Box<dyn Error>> {
         }
 
         // Only show NUMA topology on Linux where it's relevant
-        if !cfg!(target_os = "macos") && !server_info.summary.numa_topology.is_empty() {
-            println!("\nNUMA Topology:");
-            for (node_id, node) in &server_info.summary.numa_topology {
-                println!("  Node {node_id}:");
-                println!("    Memory: {}", node.memory);
-                println!("    CPUs: {:?}", node.cpus);
-
-                if !node.devices.is_empty() {
-                    println!("    Devices:");
-                    for device in &node.devices {
-                        println!(
-                            "      {} - {} (PCI ID: {})",
-                            device.type_, device.name, device.pci_id
-                        );
-                    }
-                }
-
-                println!("    Distances:");
-                let mut distances: Vec<_> = node.distances.iter().collect();
-                distances.sort_by_key(|&(k, _)| k);
-                for (to_node, distance) in distances {
-                    println!("      To Node {to_node}: {distance}");
-                }
-            }
+        if !cfg!(target_os = "macos") {
+            print!("{}", render_numa_topology(&server_info));
         }
 
-        // Get filesystem information
-        println!("\nFilesystems:");
-        let output = Command::new("df")
-            .args(["-h", "--output=source,fstype,size,used,avail,target"])
-            .output()?;
-        let fs_str = String::from_utf8(output.stdout)?;
-        for line in fs_str.lines().skip(1) {
-            let fields: Vec<&str> = line.split_whitespace().collect();
-            if fields.len() >= 6 {
-                println!(
-                    "  {} ({}) - {} total, {} used, {} available, mounted on {}",
-                    fields[0], fields[1], fields[2], fields[3], fields[4], fields[5]
-                );
-            }
-        }
+        print!("{}", render_filesystems(&server_info));
+    }
+
+    if let Some(mismatch) = server_info
+        .configuration_deviations
+        .iter()
+        .find(|deviation| deviation.code == "data_mtu_mismatch")
+    {
+        eprintln!(
+            "\nWarning: data interfaces have inconsistent MTUs: {}",
+            mismatch.actual
+        );
     }
 
     // Get chassis serial number and sanitize it for use as the file_name
     let chassis_serial = server_info.summary.chassis.serial.clone();
     let safe_filename = sanitize_filename(&chassis_serial);
 
-    fn sanitize_filename(filename: &str) -> String {
-        filename
-            .chars()
-            .map(|c| {
-                if c.is_alphanumeric() || c == '-' {
-                    c
-                } else {
-                    '_'
-                }
-            })
-            .collect::<String>()
-    }
-
-    println!("\nCreating output files for system serial number: {safe_filename}");
-
-    // Generate both TOML and JSON files
-    let toml_filename = format!("{safe_filename}_hardware_report.toml");
-    let json_filename = format!("{safe_filename}_hardware_report.json");
-
-    // Write TOML file
-    let toml_string = toml::to_string_pretty(&server_info)?;
-    std::fs::write(&toml_filename, toml_string)?;
-
-    // Write JSON file
-    let json_string = serde_json::to_string_pretty(&server_info)?;
-    std::fs::write(&json_filename, json_string)?;
+    if opt.format.is_some() || opt.output.is_some() {
+        let format = opt.format.as_ref().unwrap_or(&FileFormat::Toml);
+        let report = render_server_info(&server_info, format, opt.stable_only)?;
+        let output = opt
+            .output
+            .clone()
+            .unwrap_or_else(|| format!("{safe_filename}_hardware_report.{}", format.extension()));
 
-    println!("Configuration files have been written:");
+        if to_stdout {
+            print!("{report}");
+        } else {
+            std::fs::write(&output, report)?;
+            println!("\n{format} hardware report written to {output}");
+        }
+    } else {
+        write_report_files(&server_info, &safe_filename, opt.stable_only)?;
+    }
 
     // Handle posting if enabled
     if opt.post {
@@ -381,13 +814,352 @@ async fn main() -> Result<(), Box<dyn Error>> {
             opt.skip_tls_verify,
         )
         .await?;
-        println!("\nSuccessfully posted data to remote server");
+        if !to_stdout {
+            println!("\nSuccessfully posted data to remote server");
+        }
+    }
+
+    // Only record the fingerprint once the run succeeded, so failures are retried
+    if let Some(state_file) = &opt.skip_unchanged {
+        record_fingerprint(state_file, &fingerprint)?;
     }
 
-    // Final message about available output formats
+    Ok(())
+}
+
+/// Write the report as both `<serial>_hardware_report.toml` and `.json`
+fn write_report_files(
+    server_info: &ServerInfo,
+    safe_filename: &str,
+    stable_only: bool,
+) -> Result<(), Box<dyn Error>> {
+    println!("\nCreating output files for system serial number: {safe_filename}");
+
+    // Generate both TOML and JSON files
+    let toml_filename = format!("{safe_filename}_hardware_report.toml");
+    let json_filename = format!("{safe_filename}_hardware_report.json");
+
+    // Write TOML and JSON files
+    let (toml_string, json_string) = if stable_only {
+        let stable = server_info.stable_subset();
+        (
+            toml::to_string_pretty(&stable)?,
+            serde_json::to_string_pretty(&stable)?,
+        )
+    } else {
+        (
+            toml::to_string_pretty(server_info)?,
+            serde_json::to_string_pretty(server_info)?,
+        )
+    };
+    std::fs::write(&toml_filename, toml_string)?;
+    std::fs::write(&json_filename, json_string)?;
+
+    println!("Configuration files have been written:");
     println!("\nHardware report files are available in both JSON and TOML formats:");
     println!("  - {toml_filename}");
     println!("  - {json_filename}");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_sample_server_info() -> ServerInfo {
+        serde_json::from_str(include_str!("../../MYQQGPTJ6J_hardware_report.json")).unwrap()
+    }
+
+    #[test]
+    fn test_summary_matches_library_data() {
+        let mut server_info = load_sample_server_info();
+        server_info.machine_id = "4c4c4544-0042-3510-8051-b2c04f4e4c33".to_string();
+        server_info.machine_id_source = "system_uuid".to_string();
+        let summary = render_summary(&server_info);
+
+        assert!(summary.contains(&format!("Hostname: {}", server_info.hostname)));
+        assert!(summary.contains("Machine ID: 4c4c4544-0042-3510-8051-b2c04f4e4c33 (system_uuid)"));
+        assert!(summary.contains(&format!(
+            "BIOS: {} {} ({})",
+            server_info.summary.bios.vendor,
+            server_info.summary.bios.version,
+            server_info.summary.bios.release_date
+        )));
+        assert!(summary.contains(&format!(
+            "Chassis: {} {} (S/N: {})",
+            server_info.summary.chassis.manufacturer,
+            server_info.summary.chassis.type_,
+            server_info.summary.chassis.serial
+        )));
+        for nic in &server_info.network.interfaces {
+            assert!(summary.contains(&format!("  {} - {}", nic.name, nic.vendor)));
+        }
+    }
+
+    #[test]
+    fn test_filesystems_match_library_data() {
+        let server_info = load_sample_server_info();
+        let filesystems = render_filesystems(&server_info);
+
+        assert!(!server_info.summary.filesystems.is_empty());
+        for filesystem in &server_info.summary.filesystems {
+            assert!(filesystems.contains(filesystem.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_skip_unchanged_second_run() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let state_file = temp_dir.path().join("hardware_report.state");
+
+        // First run has no state and publishes
+        let fingerprint = load_sample_server_info().hardware_fingerprint();
+        assert!(!is_unchanged(&state_file, &fingerprint));
+        record_fingerprint(&state_file, &fingerprint).unwrap();
+
+        // Second run with identical hardware skips, even if an IP, the CPU
+        // clock, the disk probe order or SMART state changed
+        let mut second = load_sample_server_info();
+        if let Some(nic) = second.network.interfaces.first_mut() {
+            nic.ip = "192.0.2.10".to_string();
+        }
+        second.hardware.cpu.speed = "1499.998 MHz".to_string();
+        second.hardware.storage.devices.reverse();
+        for (i, device) in second.hardware.storage.devices.iter_mut().enumerate() {
+            device.name = format!("nvme{i}n1");
+            device.smart_healthy = Some(false);
+            device.wearout_percent = Some(42);
+        }
+        assert!(is_unchanged(&state_file, &second.hardware_fingerprint()));
+
+        // A hardware change publishes again
+        second.hardware.cpu.sockets += 1;
+        assert!(!is_unchanged(&state_file, &second.hardware_fingerprint()));
+    }
+
+    #[test]
+    fn test_hostname_override() {
+        let mut server_info = load_sample_server_info();
+        server_info.fqdn = format!("{}.lab.example.com", server_info.hostname);
+        let detected = server_info.hostname.clone();
+        server_info.apply_hostname_override("gpu-node-07");
+
+        assert_eq!(server_info.hostname, "gpu-node-07");
+        assert_eq!(server_info.fqdn, "gpu-node-07.lab.example.com");
+        assert_eq!(server_info.detected_hostname, Some(detected.clone()));
+
+        // The detected hostname survives a save/load round trip
+        let json = render_report(&server_info, &FileFormat::Json).unwrap();
+        let reloaded: ServerInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.hostname, "gpu-node-07");
+        assert_eq!(reloaded.detected_hostname, Some(detected));
+    }
+
+    #[test]
+    fn test_fast_flag_skips_slow_probes() {
+        let opt = Opt::from_iter(["hardware_report", "--fast"]);
+        assert!(opt.collect_options().fast);
+
+        let opt = Opt::from_iter(["hardware_report"]);
+        assert!(!opt.collect_options().fast);
+    }
+
+    #[test]
+    fn test_data_interfaces_pattern_reaches_collection() {
+        let opt = Opt::from_iter(["hardware_report", "--data-interfaces", "^ens"]);
+        let pattern = opt.collect_options().data_interfaces.unwrap();
+        assert_eq!(pattern.as_str(), "^ens");
+
+        assert!(Opt::from_iter_safe(["hardware_report", "--data-interfaces", "("]).is_err());
+    }
+
+    #[test]
+    fn test_stable_only_report() {
+        let mut server_info = load_sample_server_info();
+        server_info.hardware.memory.modules[0].serial = "80CE012345678ABC".to_string();
+        server_info.network.interfaces[0].ip = "10.20.30.40".to_string();
+
+        let stable = toml::to_string_pretty(&server_info.stable_subset()).unwrap();
+
+        assert!(stable.contains("80CE012345678ABC"));
+        assert!(stable.contains(&server_info.network.interfaces[0].mac));
+        assert!(!stable.contains("10.20.30.40"));
+        assert!(!stable.contains("100.112.156.46"));
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let server_info = load_sample_server_info();
+
+        let json = render_report(&server_info, &"json".parse().unwrap()).unwrap();
+        let parsed: ServerInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.hostname, server_info.hostname);
+
+        let yaml = render_report(&server_info, &"yaml".parse().unwrap()).unwrap();
+        assert!(yaml.starts_with("---\n"));
+        assert!(yaml.contains(&format!("\"hostname\": \"{}\"", server_info.hostname)));
+
+        let err = "xml".parse::<FileFormat>().unwrap_err();
+        assert!(err.contains("'xml'"));
+    }
+
+    #[test]
+    fn test_metric_formats() {
+        let server_info = load_sample_server_info();
+        let labels = format!(
+            "hostname=\"{}\",serial=\"{}\"",
+            server_info.hostname, server_info.summary.system_info.serial
+        );
+
+        let prometheus =
+            render_server_info(&server_info, &"prometheus".parse().unwrap(), true).unwrap();
+        assert!(prometheus.contains("# TYPE hardware_report_total_memory_bytes gauge\n"));
+        assert!(prometheus.contains(&format!("hardware_report_gpu_count{{{labels}}} ")));
+        assert!(!prometheus.contains("# UNIT"));
+        assert!(!prometheus.contains("# EOF"));
+
+        // Same families and labels, with OpenMetrics metadata
+        let openmetrics =
+            render_server_info(&server_info, &"openmetrics".parse().unwrap(), false).unwrap();
+        assert!(openmetrics.contains("# UNIT hardware_report_total_memory_bytes bytes\n"));
+        assert!(openmetrics.contains(&format!("hardware_report_gpu_count{{{labels}}} ")));
+        assert!(openmetrics.contains(&format!(
+            "hardware_report_storage_device_size_bytes{{{labels},device="
+        )));
+        assert!(openmetrics.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_reload_saved_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server_info = load_sample_server_info();
+
+        let toml_path = temp_dir.path().join("report.toml");
+        std::fs::write(
+            &toml_path,
+            render_report(&server_info, &FileFormat::Toml).unwrap(),
+        )
+        .unwrap();
+        let reloaded = ServerInfo::from_toml_file(&toml_path).unwrap();
+        assert_eq!(
+            reloaded.hardware_fingerprint(),
+            server_info.hardware_fingerprint()
+        );
+
+        // A report written before later fields existed still loads
+        let mut older = serde_json::to_value(&server_info).unwrap();
+        older.as_object_mut().unwrap().remove("detected_hostname");
+        for device in older["hardware"]["storage"]["devices"]
+            .as_array_mut()
+            .unwrap()
+        {
+            device.as_object_mut().unwrap().remove("serial");
+        }
+        let json_path = temp_dir.path().join("report.json");
+        std::fs::write(&json_path, older.to_string()).unwrap();
+        let reloaded = ServerInfo::from_json_file(&json_path).unwrap();
+        assert_eq!(reloaded.hostname, server_info.hostname);
+        assert!(reloaded.detected_hostname.is_none());
+
+        let err = ServerInfo::from_json_file(&temp_dir.path().join("missing.json")).unwrap_err();
+        assert!(err.to_string().contains("missing.json"));
+    }
+
+    #[test]
+    fn test_self_validate() {
+        let mut server_info = load_sample_server_info();
+        assert!(check_report_schema(&server_info).is_ok());
+
+        // A NaN total serializes as null where the schema wants a number
+        server_info.summary.total_storage_tb = f64::NAN;
+        let err = check_report_schema(&server_info).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("/summary/total_storage_tb: expected number, found null"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hosts_mode_writes_report_per_host() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sample = temp_dir.path().join("sample.json");
+        std::fs::write(
+            &sample,
+            include_str!("../../MYQQGPTJ6J_hardware_report.json"),
+        )
+        .unwrap();
+
+        // Stands in for ssh: the host follows "--" as the sixth argument and
+        // becomes the hostname, while every host shares a placeholder serial
+        let ssh = temp_dir.path().join("mock-ssh");
+        std::fs::write(
+            &ssh,
+            format!(
+                r#"#!/bin/sh
+[ "$5" = "--" ] || exit 1
+sed -e "s/MYQQGPTJ6J/Unknown/g" -e "s/Kennys-MacBook-Pro.local/$6/g" {}
+"#,
+                sample.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&ssh, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let hosts_file = temp_dir.path().join("hosts");
+        std::fs::write(&hosts_file, "# rack 12\nnode-a\n\nnode-b\n").unwrap();
+        let hosts = read_host_list(&hosts_file).unwrap();
+        assert_eq!(hosts, vec!["node-a", "node-b"]);
+
+        let results = collect_from_hosts(&hosts, ssh.to_str().unwrap(), 2, false).await;
+        for (host, result) in hosts.iter().zip(results) {
+            write_host_report(
+                host,
+                &result.unwrap(),
+                temp_dir.path(),
+                &[FileFormat::Json],
+                false,
+            )
+            .unwrap();
+        }
+
+        for host in ["node-a", "node-b"] {
+            let report = std::fs::read_to_string(
+                temp_dir.path().join(format!("{host}_hardware_report.json")),
+            )
+            .unwrap();
+            let server_info: ServerInfo = serde_json::from_str(&report).unwrap();
+            assert_eq!(server_info.hostname, host);
+            assert_eq!(server_info.summary.chassis.serial, "Unknown");
+        }
+
+        let colliding = ["node.a".to_string(), "node_a".to_string()];
+        assert!(check_unique_report_names(&colliding).is_err());
+        assert!(check_unique_report_names(&hosts).is_ok());
+
+        let failed =
+            collect_from_hosts(&["node-c".to_string()], "/nonexistent/ssh", 1, false).await;
+        assert!(failed[0].is_err());
+    }
+
+    #[test]
+    fn test_to_yaml_nesting() {
+        let value = serde_json::json!({
+            "devices": [{"name": "sda", "size": 1}, "plain"],
+            "empty": [],
+            "note": "a: \"b\""
+        });
+        let expected = [
+            "---",
+            "\"devices\":",
+            "  - \"name\": \"sda\"",
+            "    \"size\": 1",
+            "  - \"plain\"",
+            "\"empty\": []",
+            "\"note\": \"a: \\\"b\\\"\"",
+        ];
+        assert_eq!(to_yaml(&value), format!("{}\n", expected.join("\n")));
+    }
+}
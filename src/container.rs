@@ -17,9 +17,10 @@ limitations under the License.
 //! Dependency injection container for hardware reporting services
 
 use crate::adapters::{
-    HttpDataPublisher, LinuxSystemInfoProvider, MacOSSystemInfoProvider, UnixCommandExecutor,
+    HttpDataPublisher, LinuxSystemInfoProvider, MacOSSystemInfoProvider,
+    OutOfBandSystemInfoProvider, UnixCommandExecutor,
 };
-use crate::domain::{HardwareCollectionService, ReportConfig};
+use crate::domain::{Capabilities, HardwareCollectionService, ReportConfig};
 use crate::ports::{
     CommandExecutor, ConfigurationProvider, DataPublisher, HardwareReportingService,
     SystemInfoProvider,
@@ -41,6 +42,8 @@ pub struct ContainerConfig {
     pub http_timeout: Duration,
     /// Skip TLS verification for HTTP publishing
     pub skip_tls_verify: bool,
+    /// Skip slow per-device probes for quick inventory runs
+    pub fast_mode: bool,
 }
 
 impl Default for ContainerConfig {
@@ -51,6 +54,7 @@ impl Default for ContainerConfig {
             verbose: false,
             http_timeout: Duration::from_secs(30),
             skip_tls_verify: false,
+            fast_mode: false,
         }
     }
 }
@@ -128,12 +132,40 @@ impl ServiceContainer {
     pub fn create_system_info_provider(
         &self,
     ) -> Result<Arc<dyn SystemInfoProvider>, Box<dyn Error>> {
+        self.build_system_info_provider(&ReportConfig::default())
+    }
+
+    /// Create the platform system info provider with the report's collection options
+    fn build_system_info_provider(
+        &self,
+        report_config: &ReportConfig,
+    ) -> Result<Arc<dyn SystemInfoProvider>, Box<dyn Error>> {
+        // No command executor is created, so nothing runs on this host
+        if report_config.out_of_band_only {
+            let redfish = report_config
+                .redfish
+                .as_ref()
+                .ok_or("Out-of-band collection requires a Redfish endpoint")?;
+            return Ok(Arc::new(OutOfBandSystemInfoProvider::new(
+                redfish,
+                self.config.http_timeout,
+            )?));
+        }
+
         let command_executor = self.create_command_executor();
+        let capture_raw = report_config.include_raw;
 
         let provider: Arc<dyn SystemInfoProvider> = if cfg!(target_os = "macos") {
-            Arc::new(MacOSSystemInfoProvider::new(command_executor))
+            Arc::new(MacOSSystemInfoProvider::new(command_executor).with_raw_capture(capture_raw))
         } else if cfg!(target_os = "linux") {
-            Arc::new(LinuxSystemInfoProvider::new(command_executor))
+            Arc::new(
+                LinuxSystemInfoProvider::new(command_executor)
+                    .with_fast_mode(self.config.fast_mode)
+                    .with_raw_capture(capture_raw)
+                    .with_dmidecode_source(report_config.dmidecode_source.clone())
+                    .with_container_detection()
+                    .with_redfish(report_config.redfish.clone()),
+            )
         } else {
             return Err("Unsupported operating system".into());
         };
@@ -162,9 +194,10 @@ impl ServiceContainer {
         &self,
         report_config: Option<ReportConfig>,
     ) -> Result<Arc<dyn HardwareReportingService>, Box<dyn Error>> {
-        let system_provider = self.create_system_info_provider()?;
+        let report_config = report_config.unwrap_or_default();
+        let system_provider = self.build_system_info_provider(&report_config)?;
         let data_publisher = self.create_data_publisher()?;
-        let config_provider = self.create_configuration_provider(report_config.unwrap_or_default());
+        let config_provider = self.create_configuration_provider(report_config);
 
         let service =
             HardwareCollectionService::new(system_provider, data_publisher, config_provider);
@@ -202,6 +235,16 @@ impl ServiceContainer {
             .map_err(|e| format!("Failed to check privileges: {e}"))?;
         Ok(has_privileges)
     }
+
+    /// Probe which subsystems can be collected on this host
+    pub async fn probe_capabilities(&self) -> Result<Capabilities, Box<dyn Error>> {
+        let system_provider = self.create_system_info_provider()?;
+        let capabilities = system_provider
+            .probe_capabilities()
+            .await
+            .map_err(|e| format!("Failed to probe capabilities: {e}"))?;
+        Ok(capabilities)
+    }
 }
 
 /// Builder pattern for container configuration
@@ -247,6 +290,12 @@ impl ContainerConfigBuilder {
         self
     }
 
+    /// Skip slow per-device probes
+    pub fn fast_mode(mut self, fast: bool) -> Self {
+        self.config.fast_mode = fast;
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> ContainerConfig {
         self.config
@@ -282,11 +331,13 @@ mod tests {
             .command_timeout(Duration::from_secs(60))
             .retry_count(3)
             .verbose(true)
+            .fast_mode(true)
             .build();
 
         assert_eq!(config.command_timeout, Duration::from_secs(60));
         assert_eq!(config.retry_count, 3);
         assert!(config.verbose);
+        assert!(config.fast_mode);
     }
 
     #[test]
@@ -0,0 +1,427 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Hardware differences between two reports of the same host
+//!
+//! Components are matched by identity (serial number, GPU UUID or MAC
+//! address) rather than position, so a swapped disk shows up as one removal
+//! and one addition while reordered devices produce no changes.
+
+use crate::domain::is_unknown;
+use crate::{CpuInfo, GpuDevice, MemoryModule, NetworkInterface, ServerInfo, StorageDevice};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// What to compare when diffing reports
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Also report runtime values such as IP addresses, link speed, CPU
+    /// clock and SMART wear, which change without any hardware change
+    pub include_volatile: bool,
+}
+
+/// Hardware changes between an earlier and a later report
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HardwareDiff {
+    /// CPU fields that differ
+    pub cpu: Vec<FieldChange>,
+    /// Memory modules keyed by serial, falling back to slot location
+    pub memory_modules: Vec<ComponentChange>,
+    /// Storage devices keyed by serial, falling back to device name
+    pub storage_devices: Vec<ComponentChange>,
+    /// GPUs keyed by UUID, falling back to PCI ID and index
+    pub gpus: Vec<ComponentChange>,
+    /// Network interfaces keyed by MAC address, falling back to name
+    pub nics: Vec<ComponentChange>,
+}
+
+impl HardwareDiff {
+    /// Whether the reports describe the same hardware
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_empty()
+            && self.memory_modules.is_empty()
+            && self.storage_devices.is_empty()
+            && self.gpus.is_empty()
+            && self.nics.is_empty()
+    }
+}
+
+/// A component present in only one report, or present in both with differences
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ComponentChange {
+    /// Only in the later report
+    Added { key: String },
+    /// Only in the earlier report
+    Removed { key: String },
+    /// In both reports with differing fields
+    Changed {
+        key: String,
+        fields: Vec<FieldChange>,
+    },
+}
+
+/// A field whose value differs between the reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    /// Field name as serialized in the report
+    pub field: String,
+    /// Value in the earlier report
+    pub before: String,
+    /// Value in the later report
+    pub after: String,
+}
+
+/// Field name, rendered value and whether the field is volatile
+///
+/// The per-component field lists below are the one definition of which
+/// fields are volatile; `ServerInfo::stable_subset` and the hardware
+/// fingerprint are built from the same lists.
+pub(crate) type Field = (&'static str, String, bool);
+
+impl ServerInfo {
+    /// Hardware changes from `self` to `other`, ignoring volatile fields
+    ///
+    /// # Arguments
+    /// * `other` - Later report of the same host
+    ///
+    /// # Returns
+    /// * Added, removed and changed components; empty when nothing changed
+    pub fn diff(&self, other: &ServerInfo) -> HardwareDiff {
+        self.diff_with(other, &DiffOptions::default())
+    }
+
+    /// Hardware changes from `self` to `other` with explicit options
+    pub fn diff_with(&self, other: &ServerInfo, options: &DiffOptions) -> HardwareDiff {
+        let include_volatile = options.include_volatile;
+        HardwareDiff {
+            cpu: diff_fields(
+                &cpu_fields(&self.hardware.cpu),
+                &cpu_fields(&other.hardware.cpu),
+                include_volatile,
+            ),
+            memory_modules: diff_components(
+                &self.hardware.memory.modules,
+                &other.hardware.memory.modules,
+                memory_key,
+                memory_fields,
+                include_volatile,
+            ),
+            storage_devices: diff_components(
+                &self.hardware.storage.devices,
+                &other.hardware.storage.devices,
+                storage_key,
+                storage_fields,
+                include_volatile,
+            ),
+            gpus: diff_components(
+                &self.hardware.gpus.devices,
+                &other.hardware.gpus.devices,
+                gpu_key,
+                gpu_fields,
+                include_volatile,
+            ),
+            nics: diff_components(
+                &self.network.interfaces,
+                &other.network.interfaces,
+                nic_key,
+                nic_fields,
+                include_volatile,
+            ),
+        }
+    }
+}
+
+pub(crate) fn cpu_fields(cpu: &CpuInfo) -> Vec<Field> {
+    vec![
+        ("model", cpu.model.clone(), false),
+        ("sockets", cpu.sockets.to_string(), false),
+        ("cores", cpu.cores.to_string(), false),
+        ("threads", cpu.threads.to_string(), false),
+        ("speed", cpu.speed.clone(), true),
+    ]
+}
+
+fn memory_key(module: &MemoryModule) -> String {
+    identity_or(&module.serial, &module.location)
+}
+
+pub(crate) fn memory_fields(module: &MemoryModule) -> Vec<Field> {
+    vec![
+        ("size", module.size.clone(), false),
+        ("type_", module.type_.clone(), false),
+        ("speed", module.speed.clone(), false),
+        ("location", module.location.clone(), false),
+        ("manufacturer", module.manufacturer.clone(), false),
+        ("serial", module.serial.clone(), false),
+    ]
+}
+
+fn storage_key(device: &StorageDevice) -> String {
+    identity_or(device.serial.as_deref().unwrap_or_default(), &device.name)
+}
+
+pub(crate) fn storage_fields(device: &StorageDevice) -> Vec<Field> {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    vec![
+        ("type_", device.type_.clone(), false),
+        ("size", device.size.clone(), false),
+        ("model", device.model.clone(), false),
+        ("serial", optional(device.serial.clone()), false),
+        ("firmware", optional(device.firmware.clone()), false),
+        (
+            "controller_model",
+            optional(device.controller_model.clone()),
+            false,
+        ),
+        // Kernel names follow probe order and can change across reboots
+        ("name", device.name.clone(), true),
+        (
+            "nvme_controller",
+            optional(device.nvme_controller.clone()),
+            true,
+        ),
+        (
+            "smart_healthy",
+            optional(device.smart_healthy.map(|h| h.to_string())),
+            true,
+        ),
+        (
+            "wearout_percent",
+            optional(device.wearout_percent.map(|w| w.to_string())),
+            true,
+        ),
+    ]
+}
+
+fn gpu_key(gpu: &GpuDevice) -> String {
+    identity_or(&gpu.uuid, &format!("{} #{}", gpu.pci_id, gpu.index))
+}
+
+pub(crate) fn gpu_fields(gpu: &GpuDevice) -> Vec<Field> {
+    vec![
+        ("name", gpu.name.clone(), false),
+        ("uuid", gpu.uuid.clone(), false),
+        ("memory", gpu.memory.clone(), false),
+        ("pci_id", gpu.pci_id.clone(), false),
+        ("vendor", gpu.vendor.clone(), false),
+        ("index", gpu.index.to_string(), true),
+    ]
+}
+
+fn nic_key(nic: &NetworkInterface) -> String {
+    // Virtual interfaces and some platforms report truncated or shared MACs
+    let mac_is_identity = nic.mac.len() == 17 && nic.mac != "00:00:00:00:00:00";
+    if mac_is_identity {
+        nic.mac.clone()
+    } else {
+        nic.name.clone()
+    }
+}
+
+pub(crate) fn nic_fields(nic: &NetworkInterface) -> Vec<Field> {
+    vec![
+        ("name", nic.name.clone(), false),
+        ("mac", nic.mac.clone(), false),
+        ("type_", nic.type_.clone(), false),
+        ("vendor", nic.vendor.clone(), false),
+        ("model", nic.model.clone(), false),
+        ("pci_id", nic.pci_id.clone(), false),
+        ("ip", nic.ip.clone(), true),
+        ("prefix", nic.prefix.clone(), true),
+        ("speed", nic.speed.clone().unwrap_or_default(), true),
+    ]
+}
+
+/// Non-volatile fields of a component, keyed by field name
+pub(crate) fn stable_fields(fields: Vec<Field>) -> BTreeMap<&'static str, String> {
+    fields
+        .into_iter()
+        .filter(|(_, _, volatile)| !volatile)
+        .map(|(field, value, _)| (field, value))
+        .collect()
+}
+
+/// `identity` unless it is missing or a placeholder such as "N/A"
+fn identity_or(identity: &str, fallback: &str) -> String {
+    if is_unknown(identity) {
+        fallback.to_string()
+    } else {
+        identity.to_string()
+    }
+}
+
+/// Key each component, suffixing repeats (e.g. "en0 (2)") so keys are unique
+fn keyed<T>(components: &[T], key: impl Fn(&T) -> String) -> Vec<(String, &T)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    components
+        .iter()
+        .map(|component| {
+            let key = key(component);
+            let count = seen.entry(key.clone()).or_default();
+            *count += 1;
+            let key = if *count == 1 {
+                key
+            } else {
+                format!("{key} ({count})")
+            };
+            (key, component)
+        })
+        .collect()
+}
+
+fn diff_components<T>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> String,
+    fields: impl Fn(&T) -> Vec<Field>,
+    include_volatile: bool,
+) -> Vec<ComponentChange> {
+    let before = keyed(before, &key);
+    let after = keyed(after, &key);
+    let after_by_key: HashMap<&str, &T> = after.iter().map(|(k, c)| (k.as_str(), *c)).collect();
+    let before_keys: HashSet<&str> = before.iter().map(|(k, _)| k.as_str()).collect();
+
+    let mut changes = Vec::new();
+    for (key, component) in &before {
+        match after_by_key.get(key.as_str()) {
+            None => changes.push(ComponentChange::Removed { key: key.clone() }),
+            Some(other) => {
+                let fields = diff_fields(&fields(component), &fields(other), include_volatile);
+                if !fields.is_empty() {
+                    changes.push(ComponentChange::Changed {
+                        key: key.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    for (key, _) in &after {
+        if !before_keys.contains(key.as_str()) {
+            changes.push(ComponentChange::Added { key: key.clone() });
+        }
+    }
+    changes
+}
+
+fn diff_fields(before: &[Field], after: &[Field], include_volatile: bool) -> Vec<FieldChange> {
+    before
+        .iter()
+        .zip(after)
+        .filter(|((_, _, volatile), _)| include_volatile || !volatile)
+        .filter(|((_, a, _), (_, b, _))| a != b)
+        .map(|((field, a, _), (_, b, _))| FieldChange {
+            field: field.to_string(),
+            before: a.clone(),
+            after: b.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::sample_server_info as sample_report;
+
+    fn module(serial: &str, location: &str) -> MemoryModule {
+        MemoryModule {
+            size: "64 GB".to_string(),
+            type_: "DDR5".to_string(),
+            speed: "4800 MT/s".to_string(),
+            location: location.to_string(),
+            manufacturer: "Samsung".to_string(),
+            serial: serial.to_string(),
+            configured_speed: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_hardware_changes() {
+        let mut before = sample_report();
+        before.hardware.memory.modules = vec![module("S1", "DIMM_A1"), module("S2", "DIMM_B1")];
+        before.hardware.storage.devices[0].serial = Some("DISK-OLD".to_string());
+        let mut after = sample_report();
+        after.hardware.memory.modules = vec![module("S1", "DIMM_A1")];
+        after.hardware.storage.devices[0].serial = Some("DISK-NEW".to_string());
+        after.hardware.gpus.devices[0].memory = "80 GB".to_string();
+
+        let diff = before.diff(&after);
+
+        assert!(diff.cpu.is_empty());
+        assert_eq!(
+            diff.memory_modules,
+            vec![ComponentChange::Removed {
+                key: "S2".to_string()
+            }]
+        );
+        assert_eq!(
+            diff.storage_devices,
+            vec![
+                ComponentChange::Removed {
+                    key: "DISK-OLD".to_string()
+                },
+                ComponentChange::Added {
+                    key: "DISK-NEW".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            diff.gpus,
+            vec![ComponentChange::Changed {
+                key: before.hardware.gpus.devices[0].uuid.clone(),
+                fields: vec![FieldChange {
+                    field: "memory".to_string(),
+                    before: before.hardware.gpus.devices[0].memory.clone(),
+                    after: "80 GB".to_string(),
+                }],
+            }]
+        );
+        assert!(diff.nics.is_empty());
+        assert!(sample_report().diff(&sample_report()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_volatile_fields_opt_in() {
+        let before = sample_report();
+        let mut after = sample_report();
+        after.network.interfaces[0].ip = "10.0.0.7".to_string();
+        after.hardware.cpu.speed = "3.1 GHz".to_string();
+
+        assert!(before.diff(&after).is_empty());
+
+        let diff = before.diff_with(
+            &after,
+            &DiffOptions {
+                include_volatile: true,
+            },
+        );
+        assert_eq!(diff.cpu.len(), 1);
+        assert_eq!(diff.cpu[0].field, "speed");
+        // The sample's shared placeholder MACs fall back to the interface name
+        assert_eq!(
+            diff.nics,
+            vec![ComponentChange::Changed {
+                key: after.network.interfaces[0].name.clone(),
+                fields: vec![FieldChange {
+                    field: "ip".to_string(),
+                    before: before.network.interfaces[0].ip.clone(),
+                    after: "10.0.0.7".to_string(),
+                }],
+            }]
+        );
+    }
+}
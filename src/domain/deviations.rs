@@ -0,0 +1,602 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Audit of host settings and health checks
+//!
+//! Every finding lands in `HardwareReport::configuration_deviations` with a
+//! code and severity, so consumers read one list instead of a field per check.
+
+use crate::domain::{
+    are_gpus_homogeneous, find_bond_speed_mismatches, find_data_mtu_mismatch,
+    find_gpus_without_local_rdma_nic, find_hugepage_shortfalls, find_memory_size_mismatch,
+    format_bytes_iec, parse_size_to_bytes, ConfigurationProfile, Deviation, DeviationSeverity,
+    HardwareReport,
+};
+use regex::Regex;
+
+/// Power limits closer than this are treated as equal (nvidia-smi reports centiwatts)
+const POWER_LIMIT_TOLERANCE_WATTS: f64 = 0.5;
+
+/// List every collected setting that differs from the profile, then every
+/// failed health check
+///
+/// Settings that were not collected (`None`) are never reported as deviations.
+///
+/// # Arguments
+/// * `report` - Collected hardware report
+/// * `profile` - Expected settings
+/// * `data_interfaces` - Pattern selecting the data interfaces whose MTUs
+///   must agree; `None` skips the check
+///
+/// # Returns
+/// * Deviations in a fixed order: CPU, firmware, memory, NUMA, per-GPU and
+///   per-NIC settings, then health checks
+pub fn find_configuration_deviations(
+    report: &HardwareReport,
+    profile: &ConfigurationProfile,
+    data_interfaces: Option<&Regex>,
+) -> Vec<Deviation> {
+    let mut deviations = Vec::new();
+    let mut check = |code: &str, component: Option<String>, expected: String, actual: String| {
+        if expected != actual {
+            deviations.push(deviation(
+                code,
+                DeviationSeverity::Warning,
+                component,
+                expected,
+                actual,
+            ));
+        }
+    };
+
+    let cpu = &report.hardware.cpu;
+    if let Some(governor) = &cpu.scaling_governor {
+        check(
+            "cpu_governor",
+            None,
+            profile.cpu_governor.clone(),
+            governor.clone(),
+        );
+    }
+    let smt_enabled = cpu.smt_active.or(match cpu.smt_control.as_deref() {
+        Some("on") => Some(true),
+        Some("off" | "forceoff") => Some(false),
+        _ => None,
+    });
+    if let Some(enabled) = smt_enabled {
+        check("smt", None, on_off(profile.smt_enabled), on_off(enabled));
+    }
+    if let Some(enabled) = cpu.turbo_enabled {
+        check(
+            "turbo",
+            None,
+            on_off(profile.turbo_enabled),
+            on_off(enabled),
+        );
+    }
+
+    if let Some(boot_mode) = &report.boot_mode {
+        check(
+            "boot_mode",
+            None,
+            profile.boot_mode.clone(),
+            boot_mode.clone(),
+        );
+    }
+
+    if let Some(enabled) = report.hardware.memory.ecc_enabled {
+        check(
+            "memory_ecc",
+            None,
+            on_off(profile.ecc_enabled),
+            on_off(enabled),
+        );
+    }
+    if let Some(enabled) = report.summary.numa_balancing {
+        check(
+            "numa_balancing",
+            None,
+            on_off(profile.numa_balancing),
+            on_off(enabled),
+        );
+    }
+
+    let expected_aspm = if profile.aspm_enabled {
+        "enabled"
+    } else {
+        "disabled"
+    };
+    // Any enabled link state counts as "enabled"
+    let aspm_deviates = |state: &str| (state == "disabled") == profile.aspm_enabled;
+
+    for gpu in &report.hardware.gpus.devices {
+        let component = Some(format!("GPU {}", gpu.index));
+        if let Some(state) = gpu.aspm_state.as_deref().filter(|s| aspm_deviates(s)) {
+            check(
+                "aspm",
+                component.clone(),
+                expected_aspm.to_string(),
+                state.to_string(),
+            );
+        }
+
+        let expected_limit = profile
+            .gpu_power_limit_watts
+            .or(gpu.default_power_limit_watts);
+        if let (Some(limit), Some(expected)) = (gpu.power_limit_watts, expected_limit) {
+            if (limit - expected).abs() > POWER_LIMIT_TOLERANCE_WATTS {
+                check(
+                    "gpu_power_limit_watts",
+                    component,
+                    format!("{expected:.0}"),
+                    format!("{limit:.0}"),
+                );
+            }
+        }
+    }
+
+    for interface in &report.network.interfaces {
+        if let Some(state) = interface.aspm_state.as_deref().filter(|s| aspm_deviates(s)) {
+            check(
+                "aspm",
+                Some(interface.name.clone()),
+                expected_aspm.to_string(),
+                state.to_string(),
+            );
+        }
+    }
+
+    let gpus = &report.hardware.gpus.devices;
+    if !are_gpus_homogeneous(gpus) {
+        let mut models: Vec<String> = gpus
+            .iter()
+            .map(|gpu| format!("{} ({} MB)", gpu.name, gpu.memory_total_mb))
+            .collect();
+        models.sort();
+        models.dedup();
+        deviations.push(deviation(
+            "gpu_models_mixed",
+            DeviationSeverity::Warning,
+            None,
+            "one model".to_string(),
+            models.join(", "),
+        ));
+    }
+
+    if report
+        .time_sync
+        .as_ref()
+        .is_some_and(|time_sync| !time_sync.synchronized)
+    {
+        deviations.push(deviation(
+            "clock_unsynchronized",
+            DeviationSeverity::Warning,
+            None,
+            "synchronized".to_string(),
+            "unsynchronized".to_string(),
+        ));
+    }
+
+    for (bond, speeds) in find_bond_speed_mismatches(&report.network.interfaces) {
+        let speeds: Vec<String> = speeds.iter().map(|speed| format!("{speed} Mbps")).collect();
+        deviations.push(deviation(
+            "bond_speed_mismatch",
+            DeviationSeverity::Warning,
+            Some(bond),
+            "equal member speeds".to_string(),
+            speeds.join(", "),
+        ));
+    }
+
+    let memory = &report.hardware.memory;
+    let total_bytes = parse_size_to_bytes(&memory.total).unwrap_or(0);
+    if let Some(dimm_bytes) = find_memory_size_mismatch(total_bytes, &memory.modules) {
+        // Usually an undetected or failing DIMM
+        deviations.push(deviation(
+            "memory_size_mismatch",
+            DeviationSeverity::Critical,
+            None,
+            format_bytes_iec(dimm_bytes),
+            format_bytes_iec(total_bytes),
+        ));
+    }
+
+    for pool in find_hugepage_shortfalls(&report.hardware.memory.hugepage_pools) {
+        deviations.push(deviation(
+            "hugepage_shortfall",
+            DeviationSeverity::Warning,
+            Some(format!("hugepages-{}kB", pool.page_size_kb)),
+            pool.requested.to_string(),
+            pool.allocated.unwrap_or_default().to_string(),
+        ));
+    }
+
+    // Only hosts with an RDMA fabric are expected to have switch-local NICs
+    let interfaces = &report.network.interfaces;
+    if interfaces.iter().any(|nic| nic.rdma_device.is_some()) {
+        for index in find_gpus_without_local_rdma_nic(gpus, interfaces) {
+            deviations.push(deviation(
+                "gpu_without_local_rdma_nic",
+                DeviationSeverity::Info,
+                Some(format!("GPU {index}")),
+                "RDMA NIC behind the same PCIe switch".to_string(),
+                "none".to_string(),
+            ));
+        }
+    }
+
+    for device in &report.hardware.unbound_pci_devices {
+        deviations.push(deviation(
+            "pci_device_unbound",
+            DeviationSeverity::Critical,
+            Some(device.address.clone()),
+            "driver bound".to_string(),
+            format!("no driver ({}:{})", device.vendor_id, device.device_id),
+        ));
+    }
+
+    if let Some(data_interfaces) = data_interfaces {
+        let mismatch = find_data_mtu_mismatch(&report.network.interfaces, data_interfaces);
+        if !mismatch.is_empty() {
+            let mtus: Vec<String> = mismatch
+                .iter()
+                .map(|(name, mtu)| format!("{name}={mtu}"))
+                .collect();
+            deviations.push(deviation(
+                "data_mtu_mismatch",
+                DeviationSeverity::Warning,
+                None,
+                "equal MTUs".to_string(),
+                mtus.join(", "),
+            ));
+        }
+    }
+
+    deviations
+}
+
+fn deviation(
+    code: &str,
+    severity: DeviationSeverity,
+    component: Option<String>,
+    expected: String,
+    actual: String,
+) -> Deviation {
+    Deviation {
+        code: code.to_string(),
+        severity,
+        component,
+        expected,
+        actual,
+    }
+}
+
+fn on_off(enabled: bool) -> String {
+    if enabled { "on" } else { "off" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> HardwareReport {
+        crate::test_support::sample_server_info().into()
+    }
+
+    #[test]
+    fn test_powersave_governor_and_ecc_off_are_deviations() {
+        let mut report = sample_report();
+        report.hardware.cpu.scaling_governor = Some("powersave".to_string());
+        report.hardware.memory.ecc_enabled = Some(false);
+        report.summary.numa_balancing = Some(true);
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        assert_eq!(
+            deviations,
+            vec![
+                Deviation {
+                    code: "cpu_governor".to_string(),
+                    severity: DeviationSeverity::Warning,
+                    component: None,
+                    expected: "performance".to_string(),
+                    actual: "powersave".to_string(),
+                },
+                Deviation {
+                    code: "memory_ecc".to_string(),
+                    severity: DeviationSeverity::Warning,
+                    component: None,
+                    expected: "on".to_string(),
+                    actual: "off".to_string(),
+                },
+                Deviation {
+                    code: "numa_balancing".to_string(),
+                    severity: DeviationSeverity::Warning,
+                    component: None,
+                    expected: "off".to_string(),
+                    actual: "on".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_override_and_gpu_settings() {
+        let mut report = sample_report();
+        report.hardware.cpu.scaling_governor = Some("powersave".to_string());
+        let mut gpu = crate::domain::GpuDevice {
+            aspm_state: Some("L1".to_string()),
+            power_limit_watts: Some(500.0),
+            default_power_limit_watts: Some(700.0),
+            ..Default::default()
+        };
+        gpu.index = 3;
+        report.hardware.gpus.devices = vec![gpu];
+
+        let profile = ConfigurationProfile {
+            cpu_governor: "powersave".to_string(),
+            ..Default::default()
+        };
+        let deviations = find_configuration_deviations(&report, &profile, None);
+        let codes: Vec<&str> = deviations.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["aspm", "gpu_power_limit_watts"]);
+        assert_eq!(deviations[1].component.as_deref(), Some("GPU 3"));
+        assert_eq!(deviations[1].expected, "700");
+
+        // A deliberately capped fleet expects the cap
+        let profile = ConfigurationProfile {
+            cpu_governor: "powersave".to_string(),
+            aspm_enabled: true,
+            gpu_power_limit_watts: Some(500.0),
+            ..Default::default()
+        };
+        assert!(find_configuration_deviations(&report, &profile, None).is_empty());
+    }
+
+    #[test]
+    fn test_mixed_gpu_models_are_reported() {
+        let mut report = sample_report();
+        let gpu = |index, name: &str, memory_total_mb| crate::domain::GpuDevice {
+            index,
+            name: name.to_string(),
+            memory_total_mb,
+            ..Default::default()
+        };
+        report.hardware.gpus.devices = (0..7)
+            .map(|index| gpu(index, "NVIDIA H100 80GB HBM3", 81559))
+            .chain([gpu(7, "NVIDIA A100-SXM4-80GB", 81920)])
+            .collect();
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let mixed = deviations
+            .iter()
+            .find(|d| d.code == "gpu_models_mixed")
+            .unwrap();
+        assert_eq!(mixed.severity, DeviationSeverity::Warning);
+        assert_eq!(
+            mixed.actual,
+            "NVIDIA A100-SXM4-80GB (81920 MB), NVIDIA H100 80GB HBM3 (81559 MB)"
+        );
+
+        report.hardware.gpus.devices.truncate(7);
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+        assert!(deviations.iter().all(|d| d.code != "gpu_models_mixed"));
+    }
+
+    #[test]
+    fn test_unsynchronized_clock_is_reported() {
+        let mut report = sample_report();
+        report.time_sync = Some(crate::domain::TimeSyncInfo {
+            synchronized: false,
+            source: None,
+            offset_ms: None,
+            stratum: None,
+        });
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+        assert!(deviations.iter().any(|d| d.code == "clock_unsynchronized"));
+
+        report.time_sync.as_mut().unwrap().synchronized = true;
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+        assert!(deviations.iter().all(|d| d.code != "clock_unsynchronized"));
+    }
+
+    #[test]
+    fn test_bond_speed_mismatch_is_reported() {
+        let mut report = sample_report();
+        let iface =
+            |name: &str, speed_mbps, bond_members: &[&str]| crate::domain::NetworkInterface {
+                name: name.to_string(),
+                speed_mbps,
+                bond_members: bond_members.iter().map(|m| m.to_string()).collect(),
+                ..Default::default()
+            };
+        report.network.interfaces = vec![
+            iface("bond0", Some(35000), &["ens1f0", "ens1f1"]),
+            iface("ens1f0", Some(25000), &[]),
+            iface("ens1f1", Some(10000), &[]),
+        ];
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let bond = deviations
+            .iter()
+            .find(|d| d.code == "bond_speed_mismatch")
+            .unwrap();
+        assert_eq!(bond.component.as_deref(), Some("bond0"));
+        assert_eq!(bond.actual, "10000 Mbps, 25000 Mbps");
+    }
+
+    #[test]
+    fn test_unbound_pci_device_is_reported() {
+        let mut report = sample_report();
+        report.hardware.unbound_pci_devices = vec![crate::domain::PciDevice {
+            address: "0000:3b:00.0".to_string(),
+            vendor_id: "10de".to_string(),
+            device_id: "2330".to_string(),
+            class: "0x030200".to_string(),
+        }];
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let unbound = deviations
+            .iter()
+            .find(|d| d.code == "pci_device_unbound")
+            .unwrap();
+        assert_eq!(unbound.severity, DeviationSeverity::Critical);
+        assert_eq!(unbound.component.as_deref(), Some("0000:3b:00.0"));
+        assert_eq!(unbound.actual, "no driver (10de:2330)");
+    }
+
+    #[test]
+    fn test_data_mtu_mismatch_is_reported() {
+        let mut report = sample_report();
+        let iface = |name: &str, mtu| crate::domain::NetworkInterface {
+            name: name.to_string(),
+            mtu,
+            ..Default::default()
+        };
+        report.network.interfaces = vec![iface("ens1f0", 9000), iface("ens2f0", 1500)];
+        let profile = ConfigurationProfile::default();
+
+        let data_interfaces = Regex::new("^ens").unwrap();
+        let deviations = find_configuration_deviations(&report, &profile, Some(&data_interfaces));
+        let mtu = deviations
+            .iter()
+            .find(|d| d.code == "data_mtu_mismatch")
+            .unwrap();
+        assert_eq!(mtu.actual, "ens1f0=9000, ens2f0=1500");
+
+        // Only checked when data interfaces are configured
+        let deviations = find_configuration_deviations(&report, &profile, None);
+        assert!(deviations.iter().all(|d| d.code != "data_mtu_mismatch"));
+    }
+
+    #[test]
+    fn test_gpu_without_local_rdma_nic_is_reported() {
+        let mut report = sample_report();
+        let path = |bridges: &[&str]| bridges.iter().map(|b| b.to_string()).collect();
+        report.hardware.gpus.devices = vec![
+            crate::domain::GpuDevice {
+                index: 0,
+                pcie_path: path(&["0000:00:01.0", "0000:01:00.0", "0000:02:00.0"]),
+                ..Default::default()
+            },
+            crate::domain::GpuDevice {
+                index: 1,
+                pcie_path: path(&["0000:80:01.0"]),
+                ..Default::default()
+            },
+        ];
+        report.network.interfaces = vec![crate::domain::NetworkInterface {
+            name: "ibp3s0".to_string(),
+            rdma_device: Some("mlx5_0".to_string()),
+            pcie_path: path(&["0000:00:01.0", "0000:01:00.0", "0000:02:04.0"]),
+            ..Default::default()
+        }];
+        let profile = ConfigurationProfile::default();
+
+        let deviations = find_configuration_deviations(&report, &profile, None);
+        let components: Vec<Option<&str>> = deviations
+            .iter()
+            .filter(|d| d.code == "gpu_without_local_rdma_nic")
+            .map(|d| d.component.as_deref())
+            .collect();
+        assert_eq!(components, vec![Some("GPU 1")]);
+
+        // Hosts without an RDMA fabric are not flagged
+        report.network.interfaces[0].rdma_device = None;
+        let deviations = find_configuration_deviations(&report, &profile, None);
+        assert!(deviations
+            .iter()
+            .all(|d| d.code != "gpu_without_local_rdma_nic"));
+    }
+
+    #[test]
+    fn test_hugepage_shortfall_is_reported() {
+        let mut report = sample_report();
+        report.hardware.memory.hugepage_pools = vec![crate::domain::HugepagePool {
+            page_size_kb: 1_048_576,
+            requested: 128,
+            allocated: Some(120),
+        }];
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let shortfall = deviations
+            .iter()
+            .find(|d| d.code == "hugepage_shortfall")
+            .unwrap();
+        assert_eq!(shortfall.component.as_deref(), Some("hugepages-1048576kB"));
+        assert_eq!(
+            (shortfall.expected.as_str(), shortfall.actual.as_str()),
+            ("128", "120")
+        );
+    }
+
+    #[test]
+    fn test_nic_aspm_is_checked() {
+        let mut report = sample_report();
+        report.hardware.gpus.devices.clear();
+        report.network.interfaces = vec![crate::domain::NetworkInterface {
+            name: "ibp26s0".to_string(),
+            aspm_state: Some("L0s L1".to_string()),
+            ..Default::default()
+        }];
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let aspm = deviations.iter().find(|d| d.code == "aspm").unwrap();
+        assert_eq!(aspm.component.as_deref(), Some("ibp26s0"));
+        assert_eq!(
+            (aspm.expected.as_str(), aspm.actual.as_str()),
+            ("disabled", "L0s L1")
+        );
+    }
+
+    #[test]
+    fn test_memory_size_mismatch_is_critical() {
+        let mut report = sample_report();
+        let module = crate::domain::parse_dmidecode_memory("Memory Device\n\tSize: 32 GB")
+            .unwrap()
+            .remove(0);
+        report.hardware.memory.modules = vec![module; 16];
+        report.hardware.memory.total = "384G".to_string();
+
+        let deviations =
+            find_configuration_deviations(&report, &ConfigurationProfile::default(), None);
+
+        let mismatch = deviations
+            .iter()
+            .find(|d| d.code == "memory_size_mismatch")
+            .unwrap();
+        assert_eq!(mismatch.severity, DeviationSeverity::Critical);
+        assert_eq!(
+            (mismatch.expected.as_str(), mismatch.actual.as_str()),
+            ("512.0 GiB", "384.0 GiB")
+        );
+    }
+}
@@ -14,16 +14,41 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use super::format::format_bytes_iec;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Current report schema version
+///
+/// Covers both serialized formats: `HardwareReport` and the legacy
+/// `ServerInfo` the CLI writes carry the same number, since they are two
+/// views of one collection. Bump it when the structure or the meaning of
+/// either one changes so consumers can tell which format a document uses.
+///
+/// * 1 - First versioned format.
+pub const REPORT_VERSION: u32 = 1;
 
 /// Represents the overall hardware report (root aggregate)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HardwareReport {
+    /// Report schema version (0 for documents written before versioning)
+    #[serde(default)]
+    pub report_version: u32,
     /// System summary information
     pub summary: SystemSummary,
     /// System hostname
     pub hostname: String,
+    /// Hostname detected on the system when it was replaced by an override
+    #[serde(default)]
+    pub detected_hostname: Option<String>,
+    /// Canonical machine identifier, see `select_machine_id` for the priority order
+    #[serde(default)]
+    pub machine_id: String,
+    /// Source of `machine_id` (e.g., "system_uuid", "chassis_serial", "hostname")
+    #[serde(default)]
+    pub machine_id_source: String,
     /// Fully qualified domain name
     pub fqdn: String,
     /// Operating system IP addresses
@@ -32,10 +57,343 @@ pub struct HardwareReport {
     pub bmc_ip: Option<String>,
     /// BMC MAC address
     pub bmc_mac: Option<String>,
+    /// How the BMC obtained its IP address ("dhcp" or "static")
+    #[serde(default)]
+    pub bmc_ip_source: Option<String>,
     /// Detailed hardware information
     pub hardware: HardwareInfo,
     /// Network information
     pub network: NetworkInfo,
+    /// Pairing of RDMA-capable NICs with their nearest GPU
+    #[serde(default)]
+    pub gpu_nic_affinity: Option<GpuNicAffinity>,
+    /// Versions of the external tools used for collection (tool -> version)
+    #[serde(default)]
+    pub tool_versions: HashMap<String, String>,
+    /// Key BIOS settings under vendor-neutral names (`power_profile`, `c_states`,
+    /// `numa_per_socket`, `smt`, `determinism_slider`)
+    #[serde(default)]
+    pub bios_settings: HashMap<String, String>,
+    /// Firmware inventory from the BMC's Redfish `UpdateService`
+    #[serde(default)]
+    pub firmware_components: Vec<FirmwareComponent>,
+    /// Firmware boot mode ("UEFI" or "Legacy")
+    #[serde(default)]
+    pub boot_mode: Option<String>,
+    /// Operational OS state, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub operational: Option<OperationalInfo>,
+    /// Hardware errors from the kernel log, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub kernel_hardware_errors: Option<Vec<KernelError>>,
+    /// Most recent BMC System Event Log entries, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub sel_recent_events: Option<Vec<SelEvent>>,
+    /// Settings that differ from the configuration profile in `ReportConfig`,
+    /// followed by failed health checks
+    #[serde(default)]
+    pub configuration_deviations: Vec<Deviation>,
+    /// Clock synchronization state, when a time sync daemon can be queried
+    #[serde(default)]
+    pub time_sync: Option<TimeSyncInfo>,
+    /// Hardware or BMC watchdog, when one is configured
+    #[serde(default)]
+    pub watchdog: Option<WatchdogInfo>,
+    /// Instantaneous power draw, when DCMI, Redfish or RAPL can be read
+    #[serde(default)]
+    pub power: Option<PowerInfo>,
+    /// Temperature and fan readings from the BMC
+    #[serde(default)]
+    pub sensors: Vec<SensorReading>,
+    /// Container runtime state, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub container_support: Option<ContainerRuntimeInfo>,
+    /// cgroup limits when running inside a container
+    #[serde(default)]
+    pub container_limits: Option<ContainerLimits>,
+    /// GPU device nodes granted to each cgroup, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub gpu_cgroup_allocations: Option<Vec<GpuCgroupAllocation>>,
+    /// Cloud instance metadata; `None` when not collected or not on a cloud VM
+    #[serde(default)]
+    pub cloud: Option<CloudInfo>,
+    /// Raw stdout of the commands run during collection (command -> stdout),
+    /// present only when `ReportConfig::include_raw` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_sources: Option<HashMap<String, String>>,
+}
+
+/// Operational state of the running OS
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OperationalInfo {
+    /// Failed systemd units (empty on non-systemd systems)
+    #[serde(default)]
+    pub failed_units: Vec<String>,
+}
+
+/// Cloud instance metadata from the provider metadata endpoint
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CloudInfo {
+    /// Cloud provider ("aws", "gcp" or "azure")
+    pub provider: String,
+    /// Instance type (e.g., "p5.48xlarge")
+    #[serde(default)]
+    pub instance_type: Option<String>,
+    /// Region (e.g., "us-east-1")
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Availability zone (e.g., "us-east-1a")
+    #[serde(default)]
+    pub availability_zone: Option<String>,
+}
+
+/// Container runtime and NVIDIA container toolkit presence
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ContainerRuntimeInfo {
+    /// Container runtime in use ("containerd", "cri-o" or "docker")
+    #[serde(default)]
+    pub container_runtime: Option<String>,
+    /// Whether nvidia-container-cli is installed and functional
+    #[serde(default)]
+    pub nvidia_container_toolkit_present: bool,
+}
+
+/// cgroup resource limits applied to the container the collector runs in
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ContainerLimits {
+    /// Detected container environment (e.g., "docker", "kubernetes", "lxc")
+    pub container: String,
+    /// cgroup hierarchy version (1 or 2)
+    pub cgroup_version: u8,
+    /// CPU quota in cores; `None` when unlimited
+    #[serde(default)]
+    pub cpu_quota_cores: Option<f64>,
+    /// CPU shares (cgroup v1) or weight (cgroup v2)
+    #[serde(default)]
+    pub cpu_shares: Option<u64>,
+    /// Memory limit in bytes; `None` when unlimited
+    #[serde(default)]
+    pub memory_limit_bytes: Option<u64>,
+    /// CFS periods in which the container was throttled by its CPU quota
+    #[serde(default)]
+    pub cpu_throttled_periods: Option<u64>,
+}
+
+/// GPU device nodes a cgroup's device allowlist grants access to
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct GpuCgroupAllocation {
+    /// cgroup path relative to the devices hierarchy (e.g., "/kubepods/pod1234/abcd")
+    pub cgroup: String,
+    /// GPU device nodes the cgroup may open (e.g., "/dev/nvidia0"; "/dev/nvidia*" for all)
+    pub devices: Vec<String>,
+}
+
+/// Firmware component from the Redfish firmware inventory
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct FirmwareComponent {
+    /// Component name (e.g., "BIOS", "System CPLD", "PSU.Slot.1")
+    pub name: String,
+    /// Installed version
+    pub version: String,
+    /// Whether the BMC can update this component
+    #[serde(default)]
+    pub updateable: bool,
+}
+
+/// Hardware error reported in the kernel log
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct KernelError {
+    /// Log timestamp (seconds since boot for dmesg, wall clock for the journal)
+    pub timestamp: Option<String>,
+    /// Error category: "mce", "pcie_aer", "disk_io" or "thermal"
+    pub category: String,
+    /// Kernel log message
+    pub message: String,
+}
+
+/// How urgently a deviation needs attention
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviationSeverity {
+    /// Worth knowing, but workloads run as expected
+    Info,
+    /// Costs performance or reliability
+    #[default]
+    Warning,
+    /// Hardware is missing or failing
+    Critical,
+}
+
+/// Setting or health check that differs from what is expected of the host
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Deviation {
+    /// Check identifier (e.g., "cpu_governor", "gpu_models_mixed")
+    pub code: String,
+    /// How urgently the deviation needs attention
+    #[serde(default)]
+    pub severity: DeviationSeverity,
+    /// Device the setting belongs to, for per-device settings (e.g., "GPU 0")
+    #[serde(default)]
+    pub component: Option<String>,
+    /// Value the profile or check expects
+    pub expected: String,
+    /// Value found on the host
+    pub actual: String,
+}
+
+/// Entry from the BMC System Event Log
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SelEvent {
+    /// BMC wall-clock time of the event; `None` for entries logged before the BMC clock was set
+    pub timestamp: Option<String>,
+    /// Sensor that raised the event (e.g., "Memory #0x87")
+    pub sensor: String,
+    /// Event description and direction (e.g., "Correctable ECC | Asserted")
+    pub description: String,
+}
+
+/// Power draw measurement
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PowerInfo {
+    /// Whole-system power draw in watts, from the BMC
+    #[serde(default)]
+    pub power_draw_watts: Option<f64>,
+    /// Where `power_draw_watts` came from: "dcmi" or "redfish"
+    #[serde(default)]
+    pub source: Option<String>,
+    /// CPU package power in watts from RAPL, averaged over two samples;
+    /// excludes GPUs, NICs and the rest of the system
+    #[serde(default)]
+    pub cpu_package_watts: Option<f64>,
+    /// Power supplies reported by the BMC
+    #[serde(default)]
+    pub power_supplies: Vec<PowerSupply>,
+}
+
+/// Power supply unit reported by the BMC
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PowerSupply {
+    /// PSU name (e.g., "PS1 Status")
+    pub name: String,
+    /// Health state ("OK", "Warning", "Critical")
+    #[serde(default)]
+    pub health: Option<String>,
+    /// Rated output capacity in watts
+    #[serde(default)]
+    pub capacity_watts: Option<f64>,
+}
+
+/// Sensor reading reported by the BMC
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SensorReading {
+    /// Sensor name (e.g., "CPU1 Temp", "Fan1A")
+    pub name: String,
+    /// Current reading
+    pub reading: f64,
+    /// Reading units (e.g., "Cel", "RPM")
+    pub units: String,
+}
+
+/// BMC network address
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct BmcNetwork {
+    /// BMC IPv4 address
+    pub ip: Option<String>,
+    /// BMC MAC address
+    pub mac: Option<String>,
+    /// How the BMC obtained its address ("dhcp", "static", ...)
+    pub ip_source: Option<String>,
+}
+
+/// Clock synchronization state from chrony or systemd-timesyncd
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TimeSyncInfo {
+    /// Whether the clock is synchronized to a time source
+    pub synchronized: bool,
+    /// Time source the clock follows (server name or address)
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Offset from the time source in milliseconds (negative when behind)
+    #[serde(default)]
+    pub offset_ms: Option<f64>,
+    /// NTP stratum of the local clock
+    #[serde(default)]
+    pub stratum: Option<u8>,
+}
+
+/// Hardware or IPMI watchdog timer
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct WatchdogInfo {
+    /// Watchdog driver (e.g., "iTCO_wdt"), or "IPMI" for the BMC watchdog
+    pub device: String,
+    /// Timeout in seconds before the watchdog fires
+    pub timeout_s: Option<u32>,
+    /// Whether the timer is running
+    pub active: bool,
+}
+
+/// What collection can do on this host, determined without collecting data
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Capabilities {
+    /// Active system adapter (e.g., "linux", "macos")
+    pub adapter: String,
+    /// Whether collection runs with elevated privileges
+    pub has_privileges: bool,
+    /// Availability of each collection subsystem
+    pub subsystems: Vec<SubsystemCapability>,
+}
+
+/// Availability of a single collection subsystem
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SubsystemCapability {
+    /// Subsystem name (e.g., "cpu", "gpu", "bmc")
+    pub name: String,
+    /// Whether every tool the subsystem needs is present
+    pub available: bool,
+    /// Tools the subsystem needs that were not found
+    #[serde(default)]
+    pub missing_tools: Vec<String>,
+}
+
+impl HardwareReport {
+    /// Check whether this report uses a newer schema than this library understands
+    ///
+    /// # Returns
+    /// * `Some(String)` - Compatibility warning message
+    /// * `None` - Report schema is supported
+    pub fn compatibility_warning(&self) -> Option<String> {
+        if self.report_version > REPORT_VERSION {
+            Some(format!(
+                "Report version {} is newer than supported version {}; some fields may be ignored",
+                self.report_version, REPORT_VERSION
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Replace the collected hostname and FQDN with an intended hostname
+    ///
+    /// The detected hostname is preserved in `detected_hostname`.
+    ///
+    /// # Arguments
+    /// * `hostname` - Hostname to stamp into the report
+    pub fn apply_hostname_override(&mut self, hostname: &str) {
+        self.fqdn = crate::domain::override_fqdn(&self.hostname, &self.fqdn, hostname);
+        let detected = std::mem::replace(&mut self.hostname, hostname.to_string());
+        self.detected_hostname = Some(detected);
+    }
+
+    /// Map each logical CPU to its core, socket, NUMA node and SMT sibling
+    ///
+    /// Built from the per-CPU topology and NUMA node CPU lists; empty when
+    /// the per-CPU topology was not collected.
+    pub fn cpu_affinity_map(&self) -> BTreeMap<u32, CpuLocation> {
+        crate::domain::build_cpu_affinity_map(
+            &self.summary.cpu_topology.logical_cpus,
+            &self.summary.numa_topology,
+        )
+    }
 }
 
 /// Summary of key system components
@@ -47,6 +405,9 @@ pub struct SystemSummary {
     pub total_memory: String,
     /// Memory speed and type configuration
     pub memory_config: String,
+    /// Whether all memory modules run at their rated speed
+    #[serde(default = "default_true")]
+    pub memory_running_at_rated_speed: bool,
     /// Total storage capacity
     pub total_storage: String,
     /// Total storage capacity in TB
@@ -59,16 +420,52 @@ pub struct SystemSummary {
     pub chassis: ChassisInfo,
     /// Motherboard information
     pub motherboard: MotherboardInfo,
-    /// Total number of GPUs
+    /// Whether DMI data was collected with privileges and is complete
+    #[serde(default = "default_true")]
+    pub dmi_data_complete: bool,
+    /// Total number of physical GPUs
     pub total_gpus: usize,
+    /// Total number of MIG instances across all GPUs
+    #[serde(default)]
+    pub total_mig_instances: usize,
     /// Total number of network interfaces
     pub total_nics: usize,
+    /// PCIe lanes negotiated by GPUs and physical NICs
+    #[serde(default)]
+    pub pcie_lanes_in_use: u32,
+    /// PCIe lanes the CPUs provide, when known for the CPU model
+    #[serde(default)]
+    pub pcie_lanes_total: Option<u32>,
     /// NUMA topology information
     pub numa_topology: HashMap<String, NumaNode>,
     /// CPU topology information
     pub cpu_topology: CpuTopology,
+    /// Per-socket and total core and thread counts
+    #[serde(default)]
+    pub cpu_counts: CpuCounts,
     /// CPU configuration summary
     pub cpu_summary: String,
+    /// Trusted Platform Module
+    #[serde(default)]
+    pub tpm: TpmInfo,
+    /// Whether the kernel's automatic NUMA balancing is enabled
+    #[serde(default)]
+    pub numa_balancing: Option<bool>,
+}
+
+/// Trusted Platform Module presence and identity
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct TpmInfo {
+    /// Whether a TPM is present
+    pub present: bool,
+    /// Specification version ("1.2" or "2.0")
+    pub version: Option<String>,
+    /// TPM vendor (e.g., "Infineon")
+    pub manufacturer: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// System identification information
@@ -106,6 +503,17 @@ pub struct ChassisInfo {
     pub type_: String,
     /// Chassis serial number
     pub serial: String,
+    /// Airflow direction in NetBox terms ("front-to-rear", "rear-to-front",
+    /// "passive"), when the BMC reports it
+    #[serde(default)]
+    pub airflow: Option<String>,
+}
+
+impl ChassisInfo {
+    /// Airflow to record in NetBox, assuming front-to-rear when unknown
+    pub fn netbox_airflow(&self) -> &str {
+        self.airflow.as_deref().unwrap_or("front-to-rear")
+    }
 }
 
 /// Motherboard information
@@ -127,8 +535,32 @@ pub struct MotherboardInfo {
     pub type_: String,
 }
 
+/// Physical placement of a logical CPU from sysfs `cpuN/topology`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct LogicalCpu {
+    /// Logical CPU number
+    pub cpu: u32,
+    /// Physical core ID within the socket (`core_id`)
+    pub core: u32,
+    /// Socket (`physical_package_id`)
+    pub socket: u32,
+}
+
+/// Location of a logical CPU, as returned by `HardwareReport::cpu_affinity_map`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CpuLocation {
+    /// Physical core ID within the socket
+    pub core: u32,
+    /// Socket
+    pub socket: u32,
+    /// NUMA node, if the CPU is listed in the NUMA topology
+    pub numa_node: Option<i32>,
+    /// Other hardware thread on the same core (`None` with SMT off)
+    pub thread_sibling: Option<u32>,
+}
+
 /// CPU topology information
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CpuTopology {
     /// Total number of cores
     pub total_cores: u32,
@@ -144,6 +576,36 @@ pub struct CpuTopology {
     pub numa_nodes: u32,
     /// CPU model name
     pub cpu_model: String,
+    /// CPUs isolated from the general scheduler (isolcpus)
+    #[serde(default)]
+    pub isolated_cpus: Vec<u32>,
+    /// CPUs running in adaptive-tick mode (nohz_full)
+    #[serde(default)]
+    pub nohz_full_cpus: Vec<u32>,
+    /// Online CPUs not isolated from the general scheduler
+    #[serde(default)]
+    pub schedulable_cores: u32,
+    /// Logical CPUs that are offline
+    #[serde(default)]
+    pub offline_cpus: Vec<u32>,
+    /// Whether turbo/boost frequencies are enabled
+    #[serde(default)]
+    pub turbo_enabled: Option<bool>,
+    /// SMT control state ("on", "off", "forceoff", "notsupported", "notimplemented")
+    #[serde(default)]
+    pub smt_control: Option<String>,
+    /// Whether SMT siblings are currently active
+    #[serde(default)]
+    pub smt_active: Option<bool>,
+    /// Active cpufreq scaling driver (e.g., "intel_pstate", "acpi-cpufreq", "amd-pstate")
+    #[serde(default)]
+    pub cpufreq_driver: Option<String>,
+    /// Active cpuidle driver (e.g., "intel_idle", "acpi_idle")
+    #[serde(default)]
+    pub cpuidle_driver: Option<String>,
+    /// Physical core and socket of each logical CPU
+    #[serde(default)]
+    pub logical_cpus: Vec<LogicalCpu>,
 }
 
 /// Contains detailed hardware information
@@ -157,6 +619,36 @@ pub struct HardwareInfo {
     pub storage: StorageInfo,
     /// GPU information
     pub gpus: GpuInfo,
+    /// Non-GPU accelerators (FPGAs, NPUs)
+    #[serde(default)]
+    pub accelerators: Vec<Accelerator>,
+    /// GPUs, NICs, storage controllers and accelerators with no kernel driver bound
+    #[serde(default)]
+    pub unbound_pci_devices: Vec<PciDevice>,
+}
+
+/// Non-GPU accelerator such as an FPGA or inference NPU
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Accelerator {
+    /// Vendor name (e.g., "Xilinx")
+    pub vendor: String,
+    /// Device model (e.g., "Alveo U250 XDMA Platform")
+    pub model: String,
+    /// PCI address (e.g., "0000:3b:00.0")
+    pub pci_address: String,
+}
+
+/// PCI device identity
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PciDevice {
+    /// PCI address (e.g., "0000:3b:00.0")
+    pub address: String,
+    /// Vendor ID (e.g., "10de")
+    pub vendor_id: String,
+    /// Device ID (e.g., "2330")
+    pub device_id: String,
+    /// Device class (e.g., "0x030200")
+    pub class: String,
 }
 
 /// CPU information
@@ -165,8 +657,13 @@ pub struct CpuInfo {
     /// CPU model name
     pub model: String,
     /// Number of cores per socket
+    ///
+    /// Deprecated: easily mistaken for the machine total; prefer
+    /// [`CpuInfo::counts`]. Kept for compatibility.
     pub cores: u32,
     /// Number of threads per core
+    ///
+    /// Deprecated: prefer [`CpuInfo::counts`]. Kept for compatibility.
     pub threads: u32,
     /// Number of sockets
     pub sockets: u32,
@@ -211,6 +708,42 @@ pub struct CpuInfo {
     /// Detection methods used
     #[serde(default)]
     pub detection_methods: Vec<String>,
+    /// CPUs isolated from the general scheduler (isolcpus)
+    #[serde(default)]
+    pub isolated_cpus: Vec<u32>,
+    /// CPUs running in adaptive-tick mode (nohz_full)
+    #[serde(default)]
+    pub nohz_full_cpus: Vec<u32>,
+    /// Online logical CPUs
+    #[serde(default)]
+    pub online_cpus: Vec<u32>,
+    /// Offline logical CPUs
+    #[serde(default)]
+    pub offline_cpus: Vec<u32>,
+    /// Whether turbo/boost frequencies are enabled
+    #[serde(default)]
+    pub turbo_enabled: Option<bool>,
+    /// SMT control state ("on", "off", "forceoff", "notsupported", "notimplemented")
+    #[serde(default)]
+    pub smt_control: Option<String>,
+    /// Whether SMT siblings are currently active
+    #[serde(default)]
+    pub smt_active: Option<bool>,
+    /// Active cpufreq scaling driver (e.g., "intel_pstate", "acpi-cpufreq", "amd-pstate")
+    #[serde(default)]
+    pub cpufreq_driver: Option<String>,
+    /// Active cpuidle driver (e.g., "intel_idle", "acpi_idle")
+    #[serde(default)]
+    pub cpuidle_driver: Option<String>,
+    /// Physical core and socket of each logical CPU
+    #[serde(default)]
+    pub logical_cpus: Vec<LogicalCpu>,
+    /// Share of CPU time stolen by the hypervisor over a short sample (VMs only)
+    #[serde(default)]
+    pub cpu_steal_percent: Option<f64>,
+    /// cpufreq scaling governor of CPU 0 (e.g., "performance", "powersave")
+    #[serde(default)]
+    pub scaling_governor: Option<String>,
 }
 
 impl CpuInfo {
@@ -229,6 +762,40 @@ impl CpuInfo {
     pub fn calculate_totals(&mut self) {
         // These are typically already set correctly from parsing
     }
+
+    /// Per-socket and machine-wide core and thread counts
+    pub fn counts(&self) -> CpuCounts {
+        CpuCounts::new(self.sockets, self.cores, self.threads)
+    }
+}
+
+/// Core and thread counts with unambiguous per-socket and total names
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuCounts {
+    /// Number of populated sockets
+    pub sockets: u32,
+    /// Physical cores in each socket
+    pub cores_per_socket: u32,
+    /// Hardware threads per physical core
+    pub threads_per_core: u32,
+    /// Physical cores across all sockets
+    pub cores_total: u32,
+    /// Hardware threads across all sockets
+    pub threads_total: u32,
+}
+
+impl CpuCounts {
+    /// Derive the totals from the per-socket and per-core counts
+    pub fn new(sockets: u32, cores_per_socket: u32, threads_per_core: u32) -> Self {
+        let cores_total = sockets.saturating_mul(cores_per_socket);
+        Self {
+            sockets,
+            cores_per_socket,
+            threads_per_core,
+            cores_total,
+            threads_total: cores_total.saturating_mul(threads_per_core),
+        }
+    }
 }
 
 /// CPU cache information
@@ -261,6 +828,44 @@ pub struct MemoryInfo {
     pub speed: String,
     /// Individual memory modules
     pub modules: Vec<MemoryModule>,
+    /// Transparent hugepage mode ("always", "madvise" or "never")
+    #[serde(default)]
+    pub transparent_hugepages: Option<String>,
+    /// Hugepage pools requested on the kernel command line
+    #[serde(default)]
+    pub hugepage_pools: Vec<HugepagePool>,
+    /// Persistent memory namespaces from `ndctl` (empty without NVDIMMs or ndctl)
+    #[serde(default)]
+    pub nvdimm_namespaces: Vec<NvdimmNamespace>,
+    /// Whether the memory array uses error correction (`None` when DMI does not say)
+    #[serde(default)]
+    pub ecc_enabled: Option<bool>,
+}
+
+/// Persistent memory namespace on an NVDIMM region
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NvdimmNamespace {
+    /// Namespace device (e.g., "namespace0.0")
+    pub name: String,
+    /// Access mode ("fsdax", "devdax", "sector" or "raw")
+    pub mode: String,
+    /// Usable capacity in bytes
+    pub size_bytes: u64,
+    /// Block device for fsdax, sector and raw namespaces (e.g., "pmem0")
+    #[serde(default)]
+    pub blockdev: Option<String>,
+}
+
+/// Hugepage pool requested on the kernel command line
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HugepagePool {
+    /// Page size in KiB
+    pub page_size_kb: u64,
+    /// Pages requested with `hugepages=`
+    pub requested: u64,
+    /// Pages actually allocated; `None` when the pool could not be read
+    #[serde(default)]
+    pub allocated: Option<u64>,
 }
 
 /// Individual memory module
@@ -278,6 +883,15 @@ pub struct MemoryModule {
     pub manufacturer: String,
     /// Serial number
     pub serial: String,
+    /// Configured (actual) speed, which may be below the rated speed
+    #[serde(default)]
+    pub configured_speed: Option<String>,
+    /// Number of ranks (e.g., "2")
+    #[serde(default)]
+    pub rank: Option<String>,
+    /// Memory channel within the socket (e.g., "A", "0"), from the locator strings
+    #[serde(default)]
+    pub channel: Option<String>,
 }
 
 /// Storage information
@@ -285,6 +899,37 @@ pub struct MemoryModule {
 pub struct StorageInfo {
     /// List of storage devices
     pub devices: Vec<StorageDevice>,
+    /// SES enclosures (JBODs, backplanes) with their slot-to-disk mapping
+    #[serde(default)]
+    pub enclosures: Vec<Enclosure>,
+}
+
+/// SCSI enclosure services (SES) enclosure
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Enclosure {
+    /// SCSI address of the enclosure device (e.g., "0:0:8:0")
+    pub name: String,
+    /// Enclosure logical identifier (WWN)
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Enclosure vendor
+    #[serde(default)]
+    pub vendor: Option<String>,
+    /// Enclosure model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Drive slots, including empty ones
+    pub slots: Vec<EnclosureSlot>,
+}
+
+/// Drive slot in an enclosure
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct EnclosureSlot {
+    /// Slot label as reported by the enclosure (e.g., "Slot 01")
+    pub slot: String,
+    /// Block device in the slot (e.g., "sda"), `None` when empty
+    #[serde(default)]
+    pub disk: Option<String>,
 }
 
 /// Storage type classification
@@ -375,6 +1020,109 @@ pub struct StorageDevice {
     /// Detection method used
     #[serde(default)]
     pub detection_method: String,
+    /// Parent storage controller (kind and PCI address)
+    #[serde(default)]
+    pub controller: Option<String>,
+    /// Whether the device is behind a hardware RAID controller
+    #[serde(default)]
+    pub behind_raid: bool,
+    /// Spindle speed in RPM (`None` for solid state devices)
+    #[serde(default)]
+    pub rotation_rpm: Option<u32>,
+    /// Drive form factor (e.g., "3.5 inches")
+    #[serde(default)]
+    pub form_factor: Option<String>,
+    /// NVMe SMART/health log, when the nvme CLI is available
+    #[serde(default)]
+    pub nvme_health: Option<NvmeHealth>,
+    /// Composite temperature is at or above the drive's warning threshold
+    #[serde(default)]
+    pub overheating: bool,
+    /// Active block I/O scheduler (e.g., "mq-deadline", "none")
+    #[serde(default)]
+    pub io_scheduler: Option<String>,
+    /// RAID level of the controller virtual disk backing this device (e.g., "RAID1")
+    #[serde(default)]
+    pub raid_level: Option<String>,
+    /// Physical drives in the controller virtual disk backing this device
+    #[serde(default)]
+    pub physical_disk_count: Option<u32>,
+    /// Partitions on the device, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub partitions: Vec<Partition>,
+}
+
+/// Partition on a block device
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Partition {
+    /// Kernel partition name (e.g., "nvme0n1p1", "sda2")
+    pub name: String,
+    /// First sector, in 512-byte units
+    pub start_sector: u64,
+    /// Kernel-reported alignment offset in bytes (-1 when it cannot be aligned)
+    pub alignment_offset: i64,
+    /// Partition does not start on a physical block boundary, which causes
+    /// read-modify-write cycles and write amplification on SSDs
+    pub misaligned: bool,
+}
+
+/// NVMe SMART/health information log (`nvme smart-log`)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NvmeHealth {
+    /// Critical warning bitmap (0 when healthy; bit 0 = spare below threshold,
+    /// bit 1 = temperature, bit 2 = reliability degraded, bit 3 = read-only,
+    /// bit 4 = volatile backup failed)
+    pub critical_warning: u8,
+    /// Remaining spare capacity in percent
+    pub available_spare: Option<u8>,
+    /// Spare capacity threshold in percent
+    pub available_spare_threshold: Option<u8>,
+    /// Vendor estimate of endurance used in percent (may exceed 100)
+    pub percentage_used: Option<u8>,
+    /// Data units written, in thousands of 512-byte units
+    pub data_units_written: Option<u64>,
+    /// Data units read, in thousands of 512-byte units
+    pub data_units_read: Option<u64>,
+    /// Unrecovered data integrity errors
+    pub media_errors: Option<u64>,
+    /// Composite temperature in Celsius
+    #[serde(default)]
+    pub temperature_c: Option<i32>,
+    /// Warning composite temperature threshold (WCTEMP) in Celsius
+    #[serde(default)]
+    pub warning_temperature_c: Option<i32>,
+    /// Critical composite temperature threshold (CCTEMP) in Celsius
+    #[serde(default)]
+    pub critical_temperature_c: Option<i32>,
+}
+
+/// Drive identity and health from `smartctl -j -a`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct SmartHealth {
+    /// Serial number
+    pub serial: Option<String>,
+    /// Firmware revision
+    pub firmware: Option<String>,
+    /// Overall SMART self-assessment passed
+    pub healthy: Option<bool>,
+    /// Rated endurance used in percent (NVMe percentage_used, or derived from
+    /// the SSD wear attributes on SATA/SAS)
+    pub wearout_percent: Option<u8>,
+}
+
+/// Virtual disk presented by a MegaRAID/PERC controller
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RaidVirtualDisk {
+    /// Controller (adapter) number
+    pub controller: u32,
+    /// Virtual drive number, which is also its SCSI target ID
+    pub virtual_drive: u32,
+    /// RAID level (e.g., "RAID10")
+    pub raid_level: String,
+    /// Physical drives in the virtual disk, across all spans
+    pub physical_disk_count: u32,
+    /// Block device the OS sees (e.g., "/dev/sda"), when the tool reports it
+    pub os_device: Option<String>,
 }
 
 impl Default for StorageDevice {
@@ -394,6 +1142,16 @@ impl Default for StorageDevice {
             interface: String::new(),
             is_rotational: false,
             detection_method: String::new(),
+            controller: None,
+            behind_raid: false,
+            rotation_rpm: None,
+            form_factor: None,
+            nvme_health: None,
+            overheating: false,
+            io_scheduler: None,
+            raid_level: None,
+            physical_disk_count: None,
+            partitions: Vec::new(),
         }
     }
 }
@@ -403,11 +1161,7 @@ impl StorageDevice {
     pub fn calculate_size_fields(&mut self) {
         if self.size_bytes > 0 {
             self.size_gb = self.size_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
-            if self.size_gb >= 1000.0 {
-                self.size = format!("{:.2} TB", self.size_gb / 1024.0);
-            } else {
-                self.size = format!("{:.2} GB", self.size_gb);
-            }
+            self.size = format_bytes_iec(self.size_bytes);
         }
     }
 
@@ -424,6 +1178,26 @@ impl StorageDevice {
 pub struct GpuInfo {
     /// List of GPU devices
     pub devices: Vec<GpuDevice>,
+    /// Whether all GPUs share the same model and memory size
+    #[serde(default = "default_true")]
+    pub gpus_homogeneous: bool,
+    /// Xid errors from the kernel log, collected only when enabled in `ReportConfig`
+    #[serde(default)]
+    pub recent_xid_errors: Option<Vec<XidError>>,
+    /// NVLink connections between GPU pairs; empty without NVLink
+    #[serde(default)]
+    pub nvlink: Vec<NvLinkConnection>,
+}
+
+/// NVIDIA Xid error events reported by the driver for one GPU and code
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct XidError {
+    /// PCI address of the GPU as logged by the driver (e.g., "0000:3b:00")
+    pub pci_address: String,
+    /// Xid error code (e.g., 79 for "GPU has fallen off the bus")
+    pub code: u32,
+    /// Number of events with this code in the log
+    pub count: u32,
 }
 
 /// GPU vendor classification
@@ -503,6 +1277,122 @@ pub struct GpuDevice {
     /// Detection method used
     #[serde(default)]
     pub detection_method: String,
+    /// Current GPU core temperature in Celsius
+    #[serde(default)]
+    pub temperature_c: Option<i32>,
+    /// Current memory (HBM) temperature in Celsius
+    #[serde(default)]
+    pub temperature_memory_c: Option<i32>,
+    /// Temperature at which the GPU starts to throttle in Celsius
+    #[serde(default)]
+    pub temperature_slowdown_c: Option<i32>,
+    /// Temperature at which the GPU shuts down in Celsius
+    #[serde(default)]
+    pub temperature_shutdown_c: Option<i32>,
+    /// NVLink links (NVIDIA SXM only)
+    #[serde(default)]
+    pub nvlinks: Vec<NvLink>,
+    /// PCIe AER correctable error count
+    #[serde(default)]
+    pub aer_correctable_errors: Option<u64>,
+    /// PCIe AER uncorrectable (fatal + non-fatal) error count
+    #[serde(default)]
+    pub aer_uncorrectable_errors: Option<u64>,
+    /// PCIe link replay count reported by the driver
+    #[serde(default)]
+    pub pcie_replay_counter: Option<u64>,
+    /// Pages retired after single-bit ECC errors (pre-Ampere GPUs)
+    #[serde(default)]
+    pub retired_pages_single_bit: Option<u32>,
+    /// Pages retired after double-bit ECC errors (pre-Ampere GPUs)
+    #[serde(default)]
+    pub retired_pages_double_bit: Option<u32>,
+    /// Whether a page retirement is pending until the next driver reload
+    #[serde(default)]
+    pub retired_pages_pending: Option<bool>,
+    /// Upstream PCIe bridges, from the root port down to the device
+    #[serde(default)]
+    pub pcie_path: Vec<String>,
+    /// CPU range of the GPU's NUMA node, for process binding (e.g., "16-31")
+    #[serde(default)]
+    pub recommended_cpu_affinity: Option<String>,
+    /// Enabled PCIe ASPM link states ("disabled", "L1", "L0s L1")
+    #[serde(default)]
+    pub aspm_state: Option<String>,
+    /// Negotiated PCIe generation (sysfs `current_link_speed`, e.g. 16.0 GT/s is gen 4)
+    #[serde(default)]
+    pub pcie_gen: Option<u8>,
+    /// Negotiated PCIe link width in lanes (sysfs `current_link_width`)
+    #[serde(default)]
+    pub pcie_width: Option<u8>,
+    /// Highest PCIe generation the device supports (sysfs `max_link_speed`)
+    #[serde(default)]
+    pub pcie_max_gen: Option<u8>,
+    /// Widest PCIe link the device supports (sysfs `max_link_width`)
+    #[serde(default)]
+    pub pcie_max_width: Option<u8>,
+    /// MIG instances the GPU is partitioned into (empty when MIG is off)
+    #[serde(default)]
+    pub mig_instances: Vec<MigInstance>,
+    /// Whether resizable BAR is on, i.e. BAR1 maps all of VRAM
+    #[serde(default)]
+    pub resizable_bar_enabled: Option<bool>,
+    /// Enforced power limit in watts
+    #[serde(default)]
+    pub power_limit_watts: Option<f64>,
+    /// Driver default power limit in watts
+    #[serde(default)]
+    pub default_power_limit_watts: Option<f64>,
+    /// Current performance state ("P0" is maximum performance, up to "P12" idle)
+    #[serde(default)]
+    pub performance_state: Option<String>,
+    /// How the GPU is shared: "none", "mig", "vgpu" or "passthrough"
+    ///
+    /// vGPU and passthrough guests see a restricted device, which is why
+    /// fields such as power limits or NVLink may be missing.
+    #[serde(default)]
+    pub virtualization_mode: Option<String>,
+}
+
+/// Multi-Instance GPU partition
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MigInstance {
+    /// MIG profile (e.g., "1g.10gb", "3g.40gb")
+    pub profile: String,
+    /// MIG device UUID
+    pub uuid: String,
+}
+
+/// NVLink connection between two GPUs from the `nvidia-smi topo -m` matrix
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct NvLinkConnection {
+    /// Index of the lower-numbered GPU
+    pub gpu_a: u32,
+    /// Index of the higher-numbered GPU
+    pub gpu_b: u32,
+    /// Number of NVLinks bonded between the pair
+    pub links: u32,
+}
+
+/// NVLink link state and error counters
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct NvLink {
+    /// Link index
+    pub link: u32,
+    /// Link state ("up" or "down")
+    pub state: String,
+    /// Link speed (e.g., "26.562 GB/s")
+    #[serde(default)]
+    pub speed: Option<String>,
+    /// Replay error count
+    #[serde(default)]
+    pub replay_errors: Option<u64>,
+    /// Recovery error count
+    #[serde(default)]
+    pub recovery_errors: Option<u64>,
+    /// CRC error count (flit and data errors combined)
+    #[serde(default)]
+    pub crc_errors: Option<u64>,
 }
 
 impl Default for GpuDevice {
@@ -522,6 +1412,30 @@ impl Default for GpuDevice {
             driver_version: None,
             compute_capability: None,
             detection_method: String::new(),
+            temperature_c: None,
+            temperature_memory_c: None,
+            temperature_slowdown_c: None,
+            temperature_shutdown_c: None,
+            nvlinks: Vec::new(),
+            aer_correctable_errors: None,
+            aer_uncorrectable_errors: None,
+            pcie_replay_counter: None,
+            retired_pages_single_bit: None,
+            retired_pages_double_bit: None,
+            retired_pages_pending: None,
+            pcie_path: Vec::new(),
+            recommended_cpu_affinity: None,
+            aspm_state: None,
+            pcie_gen: None,
+            pcie_width: None,
+            pcie_max_gen: None,
+            pcie_max_width: None,
+            mig_instances: Vec::new(),
+            resizable_bar_enabled: None,
+            power_limit_watts: None,
+            default_power_limit_watts: None,
+            performance_state: None,
+            virtualization_mode: None,
         }
     }
 }
@@ -530,13 +1444,26 @@ impl GpuDevice {
     /// Set memory string from memory_total_mb
     pub fn set_memory_string(&mut self) {
         if self.memory_total_mb > 0 {
-            if self.memory_total_mb >= 1024 {
-                self.memory = format!("{:.1} GB", self.memory_total_mb as f64 / 1024.0);
-            } else {
-                self.memory = format!("{} MB", self.memory_total_mb);
-            }
+            self.memory = format_bytes_iec(self.memory_total_mb * 1024 * 1024);
         }
     }
+
+    /// Whether the PCIe link trained below the device's capability
+    ///
+    /// Generation and width are checked independently, and a check is
+    /// skipped unless both its current and max values are known. Idle GPUs
+    /// routinely drop the link to Gen1 to save power, so a lower generation
+    /// is ignored while the GPU is known to be in a power-saving P-state;
+    /// with no P-state reported (non-NVIDIA or lspci-only GPUs) it counts.
+    pub fn is_pcie_degraded(&self) -> bool {
+        let below = |current: Option<u8>, max: Option<u8>| matches!((current, max), (Some(current), Some(max)) if current < max);
+        let power_saving = self
+            .performance_state
+            .as_deref()
+            .is_some_and(|state| state != "P0");
+        below(self.pcie_width, self.pcie_max_width)
+            || (!power_saving && below(self.pcie_gen, self.pcie_max_gen))
+    }
 }
 
 /// Network information
@@ -546,6 +1473,9 @@ pub struct NetworkInfo {
     pub interfaces: Vec<NetworkInterface>,
     /// Infiniband information, if available
     pub infiniband: Option<InfinibandInfo>,
+    /// Fibre Channel host bus adapter ports
+    #[serde(default)]
+    pub fc_hbas: Vec<FcHba>,
 }
 
 /// Network interface type classification
@@ -623,6 +1553,63 @@ pub struct NetworkInterface {
     /// Link detected (carrier present)
     #[serde(default)]
     pub carrier: Option<bool>,
+    /// Received bytes counter (snapshot)
+    #[serde(default)]
+    pub rx_bytes: Option<u64>,
+    /// Transmitted bytes counter (snapshot)
+    #[serde(default)]
+    pub tx_bytes: Option<u64>,
+    /// Receive errors counter (snapshot)
+    #[serde(default)]
+    pub rx_errors: Option<u64>,
+    /// Transmit errors counter (snapshot)
+    #[serde(default)]
+    pub tx_errors: Option<u64>,
+    /// Dropped received packets counter (snapshot)
+    #[serde(default)]
+    pub rx_dropped: Option<u64>,
+    /// Dropped transmitted packets counter (snapshot)
+    #[serde(default)]
+    pub tx_dropped: Option<u64>,
+    /// PCIe AER correctable error count
+    #[serde(default)]
+    pub aer_correctable_errors: Option<u64>,
+    /// PCIe AER uncorrectable (fatal + non-fatal) error count
+    #[serde(default)]
+    pub aer_uncorrectable_errors: Option<u64>,
+    /// Member interfaces when this is a bond
+    #[serde(default)]
+    pub bond_members: Vec<String>,
+    /// RDMA device backing this interface (e.g., "mlx5_0")
+    #[serde(default)]
+    pub rdma_device: Option<String>,
+    /// Upstream PCIe bridges, from the root port down to the device
+    #[serde(default)]
+    pub pcie_path: Vec<String>,
+    /// Enabled PCIe ASPM link states ("disabled", "L1", "L0s L1")
+    #[serde(default)]
+    pub aspm_state: Option<String>,
+    /// Negotiated PCIe generation (sysfs `current_link_speed`, e.g. 16.0 GT/s is gen 4)
+    #[serde(default)]
+    pub pcie_gen: Option<u8>,
+    /// Negotiated PCIe link width in lanes (sysfs `current_link_width`)
+    #[serde(default)]
+    pub pcie_width: Option<u8>,
+    /// Highest PCIe generation the device supports (sysfs `max_link_speed`)
+    #[serde(default)]
+    pub pcie_max_gen: Option<u8>,
+    /// Widest PCIe link the device supports (sysfs `max_link_width`)
+    #[serde(default)]
+    pub pcie_max_width: Option<u8>,
+    /// Current RX ring size (`ethtool -g`)
+    #[serde(default)]
+    pub rx_ring: Option<u32>,
+    /// Current TX ring size (`ethtool -g`)
+    #[serde(default)]
+    pub tx_ring: Option<u32>,
+    /// Current combined queue count (`ethtool -l`)
+    #[serde(default)]
+    pub combined_queues: Option<u32>,
 }
 
 fn default_mtu() -> u32 {
@@ -650,10 +1637,59 @@ impl Default for NetworkInterface {
             is_up: false,
             is_virtual: false,
             carrier: None,
+            rx_bytes: None,
+            tx_bytes: None,
+            rx_errors: None,
+            tx_errors: None,
+            rx_dropped: None,
+            tx_dropped: None,
+            aer_correctable_errors: None,
+            aer_uncorrectable_errors: None,
+            bond_members: Vec::new(),
+            rdma_device: None,
+            pcie_path: Vec::new(),
+            aspm_state: None,
+            pcie_gen: None,
+            pcie_width: None,
+            pcie_max_gen: None,
+            pcie_max_width: None,
+            rx_ring: None,
+            tx_ring: None,
+            combined_queues: None,
         }
     }
 }
 
+/// How close an RDMA NIC sits to its paired GPU
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuNicLocality {
+    /// Behind the same PCIe switch
+    PcieSwitch,
+    /// On the same NUMA node, but not the same switch
+    NumaNode,
+}
+
+/// An RDMA-capable NIC paired with its nearest GPU
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GpuNicPair {
+    /// Network interface name
+    pub interface: String,
+    /// RDMA device name (e.g., "mlx5_0")
+    pub rdma_device: String,
+    /// Index of the nearest GPU
+    pub gpu_index: u32,
+    /// How the NIC and GPU are connected
+    pub locality: GpuNicLocality,
+}
+
+/// GPU to RDMA NIC topology for GPUDirect RDMA
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct GpuNicAffinity {
+    /// Each RDMA-capable NIC with its nearest GPU
+    pub pairs: Vec<GpuNicPair>,
+}
+
 /// Infiniband information
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InfinibandInfo {
@@ -672,6 +1708,36 @@ pub struct IbInterface {
     pub state: String,
     /// Interface rate
     pub rate: String,
+    /// Link layer (e.g., "InfiniBand" or "Ethernet" for RoCE)
+    #[serde(default)]
+    pub link_layer: Option<String>,
+    /// Active MTU in bytes
+    #[serde(default)]
+    pub active_mtu: Option<String>,
+}
+
+/// Fibre Channel host bus adapter port
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FcHba {
+    /// SCSI host name (e.g., "host1")
+    pub name: String,
+    /// World Wide Port Name
+    pub port_name: String,
+    /// World Wide Node Name
+    #[serde(default)]
+    pub node_name: Option<String>,
+    /// Negotiated link speed (e.g., "32 Gbit")
+    #[serde(default)]
+    pub speed: Option<String>,
+    /// Port state (e.g., "Online", "Linkdown")
+    #[serde(default)]
+    pub port_state: Option<String>,
+    /// Adapter symbolic name (model and firmware)
+    #[serde(default)]
+    pub symbolic_name: Option<String>,
+    /// World Wide Port Names of discovered remote ports
+    #[serde(default)]
+    pub remote_ports: Vec<String>,
 }
 
 /// NUMA node information
@@ -681,12 +1747,18 @@ pub struct NumaNode {
     pub id: i32,
     /// CPU list
     pub cpus: Vec<u32>,
+    /// Compact form of `cpus` (e.g., "0-63,128-191")
+    #[serde(default)]
+    pub cpu_ranges: String,
     /// Memory size
     pub memory: String,
     /// Devices attached to this node
     pub devices: Vec<NumaDevice>,
     /// Distances to other nodes (node_id -> distance)
     pub distances: HashMap<String, u32>,
+    /// Memory tier (HBM, DDR, CXL)
+    #[serde(default)]
+    pub memory_tier: Option<String>,
 }
 
 /// Device attached to a NUMA node
@@ -720,6 +1792,159 @@ pub struct ReportConfig {
     pub command_timeout: u64,
     /// Enable verbose output
     pub verbose: bool,
+    /// Hostname to record instead of the detected one
+    pub hostname_override: Option<String>,
+    /// Collect operational OS state (e.g., failed systemd units)
+    pub collect_operational: bool,
+    /// Embed raw command output in the report for debugging parsers
+    pub include_raw: bool,
+    /// Scan the kernel log for GPU Xid errors
+    pub collect_xid_errors: bool,
+    /// Scan the kernel log for MCE, PCIe AER, disk I/O and thermal errors
+    pub collect_kernel_errors: bool,
+    /// Read the most recent BMC System Event Log entries with ipmitool
+    pub collect_sel_events: bool,
+    /// Detect the container runtime and NVIDIA container toolkit
+    pub collect_container_runtime: bool,
+    /// Query the cloud provider metadata endpoint
+    pub collect_cloud_metadata: bool,
+    /// Read DMI tables from a saved `dmidecode` dump instead of running dmidecode (Linux)
+    pub dmidecode_source: Option<PathBuf>,
+    /// Regex selecting data interfaces that must share one MTU
+    pub data_interfaces: Option<String>,
+    /// BMC to read the Redfish firmware inventory from
+    pub redfish: Option<RedfishConfig>,
+    /// Count virtual and container interfaces (veth, cni, ...) in `total_nics`
+    pub count_virtual_nics: bool,
+    /// Attribute GPU device nodes to cgroups from their device allowlists
+    pub collect_gpu_cgroup_allocations: bool,
+    /// List partitions on each storage device and check their alignment
+    pub collect_partitions: bool,
+    /// Collect only from the BMC over Redfish, running no commands on the host
+    /// (requires `redfish`)
+    pub out_of_band_only: bool,
+    /// Expected settings that `configuration_deviations` is checked against
+    pub configuration_profile: ConfigurationProfile,
+    /// Called as each subsystem starts and finishes collecting
+    pub progress: Option<ProgressCallback>,
+}
+
+/// Expected host settings for the configuration deviation audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigurationProfile {
+    /// cpufreq scaling governor
+    pub cpu_governor: String,
+    /// Whether SMT is enabled
+    pub smt_enabled: bool,
+    /// Whether turbo/boost is enabled
+    pub turbo_enabled: bool,
+    /// Whether PCIe ASPM is enabled on GPU links
+    pub aspm_enabled: bool,
+    /// Firmware boot mode ("UEFI" or "Legacy")
+    pub boot_mode: String,
+    /// GPU power limit in watts; `None` expects the driver default
+    pub gpu_power_limit_watts: Option<f64>,
+    /// Whether memory error correction is enabled
+    pub ecc_enabled: bool,
+    /// Whether automatic NUMA balancing is enabled
+    pub numa_balancing: bool,
+}
+
+impl Default for ConfigurationProfile {
+    fn default() -> Self {
+        Self {
+            cpu_governor: "performance".to_string(),
+            smt_enabled: true,
+            turbo_enabled: true,
+            aspm_enabled: false,
+            boot_mode: "UEFI".to_string(),
+            gpu_power_limit_watts: None,
+            ecc_enabled: true,
+            numa_balancing: false,
+        }
+    }
+}
+
+/// Hardware subsystem collected while generating a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subsystem {
+    Cpu,
+    Memory,
+    Storage,
+    Gpus,
+    Network,
+    /// DMI, BIOS, chassis, NUMA and filesystem metadata
+    Platform,
+}
+
+impl std::fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Subsystem::Cpu => "CPU",
+            Subsystem::Memory => "memory",
+            Subsystem::Storage => "storage",
+            Subsystem::Gpus => "GPUs",
+            Subsystem::Network => "network",
+            Subsystem::Platform => "platform",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Progress event passed to a [`ProgressCallback`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubsystemProgress {
+    Started(Subsystem),
+    Completed(Subsystem),
+}
+
+/// Callback for collection progress, e.g. to drive a spinner
+///
+/// Subsystems are collected concurrently, so events for different
+/// subsystems may interleave; each `Started` precedes its `Completed`.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(SubsystemProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wrap a closure as a progress callback
+    pub fn new(callback: impl Fn(SubsystemProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Deliver one progress event
+    pub fn report(&self, progress: SubsystemProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Redfish BMC endpoint and credentials
+#[derive(Clone)]
+pub struct RedfishConfig {
+    /// BMC base URL (e.g., "https://10.0.12.34")
+    pub endpoint: String,
+    /// BMC user name
+    pub username: String,
+    /// BMC password
+    pub password: String,
+    /// Skip TLS verification; BMCs usually ship self-signed certificates
+    pub skip_tls_verify: bool,
+}
+
+impl std::fmt::Debug for RedfishConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedfishConfig")
+            .field("endpoint", &self.endpoint)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("skip_tls_verify", &self.skip_tls_verify)
+            .finish()
+    }
 }
 
 impl Default for ReportConfig {
@@ -729,6 +1954,23 @@ impl Default for ReportConfig {
             skip_sudo: false,
             command_timeout: 30,
             verbose: false,
+            hostname_override: None,
+            collect_operational: false,
+            include_raw: false,
+            collect_xid_errors: false,
+            collect_kernel_errors: false,
+            collect_sel_events: false,
+            collect_container_runtime: false,
+            collect_cloud_metadata: false,
+            dmidecode_source: None,
+            data_interfaces: None,
+            redfish: None,
+            count_virtual_nics: false,
+            collect_gpu_cgroup_allocations: false,
+            collect_partitions: false,
+            out_of_band_only: false,
+            configuration_profile: ConfigurationProfile::default(),
+            progress: None,
         }
     }
 }
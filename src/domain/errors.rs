@@ -74,6 +74,8 @@ pub enum ReportError {
     GenerationFailed(String),
     /// Report validation failed
     ValidationFailed(String),
+    /// Report rejected by a caller-supplied validator
+    Rejected(Vec<String>),
 }
 
 impl fmt::Display for ReportError {
@@ -82,6 +84,9 @@ impl fmt::Display for ReportError {
             ReportError::Domain(err) => write!(f, "{err}"),
             ReportError::GenerationFailed(msg) => write!(f, "Report generation failed: {msg}"),
             ReportError::ValidationFailed(msg) => write!(f, "Report validation failed: {msg}"),
+            ReportError::Rejected(violations) => {
+                write!(f, "Report rejected: {}", violations.join("; "))
+            }
         }
     }
 }
@@ -0,0 +1,89 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Human-readable size formatting shared by every report section
+
+const IEC_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const SI_UNITS: &[&str] = &["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+/// Format a byte count using binary (1024-based) units
+///
+/// # Arguments
+/// * `bytes` - Number of bytes
+///
+/// # Returns
+/// * Human-readable string (e.g., "1023 B", "16.0 GiB", "1.0 TiB")
+pub fn format_bytes_iec(bytes: u64) -> String {
+    format_bytes(bytes, 1024, IEC_UNITS)
+}
+
+/// Format a byte count using decimal (1000-based) units
+///
+/// # Arguments
+/// * `bytes` - Number of bytes
+///
+/// # Returns
+/// * Human-readable string (e.g., "999 B", "3.8 TB")
+pub fn format_bytes_si(bytes: u64) -> String {
+    format_bytes(bytes, 1000, SI_UNITS)
+}
+
+fn format_bytes(bytes: u64, base: u64, units: &[&str]) -> String {
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    // Compare against the rounded value so 1023.95 GiB becomes 1.0 TiB
+    // rather than "1024.0 GiB"
+    while unit_index < units.len() - 1
+        && (size >= base as f64 || (unit_index > 0 && (size * 10.0).round() >= base as f64 * 10.0))
+    {
+        size /= base as f64;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{:.1} {}", size, units[unit_index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIB: u64 = 1024 * 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_format_bytes_iec_boundaries() {
+        assert_eq!(format_bytes_iec(0), "0 B");
+        assert_eq!(format_bytes_iec(1023), "1023 B");
+        assert_eq!(format_bytes_iec(1024), "1.0 KiB");
+        assert_eq!(format_bytes_iec(16 * 1024 * 1024 * 1024), "16.0 GiB");
+        assert_eq!(format_bytes_iec(TIB - 1), "1.0 TiB");
+        assert_eq!(format_bytes_iec(TIB), "1.0 TiB");
+    }
+
+    #[test]
+    fn test_format_bytes_si_boundaries() {
+        assert_eq!(format_bytes_si(999), "999 B");
+        assert_eq!(format_bytes_si(1000), "1.0 kB");
+        assert_eq!(format_bytes_si(1023), "1.0 kB");
+        assert_eq!(format_bytes_si(1024), "1.0 kB");
+        assert_eq!(format_bytes_si(TIB), "1.1 TB");
+        assert_eq!(format_bytes_si(3_840_000_000_000), "3.8 TB");
+    }
+}
@@ -24,15 +24,55 @@ use crate::domain::entities as new;
 /// Convert from legacy ServerInfo to new HardwareReport
 impl From<crate::ServerInfo> for new::HardwareReport {
     fn from(legacy: crate::ServerInfo) -> Self {
+        let mut summary: new::SystemSummary = legacy.summary.into();
+        let hardware: new::HardwareInfo = legacy.hardware.into();
+        summary.memory_running_at_rated_speed =
+            crate::domain::is_memory_running_at_rated_speed(&hardware.memory.modules);
+        // Reports saved before ServerInfo carried a machine ID fall back to DMI
+        let (machine_id, machine_id_source) = if legacy.machine_id.is_empty() {
+            let (machine_id, source) = crate::domain::select_machine_id(
+                &summary.system_info.uuid,
+                &summary.chassis.serial,
+                &summary.motherboard.serial,
+                None,
+                &legacy.hostname,
+            );
+            (machine_id, source.to_string())
+        } else {
+            (legacy.machine_id, legacy.machine_id_source)
+        };
         new::HardwareReport {
-            summary: legacy.summary.into(),
+            report_version: legacy.report_version,
+            summary,
             hostname: legacy.hostname,
+            detected_hostname: legacy.detected_hostname,
+            machine_id,
+            machine_id_source,
             fqdn: legacy.fqdn,
             os_ip: legacy.os_ip.into_iter().map(|ip| ip.into()).collect(),
             bmc_ip: legacy.bmc_ip,
             bmc_mac: legacy.bmc_mac,
-            hardware: legacy.hardware.into(),
+            bmc_ip_source: legacy.bmc_ip_source,
+            hardware,
             network: legacy.network.into(),
+            gpu_nic_affinity: None,
+            tool_versions: Default::default(),
+            bios_settings: Default::default(),
+            firmware_components: Vec::new(),
+            boot_mode: None,
+            operational: None,
+            kernel_hardware_errors: None,
+            sel_recent_events: None,
+            configuration_deviations: legacy.configuration_deviations,
+            time_sync: None,
+            watchdog: None,
+            power: None,
+            sensors: Vec::new(),
+            container_support: None,
+            container_limits: None,
+            gpu_cgroup_allocations: None,
+            cloud: None,
+            raw_sources: None,
         }
     }
 }
@@ -41,14 +81,20 @@ impl From<crate::ServerInfo> for new::HardwareReport {
 impl From<new::HardwareReport> for crate::ServerInfo {
     fn from(new_report: new::HardwareReport) -> Self {
         crate::ServerInfo {
+            report_version: new_report.report_version,
             summary: new_report.summary.into(),
             hostname: new_report.hostname,
+            detected_hostname: new_report.detected_hostname,
+            machine_id: new_report.machine_id,
+            machine_id_source: new_report.machine_id_source,
             fqdn: new_report.fqdn,
             os_ip: new_report.os_ip.into_iter().map(|ip| ip.into()).collect(),
             bmc_ip: new_report.bmc_ip,
             bmc_mac: new_report.bmc_mac,
+            bmc_ip_source: new_report.bmc_ip_source,
             hardware: new_report.hardware.into(),
             network: new_report.network.into(),
+            configuration_deviations: new_report.configuration_deviations,
         }
     }
 }
@@ -60,21 +106,34 @@ impl From<crate::SystemSummary> for new::SystemSummary {
             system_info: legacy.system_info.into(),
             total_memory: legacy.total_memory,
             memory_config: legacy.memory_config,
+            // Needs the memory modules; the ServerInfo conversion computes it
+            memory_running_at_rated_speed: true,
             total_storage: legacy.total_storage,
             total_storage_tb: legacy.total_storage_tb,
             filesystems: legacy.filesystems,
             bios: legacy.bios.into(),
             chassis: legacy.chassis.into(),
             motherboard: legacy.motherboard.into(),
+            dmi_data_complete: true,
             total_gpus: legacy.total_gpus,
+            total_mig_instances: 0,
             total_nics: legacy.total_nics,
+            pcie_lanes_in_use: 0,
+            pcie_lanes_total: None,
             numa_topology: legacy
                 .numa_topology
                 .into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            cpu_counts: new::CpuCounts::new(
+                legacy.cpu_topology.sockets,
+                legacy.cpu_topology.cores_per_socket,
+                legacy.cpu_topology.threads_per_core,
+            ),
             cpu_topology: legacy.cpu_topology.into(),
             cpu_summary: legacy.cpu_summary,
+            tpm: Default::default(),
+            numa_balancing: None,
         }
     }
 }
@@ -158,6 +217,7 @@ impl From<crate::ChassisInfo> for new::ChassisInfo {
             manufacturer: legacy.manufacturer,
             type_: legacy.type_,
             serial: legacy.serial,
+            airflow: None,
         }
     }
 }
@@ -210,6 +270,7 @@ impl From<crate::CpuTopology> for new::CpuTopology {
             threads_per_core: legacy.threads_per_core,
             numa_nodes: legacy.numa_nodes,
             cpu_model: legacy.cpu_model,
+            ..Default::default()
         }
     }
 }
@@ -235,6 +296,8 @@ impl From<crate::HardwareInfo> for new::HardwareInfo {
             memory: legacy.memory.into(),
             storage: legacy.storage.into(),
             gpus: legacy.gpus.into(),
+            accelerators: Vec::new(),
+            unbound_pci_devices: Vec::new(),
         }
     }
 }
@@ -282,6 +345,10 @@ impl From<crate::MemoryInfo> for new::MemoryInfo {
             type_: legacy.type_,
             speed: legacy.speed,
             modules: legacy.modules.into_iter().map(|m| m.into()).collect(),
+            transparent_hugepages: None,
+            hugepage_pools: Vec::new(),
+            nvdimm_namespaces: Vec::new(),
+            ecc_enabled: None,
         }
     }
 }
@@ -306,6 +373,9 @@ impl From<crate::MemoryModule> for new::MemoryModule {
             location: legacy.location,
             manufacturer: legacy.manufacturer,
             serial: legacy.serial,
+            configured_speed: legacy.configured_speed,
+            rank: None,
+            channel: None,
         }
     }
 }
@@ -319,6 +389,7 @@ impl From<new::MemoryModule> for crate::MemoryModule {
             location: new_mod.location,
             manufacturer: new_mod.manufacturer,
             serial: new_mod.serial,
+            configured_speed: new_mod.configured_speed,
         }
     }
 }
@@ -327,6 +398,7 @@ impl From<crate::StorageInfo> for new::StorageInfo {
     fn from(legacy: crate::StorageInfo) -> Self {
         new::StorageInfo {
             devices: legacy.devices.into_iter().map(|d| d.into()).collect(),
+            enclosures: Vec::new(),
         }
     }
 }
@@ -344,8 +416,11 @@ impl From<crate::StorageDevice> for new::StorageDevice {
         new::StorageDevice {
             name: legacy.name,
             type_: legacy.type_.clone(),
+            size_bytes: crate::ServerInfo::parse_storage_size(&legacy.size).unwrap_or_default(),
             size: legacy.size,
             model: legacy.model,
+            serial_number: legacy.serial,
+            firmware_version: legacy.firmware,
             ..Default::default()
         }
     }
@@ -353,19 +428,33 @@ impl From<crate::StorageDevice> for new::StorageDevice {
 
 impl From<new::StorageDevice> for crate::StorageDevice {
     fn from(new_dev: new::StorageDevice) -> Self {
+        let nvme_controller =
+            crate::domain::nvme_namespace_controller(&new_dev.name).map(str::to_string);
         crate::StorageDevice {
             name: new_dev.name,
             type_: new_dev.type_,
             size: new_dev.size,
             model: new_dev.model,
+            serial: new_dev.serial_number,
+            firmware: new_dev.firmware_version,
+            smart_healthy: None,
+            wearout_percent: new_dev
+                .nvme_health
+                .and_then(|health| health.percentage_used),
+            nvme_controller,
+            controller_model: None,
         }
     }
 }
 
 impl From<crate::GpuInfo> for new::GpuInfo {
     fn from(legacy: crate::GpuInfo) -> Self {
+        let devices: Vec<new::GpuDevice> = legacy.devices.into_iter().map(|d| d.into()).collect();
         new::GpuInfo {
-            devices: legacy.devices.into_iter().map(|d| d.into()).collect(),
+            gpus_homogeneous: crate::domain::are_gpus_homogeneous(&devices),
+            recent_xid_errors: None,
+            nvlink: Vec::new(),
+            devices,
         }
     }
 }
@@ -384,6 +473,9 @@ impl From<crate::GpuDevice> for new::GpuDevice {
             index: legacy.index,
             name: legacy.name,
             uuid: legacy.uuid,
+            memory_total_mb: crate::ServerInfo::parse_storage_size(&legacy.memory)
+                .unwrap_or_default()
+                / (1024 * 1024),
             memory: legacy.memory,
             pci_id: legacy.pci_id,
             vendor: legacy.vendor,
@@ -412,6 +504,7 @@ impl From<crate::NetworkInfo> for new::NetworkInfo {
         new::NetworkInfo {
             interfaces: legacy.interfaces.into_iter().map(|i| i.into()).collect(),
             infiniband: legacy.infiniband.map(|ib| ib.into()),
+            fc_hbas: Vec::new(),
         }
     }
 }
@@ -432,12 +525,17 @@ impl From<crate::NetworkInterface> for new::NetworkInterface {
             mac: legacy.mac,
             ip: legacy.ip,
             prefix: legacy.prefix,
+            speed_mbps: legacy
+                .speed
+                .as_deref()
+                .and_then(crate::domain::parse_link_speed_mbps),
             speed: legacy.speed,
             type_: legacy.type_,
             vendor: legacy.vendor,
             model: legacy.model,
             pci_id: legacy.pci_id,
             numa_node: legacy.numa_node,
+            mtu: legacy.mtu,
             ..Default::default()
         }
     }
@@ -456,6 +554,7 @@ impl From<new::NetworkInterface> for crate::NetworkInterface {
             model: new_iface.model,
             pci_id: new_iface.pci_id,
             numa_node: new_iface.numa_node,
+            mtu: new_iface.mtu,
         }
     }
 }
@@ -483,6 +582,8 @@ impl From<crate::IbInterface> for new::IbInterface {
             port: legacy.port,
             state: legacy.state,
             rate: legacy.rate,
+            link_layer: legacy.link_layer,
+            active_mtu: legacy.active_mtu,
         }
     }
 }
@@ -494,6 +595,8 @@ impl From<new::IbInterface> for crate::IbInterface {
             port: new_ib.port,
             state: new_ib.state,
             rate: new_ib.rate,
+            link_layer: new_ib.link_layer,
+            active_mtu: new_ib.active_mtu,
         }
     }
 }
@@ -502,10 +605,12 @@ impl From<crate::NumaNode> for new::NumaNode {
     fn from(legacy: crate::NumaNode) -> Self {
         new::NumaNode {
             id: legacy.id,
+            cpu_ranges: crate::domain::format_cpu_list(&legacy.cpus),
             cpus: legacy.cpus,
             memory: legacy.memory,
             devices: legacy.devices.into_iter().map(|d| d.into()).collect(),
             distances: legacy.distances,
+            memory_tier: None,
         }
     }
 }
@@ -559,3 +664,63 @@ impl From<new::InterfaceIPs> for crate::InterfaceIPs {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_rated_speed_from_legacy_modules() {
+        let mut legacy = crate::test_support::sample_server_info();
+        legacy.hardware.memory.modules = vec![crate::MemoryModule {
+            size: "64 GB".to_string(),
+            type_: "DDR5".to_string(),
+            speed: "4800 MT/s".to_string(),
+            location: "DIMM_A1".to_string(),
+            manufacturer: "Samsung".to_string(),
+            serial: "S1".to_string(),
+            configured_speed: Some("4400 MT/s".to_string()),
+        }];
+
+        let report = new::HardwareReport::from(legacy);
+        // The sample predates versioning and keeps its format version
+        assert_eq!(report.report_version, 0);
+        assert!(!report.summary.memory_running_at_rated_speed);
+        assert_eq!(
+            report.hardware.memory.modules[0]
+                .configured_speed
+                .as_deref(),
+            Some("4400 MT/s")
+        );
+    }
+
+    #[test]
+    fn test_data_mtu_mismatch_survives_legacy_round_trip() {
+        let mut legacy = crate::test_support::sample_server_info();
+        let interface = legacy.network.interfaces[0].clone();
+        legacy.network.interfaces = [("ens1f0", 9000), ("ens1f1", 1500)]
+            .into_iter()
+            .map(|(name, mtu)| crate::NetworkInterface {
+                name: name.to_string(),
+                mtu,
+                ..interface.clone()
+            })
+            .collect();
+
+        // What `ServerInfo::collect_with` does with a data interface pattern
+        let report = new::HardwareReport::from(legacy.clone());
+        legacy.configuration_deviations = crate::domain::find_configuration_deviations(
+            &report,
+            &new::ConfigurationProfile::default(),
+            Some(&regex::Regex::new("^ens").unwrap()),
+        );
+
+        let reloaded = new::HardwareReport::from(legacy);
+        let mismatch = reloaded
+            .configuration_deviations
+            .iter()
+            .find(|d| d.code == "data_mtu_mismatch")
+            .unwrap();
+        assert_eq!(mismatch.actual, "ens1f0=9000, ens1f1=1500");
+    }
+}
@@ -14,13 +14,17 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod deviations;
 pub mod entities;
 pub mod errors;
+pub mod format;
 pub mod legacy_compat;
 pub mod parsers;
 pub mod services;
 
+pub use deviations::*;
 pub use entities::*;
 pub use errors::*;
+pub use format::*;
 pub use parsers::*;
 pub use services::*;
@@ -0,0 +1,119 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Non-GPU accelerator (FPGA, NPU) parsing functions
+
+use crate::domain::Accelerator;
+
+/// PCI vendors whose non-display, non-network devices are accelerators
+const ACCELERATOR_VENDORS: &[(&str, &str)] = &[
+    ("10ee", "Xilinx"),
+    ("1172", "Intel (Altera)"),
+    ("1da3", "Habana Labs"),
+    ("1de0", "Groq"),
+    ("1e52", "Tenstorrent"),
+];
+
+/// Parse non-GPU accelerators from machine-readable lspci output
+///
+/// Expected command: `lspci -Dnnmm`
+///
+/// A device is an accelerator when its class is "Processing accelerators"
+/// (0x12) or "Co-processor" (0x0b40), or when it comes from a known FPGA/NPU
+/// vendor and is not a display or network device. GPUs stay in the GPU
+/// section.
+///
+/// # Arguments
+///
+/// * `output` - Output from `lspci -Dnnmm`
+///
+/// # Returns
+///
+/// Accelerators in bus order.
+pub fn parse_lspci_accelerators(output: &str) -> Vec<Accelerator> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (address, rest) = line.trim().split_once(' ')?;
+            // Quoted fields are class, vendor, device, then subsystem vendor and device
+            let fields: Vec<&str> = rest.split('"').skip(1).step_by(2).collect();
+            let (_, class_id) = split_lspci_id(fields.first()?)?;
+            let (vendor_name, vendor_id) = split_lspci_id(fields.get(1)?)?;
+            let (model, _) = split_lspci_id(fields.get(2)?)?;
+
+            let known_vendor = ACCELERATOR_VENDORS
+                .iter()
+                .find(|(id, _)| *id == vendor_id)
+                .map(|(_, name)| *name);
+            let accelerator_class = class_id.starts_with("12") || class_id == "0b40";
+            let excluded_class = class_id.starts_with("02") || class_id.starts_with("03");
+            if !(accelerator_class || (known_vendor.is_some() && !excluded_class)) {
+                return None;
+            }
+
+            Some(Accelerator {
+                vendor: known_vendor.unwrap_or(vendor_name).to_string(),
+                model: model.to_string(),
+                pci_address: address.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Split an lspci name with a bracketed ID (e.g., "Xilinx Corporation [10ee]")
+fn split_lspci_id(field: &str) -> Option<(&str, &str)> {
+    let (name, id) = field.trim().rsplit_once(" [")?;
+    Some((name.trim(), id.strip_suffix(']')?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lspci_accelerators() {
+        let output = r#"0000:00:00.0 "Host bridge [0600]" "Intel Corporation [8086]" "Sky Lake-E DMI3 Registers [2020]" -r07 "Intel Corporation [8086]" "Device [0000]"
+0000:18:00.0 "3D controller [0302]" "NVIDIA Corporation [10de]" "GH100 [H100 SXM5 80GB] [2330]" -ra1 "NVIDIA Corporation [10de]" "Device [16c1]"
+0000:3b:00.0 "Processing accelerators [1200]" "Xilinx Corporation [10ee]" "Alveo U250 XDMA Platform [5004]" "Xilinx Corporation [10ee]" "Device [000e]"
+0000:3b:00.1 "Processing accelerators [1200]" "Xilinx Corporation [10ee]" "Device [5005]" "Xilinx Corporation [10ee]" "Device [000e]"
+0000:5e:00.0 "Ethernet controller [0200]" "Mellanox Technologies [15b3]" "MT2910 Family [ConnectX-7] [1021]" "Mellanox Technologies [15b3]" "Device [0041]""#;
+
+        let accelerators = parse_lspci_accelerators(output);
+
+        assert_eq!(accelerators.len(), 2);
+        assert_eq!(
+            accelerators[0],
+            Accelerator {
+                vendor: "Xilinx".to_string(),
+                model: "Alveo U250 XDMA Platform".to_string(),
+                pci_address: "0000:3b:00.0".to_string(),
+            }
+        );
+        assert_eq!(accelerators[1].model, "Device");
+    }
+
+    #[test]
+    fn test_parse_lspci_accelerators_by_class() {
+        // Unlisted vendors are still detected by the processing accelerator class
+        let output = r#"0000:a0:1e.0 "Processing accelerators [1200]" "Amazon.com, Inc. [1d0f]" "NeuronDevice (Inferentia2) [7264]" "Amazon.com, Inc. [1d0f]" "Device [0000]""#;
+
+        let accelerators = parse_lspci_accelerators(output);
+
+        assert_eq!(accelerators.len(), 1);
+        assert_eq!(accelerators[0].vendor, "Amazon.com, Inc.");
+        assert_eq!(accelerators[0].model, "NeuronDevice (Inferentia2)");
+    }
+}
@@ -0,0 +1,219 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! BIOS setting parsing and normalization functions
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref SUM_SETTING_RE: Regex =
+        Regex::new(r#"<Setting\s+name="([^"]+)"\s+selectedOption="([^"]*)""#).unwrap();
+}
+
+/// Vendor BIOS attribute names and the normalized key each maps to
+///
+/// Names are compared case-insensitively. Covers Dell (racadm/Redfish), HPE
+/// (ilorest/Redfish) and Supermicro (sum) on Intel and AMD platforms.
+const BIOS_SETTING_ALIASES: &[(&str, &str)] = &[
+    // Power profile
+    ("SysProfile", "power_profile"),
+    ("WorkloadProfile", "power_profile"),
+    ("PowerRegulator", "power_profile"),
+    ("Power Technology", "power_profile"),
+    ("Power Profile Selection", "power_profile"),
+    // C-states
+    ("ProcCStates", "c_states"),
+    ("MinProcIdlePower", "c_states"),
+    ("Global C-state Control", "c_states"),
+    ("Enhanced Halt State (C1E)", "c_states"),
+    // NUMA nodes per socket
+    ("NumaNodesPerSocket", "numa_per_socket"),
+    ("SubNumaCluster", "numa_per_socket"),
+    ("NumaMemoryDomainsPerSocket", "numa_per_socket"),
+    ("NUMA nodes per socket", "numa_per_socket"),
+    ("SNC", "numa_per_socket"),
+    // SMT
+    ("LogicalProc", "smt"),
+    ("ProcHyperthreading", "smt"),
+    ("ProcSMT", "smt"),
+    ("Hyper-Threading", "smt"),
+    ("SMT Control", "smt"),
+    // AMD determinism slider
+    ("DeterminismSlider", "determinism_slider"),
+    ("DeterminismControl", "determinism_slider"),
+    ("Determinism Slider", "determinism_slider"),
+    ("Determinism Control", "determinism_slider"),
+];
+
+/// Map vendor-specific BIOS attributes to normalized setting names
+///
+/// # Arguments
+///
+/// * `attributes` - Raw attribute name/value pairs from a vendor tool
+///
+/// # Returns
+///
+/// Settings keyed by normalized name (`power_profile`, `c_states`,
+/// `numa_per_socket`, `smt`, `determinism_slider`). Unrecognized attributes
+/// are dropped; when several attributes map to one key the first listed in
+/// the alias table wins.
+pub fn normalize_bios_settings(attributes: &HashMap<String, String>) -> HashMap<String, String> {
+    let by_lower_name: HashMap<String, &String> = attributes
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value))
+        .collect();
+
+    let mut settings = HashMap::new();
+    for (vendor_name, key) in BIOS_SETTING_ALIASES {
+        if settings.contains_key(*key) {
+            continue;
+        }
+        if let Some(value) = by_lower_name.get(&vendor_name.to_lowercase()) {
+            settings.insert(key.to_string(), value.to_string());
+        }
+    }
+    settings
+}
+
+/// Parse `racadm get BIOS.<group>` output
+///
+/// # Arguments
+///
+/// * `output` - Output with `Name=Value` lines; read-only attributes are prefixed with `#`
+///
+/// # Returns
+///
+/// Raw attribute name/value pairs.
+pub fn parse_racadm_bios_attributes(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('['))
+        .filter_map(|line| line.trim_start_matches('#').split_once('='))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// Parse a Supermicro `sum -c GetCurrentBiosCfg` XML dump
+///
+/// # Arguments
+///
+/// * `output` - BIOS configuration XML with `<Setting name=".." selectedOption="..">` elements
+///
+/// # Returns
+///
+/// Raw setting name/selected option pairs.
+pub fn parse_sum_bios_config(output: &str) -> HashMap<String, String> {
+    SUM_SETTING_RE
+        .captures_iter(output)
+        .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+        .collect()
+}
+
+/// Parse BIOS attributes from Redfish or `ilorest` JSON
+///
+/// # Arguments
+///
+/// * `output` - JSON object of attributes, optionally nested under `Attributes`
+///
+/// # Returns
+///
+/// Raw attribute name/value pairs; non-scalar values are skipped.
+pub fn parse_bios_attributes_json(output: &str) -> Result<HashMap<String, String>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid BIOS attribute JSON: {e}"))?;
+    let attributes = value.get("Attributes").unwrap_or(&value);
+    let object = attributes
+        .as_object()
+        .ok_or_else(|| "BIOS attributes are not a JSON object".to_string())?;
+
+    Ok(object
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some((name.clone(), value))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_dell_racadm_attributes() {
+        let output = "\
+[Key=BIOS.Setup.1-1#SysProfileSettings]
+SysProfile=PerfOptimized
+ProcCStates=Disabled
+#ProcPwrPerf=MaxPerf
+[Key=BIOS.Setup.1-1#ProcSettings]
+LogicalProc=Enabled
+NumaNodesPerSocket=4
+DeterminismSlider=PowerDeterminism
+";
+
+        let settings = normalize_bios_settings(&parse_racadm_bios_attributes(output));
+
+        assert_eq!(settings["power_profile"], "PerfOptimized");
+        assert_eq!(settings["c_states"], "Disabled");
+        assert_eq!(settings["smt"], "Enabled");
+        assert_eq!(settings["numa_per_socket"], "4");
+        assert_eq!(settings["determinism_slider"], "PowerDeterminism");
+        assert_eq!(settings.len(), 5);
+    }
+
+    #[test]
+    fn test_normalize_supermicro_sum_attributes() {
+        let output = r#"<?xml version="1.0" encoding="ISO-8859-1" standalone="yes"?>
+<BiosCfg>
+  <Menu name="Advanced">
+    <Menu name="CPU Configuration">
+      <Setting name="SMT Control" selectedOption="Disable" type="Option">
+        <Information>
+          <AvailableOptions>
+            <Option value="0">Auto</Option>
+            <Option value="1">Disable</Option>
+          </AvailableOptions>
+        </Information>
+      </Setting>
+      <Setting name="Global C-state Control" selectedOption="Enabled" type="Option"/>
+      <Setting name="NUMA nodes per socket" selectedOption="NPS1" type="Option"/>
+      <Setting name="Determinism Control" selectedOption="Manual" type="Option"/>
+      <Setting name="Determinism Slider" selectedOption="Performance" type="Option"/>
+    </Menu>
+  </Menu>
+</BiosCfg>
+"#;
+
+        let settings = normalize_bios_settings(&parse_sum_bios_config(output));
+
+        assert_eq!(settings["smt"], "Disable");
+        assert_eq!(settings["c_states"], "Enabled");
+        assert_eq!(settings["numa_per_socket"], "NPS1");
+        // The slider itself takes precedence over its control toggle
+        assert_eq!(settings["determinism_slider"], "Performance");
+        assert!(!settings.contains_key("power_profile"));
+    }
+}
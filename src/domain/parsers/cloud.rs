@@ -0,0 +1,135 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Cloud instance metadata parsing functions
+
+use crate::domain::CloudInfo;
+use serde_json::Value;
+
+/// Parse an AWS EC2 instance identity document
+///
+/// # Arguments
+///
+/// * `document` - JSON from `/latest/dynamic/instance-identity/document`
+///
+/// # Returns
+///
+/// Cloud info with provider "aws".
+pub fn parse_ec2_identity_document(document: &str) -> Result<CloudInfo, String> {
+    let value: Value = serde_json::from_str(document)
+        .map_err(|e| format!("Invalid EC2 identity document: {e}"))?;
+
+    Ok(CloudInfo {
+        provider: "aws".to_string(),
+        instance_type: json_string(&value, "instanceType"),
+        region: json_string(&value, "region"),
+        availability_zone: json_string(&value, "availabilityZone"),
+    })
+}
+
+/// Parse GCP instance metadata
+///
+/// # Arguments
+///
+/// * `metadata` - JSON from `/computeMetadata/v1/instance/?recursive=true`
+///
+/// # Returns
+///
+/// Cloud info with provider "gcp". The region is derived from the zone
+/// (e.g., "us-central1-a" is in "us-central1").
+pub fn parse_gcp_instance_metadata(metadata: &str) -> Result<CloudInfo, String> {
+    let value: Value =
+        serde_json::from_str(metadata).map_err(|e| format!("Invalid GCP metadata: {e}"))?;
+
+    // Both fields are resource paths such as "projects/123/zones/us-central1-a"
+    let last_segment = |key: &str| {
+        json_string(&value, key).and_then(|path| path.rsplit('/').next().map(str::to_string))
+    };
+    let availability_zone = last_segment("zone");
+    let region = availability_zone
+        .as_deref()
+        .and_then(|zone| zone.rsplit_once('-'))
+        .map(|(region, _)| region.to_string());
+
+    Ok(CloudInfo {
+        provider: "gcp".to_string(),
+        instance_type: last_segment("machineType"),
+        region,
+        availability_zone,
+    })
+}
+
+/// Parse Azure instance metadata
+///
+/// # Arguments
+///
+/// * `metadata` - JSON from `/metadata/instance?api-version=2021-02-01`
+///
+/// # Returns
+///
+/// Cloud info with provider "azure".
+pub fn parse_azure_instance_metadata(metadata: &str) -> Result<CloudInfo, String> {
+    let value: Value =
+        serde_json::from_str(metadata).map_err(|e| format!("Invalid Azure metadata: {e}"))?;
+    let compute = value
+        .get("compute")
+        .ok_or_else(|| "Azure metadata has no compute section".to_string())?;
+
+    Ok(CloudInfo {
+        provider: "azure".to_string(),
+        instance_type: json_string(compute, "vmSize"),
+        region: json_string(compute, "location"),
+        availability_zone: json_string(compute, "zone"),
+    })
+}
+
+/// Read a non-empty string field from a JSON object
+fn json_string(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gcp_instance_metadata() {
+        let metadata = r#"{"machineType": "projects/123/machineTypes/a3-highgpu-8g", "zone": "projects/123/zones/us-central1-a"}"#;
+
+        let cloud = parse_gcp_instance_metadata(metadata).unwrap();
+
+        assert_eq!(cloud.provider, "gcp");
+        assert_eq!(cloud.instance_type.as_deref(), Some("a3-highgpu-8g"));
+        assert_eq!(cloud.region.as_deref(), Some("us-central1"));
+        assert_eq!(cloud.availability_zone.as_deref(), Some("us-central1-a"));
+    }
+
+    #[test]
+    fn test_parse_azure_instance_metadata() {
+        let metadata =
+            r#"{"compute": {"vmSize": "Standard_ND96asr_v4", "location": "eastus", "zone": ""}}"#;
+
+        let cloud = parse_azure_instance_metadata(metadata).unwrap();
+
+        assert_eq!(cloud.instance_type.as_deref(), Some("Standard_ND96asr_v4"));
+        assert_eq!(cloud.region.as_deref(), Some("eastus"));
+        assert_eq!(cloud.availability_zone, None);
+    }
+}
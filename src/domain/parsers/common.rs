@@ -34,7 +34,7 @@ lazy_static! {
 /// * `Ok(u64)` - Size in bytes
 /// * `Err(String)` - Parse error description
 pub fn parse_size_to_bytes(size_str: &str) -> Result<u64, String> {
-    if size_str.trim().is_empty() || size_str == "Unknown" {
+    if is_unknown(size_str) {
         return Ok(0);
     }
 
@@ -116,6 +116,41 @@ pub fn parse_key_value(line: &str, separator: char) -> Result<(String, String),
     }
 }
 
+/// Marker for a value that could not be determined
+pub const UNKNOWN: &str = "Unknown";
+
+/// Placeholders that firmware and tools print instead of a real value
+const UNKNOWN_PLACEHOLDERS: [&str; 10] = [
+    "unknown",
+    "n/a",
+    "not specified",
+    "not available",
+    "not provided",
+    "none",
+    "default string",
+    "to be filled by o.e.m.",
+    "0123456789",
+    "00000000-0000-0000-0000-000000000000",
+];
+
+/// Check whether a value is missing or a placeholder
+///
+/// Recognizes [`UNKNOWN`], empty strings, the prefixed markers older reports
+/// used ("Unknown Product", "Unknown S/N", ...), "N/A" and DMI placeholders
+/// such as "To Be Filled By O.E.M.".
+///
+/// # Arguments
+/// * `value` - Value to check
+///
+/// # Returns
+/// * `true` if the value carries no information
+pub fn is_unknown(value: &str) -> bool {
+    let value = value.trim().to_lowercase();
+    value.is_empty()
+        || value.starts_with("unknown ")
+        || UNKNOWN_PLACEHOLDERS.contains(&value.as_str())
+}
+
 /// Clean and normalize a string value
 ///
 /// # Arguments
@@ -147,40 +182,174 @@ pub fn parse_boolean(value: &str) -> Result<bool, String> {
     }
 }
 
-/// Convert bytes to human-readable format
+/// Extract a version number from a tool's version output
+///
+/// # Arguments
+/// * `output` - Output of a command run with its version flag
+///
+/// # Returns
+/// * `Some(String)` - First version-like token (e.g., "3.3", "550.54.15")
+/// * `None` - No version found
+pub fn parse_tool_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ':' || c == ','))
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            token.starts_with(|c: char| c.is_ascii_digit())
+                && token.contains('.')
+                && token
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        })
+        .map(|token| token.to_string())
+}
+
+/// Parse a PCIe AER error counter file
+///
+/// Files list one counter per line (e.g., "RxErr 0") and end with a total
+/// line such as "TOTAL_ERR_COR 2".
 ///
 /// # Arguments
-/// * `bytes` - Number of bytes
+/// * `content` - Content of `/sys/bus/pci/devices/{addr}/aer_dev_*`
 ///
 /// # Returns
-/// * Human-readable string (e.g., "16.0 GB", "2.5 TB")
-pub fn bytes_to_human_readable(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    const THRESHOLD: u64 = 1024;
+/// * `Some(u64)` - Total error count
+/// * `None` - No counters found
+pub fn parse_aer_counters(content: &str) -> Option<u64> {
+    let counters: Vec<(&str, u64)> = content
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.trim().split_once(char::is_whitespace)?;
+            Some((name, value.trim().parse().ok()?))
+        })
+        .collect();
 
-    if bytes == 0 {
-        return "0 B".to_string();
+    if counters.is_empty() {
+        return None;
     }
 
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
+    counters
+        .iter()
+        .find(|(name, _)| name.starts_with("TOTAL_ERR"))
+        .map(|(_, total)| *total)
+        .or_else(|| Some(counters.iter().map(|(_, count)| count).sum()))
+}
 
-    while size >= THRESHOLD as f64 && unit_index < UNITS.len() - 1 {
-        size /= THRESHOLD as f64;
-        unit_index += 1;
+/// Normalize a PCI address to the sysfs form
+///
+/// # Arguments
+/// * `address` - PCI address, possibly with an 8-digit domain ("00000000:18:00.0")
+///
+/// # Returns
+/// * Address with a 4-digit lowercase domain (e.g., "0000:18:00.0")
+pub fn normalize_pci_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    match address.split_once(':') {
+        Some((domain, rest)) if domain.len() > 4 => {
+            format!("{}:{}", &domain[domain.len() - 4..], rest)
+        }
+        _ => address,
     }
+}
 
-    if unit_index == 0 {
-        format!("{} {}", bytes, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
+/// Map a `dmidecode -t` argument to the DMI structure types it selects
+///
+/// # Arguments
+/// * `type_arg` - Type keyword (e.g., "bios", "memory") or a numeric type
+///
+/// # Returns
+/// * DMI type numbers, empty for an unrecognized keyword
+pub fn dmidecode_types_for_keyword(type_arg: &str) -> Vec<u8> {
+    match type_arg {
+        "bios" => vec![0, 13],
+        "system" => vec![1, 12, 15, 23, 32],
+        "baseboard" => vec![2, 10, 41],
+        "chassis" => vec![3],
+        "processor" => vec![4],
+        "memory" => vec![5, 6, 16, 17],
+        "cache" => vec![7],
+        "connector" => vec![8],
+        "slot" => vec![9],
+        other => other
+            .split(',')
+            .filter_map(|t| t.trim().parse().ok())
+            .collect(),
+    }
+}
+
+/// Extract the sections of the given DMI types from a full dmidecode dump
+///
+/// Sections start at a "Handle 0x..., DMI type N, M bytes" line and run
+/// until the next blank line.
+///
+/// # Arguments
+/// * `dump` - Full `dmidecode` output
+/// * `types` - DMI type numbers to keep
+///
+/// # Returns
+/// * Matching sections in dump order, as `dmidecode -t` would print them
+pub fn filter_dmidecode_sections(dump: &str, types: &[u8]) -> String {
+    let mut output = String::new();
+    let mut keep = false;
+
+    for line in dump.lines() {
+        if let Some(header) = line.strip_prefix("Handle ") {
+            keep = header
+                .split(", ")
+                .find_map(|part| part.strip_prefix("DMI type "))
+                .and_then(|t| t.trim().parse::<u8>().ok())
+                .is_some_and(|t| types.contains(&t));
+        } else if line.trim().is_empty() {
+            if keep {
+                output.push('\n');
+            }
+            keep = false;
+            continue;
+        }
+
+        if keep {
+            output.push_str(line);
+            output.push('\n');
+        }
     }
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_unknown_recognizes_all_markers() {
+        for marker in [
+            UNKNOWN,
+            "",
+            "  ",
+            "unknown",
+            "Unknown CPU",
+            "Unknown Product",
+            "Unknown Version",
+            "Unknown S/N",
+            "Unknown Manufacturer",
+            "Unknown Type",
+            "Unknown Vendor",
+            "Unknown Date",
+            "N/A",
+            "Not Specified",
+            "Not Provided",
+            "To Be Filled By O.E.M.",
+            "Default string",
+            "00000000-0000-0000-0000-000000000000",
+        ] {
+            assert!(is_unknown(marker), "{marker:?} should be unknown");
+        }
+
+        for value in ["PowerEdge R760", "J4X2K93", "Unknownium", "DDR5"] {
+            assert!(!is_unknown(value), "{value:?} should be known");
+        }
+    }
+
     #[test]
     fn test_parse_size_to_bytes() {
         assert_eq!(
@@ -233,10 +402,50 @@ mod tests {
     }
 
     #[test]
-    fn test_bytes_to_human_readable() {
-        assert_eq!(bytes_to_human_readable(0), "0 B");
-        assert_eq!(bytes_to_human_readable(512), "512 B");
-        assert_eq!(bytes_to_human_readable(1024), "1.0 KB");
-        assert_eq!(bytes_to_human_readable(16 * 1024 * 1024 * 1024), "16.0 GB");
+    fn test_parse_tool_version() {
+        assert_eq!(parse_tool_version("3.3\n"), Some("3.3".to_string()));
+        assert_eq!(
+            parse_tool_version("lspci version 3.7.0\n"),
+            Some("3.7.0".to_string())
+        );
+        assert_eq!(
+            parse_tool_version("ipmitool version 1.8.18\n"),
+            Some("1.8.18".to_string())
+        );
+        let nvidia_smi = "NVIDIA-SMI version  : 550.54.15\n\
+                          NVML version        : 550.54\n\
+                          DRIVER version      : 550.54.15\n\
+                          CUDA Version        : 12.4\n";
+        assert_eq!(
+            parse_tool_version(nvidia_smi),
+            Some("550.54.15".to_string())
+        );
+        assert_eq!(parse_tool_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_parse_aer_counters() {
+        let correctable = "RxErr 0\nBadTLP 3\nBadDLLP 1\nRollover 0\nTimeout 0\nNonFatalErr 0\nCorrIntErr 0\nHeaderOF 0\nTOTAL_ERR_COR 4\n";
+        assert_eq!(parse_aer_counters(correctable), Some(4));
+
+        let fatal = "Undefined 0\nDLP 0\nSDES 0\nTLP 0\nFCP 0\nCmpltTO 0\nCmpltAbrt 0\nUnxCmplt 0\nRxOF 0\nMalfTLP 1\nECRC 0\nUnsupReq 0\nACSViol 0\nUncorrIntErr 0\nBlockedTLP 0\nAtomicOpBlocked 0\nTLPBlockedErr 0\nPoisonTLPBlocked 0\nTOTAL_ERR_FATAL 1\n";
+        assert_eq!(parse_aer_counters(fatal), Some(1));
+
+        // Older kernels omit the total line
+        assert_eq!(parse_aer_counters("RxErr 2\nBadTLP 1\n"), Some(3));
+        assert_eq!(parse_aer_counters(""), None);
+    }
+
+    #[test]
+    fn test_normalize_pci_address() {
+        assert_eq!(normalize_pci_address("00000000:1A:00.0"), "0000:1a:00.0");
+        assert_eq!(normalize_pci_address("0000:3b:00.0"), "0000:3b:00.0");
+    }
+
+    #[test]
+    fn test_dmidecode_types_for_keyword() {
+        assert_eq!(dmidecode_types_for_keyword("chassis"), vec![3]);
+        assert_eq!(dmidecode_types_for_keyword("2"), vec![2]);
+        assert!(dmidecode_types_for_keyword("unknown").is_empty());
     }
 }
@@ -16,10 +16,12 @@ limitations under the License.
 
 //! CPU information parsing functions
 
-use super::common::{clean_value, extract_dmidecode_value, parse_key_value};
-use crate::domain::{CpuInfo, CpuTopology};
+use super::common::{clean_value, extract_dmidecode_value, is_unknown, parse_key_value, UNKNOWN};
+use super::numa::parse_cpu_list;
+use crate::domain::{CpuInfo, CpuLocation, CpuTopology, LogicalCpu, NumaNode};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 
 lazy_static! {
     static ref CPU_SPEED_RE: Regex = Regex::new(r"(\d+(?:\.\d+)?)\s*(MHz|GHz)").unwrap();
@@ -91,25 +93,21 @@ pub fn parse_proc_cpuinfo(content: &str) -> Result<CpuInfo, String> {
 
             match key {
                 "vendor_id" => cpu_info.vendor = value.to_string(),
-                "model name" => {
-                    if cpu_info.model.is_empty() {
-                        cpu_info.model = value.to_string();
-                    }
+                "model name" if cpu_info.model.is_empty() => {
+                    cpu_info.model = value.to_string();
                 }
                 "flags" | "Features" => {
                     cpu_info.flags = value.split_whitespace().map(|s| s.to_string()).collect();
                 }
-                "CPU implementer" => {
+                "CPU implementer" if cpu_info.vendor.is_empty() => {
                     // ARM CPU implementer code
-                    if cpu_info.vendor.is_empty() {
-                        cpu_info.vendor = match value {
-                            "0x41" => "ARM".to_string(),
-                            "0x4e" => "NVIDIA".to_string(),
-                            "0x51" => "Qualcomm".to_string(),
-                            "0x61" => "Apple".to_string(),
-                            _ => value.to_string(),
-                        };
-                    }
+                    cpu_info.vendor = match value {
+                        "0x41" => "ARM".to_string(),
+                        "0x4e" => "NVIDIA".to_string(),
+                        "0x51" => "Qualcomm".to_string(),
+                        "0x61" => "Apple".to_string(),
+                        _ => value.to_string(),
+                    };
                 }
                 _ => {}
             }
@@ -128,11 +126,11 @@ pub fn parse_proc_cpuinfo(content: &str) -> Result<CpuInfo, String> {
 /// * `Ok(CpuInfo)` - Parsed CPU information
 /// * `Err(String)` - Parse error description
 pub fn parse_lscpu_output(lscpu_output: &str) -> Result<CpuInfo, String> {
-    let mut model = "Unknown CPU".to_string();
+    let mut model = UNKNOWN.to_string();
     let mut cores = 1u32;
     let mut threads = 1u32;
     let mut sockets = 1u32;
-    let mut speed = "Unknown".to_string();
+    let mut speed = UNKNOWN.to_string();
 
     for line in lscpu_output.lines() {
         if let Ok((key, value)) = parse_key_value(line, ':') {
@@ -190,11 +188,11 @@ pub fn parse_lscpu_output(lscpu_output: &str) -> Result<CpuInfo, String> {
 /// * `Err(String)` - Parse error description
 pub fn parse_dmidecode_cpu(dmidecode_output: &str) -> Result<CpuInfo, String> {
     let model = extract_dmidecode_value(dmidecode_output, "Version")
-        .unwrap_or_else(|_| "Unknown CPU".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
 
     let speed = extract_dmidecode_value(dmidecode_output, "Current Speed")
         .or_else(|_| extract_dmidecode_value(dmidecode_output, "Max Speed"))
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
 
     let core_count_str =
         extract_dmidecode_value(dmidecode_output, "Core Count").unwrap_or_else(|_| "1".to_string());
@@ -223,9 +221,9 @@ pub fn parse_dmidecode_cpu(dmidecode_output: &str) -> Result<CpuInfo, String> {
 /// * `Ok(CpuInfo)` - Parsed CPU information
 /// * `Err(String)` - Parse error description
 pub fn parse_macos_cpu_info(system_profiler_output: &str) -> Result<CpuInfo, String> {
-    let mut model = "Unknown CPU".to_string();
+    let mut model = UNKNOWN.to_string();
     let mut cores = 1u32;
-    let mut speed = "Unknown".to_string();
+    let mut speed = UNKNOWN.to_string();
 
     for line in system_profiler_output.lines() {
         let trimmed = line.trim();
@@ -234,7 +232,7 @@ pub fn parse_macos_cpu_info(system_profiler_output: &str) -> Result<CpuInfo, Str
             model = trimmed
                 .split(':')
                 .nth(1)
-                .unwrap_or("Unknown CPU")
+                .unwrap_or(UNKNOWN)
                 .trim()
                 .to_string();
         } else if trimmed.starts_with("Processor Name:") {
@@ -242,7 +240,7 @@ pub fn parse_macos_cpu_info(system_profiler_output: &str) -> Result<CpuInfo, Str
             model = trimmed
                 .split(':')
                 .nth(1)
-                .unwrap_or("Unknown CPU")
+                .unwrap_or(UNKNOWN)
                 .trim()
                 .to_string();
         } else if trimmed.starts_with("Total Number of Cores:") {
@@ -258,7 +256,7 @@ pub fn parse_macos_cpu_info(system_profiler_output: &str) -> Result<CpuInfo, Str
             speed = trimmed
                 .split(':')
                 .nth(1)
-                .unwrap_or("Unknown")
+                .unwrap_or(UNKNOWN)
                 .trim()
                 .to_string();
         }
@@ -284,7 +282,7 @@ pub fn parse_macos_cpu_info(system_profiler_output: &str) -> Result<CpuInfo, Str
 /// * Combined and enhanced CPU information
 pub fn combine_cpu_info(primary: CpuInfo, secondary: CpuInfo) -> CpuInfo {
     CpuInfo {
-        model: if primary.model != "Unknown CPU" && !primary.model.is_empty() {
+        model: if !is_unknown(&primary.model) {
             primary.model
         } else {
             secondary.model
@@ -304,7 +302,7 @@ pub fn combine_cpu_info(primary: CpuInfo, secondary: CpuInfo) -> CpuInfo {
         } else {
             secondary.sockets
         },
-        speed: if primary.speed != "Unknown" && !primary.speed.is_empty() {
+        speed: if !is_unknown(&primary.speed) {
             primary.speed
         } else {
             secondary.speed
@@ -313,6 +311,213 @@ pub fn combine_cpu_info(primary: CpuInfo, secondary: CpuInfo) -> CpuInfo {
     }
 }
 
+/// Parse a CPU list parameter from the kernel command line
+///
+/// Flags preceding the list (e.g., `isolcpus=domain,managed_irq,2-7`) are
+/// skipped.
+///
+/// # Arguments
+/// * `cmdline` - Content of `/proc/cmdline`
+/// * `param` - Parameter name (e.g., "isolcpus", "nohz_full")
+///
+/// # Returns
+/// * Expanded, sorted list of CPU IDs (empty if the parameter is absent)
+pub fn parse_cmdline_cpu_list(cmdline: &str, param: &str) -> Vec<u32> {
+    let prefix = format!("{param}=");
+    let mut cpus: Vec<u32> = cmdline
+        .split_whitespace()
+        .filter_map(|arg| arg.strip_prefix(&prefix))
+        .flat_map(|value| {
+            let list = value
+                .split(',')
+                .filter(|item| item.starts_with(|c: char| c.is_ascii_digit()))
+                .collect::<Vec<_>>()
+                .join(",");
+            parse_cpu_list(&list).unwrap_or_default()
+        })
+        .collect();
+
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Determine whether CPU turbo/boost is enabled
+///
+/// # Arguments
+/// * `intel_no_turbo` - Content of `intel_pstate/no_turbo`, if present
+/// * `cpufreq_boost` - Content of `cpufreq/boost`, if present
+///
+/// # Returns
+/// * `Some(bool)` - Turbo state (`no_turbo` is inverted)
+/// * `None` - Neither control is available
+pub fn parse_turbo_enabled(
+    intel_no_turbo: Option<&str>,
+    cpufreq_boost: Option<&str>,
+) -> Option<bool> {
+    match (intel_no_turbo, cpufreq_boost) {
+        (Some(no_turbo), _) => parse_sysfs_flag(no_turbo).map(|disabled| !disabled),
+        (None, Some(boost)) => parse_sysfs_flag(boost),
+        (None, None) => None,
+    }
+}
+
+/// Parse the SMT control state
+///
+/// "off" means SMT was disabled at runtime or via `nosmt`, "forceoff" that it
+/// cannot be re-enabled, and "notsupported" that the CPU or firmware (BIOS)
+/// does not provide SMT.
+///
+/// # Arguments
+/// * `content` - Content of `/sys/devices/system/cpu/smt/control`
+///
+/// # Returns
+/// * `Some(String)` - Recognized control state
+/// * `None` - Unrecognized content
+pub fn parse_smt_control(content: &str) -> Option<String> {
+    match content.trim() {
+        state @ ("on" | "off" | "forceoff" | "notsupported" | "notimplemented") => {
+            Some(state.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Parse the SMT active flag
+///
+/// # Arguments
+/// * `content` - Content of `/sys/devices/system/cpu/smt/active`
+pub fn parse_smt_active(content: &str) -> Option<bool> {
+    parse_sysfs_flag(content)
+}
+
+/// Parse a cpufreq or cpuidle driver (or cpufreq governor) name
+///
+/// # Arguments
+/// * `content` - Content of `cpu0/cpufreq/scaling_driver`, `cpu0/cpufreq/scaling_governor`
+///   or `cpuidle/current_driver`
+///
+/// # Returns
+/// * `Some(String)` - Driver or governor name
+/// * `None` - Empty or "none" (no driver bound)
+pub fn parse_cpu_driver(content: &str) -> Option<String> {
+    match content.trim() {
+        "" | "none" => None,
+        driver => Some(driver.to_string()),
+    }
+}
+
+/// Parse the aggregate `cpu` line of `/proc/stat`
+///
+/// # Arguments
+/// * `content` - Content of `/proc/stat`
+///
+/// # Returns
+/// * `Some((total, steal))` - Jiffies across all states and in the steal state
+/// * `None` - No aggregate line, or a kernel without the steal column
+pub fn parse_proc_stat_cpu(content: &str) -> Option<(u64, u64)> {
+    let line = content.lines().find(|line| line.starts_with("cpu "))?;
+    // user nice system idle iowait irq softirq steal guest guest_nice
+    let values: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|value| value.parse().ok())
+        .collect();
+    let steal = *values.get(7)?;
+    // guest time is already included in user and nice
+    let total = values.iter().take(8).sum();
+    Some((total, steal))
+}
+
+/// Percentage of CPU time stolen by the hypervisor between two samples
+///
+/// # Arguments
+/// * `first` - `(total, steal)` from [`parse_proc_stat_cpu`]
+/// * `second` - A later sample
+///
+/// # Returns
+/// * `None` when no time elapsed between the samples
+pub fn cpu_steal_percent(first: (u64, u64), second: (u64, u64)) -> Option<f64> {
+    let total = second.0.checked_sub(first.0).filter(|total| *total > 0)?;
+    let steal = second.1.saturating_sub(first.1);
+    Some(steal as f64 * 100.0 / total as f64)
+}
+
+/// Build a logical CPU -> physical location map
+///
+/// # Arguments
+/// * `logical_cpus` - Core and socket of each logical CPU
+/// * `numa_topology` - NUMA nodes with their CPU lists
+///
+/// # Returns
+/// * Location of each logical CPU, keyed and ordered by CPU number
+pub fn build_cpu_affinity_map(
+    logical_cpus: &[LogicalCpu],
+    numa_topology: &HashMap<String, NumaNode>,
+) -> BTreeMap<u32, CpuLocation> {
+    let numa_node_of: HashMap<u32, i32> = numa_topology
+        .values()
+        .flat_map(|node| node.cpus.iter().map(move |cpu| (*cpu, node.id)))
+        .collect();
+
+    logical_cpus
+        .iter()
+        .map(|logical| {
+            let thread_sibling = logical_cpus
+                .iter()
+                .filter(|other| {
+                    other.cpu != logical.cpu
+                        && other.core == logical.core
+                        && other.socket == logical.socket
+                })
+                .map(|other| other.cpu)
+                .min();
+            (
+                logical.cpu,
+                CpuLocation {
+                    core: logical.core,
+                    socket: logical.socket,
+                    numa_node: numa_node_of.get(&logical.cpu).copied(),
+                    thread_sibling,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Parse a sysfs "0"/"1" flag
+fn parse_sysfs_flag(content: &str) -> Option<bool> {
+    match content.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Calculate the number of CPUs available for general scheduling
+///
+/// # Arguments
+/// * `online_cpus` - Online logical CPUs (empty if unknown)
+/// * `isolated_cpus` - CPUs isolated from the general scheduler
+/// * `total_threads` - Total logical CPUs, used when the online list is unknown
+///
+/// # Returns
+/// * Online CPUs minus isolated CPUs
+pub fn calculate_schedulable_cores(
+    online_cpus: &[u32],
+    isolated_cpus: &[u32],
+    total_threads: u32,
+) -> u32 {
+    if online_cpus.is_empty() {
+        return total_threads.saturating_sub(isolated_cpus.len() as u32);
+    }
+
+    online_cpus
+        .iter()
+        .filter(|cpu| !isolated_cpus.contains(cpu))
+        .count() as u32
+}
+
 /// Create CPU topology from CPU info
 ///
 /// # Arguments
@@ -322,17 +527,31 @@ pub fn combine_cpu_info(primary: CpuInfo, secondary: CpuInfo) -> CpuInfo {
 /// # Returns
 /// * CPU topology information
 pub fn create_cpu_topology(cpu_info: &CpuInfo, numa_nodes: Option<u32>) -> CpuTopology {
-    let total_cores = cpu_info.cores * cpu_info.sockets;
-    let total_threads = total_cores * cpu_info.threads;
+    let counts = cpu_info.counts();
+    let total_threads = counts.threads_total;
 
     CpuTopology {
-        total_cores,
+        total_cores: counts.cores_total,
         total_threads,
-        sockets: cpu_info.sockets,
-        cores_per_socket: cpu_info.cores,
-        threads_per_core: cpu_info.threads,
+        sockets: counts.sockets,
+        cores_per_socket: counts.cores_per_socket,
+        threads_per_core: counts.threads_per_core,
         numa_nodes: numa_nodes.unwrap_or(1),
         cpu_model: cpu_info.model.clone(),
+        isolated_cpus: cpu_info.isolated_cpus.clone(),
+        nohz_full_cpus: cpu_info.nohz_full_cpus.clone(),
+        schedulable_cores: calculate_schedulable_cores(
+            &cpu_info.online_cpus,
+            &cpu_info.isolated_cpus,
+            total_threads,
+        ),
+        offline_cpus: cpu_info.offline_cpus.clone(),
+        turbo_enabled: cpu_info.turbo_enabled,
+        smt_control: cpu_info.smt_control.clone(),
+        smt_active: cpu_info.smt_active,
+        cpufreq_driver: cpu_info.cpufreq_driver.clone(),
+        cpuidle_driver: cpu_info.cpuidle_driver.clone(),
+        logical_cpus: cpu_info.logical_cpus.clone(),
     }
 }
 
@@ -463,6 +682,119 @@ CPU MHz:                         2300.000"#;
         assert_eq!(topology.numa_nodes, 1);
     }
 
+    #[test]
+    fn test_parse_cmdline_cpu_list() {
+        let cmdline =
+            "BOOT_IMAGE=/vmlinuz-6.8.0 root=UUID=abcd ro isolcpus=2-7,10 nohz_full=2-7 quiet";
+
+        assert_eq!(
+            parse_cmdline_cpu_list(cmdline, "isolcpus"),
+            vec![2, 3, 4, 5, 6, 7, 10]
+        );
+        assert_eq!(
+            parse_cmdline_cpu_list(cmdline, "nohz_full"),
+            vec![2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(
+            parse_cmdline_cpu_list("isolcpus=domain,managed_irq,4-5", "isolcpus"),
+            vec![4, 5]
+        );
+        assert!(parse_cmdline_cpu_list("ro quiet", "isolcpus").is_empty());
+    }
+
+    #[test]
+    fn test_schedulable_cores_excludes_isolated() {
+        let cpu_info = CpuInfo {
+            model: "AMD EPYC 9554".to_string(),
+            cores: 64,
+            threads: 2,
+            sockets: 1,
+            online_cpus: (0..128).collect(),
+            isolated_cpus: (2..10).collect(),
+            ..Default::default()
+        };
+
+        let topology = create_cpu_topology(&cpu_info, Some(1));
+        assert_eq!(topology.schedulable_cores, 120);
+        assert_eq!(calculate_schedulable_cores(&[], &[2, 3], 16), 14);
+    }
+
+    #[test]
+    fn test_cpu_counts_two_sockets() {
+        let cpu_info = CpuInfo {
+            model: "AMD EPYC 9554".to_string(),
+            cores: 64,
+            threads: 2,
+            sockets: 2,
+            ..Default::default()
+        };
+
+        let counts = cpu_info.counts();
+        assert_eq!(counts.cores_per_socket, 64);
+        assert_eq!(counts.cores_total, counts.cores_per_socket * counts.sockets);
+        assert_eq!(counts.cores_total, 128);
+        assert_eq!(counts.threads_total, 256);
+
+        let topology = create_cpu_topology(&cpu_info, Some(2));
+        assert_eq!(topology.total_cores, counts.cores_total);
+        assert_eq!(topology.total_threads, counts.threads_total);
+    }
+
+    #[test]
+    fn test_parse_turbo_enabled() {
+        // Intel with no_turbo=1 means turbo is disabled
+        assert_eq!(parse_turbo_enabled(Some("1\n"), None), Some(false));
+        assert_eq!(parse_turbo_enabled(Some("0\n"), None), Some(true));
+        // AMD with boost=1 means boost is enabled
+        assert_eq!(parse_turbo_enabled(None, Some("1\n")), Some(true));
+        assert_eq!(parse_turbo_enabled(None, Some("0\n")), Some(false));
+        assert_eq!(parse_turbo_enabled(None, None), None);
+    }
+
+    #[test]
+    fn test_parse_smt_control() {
+        assert_eq!(parse_smt_control("on\n").as_deref(), Some("on"));
+        assert_eq!(parse_smt_control("off\n").as_deref(), Some("off"));
+        assert_eq!(parse_smt_control("forceoff\n").as_deref(), Some("forceoff"));
+        assert_eq!(
+            parse_smt_control("notsupported\n").as_deref(),
+            Some("notsupported")
+        );
+        assert_eq!(
+            parse_smt_control("notimplemented\n").as_deref(),
+            Some("notimplemented")
+        );
+        assert_eq!(parse_smt_control("garbage\n"), None);
+
+        assert_eq!(parse_smt_active("1\n"), Some(true));
+        assert_eq!(parse_smt_active("0\n"), Some(false));
+    }
+
+    #[test]
+    fn test_parse_cpu_driver() {
+        assert_eq!(
+            parse_cpu_driver("intel_pstate\n"),
+            Some("intel_pstate".to_string())
+        );
+        assert_eq!(parse_cpu_driver("none\n"), None);
+        assert_eq!(parse_cpu_driver(""), None);
+    }
+
+    #[test]
+    fn test_cpu_steal_percent() {
+        let first = "cpu  4705 150 1120 16250 520 0 28 120 0 0\ncpu0 2351 75 560 8125 260 0 14 60 0 0\nintr 0\n";
+        let second = "cpu  4905 150 1220 16550 520 0 28 270 0 0\ncpu0 2451 75 610 8275 260 0 14 135 0 0\nintr 0\n";
+
+        let first = parse_proc_stat_cpu(first).unwrap();
+        let second = parse_proc_stat_cpu(second).unwrap();
+        assert_eq!(first, (22893, 120));
+
+        // 150 of 750 elapsed jiffies were stolen
+        assert_eq!(cpu_steal_percent(first, second), Some(20.0));
+        assert_eq!(cpu_steal_percent(first, first), None);
+        assert_eq!(parse_proc_stat_cpu("cpu  4705 150 1120 16250\n"), None);
+    }
+
     #[test]
     fn test_create_cpu_summary() {
         let topology = CpuTopology {
@@ -473,6 +805,7 @@ CPU MHz:                         2300.000"#;
             threads_per_core: 2,
             numa_nodes: 2,
             cpu_model: "Intel Xeon Gold 6226R".to_string(),
+            ..Default::default()
         };
 
         let summary = create_cpu_summary(&topology);
@@ -482,4 +815,47 @@ CPU MHz:                         2300.000"#;
         assert!(summary.contains("2 Threads/Core"));
         assert!(summary.contains("2 NUMA Nodes"));
     }
+
+    #[test]
+    fn test_build_cpu_affinity_map() {
+        // 2 sockets x 2 cores x 2 threads, siblings numbered N and N+4
+        let logical_cpus: Vec<LogicalCpu> = (0..8)
+            .map(|cpu| LogicalCpu {
+                cpu,
+                core: cpu % 2,
+                socket: (cpu % 4) / 2,
+            })
+            .collect();
+        let numa_topology: HashMap<String, NumaNode> =
+            [(0, vec![0, 1, 4, 5]), (1, vec![2, 3, 6, 7])]
+                .into_iter()
+                .map(|(id, cpus)| {
+                    (
+                        id.to_string(),
+                        NumaNode {
+                            id,
+                            cpu_ranges: String::new(),
+                            cpus,
+                            memory: "256 GB".to_string(),
+                            devices: Vec::new(),
+                            distances: HashMap::new(),
+                            memory_tier: None,
+                        },
+                    )
+                })
+                .collect();
+
+        let map = build_cpu_affinity_map(&logical_cpus, &numa_topology);
+
+        assert_eq!(map.len(), 8);
+        for (cpu, location) in &map {
+            let sibling = location.thread_sibling.expect("SMT sibling");
+            assert_eq!(sibling, (cpu + 4) % 8);
+            assert_eq!(map[&sibling].core, location.core);
+            assert_eq!(map[&sibling].socket, location.socket);
+            assert_eq!(map[&sibling].numa_node, location.numa_node);
+        }
+        assert_eq!(map[&2].numa_node, Some(1));
+        assert_eq!(map[&2].socket, 1);
+    }
 }
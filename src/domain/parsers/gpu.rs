@@ -16,7 +16,9 @@ limitations under the License.
 
 //! GPU information parsing functions
 
-use crate::domain::{GpuDevice, GpuVendor};
+use super::common::{normalize_pci_address, UNKNOWN};
+use crate::domain::{GpuDevice, GpuVendor, MigInstance, NvLink, NvLinkConnection, XidError};
+use std::collections::HashMap;
 
 /// Parse nvidia-smi CSV output
 ///
@@ -95,6 +97,390 @@ pub fn parse_nvidia_smi_output(output: &str) -> Result<Vec<GpuDevice>, String> {
     Ok(devices)
 }
 
+/// Parse per-GPU details from full nvidia-smi query output
+///
+/// Expected command: `nvidia-smi -q`
+///
+/// Reads temperatures and thermal limits, retired page counts and the
+/// virtualization mode from each GPU section. The "GPU Virtualization Mode"
+/// section reports vGPU (guest or host) and passthrough; otherwise an enabled
+/// "MIG Mode" means "mig" and anything else "none". GPUs that use row
+/// remapping report "N/A" retired pages, which leaves those fields `None`.
+///
+/// # Arguments
+///
+/// * `output` - Output from nvidia-smi
+///
+/// # Returns
+///
+/// One GPU device per GPU section, in output order, with only the normalized
+/// PCI bus ID and the fields above populated. Callers match sections to
+/// devices by bus ID, since section order is not guaranteed to follow the
+/// CSV query's index order.
+pub fn parse_nvidia_smi_query(output: &str) -> Vec<GpuDevice> {
+    let mut devices: Vec<GpuDevice> = Vec::new();
+    let mut mig_enabled: Vec<Option<bool>> = Vec::new();
+    let mut heading = "";
+
+    for line in output.lines() {
+        if let Some(bus_id) = line.strip_prefix("GPU ") {
+            devices.push(GpuDevice {
+                index: devices.len() as u32,
+                pci_bus_id: Some(normalize_pci_address(bus_id.trim())),
+                vendor: "NVIDIA".to_string(),
+                vendor_enum: GpuVendor::Nvidia,
+                detection_method: "nvidia-smi".to_string(),
+                ..Default::default()
+            });
+            mig_enabled.push(None);
+            heading = "";
+            continue;
+        }
+
+        let (Some(device), Some(mig)) = (devices.last_mut(), mig_enabled.last_mut()) else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once(':') else {
+            heading = line.trim();
+            continue;
+        };
+        let value = value.trim();
+
+        // Temperatures look like "34 C" or "N/A"
+        let celsius = || {
+            value
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.parse::<i32>().ok())
+        };
+
+        match (heading, key.trim()) {
+            ("Temperature", "GPU Current Temp") => device.temperature_c = celsius(),
+            ("Temperature", "Memory Current Temp") => device.temperature_memory_c = celsius(),
+            ("Temperature", "GPU Slowdown Temp") => device.temperature_slowdown_c = celsius(),
+            ("Temperature", "GPU Shutdown Temp") => device.temperature_shutdown_c = celsius(),
+            ("Retired Pages", "Single Bit ECC") => {
+                device.retired_pages_single_bit = value.parse().ok();
+            }
+            ("Retired Pages", "Double Bit ECC") => {
+                device.retired_pages_double_bit = value.parse().ok();
+            }
+            // Older drivers call this "Pending Page Blacklist"
+            ("Retired Pages", "Pending Page Retirement" | "Pending Page Blacklist") => {
+                device.retired_pages_pending = match value {
+                    "Yes" => Some(true),
+                    "No" => Some(false),
+                    _ => None,
+                };
+            }
+            ("GPU Virtualization Mode", "Virtualization Mode") => {
+                device.virtualization_mode = Some(value.to_ascii_lowercase());
+            }
+            ("MIG Mode", "Current") => *mig = Some(value == "Enabled"),
+            _ => {}
+        }
+    }
+
+    for (device, mig) in devices.iter_mut().zip(mig_enabled) {
+        let mode = match device.virtualization_mode.as_deref() {
+            Some("pass-through") => "passthrough",
+            Some("vgpu" | "host vgpu") => "vgpu",
+            _ if mig == Some(true) => "mig",
+            Some(_) => "none",
+            None if mig.is_some() => "none",
+            None => continue,
+        };
+        device.virtualization_mode = Some(mode.to_string());
+    }
+
+    devices
+}
+
+/// Parse NVLink status and error counter output
+///
+/// Expected commands: `nvidia-smi nvlink -s` and `nvidia-smi nvlink -e`
+///
+/// # Arguments
+///
+/// * `status_output` - Output from `nvidia-smi nvlink -s`
+/// * `errors_output` - Output from `nvidia-smi nvlink -e` (may be empty)
+///
+/// # Returns
+///
+/// NVLink links keyed by GPU index.
+pub fn parse_nvidia_smi_nvlink(
+    status_output: &str,
+    errors_output: &str,
+) -> Result<HashMap<u32, Vec<NvLink>>, String> {
+    let mut links: HashMap<u32, Vec<NvLink>> = HashMap::new();
+
+    for (gpu, link, value) in nvlink_lines(status_output) {
+        let (state, speed) = if value.contains("inactive") {
+            ("down", None)
+        } else {
+            ("up", Some(value.to_string()))
+        };
+        links.entry(gpu).or_default().push(NvLink {
+            link,
+            state: state.to_string(),
+            speed,
+            ..Default::default()
+        });
+    }
+
+    for (gpu, link, value) in nvlink_lines(errors_output) {
+        let Some(nvlink) = links
+            .get_mut(&gpu)
+            .and_then(|gpu_links| gpu_links.iter_mut().find(|l| l.link == link))
+        else {
+            continue;
+        };
+        let Some((counter, count)) = value.rsplit_once(':') else {
+            continue;
+        };
+        let Ok(count) = count.trim().parse::<u64>() else {
+            continue;
+        };
+
+        let field = match counter.trim() {
+            "Replay Errors" => &mut nvlink.replay_errors,
+            "Recovery Errors" => &mut nvlink.recovery_errors,
+            c if c.starts_with("CRC") => &mut nvlink.crc_errors,
+            _ => continue,
+        };
+        *field = Some(field.unwrap_or(0) + count);
+    }
+
+    Ok(links)
+}
+
+/// Parse the GPU interconnect matrix from `nvidia-smi topo -m`
+///
+/// Only `NV#` cells become connections; `X`, `SYS`, `NODE`, `PHB`, `PXB` and
+/// `PIX` cells are PCIe or self paths and are skipped, as are NIC rows and
+/// columns.
+///
+/// # Arguments
+///
+/// * `output` - Output from `nvidia-smi topo -m`
+///
+/// # Returns
+///
+/// One connection per GPU pair, ordered by `gpu_a` then `gpu_b`.
+pub fn parse_nvidia_smi_topo_matrix(output: &str) -> Vec<NvLinkConnection> {
+    let gpu_index = |token: &str| {
+        token
+            .strip_prefix("GPU")
+            .and_then(|i| i.parse::<u32>().ok())
+    };
+
+    let mut lines = output.lines();
+    let Some(columns) = lines.by_ref().find_map(|line| {
+        let columns: Vec<u32> = line.split_whitespace().map_while(gpu_index).collect();
+        (!columns.is_empty() && line.starts_with(char::is_whitespace)).then_some(columns)
+    }) else {
+        return Vec::new();
+    };
+
+    let mut connections = Vec::new();
+    for line in lines {
+        let mut cells = line.split_whitespace();
+        let Some(gpu_a) = cells.next().and_then(gpu_index) else {
+            continue;
+        };
+        for (&gpu_b, cell) in columns.iter().zip(cells) {
+            let Some(links) = cell.strip_prefix("NV").and_then(|n| n.parse().ok()) else {
+                continue;
+            };
+            if gpu_a < gpu_b {
+                connections.push(NvLinkConnection {
+                    gpu_a,
+                    gpu_b,
+                    links,
+                });
+            }
+        }
+    }
+
+    connections.sort_by_key(|c| (c.gpu_a, c.gpu_b));
+    connections
+}
+
+/// Split nvidia-smi nvlink output into (GPU index, link index, value) tuples
+fn nvlink_lines(output: &str) -> Vec<(u32, u32, &str)> {
+    let mut lines = Vec::new();
+    let mut current_gpu = None;
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("GPU ") {
+            current_gpu = rest.split(':').next().and_then(|i| i.trim().parse().ok());
+            continue;
+        }
+
+        let (Some(gpu), Some(rest)) = (current_gpu, line.trim().strip_prefix("Link ")) else {
+            continue;
+        };
+        if let Some((link, value)) = rest.split_once(':') {
+            if let Ok(link) = link.trim().parse() {
+                lines.push((gpu, link, value.trim()));
+            }
+        }
+    }
+
+    lines
+}
+
+/// Check whether all GPUs share the same model and memory size
+///
+/// # Arguments
+///
+/// * `devices` - List of GPU devices
+///
+/// # Returns
+///
+/// `false` if any device differs in name or total memory from the others.
+pub fn are_gpus_homogeneous(devices: &[GpuDevice]) -> bool {
+    devices.windows(2).all(|pair| {
+        pair[0].name == pair[1].name && pair[0].memory_total_mb == pair[1].memory_total_mb
+    })
+}
+
+/// Parse MIG instances from `nvidia-smi -L` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `nvidia-smi -L`
+///
+/// # Returns
+///
+/// MIG instances keyed by the index of the GPU they belong to. GPUs without
+/// MIG enabled have no entry.
+pub fn parse_nvidia_smi_mig_list(output: &str) -> HashMap<u32, Vec<MigInstance>> {
+    let mut instances: HashMap<u32, Vec<MigInstance>> = HashMap::new();
+    let mut current_gpu = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("GPU ") {
+            current_gpu = rest.split(':').next().and_then(|i| i.trim().parse().ok());
+        } else if let (Some(rest), Some(gpu)) = (trimmed.strip_prefix("MIG "), current_gpu) {
+            // "MIG 1g.10gb     Device  0: (UUID: MIG-...)"
+            let Some(profile) = rest.split_whitespace().next() else {
+                continue;
+            };
+            let Some(uuid) = rest
+                .split_once("UUID:")
+                .map(|(_, uuid)| uuid.trim().trim_end_matches(')').to_string())
+            else {
+                continue;
+            };
+            instances.entry(gpu).or_default().push(MigInstance {
+                profile: profile.to_string(),
+                uuid,
+            });
+        }
+    }
+
+    instances
+}
+
+/// Count MIG instances across all GPUs
+///
+/// # Arguments
+///
+/// * `devices` - List of GPU devices
+pub fn count_mig_instances(devices: &[GpuDevice]) -> usize {
+    devices
+        .iter()
+        .map(|device| device.mig_instances.len())
+        .sum()
+}
+
+/// Parse the size of BAR1 from a PCI device's sysfs `resource` file
+///
+/// # Arguments
+///
+/// * `resource` - Contents of `/sys/bus/pci/devices/<addr>/resource`, one
+///   "start end flags" line per resource
+///
+/// # Returns
+///
+/// BAR1 size in bytes; `None` when BAR1 is absent or unassigned.
+pub fn parse_pci_resource_bar1_size(resource: &str) -> Option<u64> {
+    let line = resource.lines().nth(1)?;
+    let mut fields = line
+        .split_whitespace()
+        .map(|field| u64::from_str_radix(field.trim_start_matches("0x"), 16));
+    let start = fields.next()?.ok()?;
+    let end = fields.next()?.ok()?;
+    (end > start).then(|| end - start + 1)
+}
+
+/// Infer whether resizable BAR is enabled from the BAR1 aperture
+///
+/// Without resizable BAR the GPU exposes a small fixed window (typically
+/// 256 MiB); with it, BAR1 is sized to cover the whole framebuffer.
+///
+/// # Arguments
+///
+/// * `bar1_bytes` - BAR1 size in bytes
+/// * `vram_mb` - Total GPU memory in MiB
+///
+/// # Returns
+///
+/// `None` when the GPU memory size is unknown.
+pub fn is_resizable_bar_enabled(bar1_bytes: u64, vram_mb: u64) -> Option<bool> {
+    (vram_mb > 0).then(|| bar1_bytes >= vram_mb * 1024 * 1024)
+}
+
+/// Parse NVIDIA Xid errors from kernel log output
+///
+/// Expected commands: `dmesg` or `journalctl -k`, with lines such as
+/// `NVRM: Xid (PCI:0000:3b:00): 79, pid=1234, GPU has fallen off the bus.`
+///
+/// # Arguments
+///
+/// * `output` - Kernel log output
+///
+/// # Returns
+///
+/// One entry per GPU and Xid code, in order of first occurrence, with event counts.
+pub fn parse_xid_errors(output: &str) -> Vec<XidError> {
+    let mut errors: Vec<XidError> = Vec::new();
+
+    for line in output.lines() {
+        let Some((_, rest)) = line.split_once("NVRM: Xid (PCI:") else {
+            continue;
+        };
+        let Some((pci_address, rest)) = rest.split_once("):") else {
+            continue;
+        };
+        let Some(code) = rest
+            .trim()
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|code| code.parse().ok())
+        else {
+            continue;
+        };
+        let pci_address = pci_address.trim().to_lowercase();
+
+        match errors
+            .iter_mut()
+            .find(|e| e.pci_address == pci_address && e.code == code)
+        {
+            Some(existing) => existing.count += 1,
+            None => errors.push(XidError {
+                pci_address,
+                code,
+                count: 1,
+            }),
+        }
+    }
+
+    errors
+}
+
 /// Parse lspci output for GPU devices
 ///
 /// Expected command: `lspci -nn`
@@ -123,7 +509,7 @@ pub fn parse_lspci_gpu_output(output: &str) -> Result<Vec<GpuDevice>, String> {
             let vendor = GpuVendor::from_pci_vendor(vendor_id);
             (vendor.clone(), vendor.name().to_string())
         } else {
-            (GpuVendor::Unknown, "Unknown".to_string())
+            (GpuVendor::Unknown, UNKNOWN.to_string())
         };
 
         // Extract name (everything after the colon and space)
@@ -151,6 +537,146 @@ pub fn parse_lspci_gpu_output(output: &str) -> Result<Vec<GpuDevice>, String> {
     Ok(devices)
 }
 
+/// Parse Intel GPUs from `xpu-smi discovery -j` output
+///
+/// # Arguments
+///
+/// * `output` - JSON output from `xpu-smi discovery -j`
+///
+/// # Returns
+///
+/// List of GPU devices; `pci_bus_id` holds the PCI address when reported.
+pub fn parse_xpu_smi_discovery(output: &str) -> Result<Vec<GpuDevice>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid xpu-smi JSON: {e}"))?;
+    let device_list = json
+        .get("device_list")
+        .and_then(|list| list.as_array())
+        .ok_or("xpu-smi output has no device_list")?;
+
+    let devices = device_list
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry["device_type"].as_str().unwrap_or("GPU") == "GPU")
+        .map(|(position, entry)| {
+            let index = entry["device_id"]
+                .as_u64()
+                .map(|id| id as u32)
+                .unwrap_or(position as u32);
+            // "0xbd5" -> "8086:0bd5"
+            let pci_id = entry["pci_device_id"]
+                .as_str()
+                .and_then(|id| u16::from_str_radix(id.trim_start_matches("0x"), 16).ok())
+                .map(|id| format!("8086:{id:04x}"))
+                .unwrap_or_default();
+            let memory_total_mb = entry["memory_physical_size_byte"]
+                .as_str()
+                .and_then(|bytes| bytes.parse::<u64>().ok())
+                .or_else(|| entry["memory_physical_size_byte"].as_u64())
+                .map(|bytes| bytes / (1024 * 1024))
+                .unwrap_or(0);
+
+            GpuDevice {
+                index,
+                name: entry["device_name"].as_str().unwrap_or(UNKNOWN).to_string(),
+                uuid: entry["uuid"].as_str().unwrap_or_default().to_string(),
+                memory: if memory_total_mb > 0 {
+                    format!("{memory_total_mb} MB")
+                } else {
+                    UNKNOWN.to_string()
+                },
+                memory_total_mb,
+                pci_id,
+                pci_bus_id: entry["pci_bdf_address"].as_str().map(normalize_pci_address),
+                vendor: GpuVendor::Intel.name().to_string(),
+                vendor_enum: GpuVendor::Intel,
+                detection_method: "xpu-smi".to_string(),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Ok(devices)
+}
+
+/// Parse Intel discrete GPUs from lspci output
+///
+/// Expected command: `lspci -D -nn -d 8086:`
+///
+/// Matches VGA [0300], 3D [0302] and display [0380] controllers. The
+/// integrated GPU, which Intel always places at 00:02.0, is skipped.
+///
+/// # Arguments
+///
+/// * `output` - Output from `lspci -D -nn -d 8086:`
+pub fn parse_lspci_intel_gpus(output: &str) -> Vec<GpuDevice> {
+    output
+        .lines()
+        .filter(|line| {
+            ["[0300]", "[0302]", "[0380]"]
+                .iter()
+                .any(|class| line.contains(class))
+        })
+        .filter_map(|line| {
+            let (address, rest) = line.split_once(' ')?;
+            let address = normalize_pci_address(address);
+            if address.ends_with(":00:02.0") {
+                return None;
+            }
+            let pci_id = extract_pci_id(line)?;
+            let name = rest
+                .split_once(": ")
+                .map(|(_, name)| name.trim())
+                .unwrap_or(rest)
+                .to_string();
+            Some(GpuDevice {
+                name,
+                uuid: format!("pci-{address}"),
+                memory: UNKNOWN.to_string(),
+                pci_id,
+                pci_bus_id: Some(address),
+                vendor: GpuVendor::Intel.name().to_string(),
+                vendor_enum: GpuVendor::Intel,
+                detection_method: "lspci".to_string(),
+                ..Default::default()
+            })
+        })
+        .enumerate()
+        .map(|(index, device)| GpuDevice {
+            index: index as u32,
+            ..device
+        })
+        .collect()
+}
+
+/// Add GPUs found by a secondary source, skipping ones already present
+///
+/// Devices are matched by PCI address; devices without one are always added.
+/// Added devices are renumbered after the existing ones.
+///
+/// # Arguments
+///
+/// * `devices` - GPUs from the primary source
+/// * `additional` - GPUs from the secondary source
+pub fn merge_gpu_devices(devices: &mut Vec<GpuDevice>, additional: Vec<GpuDevice>) {
+    for mut device in additional {
+        let duplicate = device.pci_bus_id.as_ref().is_some_and(|address| {
+            devices
+                .iter()
+                .any(|existing| existing.pci_bus_id.as_ref() == Some(address))
+        });
+        if duplicate {
+            continue;
+        }
+        device.index = devices
+            .iter()
+            .map(|existing| existing.index + 1)
+            .max()
+            .unwrap_or(0);
+        devices.push(device);
+    }
+}
+
 /// Extract PCI vendor:device ID from lspci output line
 ///
 /// Looks for pattern like [10de:2204] - must be 4 hex chars : 4 hex chars
@@ -187,6 +713,83 @@ fn extract_pci_id(line: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_intel_gpus_merged_by_pci_address() {
+        let xpu_smi = r#"{
+    "device_list": [
+        {
+            "device_function_type": "physical",
+            "device_id": 0,
+            "device_name": "Intel(R) Data Center GPU Max 1550",
+            "device_type": "GPU",
+            "drm_device": "/dev/dri/card1",
+            "pci_bdf_address": "0000:29:00.0",
+            "pci_device_id": "0xbd5",
+            "uuid": "01000000-0000-0000-0000-000000290000",
+            "vendor_name": "Intel(R) Corporation"
+        }
+    ]
+}"#;
+        let lspci = "\
+0000:00:02.0 VGA compatible controller [0300]: Intel Corporation UHD Graphics 770 [8086:4680] (rev 0c)
+0000:29:00.0 Display controller [0380]: Intel Corporation Ponte Vecchio XT (2 Tile) [Data Center GPU Max 1550] [8086:0bd5] (rev 2f)
+0000:3a:00.0 Display controller [0380]: Intel Corporation Ponte Vecchio XT (2 Tile) [Data Center GPU Max 1550] [8086:0bd5] (rev 2f)
+0000:3a:00.1 System peripheral [0880]: Intel Corporation Device [8086:0b25]
+";
+
+        let mut devices = parse_xpu_smi_discovery(xpu_smi).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].pci_id, "8086:0bd5");
+        assert_eq!(devices[0].pci_bus_id.as_deref(), Some("0000:29:00.0"));
+        assert_eq!(devices[0].vendor, "Intel");
+
+        let scanned = parse_lspci_intel_gpus(lspci);
+        assert_eq!(scanned.len(), 2);
+        assert_eq!(scanned[1].pci_id, "8086:0bd5");
+
+        merge_gpu_devices(&mut devices, scanned);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].detection_method, "xpu-smi");
+        assert_eq!(devices[1].pci_bus_id.as_deref(), Some("0000:3a:00.0"));
+        assert_eq!(devices[1].index, 1);
+        assert!(devices.iter().all(|d| d.vendor_enum == GpuVendor::Intel));
+    }
+
+    #[test]
+    fn test_resizable_bar_from_pci_resource() {
+        // H100 80GB: BAR1 is a 128 GiB 64-bit prefetchable window
+        let enabled = "\
+0x00000000e2000000 0x00000000e2ffffff 0x0000000000040200
+0x0000220000000000 0x0000221fffffffff 0x000000000014220c
+0x0000000000000000 0x0000000000000000 0x0000000000000000
+0x0000222000000000 0x0000222001ffffff 0x000000000014220c
+";
+        // Same card with resizable BAR disabled in BIOS: 256 MiB window
+        let disabled = "\
+0x00000000e2000000 0x00000000e2ffffff 0x0000000000040200
+0x00000000c0000000 0x00000000cfffffff 0x000000000014220c
+0x0000000000000000 0x0000000000000000 0x0000000000000000
+0x00000000d0000000 0x00000000d1ffffff 0x000000000014220c
+";
+
+        let bar1 = parse_pci_resource_bar1_size(enabled).unwrap();
+        assert_eq!(bar1, 128 << 30);
+        assert_eq!(is_resizable_bar_enabled(bar1, 81559), Some(true));
+
+        let bar1 = parse_pci_resource_bar1_size(disabled).unwrap();
+        assert_eq!(bar1, 256 << 20);
+        assert_eq!(is_resizable_bar_enabled(bar1, 81559), Some(false));
+
+        assert_eq!(is_resizable_bar_enabled(bar1, 0), None);
+        assert_eq!(
+            parse_pci_resource_bar1_size(
+                "0x00000000e2000000 0x00000000e2ffffff 0x0000000000040200\n\
+                 0x0000000000000000 0x0000000000000000 0x0000000000000000\n"
+            ),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_nvidia_smi_output() {
         let output = "0, NVIDIA GeForce RTX 3090, GPU-12345678-1234-1234-1234-123456789012, 24576, 24000, 00000000:01:00.0, 535.129.03, 8.6";
@@ -210,6 +813,297 @@ mod tests {
         assert_eq!(devices[1].vendor, "Intel");
     }
 
+    #[test]
+    fn test_mig_instances_counted_separately_from_gpus() {
+        // 8x A100 80GB, each split into seven 1g.10gb instances
+        let mut output = String::new();
+        for gpu in 0..8 {
+            output.push_str(&format!(
+                "GPU {gpu}: NVIDIA A100-SXM4-80GB (UUID: GPU-5a1c0e7e-0000-0000-0000-00000000000{gpu})\n"
+            ));
+            for device in 0..7 {
+                output.push_str(&format!(
+                    "  MIG 1g.10gb     Device  {device}: (UUID: MIG-{gpu}{device}a4e0c2-7d1b-5f0e-9c3a-000000000000)\n"
+                ));
+            }
+        }
+
+        let mut instances = parse_nvidia_smi_mig_list(&output);
+        let devices: Vec<GpuDevice> = (0..8)
+            .map(|index| GpuDevice {
+                index,
+                mig_instances: instances.remove(&index).unwrap_or_default(),
+                ..Default::default()
+            })
+            .collect();
+
+        assert_eq!(devices.len(), 8);
+        assert_eq!(count_mig_instances(&devices), 56);
+        assert_eq!(devices[3].mig_instances[6].profile, "1g.10gb");
+        assert_eq!(
+            devices[3].mig_instances[6].uuid,
+            "MIG-36a4e0c2-7d1b-5f0e-9c3a-000000000000"
+        );
+
+        // MIG disabled: GPUs only
+        let plain =
+            "GPU 0: NVIDIA H100 80GB HBM3 (UUID: GPU-1b2c3d4e-0000-0000-0000-000000000000)\n";
+        assert!(parse_nvidia_smi_mig_list(plain).is_empty());
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_query_temperature() {
+        // H100 SXM5 GPUs, which report HBM temperature separately
+        let output = r#"
+==============NVSMI LOG==============
+
+Timestamp                                 : Tue Mar 12 10:15:42 2024
+Driver Version                            : 535.129.03
+CUDA Version                              : 12.2
+
+Attached GPUs                             : 2
+GPU 00000000:18:00.0
+    Temperature
+        GPU Current Temp                  : 34 C
+        GPU T.Limit Temp                  : N/A
+        GPU Shutdown Temp                 : 92 C
+        GPU Slowdown Temp                 : 89 C
+        GPU Max Operating Temp            : 87 C
+        GPU Target Temperature            : N/A
+        Memory Current Temp               : 43 C
+        Memory Max Operating Temp         : 95 C
+
+GPU 00000000:2A:00.0
+    Temperature
+        GPU Current Temp                  : 36 C
+        GPU Shutdown Temp                 : 92 C
+        GPU Slowdown Temp                 : 89 C
+        Memory Current Temp               : N/A
+"#;
+
+        let devices = parse_nvidia_smi_query(output);
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].pci_bus_id.as_deref(), Some("0000:18:00.0"));
+        assert_eq!(devices[1].pci_bus_id.as_deref(), Some("0000:2a:00.0"));
+        assert_eq!(devices[0].temperature_c, Some(34));
+        assert_eq!(devices[0].temperature_memory_c, Some(43));
+        assert_eq!(devices[0].temperature_slowdown_c, Some(89));
+        assert_eq!(devices[0].temperature_shutdown_c, Some(92));
+        assert_eq!(devices[1].temperature_c, Some(36));
+        assert_eq!(devices[1].temperature_memory_c, None);
+    }
+
+    #[test]
+    fn test_mixed_gpus_not_homogeneous() {
+        let mut output = String::new();
+        for index in 0..7 {
+            output.push_str(&format!(
+                "{index}, NVIDIA H100 80GB HBM3, GPU-h100-{index}, 81559\n"
+            ));
+        }
+        output.push_str("7, NVIDIA A100-SXM4-80GB, GPU-a100-7, 81920\n");
+
+        let devices = parse_nvidia_smi_output(&output).unwrap();
+
+        assert_eq!(devices.len(), 8);
+        assert!(!are_gpus_homogeneous(&devices));
+        assert!(are_gpus_homogeneous(&devices[..7]));
+        assert!(are_gpus_homogeneous(&[]));
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_nvlink() {
+        let status = "\
+GPU 0: NVIDIA H100 80GB HBM3 (UUID: GPU-3f2a9c1e-0000-0000-0000-000000000000)
+\t Link 0: 26.562 GB/s
+\t Link 1: <inactive>
+\t Link 2: 26.562 GB/s
+GPU 1: NVIDIA H100 80GB HBM3 (UUID: GPU-7b1d4e2f-0000-0000-0000-000000000000)
+\t Link 0: 26.562 GB/s
+";
+        let errors = "\
+GPU 0: NVIDIA H100 80GB HBM3 (UUID: GPU-3f2a9c1e-0000-0000-0000-000000000000)
+\t Link 0: Replay Errors: 0
+\t Link 0: Recovery Errors: 0
+\t Link 0: CRC Errors: 12
+\t Link 2: Replay Errors: 3
+";
+
+        let links = parse_nvidia_smi_nvlink(status, errors).unwrap();
+
+        assert_eq!(links[&0].len(), 3);
+        assert_eq!(links[&0][0].state, "up");
+        assert_eq!(links[&0][0].speed.as_deref(), Some("26.562 GB/s"));
+        assert_eq!(links[&0][0].crc_errors, Some(12));
+        assert_eq!(links[&0][0].replay_errors, Some(0));
+        assert_eq!(links[&0][1].state, "down");
+        assert_eq!(links[&0][1].speed, None);
+        assert_eq!(links[&0][2].replay_errors, Some(3));
+        assert_eq!(links[&1].len(), 1);
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_query_virtualization_modes() {
+        let output = "
+==============NVSMI LOG==============
+
+Driver Version                            : 535.129.03
+Attached GPUs                             : 3
+GPU 00000000:02:00.0
+    Product Name                          : GRID A100-40C
+    ECC Mode
+        Current                           : Enabled
+        Pending                           : Enabled
+    MIG Mode
+        Current                           : N/A
+        Pending                           : N/A
+    GPU Virtualization Mode
+        Virtualization Mode               : VGPU
+        Host VGPU Mode                    : N/A
+    vGPU Software Licensed Product
+        Product Name                      : NVIDIA Virtual Compute Server
+
+GPU 00000000:03:00.0
+    Product Name                          : NVIDIA A100-SXM4-80GB
+    MIG Mode
+        Current                           : Enabled
+        Pending                           : Enabled
+    GPU Virtualization Mode
+        Virtualization Mode               : None
+        Host VGPU Mode                    : N/A
+
+GPU 00000000:04:00.0
+    Product Name                          : NVIDIA H100 80GB HBM3
+    MIG Mode
+        Current                           : Disabled
+        Pending                           : Disabled
+    GPU Virtualization Mode
+        Virtualization Mode               : Pass-Through
+        Host VGPU Mode                    : N/A
+";
+
+        let modes: Vec<Option<String>> = parse_nvidia_smi_query(output)
+            .into_iter()
+            .map(|device| device.virtualization_mode)
+            .collect();
+
+        assert_eq!(
+            modes,
+            vec![
+                Some("vgpu".to_string()),
+                Some("mig".to_string()),
+                Some("passthrough".to_string()),
+            ]
+        );
+        let older = parse_nvidia_smi_query("GPU 00000000:05:00.0\n    Product Name : Tesla T4\n");
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].virtualization_mode, None);
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_topo_matrix() {
+        let output = "\tGPU0\tGPU1\tGPU2\tGPU3\tNIC0\tCPU Affinity\tNUMA Affinity\tGPU NUMA ID
+GPU0\t X \tNV18\tNV18\tSYS\tPHB\t0-47\t0\t\tN/A
+GPU1\tNV18\t X \tNV18\tSYS\tPXB\t0-47\t0\t\tN/A
+GPU2\tNV18\tNV18\t X \tSYS\tSYS\t48-95\t1\t\tN/A
+GPU3\tSYS\tSYS\tSYS\t X \tNODE\t48-95\t1\t\tN/A
+NIC0\tPHB\tPXB\tSYS\tNODE\t X \t\t\t\t
+
+Legend:
+
+  X    = Self
+  SYS  = Connection traversing PCIe as well as the SMP interconnect between NUMA nodes
+  NV#  = Connection traversing a bonded set of # NVLinks
+";
+
+        let connections = parse_nvidia_smi_topo_matrix(output);
+
+        assert_eq!(
+            connections,
+            vec![
+                NvLinkConnection {
+                    gpu_a: 0,
+                    gpu_b: 1,
+                    links: 18
+                },
+                NvLinkConnection {
+                    gpu_a: 0,
+                    gpu_b: 2,
+                    links: 18
+                },
+                NvLinkConnection {
+                    gpu_a: 1,
+                    gpu_b: 2,
+                    links: 18
+                },
+            ]
+        );
+
+        // PCIe-only boxes have no NV# cells
+        let pcie_only = "\tGPU0\tGPU1\tCPU Affinity
+GPU0\t X \tPHB\t0-15
+GPU1\tPHB\t X \t0-15
+";
+        assert!(parse_nvidia_smi_topo_matrix(pcie_only).is_empty());
+        assert!(parse_nvidia_smi_topo_matrix("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_query_retired_pages() {
+        let output = "\
+==============NVSMI LOG==============
+
+Timestamp                                 : Wed Oct 14 09:12:44 2026
+Driver Version                            : 535.104.05
+CUDA Version                              : 12.2
+
+Attached GPUs                             : 2
+GPU 00000000:00:1E.0
+    Retired Pages
+        Single Bit ECC                    : 2
+        Double Bit ECC                    : 1
+        Pending Page Blacklist            : Yes
+
+GPU 00000000:00:1F.0
+    Retired Pages
+        Single Bit ECC                    : N/A
+        Double Bit ECC                    : N/A
+        Pending Page Blacklist            : N/A
+";
+
+        let devices = parse_nvidia_smi_query(output);
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].retired_pages_single_bit, Some(2));
+        assert_eq!(devices[0].retired_pages_double_bit, Some(1));
+        assert_eq!(devices[0].retired_pages_pending, Some(true));
+        assert_eq!(devices[1].retired_pages_single_bit, None);
+        assert_eq!(devices[1].retired_pages_pending, None);
+    }
+
+    #[test]
+    fn test_parse_xid_errors() {
+        let output = "\
+[  812.334512] NVRM: GPU at PCI:0000:3b:00: GPU-3f2a9c1e-0000-0000-0000-000000000000
+[  812.334520] NVRM: Xid (PCI:0000:3b:00): 79, pid=2841, name=python3, GPU has fallen off the bus.
+[  812.401337] nvidia-uvm: Unregistered the UVM driver.
+[ 9120.002114] NVRM: Xid (PCI:0000:3b:00): 79, pid='<unknown>', name=<unknown>, GPU has fallen off the bus.
+";
+
+        let errors = parse_xid_errors(output);
+
+        assert_eq!(
+            errors,
+            vec![XidError {
+                pci_address: "0000:3b:00".to_string(),
+                code: 79,
+                count: 2,
+            }]
+        );
+        assert!(parse_xid_errors("").is_empty());
+    }
+
     #[test]
     fn test_extract_pci_id() {
         assert_eq!(extract_pci_id("[10de:2204]"), Some("10de:2204".to_string()));
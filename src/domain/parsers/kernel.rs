@@ -0,0 +1,128 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Kernel log hardware error parsing functions
+
+use crate::domain::KernelError;
+
+/// Maximum number of kernel hardware errors kept; older entries are dropped first
+pub const MAX_KERNEL_ERRORS: usize = 100;
+
+/// Message patterns for each hardware error category
+const KERNEL_ERROR_PATTERNS: &[(&str, &[&str])] = &[
+    ("mce", &["mce:", "Machine check", "[Hardware Error]"]),
+    ("pcie_aer", &["AER:"]),
+    (
+        "disk_io",
+        &["I/O error", "critical medium error", "Medium Error"],
+    ),
+    (
+        "thermal",
+        &["temperature above threshold", "critical temperature"],
+    ),
+];
+
+/// Parse hardware errors from kernel log output
+///
+/// Expected commands: `dmesg` (`[  123.456789] message`) or `journalctl -k`
+/// (`Mar 12 10:15:42 host kernel: message`). NVIDIA Xid errors are reported
+/// separately by [`crate::domain::parse_xid_errors`].
+///
+/// # Arguments
+///
+/// * `output` - Kernel log output
+///
+/// # Returns
+///
+/// Matching errors in log order, keeping only the last [`MAX_KERNEL_ERRORS`].
+pub fn parse_kernel_hardware_errors(output: &str) -> Vec<KernelError> {
+    let mut errors: Vec<KernelError> = output
+        .lines()
+        .filter_map(|line| {
+            let (timestamp, message) = split_kernel_log_line(line);
+            let (category, _) = KERNEL_ERROR_PATTERNS
+                .iter()
+                .find(|(_, patterns)| patterns.iter().any(|pattern| message.contains(pattern)))?;
+            Some(KernelError {
+                timestamp,
+                category: category.to_string(),
+                message: message.to_string(),
+            })
+        })
+        .collect();
+
+    if errors.len() > MAX_KERNEL_ERRORS {
+        errors.drain(..errors.len() - MAX_KERNEL_ERRORS);
+    }
+    errors
+}
+
+/// Split a kernel log line into its timestamp and message
+fn split_kernel_log_line(line: &str) -> (Option<String>, &str) {
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix('[') {
+        if let Some((timestamp, message)) = rest.split_once(']') {
+            return (Some(timestamp.trim().to_string()), message.trim());
+        }
+    }
+    if let Some((prefix, message)) = line.split_once(" kernel: ") {
+        // Drop the hostname that follows the journal timestamp
+        let timestamp = prefix.rsplit_once(' ').map(|(ts, _)| ts.to_string());
+        return (timestamp, message.trim());
+    }
+    (None, line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kernel_hardware_errors() {
+        let output = r#"[    0.000000] Linux version 6.5.0-35-generic (buildd@lcy02-amd64-079)
+[ 1523.118734] mce: [Hardware Error]: Machine check events logged
+[ 1523.118790] mce: [Hardware Error]: CPU 12: Machine Check: 0 Bank 7: cc00008000010090
+[ 2210.442198] pcieport 0000:3a:00.0: AER: Corrected error received: 0000:3b:00.0
+[ 2210.442241] nvidia 0000:3b:00.0: PCIe Bus Error: severity=Corrected, type=Physical Layer, (Receiver ID)
+[ 2300.000012] NVRM: Xid (PCI:0000:3b:00): 79, pid=1234, GPU has fallen off the bus."#;
+
+        let errors = parse_kernel_hardware_errors(output);
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].category, "mce");
+        assert_eq!(errors[0].timestamp.as_deref(), Some("1523.118734"));
+        assert_eq!(
+            errors[0].message,
+            "mce: [Hardware Error]: Machine check events logged"
+        );
+        assert_eq!(errors[2].category, "pcie_aer");
+        assert_eq!(errors[2].timestamp.as_deref(), Some("2210.442198"));
+        assert!(errors[2].message.contains("AER: Corrected error received"));
+    }
+
+    #[test]
+    fn test_parse_kernel_hardware_errors_journal() {
+        let output = "Mar 12 10:15:42 gpu-node-01 kernel: blk_update_request: I/O error, dev sdb, sector 2048 op 0x0:(READ)\n\
+                      Mar 12 10:16:01 gpu-node-01 kernel: CPU0: Package temperature above threshold, cpu clock throttled";
+
+        let errors = parse_kernel_hardware_errors(output);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].category, "disk_io");
+        assert_eq!(errors[0].timestamp.as_deref(), Some("Mar 12 10:15:42"));
+        assert_eq!(errors[1].category, "thermal");
+    }
+}
@@ -16,8 +16,15 @@ limitations under the License.
 
 //! Memory information parsing functions
 
-use super::common::{bytes_to_human_readable, clean_value, parse_size_to_bytes};
-use crate::domain::{MemoryInfo, MemoryModule};
+use super::common::{clean_value, is_unknown, parse_size_to_bytes, UNKNOWN};
+use crate::domain::{format_bytes_iec, HugepagePool, MemoryInfo, MemoryModule, NvdimmNamespace};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref CHANNEL_RE: Regex = Regex::new(r"(?i)channel[\s_-]?([a-z0-9]+)").unwrap();
+    static ref DIMM_SLOT_RE: Regex = Regex::new(r"(?i)(?:dimm[\s_-]?|^)([a-z])\d+$").unwrap();
+}
 
 /// Parse memory information from Linux free command output
 ///
@@ -34,7 +41,7 @@ pub fn parse_free_output(free_output: &str) -> Result<String, String> {
             if parts.len() >= 2 {
                 if let Ok(total_kb) = parts[1].parse::<u64>() {
                     let total_bytes = total_kb * 1024;
-                    return Ok(bytes_to_human_readable(total_bytes));
+                    return Ok(format_bytes_iec(total_bytes));
                 }
             }
         }
@@ -54,6 +61,7 @@ pub fn parse_dmidecode_memory(dmidecode_output: &str) -> Result<Vec<MemoryModule
     let mut modules = Vec::new();
     let mut current_module: Option<MemoryModule> = None;
     let mut in_memory_device = false;
+    let mut bank_locator = String::new();
 
     for line in dmidecode_output.lines() {
         let trimmed = line.trim();
@@ -61,19 +69,22 @@ pub fn parse_dmidecode_memory(dmidecode_output: &str) -> Result<Vec<MemoryModule
         if trimmed.starts_with("Memory Device") {
             // Save previous module if it exists
             if let Some(module) = current_module.take() {
-                if module.size != "No Module Installed" && module.size != "Unknown" {
+                if module.size != "No Module Installed" && !is_unknown(&module.size) {
                     modules.push(module);
                 }
             }
 
             // Start new module
             current_module = Some(MemoryModule {
-                size: "Unknown".to_string(),
-                type_: "Unknown".to_string(),
-                speed: "Unknown".to_string(),
-                location: "Unknown".to_string(),
-                manufacturer: "Unknown".to_string(),
-                serial: "Unknown".to_string(),
+                size: UNKNOWN.to_string(),
+                type_: UNKNOWN.to_string(),
+                speed: UNKNOWN.to_string(),
+                location: UNKNOWN.to_string(),
+                manufacturer: UNKNOWN.to_string(),
+                serial: UNKNOWN.to_string(),
+                configured_speed: None,
+                rank: None,
+                channel: None,
             });
             in_memory_device = true;
             continue;
@@ -85,22 +96,37 @@ pub fn parse_dmidecode_memory(dmidecode_output: &str) -> Result<Vec<MemoryModule
 
         if let Some(ref mut module) = current_module {
             if trimmed.starts_with("Size:") {
-                let size = trimmed.split(':').nth(1).unwrap_or("Unknown").trim();
+                let size = trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim();
                 if size != "No Module Installed" {
                     module.size = clean_value(size);
                 }
             } else if trimmed.starts_with("Type:") {
-                module.type_ = clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+                module.type_ = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
             } else if trimmed.starts_with("Speed:") {
-                module.speed = clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+                module.speed = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
             } else if trimmed.starts_with("Locator:") {
-                module.location =
-                    clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+                module.location = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
+                module.channel = parse_memory_channel(&module.location, &bank_locator);
+            } else if trimmed.starts_with("Bank Locator:") {
+                bank_locator = clean_value(trimmed.split(':').nth(1).unwrap_or("").trim());
+                module.channel = parse_memory_channel(&module.location, &bank_locator);
+            } else if trimmed.starts_with("Rank:") {
+                let rank = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
+                if !is_unknown(&rank) {
+                    module.rank = Some(rank);
+                }
             } else if trimmed.starts_with("Manufacturer:") {
                 module.manufacturer =
-                    clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+                    clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
             } else if trimmed.starts_with("Serial Number:") {
-                module.serial = clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+                module.serial = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
+            } else if trimmed.starts_with("Configured Memory Speed:")
+                || trimmed.starts_with("Configured Clock Speed:")
+            {
+                let speed = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
+                if !is_unknown(&speed) {
+                    module.configured_speed = Some(speed);
+                }
             }
         }
 
@@ -112,7 +138,7 @@ pub fn parse_dmidecode_memory(dmidecode_output: &str) -> Result<Vec<MemoryModule
 
     // Save last module
     if let Some(module) = current_module {
-        if module.size != "No Module Installed" && module.size != "Unknown" {
+        if module.size != "No Module Installed" && !is_unknown(&module.size) {
             modules.push(module);
         }
     }
@@ -129,9 +155,9 @@ pub fn parse_dmidecode_memory(dmidecode_output: &str) -> Result<Vec<MemoryModule
 /// * `Ok(MemoryInfo)` - Parsed memory information
 /// * `Err(String)` - Parse error description
 pub fn parse_macos_memory_info(system_profiler_output: &str) -> Result<MemoryInfo, String> {
-    let mut total = "Unknown".to_string();
-    let mut type_ = "Unknown".to_string();
-    let mut manufacturer = "Unknown".to_string();
+    let mut total = UNKNOWN.to_string();
+    let mut type_ = UNKNOWN.to_string();
+    let mut manufacturer = UNKNOWN.to_string();
     let mut modules = Vec::new();
 
     for line in system_profiler_output.lines() {
@@ -141,32 +167,35 @@ pub fn parse_macos_memory_info(system_profiler_output: &str) -> Result<MemoryInf
             total = trimmed
                 .split(':')
                 .nth(1)
-                .unwrap_or("Unknown")
+                .unwrap_or(UNKNOWN)
                 .trim()
                 .to_string();
         } else if trimmed.starts_with("Type:") {
-            type_ = clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+            type_ = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
         } else if trimmed.starts_with("Manufacturer:") {
-            manufacturer = clean_value(trimmed.split(':').nth(1).unwrap_or("Unknown").trim());
+            manufacturer = clean_value(trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim());
         }
     }
 
     // For Apple Silicon, create a synthetic module entry
-    if type_ != "Unknown" || manufacturer != "Unknown" {
+    if !is_unknown(&type_) || !is_unknown(&manufacturer) {
         modules.push(MemoryModule {
             size: total.clone(),
             type_: type_.clone(),
             speed: "Integrated".to_string(),
             location: "System Memory".to_string(),
             manufacturer: manufacturer.clone(),
-            serial: "N/A".to_string(),
+            serial: UNKNOWN.to_string(),
+            configured_speed: None,
+            rank: None,
+            channel: None,
         });
     }
 
     let speed = if type_.contains("LPDDR") {
         "Integrated".to_string()
     } else {
-        "Unknown".to_string()
+        UNKNOWN.to_string()
     };
 
     Ok(MemoryInfo {
@@ -174,6 +203,10 @@ pub fn parse_macos_memory_info(system_profiler_output: &str) -> Result<MemoryInf
         type_,
         speed,
         modules,
+        transparent_hugepages: None,
+        hugepage_pools: Vec::new(),
+        nvdimm_namespaces: Vec::new(),
+        ecc_enabled: None,
     })
 }
 
@@ -202,9 +235,9 @@ pub fn calculate_total_memory_from_modules(modules: &[MemoryModule]) -> String {
         .sum();
 
     if total_bytes > 0 {
-        bytes_to_human_readable(total_bytes)
+        format_bytes_iec(total_bytes)
     } else {
-        "Unknown".to_string()
+        UNKNOWN.to_string()
     }
 }
 
@@ -217,7 +250,7 @@ pub fn calculate_total_memory_from_modules(modules: &[MemoryModule]) -> String {
 /// * Common memory type or "Mixed" if different types
 pub fn determine_memory_type(modules: &[MemoryModule]) -> String {
     if modules.is_empty() {
-        return "Unknown".to_string();
+        return UNKNOWN.to_string();
     }
 
     let first_type = &modules[0].type_;
@@ -237,7 +270,7 @@ pub fn determine_memory_type(modules: &[MemoryModule]) -> String {
 /// * Common memory speed or "Mixed" if different speeds
 pub fn determine_memory_speed(modules: &[MemoryModule]) -> String {
     if modules.is_empty() {
-        return "Unknown".to_string();
+        return UNKNOWN.to_string();
     }
 
     let first_speed = &modules[0].speed;
@@ -248,6 +281,249 @@ pub fn determine_memory_speed(modules: &[MemoryModule]) -> String {
     }
 }
 
+/// Check whether all memory modules run at their rated speed
+///
+/// Modules without a configured speed, or with a speed that cannot be parsed,
+/// are assumed to run at their rated speed.
+///
+/// # Arguments
+/// * `modules` - List of memory modules
+///
+/// # Returns
+/// * `false` if any module's configured speed is below its rated speed
+pub fn is_memory_running_at_rated_speed(modules: &[MemoryModule]) -> bool {
+    modules.iter().all(|module| {
+        let rated = parse_memory_speed(&module.speed);
+        let configured = module
+            .configured_speed
+            .as_deref()
+            .and_then(parse_memory_speed);
+        match (rated, configured) {
+            (Some(rated), Some(configured)) => configured >= rated,
+            _ => true,
+        }
+    })
+}
+
+/// Share of installed DIMM capacity the OS total may fall short by, covering
+/// firmware-reserved and kernel memory excluded from `MemTotal`
+const MEMORY_RESERVED_TOLERANCE_PERCENT: u64 = 5;
+
+/// Check whether total memory disagrees with the sum of installed DIMM sizes
+///
+/// # Arguments
+/// * `total_bytes` - Memory reported by the OS (`free`, `/proc/meminfo`, sysctl)
+/// * `modules` - Memory modules parsed from dmidecode
+///
+/// # Returns
+/// * `Some(dimm_bytes)` - Installed DIMM capacity, when the totals differ by
+///   more than the reserved-memory tolerance
+/// * `None` - The totals agree, or either total is unknown
+pub fn find_memory_size_mismatch(total_bytes: u64, modules: &[MemoryModule]) -> Option<u64> {
+    let dimm_bytes: u64 = modules
+        .iter()
+        .filter_map(|module| parse_size_to_bytes(&module.size).ok())
+        .sum();
+    if total_bytes == 0 || dimm_bytes == 0 {
+        return None;
+    }
+
+    (total_bytes.abs_diff(dimm_bytes) > dimm_bytes * MEMORY_RESERVED_TOLERANCE_PERCENT / 100)
+        .then_some(dimm_bytes)
+}
+
+/// Parse the memory channel of a DIMM from its dmidecode locators
+///
+/// Handles explicit channel names ("P0_Node0_Channel0_Dimm0", "ChannelA-DIMM0")
+/// and lettered slots ("CPU1_DIMM_A1", "P1-DIMMB2", "A1") where the letter is
+/// the channel.
+///
+/// # Arguments
+/// * `locator` - "Locator" value
+/// * `bank_locator` - "Bank Locator" value
+///
+/// # Returns
+/// * `Some(String)` - Channel within the socket (e.g., "0", "A")
+/// * `None` - Neither locator names a channel
+pub fn parse_memory_channel(locator: &str, bank_locator: &str) -> Option<String> {
+    [bank_locator, locator]
+        .iter()
+        .find_map(|value| CHANNEL_RE.captures(value))
+        .or_else(|| DIMM_SLOT_RE.captures(locator.trim()))
+        .map(|caps| caps[1].to_uppercase())
+}
+
+/// Parse the active transparent hugepage mode
+///
+/// # Arguments
+/// * `content` - Content of `/sys/kernel/mm/transparent_hugepage/enabled`
+///   (e.g., "always [madvise] never")
+///
+/// # Returns
+/// * `Some(String)` - The bracketed active mode
+/// * `None` - No active mode is marked
+pub fn parse_transparent_hugepages(content: &str) -> Option<String> {
+    content
+        .split_whitespace()
+        .find_map(|mode| mode.strip_prefix('[')?.strip_suffix(']'))
+        .map(|mode| mode.to_string())
+}
+
+/// Parse a hugepage size such as "1G" or "2M" into KiB
+fn parse_hugepage_size_kb(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (number, multiplier) = match size.char_indices().last()? {
+        (i, 'K' | 'k') => (&size[..i], 1),
+        (i, 'M' | 'm') => (&size[..i], 1024),
+        (i, 'G' | 'g') => (&size[..i], 1024 * 1024),
+        _ => (size, 1),
+    };
+    number.parse::<u64>().ok().map(|n| n * multiplier)
+}
+
+/// Parse hugepage pools requested on the kernel command line
+///
+/// Each `hugepages=` applies to the preceding `hugepagesz=`, or to the
+/// default hugepage size when none precedes it.
+///
+/// # Arguments
+/// * `cmdline` - Content of `/proc/cmdline`
+/// * `default_size_kb` - Default hugepage size (`Hugepagesize` in `/proc/meminfo`)
+///
+/// # Returns
+/// * Requested pools, with `allocated` left unset
+pub fn parse_cmdline_hugepages(cmdline: &str, default_size_kb: u64) -> Vec<HugepagePool> {
+    let mut pools = Vec::new();
+    let mut page_size_kb = default_size_kb;
+
+    for param in cmdline.split_whitespace() {
+        match param.split_once('=') {
+            Some(("hugepagesz", size)) => {
+                if let Some(size) = parse_hugepage_size_kb(size) {
+                    page_size_kb = size;
+                }
+            }
+            Some(("hugepages", count)) => {
+                // Per-node syntax ("0:64,1:64") is not summed here
+                if let Ok(requested) = count.parse::<u64>() {
+                    pools.push(HugepagePool {
+                        page_size_kb,
+                        requested,
+                        allocated: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    pools
+}
+
+/// Parse the default hugepage pool from `/proc/meminfo`
+///
+/// # Returns
+/// * `Some((page_size_kb, total_pages))` - `Hugepagesize` and `HugePages_Total`
+/// * `None` - Hugepages are not supported by the kernel
+pub fn parse_meminfo_hugepages(meminfo: &str) -> Option<(u64, u64)> {
+    let value = |key: &str| {
+        meminfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.split_whitespace().next()?.parse::<u64>().ok())?
+        })
+    };
+    Some((value("Hugepagesize")?, value("HugePages_Total")?))
+}
+
+/// Find requested hugepage pools that were allocated short
+///
+/// # Returns
+/// * Pools with fewer pages allocated than requested; pools that could not
+///   be read are skipped
+pub fn find_hugepage_shortfalls(pools: &[HugepagePool]) -> Vec<&HugepagePool> {
+    pools
+        .iter()
+        .filter(|pool| {
+            pool.allocated
+                .is_some_and(|allocated| allocated < pool.requested)
+        })
+        .collect()
+}
+
+/// Parse whether the system memory array uses error correction
+///
+/// # Arguments
+/// * `dmidecode_output` - Output from `dmidecode -t memory` (includes the
+///   Physical Memory Array, type 16)
+///
+/// # Returns
+/// * `Some(false)` - "Error Correction Type: None"
+/// * `Some(true)` - Parity, CRC or single/multi-bit ECC
+/// * `None` - No system memory array, or the type is "Unknown"/"Other"
+pub fn parse_dmidecode_memory_ecc(dmidecode_output: &str) -> Option<bool> {
+    let mut in_system_array = false;
+    for line in dmidecode_output.lines() {
+        let trimmed = line.trim();
+        if trimmed == "Physical Memory Array" {
+            in_system_array = false;
+        } else if let Some(use_) = trimmed.strip_prefix("Use:") {
+            in_system_array = use_.trim() == "System Memory";
+        } else if let Some(ecc) = trimmed.strip_prefix("Error Correction Type:") {
+            if !in_system_array {
+                continue;
+            }
+            return match ecc.trim() {
+                "None" => Some(false),
+                "Unknown" | "Other" => None,
+                _ => Some(true),
+            };
+        }
+    }
+    None
+}
+
+/// Parse persistent memory namespaces from `ndctl list -N` output
+///
+/// # Arguments
+/// * `output` - JSON from `ndctl list -N`; a single namespace may be printed
+///   as a bare object rather than an array
+///
+/// # Returns
+/// * Namespaces in the order ndctl lists them
+pub fn parse_ndctl_namespaces(output: &str) -> Result<Vec<NvdimmNamespace>, String> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid ndctl JSON: {e}"))?;
+    let entries = match value {
+        serde_json::Value::Array(entries) => entries,
+        entry => vec![entry],
+    };
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            // Sizes are bytes, or "125.01 GiB (134.23 GB)" strings with --human
+            let size = &entry["size"];
+            let size_bytes = size.as_u64().or_else(|| {
+                let human = size.as_str()?;
+                parse_size_to_bytes(human.split(" (").next()?).ok()
+            })?;
+            Some(NvdimmNamespace {
+                name: entry["dev"].as_str()?.to_string(),
+                mode: entry["mode"].as_str().unwrap_or(UNKNOWN).to_string(),
+                size_bytes,
+                blockdev: entry["blockdev"].as_str().map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+/// Parse the numeric part of a memory speed string (e.g., "4800 MT/s" -> 4800)
+fn parse_memory_speed(speed: &str) -> Option<u32> {
+    speed.split_whitespace().next()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,7 +535,7 @@ Mem:        16777216     8388608     4194304           0     4194304     8388608
 Swap:        2097152           0     2097152"#;
 
         let total_memory = parse_free_output(free_output).unwrap();
-        assert_eq!(total_memory, "16.0 GB");
+        assert_eq!(total_memory, "16.0 GiB");
     }
 
     #[test]
@@ -298,6 +574,10 @@ Swap:        2097152           0     2097152"#;
             type_: "DDR4".to_string(),
             speed: "3200 MT/s".to_string(),
             modules: vec![],
+            transparent_hugepages: None,
+            hugepage_pools: Vec::new(),
+            nvdimm_namespaces: Vec::new(),
+            ecc_enabled: None,
         };
 
         let config = create_memory_config_string(&memory_info);
@@ -314,6 +594,9 @@ Swap:        2097152           0     2097152"#;
                 location: "DIMM0".to_string(),
                 manufacturer: "Samsung".to_string(),
                 serial: "123".to_string(),
+                configured_speed: None,
+                rank: None,
+                channel: None,
             },
             MemoryModule {
                 size: "16 GB".to_string(),
@@ -322,10 +605,228 @@ Swap:        2097152           0     2097152"#;
                 location: "DIMM1".to_string(),
                 manufacturer: "Samsung".to_string(),
                 serial: "456".to_string(),
+                configured_speed: None,
+                rank: None,
+                channel: None,
             },
         ];
 
         assert_eq!(determine_memory_type(&modules), "DDR4");
         assert_eq!(determine_memory_speed(&modules), "3200 MT/s");
     }
+
+    #[test]
+    fn test_memory_running_below_rated_speed() {
+        let dmidecode_output = r#"Memory Device
+	Array Handle: 0x0026
+	Error Information Handle: Not Provided
+	Total Width: 80 bits
+	Data Width: 64 bits
+	Size: 64 GB
+	Form Factor: DIMM
+	Locator: P0 CHANNEL A
+	Bank Locator: BANK 0
+	Type: DDR5
+	Type Detail: Synchronous Registered (Buffered)
+	Speed: 4800 MT/s
+	Manufacturer: Samsung
+	Serial Number: 80CE0123
+	Part Number: M321R8GA0BB0-CQKZJ
+	Rank: 2
+	Configured Memory Speed: 4000 MT/s"#;
+
+        let modules = parse_dmidecode_memory(dmidecode_output).unwrap();
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0].speed, "4800 MT/s");
+        assert_eq!(modules[0].configured_speed.as_deref(), Some("4000 MT/s"));
+        assert!(!is_memory_running_at_rated_speed(&modules));
+    }
+
+    #[test]
+    fn test_memory_size_mismatch_with_missing_dimms() {
+        let dmidecode_output =
+            "Memory Device\n\tSize: 32 GB\n\tLocator: DIMM_A1\n\tType: DDR5\n\tSpeed: 4800 MT/s";
+        let module = parse_dmidecode_memory(dmidecode_output).unwrap().remove(0);
+        let modules = vec![module; 16];
+        let gib = 1024 * 1024 * 1024;
+
+        // 512 GB of DIMMs but only 384 GB visible to the OS
+        assert_eq!(
+            find_memory_size_mismatch(384 * gib, &modules),
+            Some(512 * gib)
+        );
+        // A few GB reserved by firmware and the kernel is expected
+        assert_eq!(find_memory_size_mismatch(503 * gib, &modules), None);
+        assert_eq!(find_memory_size_mismatch(0, &modules), None);
+    }
+
+    #[test]
+    fn test_parse_transparent_hugepages() {
+        assert_eq!(
+            parse_transparent_hugepages("[always] madvise never\n"),
+            Some("always".to_string())
+        );
+        assert_eq!(
+            parse_transparent_hugepages("always [madvise] never"),
+            Some("madvise".to_string())
+        );
+        assert_eq!(parse_transparent_hugepages("always madvise never"), None);
+    }
+
+    #[test]
+    fn test_parse_cmdline_hugepages() {
+        let cmdline = "BOOT_IMAGE=/vmlinuz ro default_hugepagesz=1G hugepagesz=1G hugepages=128 hugepagesz=2M hugepages=1024";
+        assert_eq!(
+            parse_cmdline_hugepages(cmdline, 1_048_576),
+            vec![
+                HugepagePool {
+                    page_size_kb: 1_048_576,
+                    requested: 128,
+                    allocated: None,
+                },
+                HugepagePool {
+                    page_size_kb: 2048,
+                    requested: 1024,
+                    allocated: None,
+                },
+            ]
+        );
+
+        // Without hugepagesz the default size applies
+        assert_eq!(
+            parse_cmdline_hugepages("ro hugepages=64", 2048)[0].page_size_kb,
+            2048
+        );
+        assert!(parse_cmdline_hugepages("ro quiet", 2048).is_empty());
+    }
+
+    #[test]
+    fn test_parse_meminfo_hugepages() {
+        let meminfo = "MemTotal:       2113486848 kB\nHugePages_Total:     120\nHugePages_Free:      120\nHugepagesize:    1048576 kB\n";
+        assert_eq!(parse_meminfo_hugepages(meminfo), Some((1_048_576, 120)));
+        assert_eq!(parse_meminfo_hugepages("MemTotal: 1024 kB\n"), None);
+    }
+
+    #[test]
+    fn test_parse_dmidecode_memory_rank_and_channel() {
+        let dmidecode_output = r#"Memory Device
+	Size: 64 GB
+	Locator: DIMM 0
+	Bank Locator: P0_Node0_Channel0_Dimm0
+	Type: DDR5
+	Speed: 4800 MT/s
+	Manufacturer: Samsung
+	Serial Number: 80CE01234567
+	Rank: 2
+
+Memory Device
+	Size: 64 GB
+	Locator: DIMM 0
+	Bank Locator: P0_Node0_Channel3_Dimm0
+	Type: DDR5
+	Speed: 4800 MT/s
+	Manufacturer: Samsung
+	Serial Number: 80CE01234568
+	Rank: Unknown"#;
+
+        let modules = parse_dmidecode_memory(dmidecode_output).unwrap();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].rank.as_deref(), Some("2"));
+        assert_eq!(modules[0].channel.as_deref(), Some("0"));
+        assert_eq!(modules[1].rank, None);
+        assert_eq!(modules[1].channel.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_parse_memory_channel() {
+        assert_eq!(
+            parse_memory_channel("DIMM 0", "P0_Node0_Channel0_Dimm0"),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            parse_memory_channel("ChannelA-DIMM0", "BANK 0"),
+            Some("A".to_string())
+        );
+        assert_eq!(
+            parse_memory_channel("CPU1_DIMM_B1", "NODE 1"),
+            Some("B".to_string())
+        );
+        assert_eq!(
+            parse_memory_channel("P2-DIMMC1", "P1_Node1"),
+            Some("C".to_string())
+        );
+        assert_eq!(
+            parse_memory_channel("A1", "Not Specified"),
+            Some("A".to_string())
+        );
+        assert_eq!(parse_memory_channel("DIMM 0", "BANK 0"), None);
+    }
+
+    #[test]
+    fn test_parse_ndctl_namespaces() {
+        let output = r#"[
+  {
+    "dev":"namespace1.0",
+    "mode":"fsdax",
+    "map":"dev",
+    "size":266352984064,
+    "uuid":"a6b4c7f2-1d3e-4b1a-9c2f-3e8d7a6b5c4d",
+    "sector_size":512,
+    "align":2097152,
+    "blockdev":"pmem1"
+  },
+  {
+    "dev":"namespace0.0",
+    "mode":"devdax",
+    "map":"dev",
+    "size":266352984064,
+    "uuid":"0e3a2b1c-5d4f-4e6a-8b7c-9d0e1f2a3b4c",
+    "chardev":"dax0.0",
+    "align":2097152
+  }
+]"#;
+        let namespaces = parse_ndctl_namespaces(output).unwrap();
+        assert_eq!(namespaces.len(), 2);
+        assert_eq!(
+            namespaces[0],
+            NvdimmNamespace {
+                name: "namespace1.0".to_string(),
+                mode: "fsdax".to_string(),
+                size_bytes: 266352984064,
+                blockdev: Some("pmem1".to_string()),
+            }
+        );
+        assert_eq!(namespaces[1].blockdev, None);
+
+        let single = r#"{"dev":"namespace0.0","mode":"sector","size":"248.06 GiB (266.35 GB)","blockdev":"pmem0s"}"#;
+        let namespaces = parse_ndctl_namespaces(single).unwrap();
+        assert_eq!(namespaces[0].mode, "sector");
+        assert_eq!(namespaces[0].size_bytes / (1 << 30), 248);
+
+        assert!(parse_ndctl_namespaces("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_dmidecode_memory_ecc() {
+        let array = |ecc: &str| {
+            format!(
+                "Handle 0x1000, DMI type 16, 23 bytes\n\
+                 Physical Memory Array\n\
+                 \tLocation: System Board Or Motherboard\n\
+                 \tUse: System Memory\n\
+                 \tError Correction Type: {ecc}\n\
+                 \tMaximum Capacity: 2 TB\n"
+            )
+        };
+        assert_eq!(
+            parse_dmidecode_memory_ecc(&array("Multi-bit ECC")),
+            Some(true)
+        );
+        assert_eq!(parse_dmidecode_memory_ecc(&array("None")), Some(false));
+        assert_eq!(parse_dmidecode_memory_ecc(&array("Unknown")), None);
+
+        // Flash arrays do not describe system memory
+        let flash = array("None").replace("System Memory", "Flash Memory");
+        assert_eq!(parse_dmidecode_memory_ecc(&flash), None);
+    }
 }
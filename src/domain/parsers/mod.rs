@@ -19,18 +19,34 @@ limitations under the License.
 //! These functions are pure (no side effects) and can be easily tested in isolation.
 //! They take string input and return domain objects or parsing errors.
 
+pub mod accelerator;
+pub mod bios;
+pub mod cloud;
 pub mod common;
 pub mod cpu;
 pub mod gpu;
+pub mod kernel;
 pub mod memory;
 pub mod network;
+pub mod numa;
+pub mod pcie;
+pub mod power;
+pub mod redfish;
 pub mod storage;
 pub mod system;
 
+pub use accelerator::*;
+pub use bios::*;
+pub use cloud::*;
 pub use common::*;
 pub use cpu::*;
 pub use gpu::*;
+pub use kernel::*;
 pub use memory::*;
 pub use network::*;
+pub use numa::*;
+pub use pcie::*;
+pub use power::*;
+pub use redfish::*;
 pub use storage::*;
 pub use system::*;
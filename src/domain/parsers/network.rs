@@ -16,7 +16,9 @@ limitations under the License.
 
 //! Network information parsing functions
 
+use super::common::UNKNOWN;
 use crate::domain::NetworkInterface;
+use regex::Regex;
 
 /// Parse network interfaces from `ip addr show` command output
 pub fn parse_ip_output(ip_output: &str) -> Result<Vec<NetworkInterface>, String> {
@@ -66,9 +68,9 @@ pub fn parse_ip_output(ip_output: &str) -> Result<Vec<NetworkInterface>, String>
                     prefix: String::new(),
                     speed: None,
                     type_: interface_type,
-                    vendor: "Unknown".to_string(),
-                    model: "Unknown".to_string(),
-                    pci_id: "Unknown".to_string(),
+                    vendor: UNKNOWN.to_string(),
+                    model: UNKNOWN.to_string(),
+                    pci_id: UNKNOWN.to_string(),
                     numa_node: None,
                     ..Default::default()
                 });
@@ -114,6 +116,193 @@ pub fn parse_ip_output(ip_output: &str) -> Result<Vec<NetworkInterface>, String>
     Ok(interfaces)
 }
 
+/// Parse a sysfs interface statistics counter
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/class/net/{iface}/statistics/{counter}`
+pub fn parse_sysfs_counter(content: &str) -> Option<u64> {
+    content.trim().parse().ok()
+}
+
+/// Parse current ring sizes from `ethtool -g` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `ethtool -g {iface}`
+///
+/// # Returns
+///
+/// `(rx_ring, tx_ring)` from the "Current hardware settings" section
+pub fn parse_ethtool_ring(output: &str) -> (Option<u32>, Option<u32>) {
+    (
+        ethtool_current_setting(output, "RX"),
+        ethtool_current_setting(output, "TX"),
+    )
+}
+
+/// Parse the current combined queue count from `ethtool -l` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `ethtool -l {iface}`
+pub fn parse_ethtool_channels(output: &str) -> Option<u32> {
+    ethtool_current_setting(output, "Combined")
+}
+
+/// Read a numeric value from the "Current hardware settings" section of
+/// `ethtool -g`/`-l` output; "n/a" values are `None`
+fn ethtool_current_setting(output: &str, key: &str) -> Option<u32> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("Current hardware settings"))
+        .skip(1)
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().parse().ok())?
+        })
+}
+
+/// Parse a link speed string into megabits per second
+///
+/// # Arguments
+///
+/// * `speed` - Speed such as "1000 Mbps", "25000Mb/s" or "100 Gbps"
+///
+/// # Returns
+///
+/// `None` for strings without a recognized unit (e.g., "Unknown!").
+pub fn parse_link_speed_mbps(speed: &str) -> Option<u32> {
+    let speed = speed.replace(' ', "").to_lowercase();
+    let digits_end = speed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(speed.len());
+    let value: f64 = speed[..digits_end].parse().ok()?;
+    let multiplier = match speed[digits_end..]
+        .trim_end_matches("/s")
+        .trim_end_matches("ps")
+    {
+        "kb" => 1e-3,
+        "mb" => 1.0,
+        "gb" => 1e3,
+        _ => return None,
+    };
+    Some((value * multiplier) as u32)
+}
+
+/// Find bonds whose members negotiated different link speeds
+///
+/// A member running slower than its peers silently caps the bond's throughput.
+/// Members without a known speed (e.g., link down) are ignored.
+///
+/// # Arguments
+///
+/// * `interfaces` - All interfaces, with `bond_members` and `speed_mbps` populated
+///
+/// # Returns
+///
+/// Name and distinct member speeds (ascending, in Mbps) of each mismatched bond.
+pub fn find_bond_speed_mismatches(interfaces: &[NetworkInterface]) -> Vec<(String, Vec<u32>)> {
+    let speeds: std::collections::HashMap<&str, u32> = interfaces
+        .iter()
+        .filter_map(|iface| iface.speed_mbps.map(|speed| (iface.name.as_str(), speed)))
+        .collect();
+
+    interfaces
+        .iter()
+        .filter_map(|iface| {
+            let mut member_speeds: Vec<u32> = iface
+                .bond_members
+                .iter()
+                .filter_map(|member| speeds.get(member.as_str()).copied())
+                .collect();
+            member_speeds.sort_unstable();
+            member_speeds.dedup();
+            (member_speeds.len() > 1).then(|| (iface.name.clone(), member_speeds))
+        })
+        .collect()
+}
+
+/// Interface name prefixes created by container runtimes and CNI plugins
+const CONTAINER_INTERFACE_PREFIXES: &[&str] = &["veth", "cni", "flannel", "cali", "docker"];
+
+/// Whether an interface belongs to a container network namespace or bridge
+///
+/// # Arguments
+///
+/// * `name` - Interface name (e.g., "veth3a1f2c", "cni0", "flannel.1", "cali8d2e")
+pub fn is_container_interface(name: &str) -> bool {
+    CONTAINER_INTERFACE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Count NICs for the report summary
+///
+/// # Arguments
+///
+/// * `interfaces` - All interfaces, with `is_virtual` populated
+/// * `include_virtual` - Also count virtual and container interfaces
+pub fn count_nics(interfaces: &[NetworkInterface], include_virtual: bool) -> usize {
+    interfaces
+        .iter()
+        .filter(|iface| include_virtual || !iface.is_virtual)
+        .count()
+}
+
+/// Find data interfaces whose MTUs disagree
+///
+/// Storage fabrics need every data NIC on the same (usually jumbo) MTU; a
+/// single interface left at 1500 causes fragmentation or dropped frames.
+///
+/// # Arguments
+///
+/// * `interfaces` - All interfaces, with `mtu` populated
+/// * `data_interfaces` - Pattern selecting the data interfaces by name
+///
+/// # Returns
+///
+/// Name and MTU of every matching interface when they disagree, otherwise empty.
+pub fn find_data_mtu_mismatch(
+    interfaces: &[NetworkInterface],
+    data_interfaces: &Regex,
+) -> Vec<(String, u32)> {
+    let data: Vec<(String, u32)> = interfaces
+        .iter()
+        .filter(|iface| data_interfaces.is_match(&iface.name))
+        .map(|iface| (iface.name.clone(), iface.mtu))
+        .collect();
+
+    if data.windows(2).all(|pair| pair[0].1 == pair[1].1) {
+        return Vec::new();
+    }
+    data
+}
+
+/// Parse a Fibre Channel World Wide Name from sysfs
+///
+/// # Arguments
+///
+/// * `content` - Content of e.g. `/sys/class/fc_host/{host}/port_name` ("0x21000024ff7b1a2c")
+///
+/// # Returns
+///
+/// Colon-separated WWN (e.g., "21:00:00:24:ff:7b:1a:2c"), or `None` if invalid.
+pub fn parse_fc_wwn(content: &str) -> Option<String> {
+    let hex = content.trim().trim_start_matches("0x");
+    if hex.len() != 16 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).to_lowercase())
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
 /// Parse network interfaces from macOS ifconfig output  
 pub fn parse_macos_network_info(ifconfig_output: &str) -> Result<Vec<NetworkInterface>, String> {
     let mut interfaces = Vec::new();
@@ -136,26 +325,26 @@ pub fn parse_macos_network_info(ifconfig_output: &str) -> Result<Vec<NetworkInte
                 let vendor = if name.starts_with("en") || name.starts_with("bridge") {
                     "Apple"
                 } else {
-                    "Unknown"
+                    UNKNOWN
                 };
                 let model = match interface_type.as_str() {
                     "AirPort" => "Wi-Fi 802.11 a/b/g/n/ac/ax",
                     "Ethernet" => "Ethernet",
-                    "VPN (io.tailscale.ipn.macos)" => "Unknown",
-                    _ => "Unknown",
+                    "VPN (io.tailscale.ipn.macos)" => UNKNOWN,
+                    _ => UNKNOWN,
                 };
 
                 let pci_id = if vendor == "Apple" {
                     "Apple Fabric (Integrated)".to_string()
                 } else {
-                    "Unknown".to_string()
+                    UNKNOWN.to_string()
                 };
 
                 current_interface = Some(NetworkInterface {
                     name: name.to_string(),
-                    mac: "Unknown".to_string(),
-                    ip: "Unknown".to_string(),
-                    prefix: "Unknown".to_string(),
+                    mac: UNKNOWN.to_string(),
+                    ip: UNKNOWN.to_string(),
+                    prefix: UNKNOWN.to_string(),
                     speed: None,
                     type_: interface_type,
                     vendor: vendor.to_string(),
@@ -208,7 +397,7 @@ fn classify_interface_type(name: &str) -> String {
     } else if name.starts_with("lo") {
         "Loopback".to_string()
     } else {
-        "Unknown".to_string()
+        UNKNOWN.to_string()
     }
 }
 
@@ -217,6 +406,136 @@ fn estimate_interface_speed(name: &str, interface_type: &str) -> Option<String>
     match interface_type {
         "AirPort" => Some("1200 Mbps".to_string()), // Wi-Fi 6 typical
         "Ethernet" if name.starts_with("en") => Some("1000 Mbps".to_string()),
-        _ => Some("Unknown".to_string()),
+        _ => Some(UNKNOWN.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link_speed_mbps() {
+        assert_eq!(parse_link_speed_mbps("25000Mb/s"), Some(25_000));
+        assert_eq!(parse_link_speed_mbps("1000 Mbps"), Some(1_000));
+        assert_eq!(parse_link_speed_mbps("100 Gbps"), Some(100_000));
+        assert_eq!(parse_link_speed_mbps("Unknown!"), None);
+    }
+
+    fn iface(name: &str, speed_mbps: Option<u32>, bond_members: &[&str]) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            speed_mbps,
+            bond_members: bond_members.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_ethtool_ring_and_channels() {
+        // ConnectX-6 Dx with ethtool 6.x, which adds extra ring fields
+        let ring = "Ring parameters for ens1f0np0:
+Pre-set maximums:
+RX:		8192
+RX Mini:	n/a
+RX Jumbo:	n/a
+TX:		8192
+Current hardware settings:
+RX:		1024
+RX Mini:	n/a
+RX Jumbo:	n/a
+TX:		2048
+RX Buf Len:	n/a
+CQE Size:	n/a
+TX Push:	off
+TCP data split:	off
+";
+        assert_eq!(parse_ethtool_ring(ring), (Some(1024), Some(2048)));
+
+        let channels = "Channel parameters for ens1f0np0:
+Pre-set maximums:
+RX:		n/a
+TX:		n/a
+Other:		n/a
+Combined:	63
+Current hardware settings:
+RX:		n/a
+TX:		n/a
+Other:		n/a
+Combined:	16
+";
+        assert_eq!(parse_ethtool_channels(channels), Some(16));
+        assert_eq!(
+            parse_ethtool_channels("netlink error: Operation not supported"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_bond_speed_mismatches() {
+        let interfaces = vec![
+            iface("bond0", Some(35000), &["ens1f0", "ens1f1"]),
+            iface("ens1f0", Some(25000), &[]),
+            iface("ens1f1", Some(10000), &[]),
+            iface("bond1", Some(50000), &["ens2f0", "ens2f1"]),
+            iface("ens2f0", Some(25000), &[]),
+            iface("ens2f1", Some(25000), &[]),
+        ];
+
+        // bond1's members agree, and plain interfaces are not bonds
+        assert_eq!(
+            find_bond_speed_mismatches(&interfaces),
+            vec![("bond0".to_string(), vec![10000, 25000])]
+        );
+    }
+
+    #[test]
+    fn test_find_data_mtu_mismatch() {
+        let data_interfaces = Regex::new("^ens").unwrap();
+        let mut interfaces = vec![
+            NetworkInterface {
+                name: "ens1f0".to_string(),
+                mtu: 9000,
+                ..Default::default()
+            },
+            NetworkInterface {
+                name: "ens2f0".to_string(),
+                mtu: 1500,
+                ..Default::default()
+            },
+            // Management NIC is outside the pattern and ignored
+            NetworkInterface {
+                name: "eno1".to_string(),
+                mtu: 1500,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            find_data_mtu_mismatch(&interfaces, &data_interfaces),
+            vec![("ens1f0".to_string(), 9000), ("ens2f0".to_string(), 1500)]
+        );
+
+        interfaces[1].mtu = 9000;
+        assert!(find_data_mtu_mismatch(&interfaces, &data_interfaces).is_empty());
+    }
+
+    #[test]
+    fn test_count_nics_skips_container_interfaces() {
+        let interfaces: Vec<NetworkInterface> = ["eth0", "veth3a1f2c", "veth9b0e47", "cni0"]
+            .into_iter()
+            .map(|name| NetworkInterface {
+                name: name.to_string(),
+                is_virtual: is_container_interface(name),
+                ..Default::default()
+            })
+            .collect();
+
+        assert!(!interfaces[0].is_virtual);
+        assert!(interfaces[1..].iter().all(|iface| iface.is_virtual));
+        assert_eq!(count_nics(&interfaces, false), 1);
+        assert_eq!(count_nics(&interfaces, true), 4);
+        assert!(is_container_interface("flannel.1"));
+        assert!(is_container_interface("cali8d2e41a7f3c"));
     }
 }
@@ -0,0 +1,291 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! NUMA topology parsing functions
+
+use crate::domain::{GpuDevice, NumaNode};
+use std::collections::HashMap;
+
+/// Distance below which a memory-only node is considered on-package HBM
+const HBM_MAX_DISTANCE: u32 = 30;
+
+/// Parse a sysfs CPU list (e.g. "0-3,8,10-11")
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/devices/system/node/node*/cpulist`
+///
+/// # Returns
+///
+/// * `Ok(Vec<u32>)` - Expanded list of CPU IDs
+/// * `Err(String)` - Parsing error
+pub fn parse_cpu_list(content: &str) -> Result<Vec<u32>, String> {
+    let mut cpus = Vec::new();
+
+    for range in content.trim().split(',').filter(|r| !r.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let start: u32 = start
+                    .parse()
+                    .map_err(|_| format!("Invalid CPU range: {range}"))?;
+                let end: u32 = end
+                    .parse()
+                    .map_err(|_| format!("Invalid CPU range: {range}"))?;
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(
+                range
+                    .parse()
+                    .map_err(|_| format!("Invalid CPU ID: {range}"))?,
+            ),
+        }
+    }
+
+    Ok(cpus)
+}
+
+/// Format a CPU list in the compact sysfs range form
+///
+/// # Arguments
+///
+/// * `cpus` - Logical CPU IDs, in any order
+///
+/// # Returns
+///
+/// Comma-separated ranges (e.g., "0-63,128-191"), or an empty string for no CPUs.
+pub fn format_cpu_list(cpus: &[u32]) -> String {
+    let mut sorted = cpus.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = sorted.into_iter().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().unwrap_or(end);
+        }
+        ranges.push(if start == end {
+            start.to_string()
+        } else {
+            format!("{start}-{end}")
+        });
+    }
+
+    ranges.join(",")
+}
+
+/// Parse total memory of a NUMA node
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/devices/system/node/node*/meminfo`
+///
+/// # Returns
+///
+/// * `Some(u64)` - Total node memory in kB
+/// * `None` - MemTotal line not found
+pub fn parse_node_meminfo_total(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find(|line| line.contains("MemTotal:"))
+        .and_then(|line| line.split(':').nth(1))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+/// Parse NUMA node distances
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/devices/system/node/node*/distance`
+///
+/// # Returns
+///
+/// * `HashMap<String, u32>` - Distance to each node, keyed by node ID
+pub fn parse_node_distances(content: &str) -> HashMap<String, u32> {
+    content
+        .split_whitespace()
+        .enumerate()
+        .filter_map(|(id, distance)| distance.parse().ok().map(|d| (id.to_string(), d)))
+        .collect()
+}
+
+/// Infer the memory tier of each NUMA node from the topology
+///
+/// Nodes with CPUs are treated as DDR. Memory-only nodes close to a CPU
+/// node (as on Xeon Max flat mode) are treated as HBM, while distant
+/// memory-only nodes are treated as CXL-attached memory.
+///
+/// # Arguments
+///
+/// * `nodes` - NUMA nodes keyed by node ID
+pub fn infer_numa_memory_tiers(nodes: &mut HashMap<String, NumaNode>) {
+    let cpu_nodes: Vec<String> = nodes
+        .iter()
+        .filter(|(_, node)| !node.cpus.is_empty())
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    for node in nodes.values_mut() {
+        let tier = if !node.cpus.is_empty() {
+            "DDR"
+        } else {
+            let nearest_cpu_node = cpu_nodes
+                .iter()
+                .filter_map(|id| node.distances.get(id))
+                .min()
+                .copied();
+
+            match nearest_cpu_node {
+                Some(distance) if distance < HBM_MAX_DISTANCE => "HBM",
+                Some(_) => "CXL",
+                None => continue,
+            }
+        };
+        node.memory_tier = Some(tier.to_string());
+    }
+}
+
+/// Set each GPU's recommended CPU affinity to the CPU range of its NUMA node
+///
+/// GPUs without a NUMA node, or on a node with no CPUs, are left unset.
+///
+/// # Arguments
+///
+/// * `gpus` - GPU devices with `numa_node` populated
+/// * `nodes` - NUMA nodes keyed by node ID
+pub fn assign_gpu_cpu_affinity(gpus: &mut [GpuDevice], nodes: &HashMap<String, NumaNode>) {
+    for gpu in gpus {
+        gpu.recommended_cpu_affinity = gpu
+            .numa_node
+            .and_then(|id| nodes.values().find(|node| node.id == id))
+            .filter(|node| !node.cpu_ranges.is_empty())
+            .map(|node| node.cpu_ranges.clone());
+    }
+}
+
+/// Parse the automatic NUMA balancing sysctl
+///
+/// # Arguments
+///
+/// * `content` - Content of `/proc/sys/kernel/numa_balancing`
+///
+/// # Returns
+///
+/// * `Some(bool)` - Whether balancing is enabled; any non-zero mode
+///   (including memory tiering, `2`) counts as enabled
+/// * `None` - Content is not a number
+pub fn parse_numa_balancing(content: &str) -> Option<bool> {
+    content.trim().parse::<u8>().ok().map(|mode| mode != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32, cpus: Vec<u32>, distances: &str) -> NumaNode {
+        NumaNode {
+            id,
+            cpu_ranges: format_cpu_list(&cpus),
+            cpus,
+            memory: "16384 MB".to_string(),
+            devices: Vec::new(),
+            distances: parse_node_distances(distances),
+            memory_tier: None,
+        }
+    }
+
+    #[test]
+    fn test_assign_gpu_cpu_affinity() {
+        let nodes: HashMap<String, NumaNode> = [
+            node(0, (0..16).collect(), "10 21"),
+            node(1, (16..32).collect(), "21 10"),
+        ]
+        .into_iter()
+        .map(|node| (node.id.to_string(), node))
+        .collect();
+
+        let mut gpus = vec![
+            GpuDevice {
+                index: 0,
+                numa_node: Some(1),
+                ..Default::default()
+            },
+            GpuDevice {
+                index: 1,
+                numa_node: None,
+                ..Default::default()
+            },
+        ];
+        assign_gpu_cpu_affinity(&mut gpus, &nodes);
+
+        assert_eq!(gpus[0].recommended_cpu_affinity.as_deref(), Some("16-31"));
+        assert_eq!(gpus[1].recommended_cpu_affinity, None);
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11\n").unwrap(),
+            vec![0, 1, 2, 3, 8, 10, 11]
+        );
+        assert!(parse_cpu_list("\n").unwrap().is_empty());
+        assert!(parse_cpu_list("0-a").is_err());
+    }
+
+    #[test]
+    fn test_parse_cpu_list_offline() {
+        // /sys/devices/system/cpu/offline
+        assert_eq!(parse_cpu_list("5,7-9\n").unwrap(), vec![5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_format_cpu_list() {
+        assert_eq!(format_cpu_list(&[0, 1, 2, 3, 8, 9]), "0-3,8-9");
+        assert_eq!(format_cpu_list(&[9, 8, 5, 0, 1, 2, 3]), "0-3,5,8-9");
+        assert_eq!(format_cpu_list(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_node_meminfo_total() {
+        let meminfo = "Node 1 MemTotal:       16777216 kB\nNode 1 MemFree:        16000000 kB\n";
+        assert_eq!(parse_node_meminfo_total(meminfo), Some(16777216));
+        assert_eq!(parse_node_meminfo_total(""), None);
+    }
+
+    #[test]
+    fn test_infer_numa_memory_tiers() {
+        let mut nodes = HashMap::new();
+        nodes.insert("0".to_string(), node(0, vec![0, 1, 2, 3], "10 13 50"));
+        nodes.insert("1".to_string(), node(1, Vec::new(), "13 10 60"));
+        nodes.insert("2".to_string(), node(2, Vec::new(), "50 60 10"));
+
+        infer_numa_memory_tiers(&mut nodes);
+
+        assert_eq!(nodes["0"].memory_tier.as_deref(), Some("DDR"));
+        assert_eq!(nodes["1"].memory_tier.as_deref(), Some("HBM"));
+        assert_eq!(nodes["2"].memory_tier.as_deref(), Some("CXL"));
+    }
+
+    #[test]
+    fn test_parse_numa_balancing() {
+        assert_eq!(parse_numa_balancing("0\n"), Some(false));
+        assert_eq!(parse_numa_balancing("1\n"), Some(true));
+        assert_eq!(parse_numa_balancing("2\n"), Some(true));
+        assert_eq!(parse_numa_balancing(""), None);
+    }
+}
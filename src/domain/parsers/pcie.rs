@@ -0,0 +1,576 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! PCIe topology parsing functions
+
+use crate::domain::{GpuDevice, GpuNicAffinity, GpuNicLocality, GpuNicPair, NetworkInterface};
+use std::collections::HashSet;
+
+/// Shared bridges needed for two devices to sit behind the same switch
+/// (root port plus the switch upstream port)
+const SWITCH_SHARED_BRIDGES: usize = 2;
+
+/// Parse the upstream PCIe bridges out of a canonical sysfs device path
+///
+/// # Arguments
+///
+/// * `device_path` - Resolved `/sys/bus/pci/devices/<address>` path, e.g.
+///   `/sys/devices/pci0000:00/0000:00:01.0/0000:01:00.0/0000:02:08.0/0000:03:00.0`
+///
+/// # Returns
+///
+/// * `Vec<String>` - Bridge addresses from the root port down, excluding the device itself
+pub fn parse_pcie_path(device_path: &str) -> Vec<String> {
+    let mut addresses: Vec<String> = device_path
+        .split('/')
+        .filter(|component| is_pci_address(component))
+        .map(str::to_lowercase)
+        .collect();
+    addresses.pop();
+    addresses
+}
+
+fn is_pci_address(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    bytes.len() == 12
+        && bytes[4] == b':'
+        && bytes[7] == b':'
+        && bytes[10] == b'.'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| matches!(i, 4 | 7 | 10) || b.is_ascii_hexdigit())
+}
+
+fn shared_bridges(a: &[String], b: &[String]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Parse the ASPM state from the `LnkCtl` line of `lspci -vv`
+///
+/// # Arguments
+///
+/// * `output` - Output of `lspci -vv -s <address>`
+///
+/// # Returns
+///
+/// * `Some(String)` - "disabled", or the enabled states (e.g., "L1", "L0s L1")
+/// * `None` - No link control capability
+pub fn parse_lspci_aspm(output: &str) -> Option<String> {
+    // "LnkCtl:	ASPM L0s L1 Enabled; RCB 64 bytes, Disabled- CommClk+"
+    let link_control = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("LnkCtl:"))?;
+    let aspm = link_control
+        .split(';')
+        .next()?
+        .trim()
+        .strip_prefix("ASPM")?
+        .trim();
+
+    if aspm == "Disabled" {
+        return Some("disabled".to_string());
+    }
+    aspm.strip_suffix("Enabled")
+        .map(|states| states.trim().to_string())
+        .filter(|states| !states.is_empty())
+}
+
+/// Build an ASPM state from the sysfs `link/l0s_aspm` and `link/l1_aspm` flags
+///
+/// # Arguments
+///
+/// * `l0s` - Whether L0s is enabled, if the attribute exists
+/// * `l1` - Whether L1 is enabled, if the attribute exists
+///
+/// # Returns
+///
+/// Same vocabulary as [`parse_lspci_aspm`], or `None` when neither attribute exists.
+pub fn aspm_state_from_link_flags(l0s: Option<bool>, l1: Option<bool>) -> Option<String> {
+    if l0s.is_none() && l1.is_none() {
+        return None;
+    }
+    let states: Vec<&str> = [("L0s", l0s), ("L1", l1)]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled == Some(true))
+        .map(|(state, _)| state)
+        .collect();
+    Some(if states.is_empty() {
+        "disabled".to_string()
+    } else {
+        states.join(" ")
+    })
+}
+
+/// Parse a sysfs PCIe link width attribute
+///
+/// # Arguments
+///
+/// * `content` - Content of `current_link_width` or `max_link_width`
+///
+/// # Returns
+///
+/// * `Some(u8)` - Lane count
+/// * `None` - Unparseable, or 0 (link down)
+pub fn parse_pcie_link_width(content: &str) -> Option<u8> {
+    content.trim().parse().ok().filter(|&width| width > 0)
+}
+
+/// Parse a sysfs PCIe link speed attribute into a PCIe generation
+///
+/// # Arguments
+///
+/// * `content` - Content of `current_link_speed` or `max_link_speed`
+///   (e.g., "16.0 GT/s PCIe" or "8.0 GT/s")
+///
+/// # Returns
+///
+/// * `Some(u8)` - PCIe generation (8.0 GT/s is gen 3, 16.0 GT/s gen 4, ...)
+/// * `None` - Unknown speed (e.g., "Unknown" for a link that is down)
+pub fn parse_pcie_link_speed_gen(content: &str) -> Option<u8> {
+    let transfer_rate: f64 = content.split_whitespace().next()?.parse().ok()?;
+    // Gen 1 and 2 doubled the signalling rate with 8b/10b; from gen 3
+    // (128b/130b) each generation doubles 8 GT/s
+    if transfer_rate >= 8.0 {
+        Some(3 + (transfer_rate / 8.0).log2().round() as u8)
+    } else if transfer_rate >= 5.0 {
+        Some(2)
+    } else if transfer_rate >= 2.5 {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Sum the negotiated link widths of GPUs and physical NICs
+///
+/// Functions of a multi-function device (e.g., the ports of a dual-port NIC)
+/// share one link, so devices behind the same parent bridge are counted once.
+///
+/// # Arguments
+///
+/// * `gpus` - GPU devices with `pcie_width` and `pcie_path` populated
+/// * `interfaces` - Network interfaces with `pcie_width` and `pcie_path` populated
+pub fn count_pcie_lanes_in_use(gpus: &[GpuDevice], interfaces: &[NetworkInterface]) -> u32 {
+    let links = gpus
+        .iter()
+        .map(|gpu| (gpu.pcie_width, &gpu.pcie_path))
+        .chain(
+            interfaces
+                .iter()
+                .filter(|iface| !iface.is_virtual)
+                .map(|iface| (iface.pcie_width, &iface.pcie_path)),
+        );
+
+    let mut seen_parents = HashSet::new();
+    links
+        .filter_map(|(width, pcie_path)| {
+            let width = width?;
+            match pcie_path.last() {
+                Some(parent) if !seen_parents.insert(parent) => None,
+                _ => Some(u32::from(width)),
+            }
+        })
+        .sum()
+}
+
+/// PCIe lanes provided by the CPUs, for CPU families with a known lane count
+///
+/// # Arguments
+///
+/// * `cpu_model` - CPU model string (e.g., "Intel(R) Xeon(R) Platinum 8480+")
+/// * `sockets` - Number of populated sockets
+///
+/// # Returns
+///
+/// * `Some(u32)` - Lanes across all sockets
+/// * `None` - Unknown CPU family
+pub fn platform_pcie_lanes(cpu_model: &str, sockets: u32) -> Option<u32> {
+    if sockets == 0 {
+        return None;
+    }
+
+    if cpu_model.contains("EPYC") {
+        // Lane count is per platform: in 2P systems half of each socket's
+        // lanes carry the inter-socket (xGMI) links
+        let series = cpu_model
+            .split_whitespace()
+            .skip_while(|word| !word.contains("EPYC"))
+            .nth(1)
+            .and_then(|model| model.chars().next())?;
+        return match (series, sockets) {
+            ('7', _) | ('9', 1) => Some(128),
+            ('9', _) => Some(160),
+            _ => None,
+        };
+    }
+
+    if cpu_model.contains("Xeon") {
+        // Scalable processors: the second digit of the model number is the
+        // generation (8180, 8280, 8380, 8480, 8580)
+        let generation = cpu_model
+            .split_whitespace()
+            .skip_while(|word| !matches!(*word, "Platinum" | "Gold" | "Silver" | "Bronze"))
+            .nth(1)
+            .and_then(|model| model.chars().nth(1))?;
+        let lanes_per_socket = match generation {
+            '1' | '2' => 48,
+            '3' => 64,
+            '4' | '5' => 80,
+            _ => return None,
+        };
+        return Some(lanes_per_socket * sockets);
+    }
+
+    None
+}
+
+/// Pair each RDMA-capable NIC with its nearest GPU
+///
+/// A GPU behind the same PCIe switch wins, preferring the deepest shared
+/// bridge; otherwise the first GPU on the NIC's NUMA node is used. NICs with
+/// neither are left unpaired.
+///
+/// # Arguments
+///
+/// * `gpus` - GPU devices with `pcie_path` and `numa_node` populated
+/// * `interfaces` - Network interfaces with `rdma_device` and `pcie_path` populated
+///
+/// # Returns
+///
+/// * `GpuNicAffinity` - NIC/GPU pairs
+pub fn pair_gpus_with_rdma_nics(
+    gpus: &[GpuDevice],
+    interfaces: &[NetworkInterface],
+) -> GpuNicAffinity {
+    let rdma_nics: Vec<(&NetworkInterface, &String)> = interfaces
+        .iter()
+        .filter_map(|iface| iface.rdma_device.as_ref().map(|rdma| (iface, rdma)))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (nic, rdma_device) in &rdma_nics {
+        let by_switch = gpus
+            .iter()
+            .map(|gpu| (gpu, shared_bridges(&gpu.pcie_path, &nic.pcie_path)))
+            .filter(|(_, shared)| *shared >= SWITCH_SHARED_BRIDGES)
+            .max_by(|(a, a_shared), (b, b_shared)| {
+                a_shared.cmp(b_shared).then(b.index.cmp(&a.index))
+            })
+            .map(|(gpu, _)| (gpu, GpuNicLocality::PcieSwitch));
+        let nearest = by_switch.or_else(|| {
+            nic.numa_node.and_then(|node| {
+                gpus.iter()
+                    .filter(|gpu| gpu.numa_node == Some(node))
+                    .min_by_key(|gpu| gpu.index)
+                    .map(|gpu| (gpu, GpuNicLocality::NumaNode))
+            })
+        });
+        if let Some((gpu, locality)) = nearest {
+            pairs.push(GpuNicPair {
+                interface: nic.name.clone(),
+                rdma_device: (*rdma_device).clone(),
+                gpu_index: gpu.index,
+                locality,
+            });
+        }
+    }
+
+    GpuNicAffinity { pairs }
+}
+
+/// Find GPUs with no RDMA NIC behind the same PCIe switch
+///
+/// GPUDirect RDMA traffic from such a GPU crosses the CPU root complex.
+///
+/// # Arguments
+///
+/// * `gpus` - GPU devices with `pcie_path` populated
+/// * `interfaces` - Network interfaces with `rdma_device` and `pcie_path` populated
+///
+/// # Returns
+///
+/// * Indexes of the GPUs without a switch-local RDMA NIC
+pub fn find_gpus_without_local_rdma_nic(
+    gpus: &[GpuDevice],
+    interfaces: &[NetworkInterface],
+) -> Vec<u32> {
+    gpus.iter()
+        .filter(|gpu| {
+            !interfaces.iter().any(|nic| {
+                nic.rdma_device.is_some()
+                    && shared_bridges(&gpu.pcie_path, &nic.pcie_path) >= SWITCH_SHARED_BRIDGES
+            })
+        })
+        .map(|gpu| gpu.index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(bridges: &[&str]) -> Vec<String> {
+        bridges.iter().map(|b| b.to_string()).collect()
+    }
+
+    fn gpu(index: u32, pcie_path: Vec<String>, numa_node: i32) -> GpuDevice {
+        GpuDevice {
+            index,
+            pcie_path,
+            numa_node: Some(numa_node),
+            ..Default::default()
+        }
+    }
+
+    fn nic(name: &str, rdma: Option<&str>, pcie_path: Vec<String>, numa: i32) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            rdma_device: rdma.map(str::to_string),
+            pcie_path,
+            numa_node: Some(numa),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_pcie_path() {
+        let bridges = parse_pcie_path(
+            "/sys/devices/pci0000:00/0000:00:01.0/0000:01:00.0/0000:02:08.0/0000:03:00.0",
+        );
+        assert_eq!(
+            bridges,
+            path(&["0000:00:01.0", "0000:01:00.0", "0000:02:08.0"])
+        );
+        assert!(parse_pcie_path("/sys/devices/pci0000:00/0000:00:1f.6").is_empty());
+        assert!(parse_pcie_path("/sys/devices/virtual/net/lo").is_empty());
+    }
+
+    #[test]
+    fn test_parse_lspci_aspm() {
+        let output = "\
+3b:00.0 Ethernet controller: Mellanox Technologies MT2910 Family [ConnectX-7]
+	Subsystem: Mellanox Technologies Device 0026
+	Control: I/O- Mem+ BusMaster+ SpecCycle- MemWINV- VGASnoop- ParErr- Stepping- SERR- FastB2B- DisINTx+
+		LnkCap:	Port #0, Speed 32GT/s, Width x16, ASPM not supported
+			ClockPM- Surprise- LLActRep- BwNot- ASPMOptComp+
+		LnkCtl:	ASPM L1 Enabled; RCB 64 bytes, Disabled- CommClk+
+			ExtSynch- ClockPM- AutWidDis- BWInt- AutBWInt-
+		LnkSta:	Speed 32GT/s, Width x16
+";
+        assert_eq!(parse_lspci_aspm(output).as_deref(), Some("L1"));
+        assert_eq!(
+            parse_lspci_aspm("\t\tLnkCtl:\tASPM Disabled; RCB 64 bytes, Disabled- CommClk+\n")
+                .as_deref(),
+            Some("disabled")
+        );
+        assert_eq!(
+            parse_lspci_aspm("\t\tLnkCtl:\tASPM L0s L1 Enabled; RCB 64 bytes\n").as_deref(),
+            Some("L0s L1")
+        );
+        assert_eq!(parse_lspci_aspm("\tControl: I/O- Mem+ BusMaster+\n"), None);
+    }
+
+    #[test]
+    fn test_aspm_state_from_link_flags() {
+        assert_eq!(
+            aspm_state_from_link_flags(Some(false), Some(true)).as_deref(),
+            Some("L1")
+        );
+        assert_eq!(
+            aspm_state_from_link_flags(Some(false), Some(false)).as_deref(),
+            Some("disabled")
+        );
+        assert_eq!(aspm_state_from_link_flags(None, None), None);
+    }
+
+    #[test]
+    fn test_count_pcie_lanes_in_use() {
+        let gpus = [0, 1].map(|index| GpuDevice {
+            pcie_width: Some(16),
+            ..gpu(index, path(&[&format!("0000:{index}0:01.0")]), 0)
+        });
+        let interfaces = vec![
+            NetworkInterface {
+                pcie_width: Some(8),
+                ..nic("enp59s0f0", None, path(&["0000:3a:00.0"]), 0)
+            },
+            // Second port of the same card shares the x8 link
+            NetworkInterface {
+                pcie_width: Some(8),
+                ..nic("enp59s0f1", None, path(&["0000:3a:00.0"]), 0)
+            },
+            NetworkInterface {
+                is_virtual: true,
+                ..nic("docker0", None, Vec::new(), 0)
+            },
+        ];
+
+        assert_eq!(count_pcie_lanes_in_use(&gpus, &interfaces), 40);
+        assert_eq!(parse_pcie_link_width("16\n"), Some(16));
+        assert_eq!(parse_pcie_link_width("0\n"), None);
+    }
+
+    #[test]
+    fn test_gpu_pcie_degraded() {
+        let healthy = GpuDevice {
+            pcie_gen: Some(5),
+            pcie_width: Some(16),
+            pcie_max_gen: Some(5),
+            pcie_max_width: Some(16),
+            ..Default::default()
+        };
+        assert!(!healthy.is_pcie_degraded());
+
+        let narrow = GpuDevice {
+            pcie_width: Some(8),
+            ..healthy.clone()
+        };
+        assert!(narrow.is_pcie_degraded());
+
+        // A lower generation is a downgrade unless the GPU is known to be
+        // saving power; an unknown P-state (Intel, AMD, lspci) still counts
+        let slow = GpuDevice {
+            pcie_gen: Some(1),
+            ..healthy.clone()
+        };
+        assert_eq!(slow.performance_state, None);
+        assert!(slow.is_pcie_degraded());
+        let idle = GpuDevice {
+            performance_state: Some("P8".to_string()),
+            ..slow.clone()
+        };
+        assert!(!idle.is_pcie_degraded());
+        let busy = GpuDevice {
+            performance_state: Some("P0".to_string()),
+            ..slow.clone()
+        };
+        assert!(busy.is_pcie_degraded());
+
+        // Only one of the four attributes could be read
+        let width_only = GpuDevice {
+            pcie_width: Some(8),
+            ..Default::default()
+        };
+        assert!(!width_only.is_pcie_degraded());
+
+        // The width pair alone still detects a downgrade
+        let widths_only = GpuDevice {
+            pcie_width: Some(8),
+            pcie_max_width: Some(16),
+            ..Default::default()
+        };
+        assert!(widths_only.is_pcie_degraded());
+    }
+
+    #[test]
+    fn test_parse_pcie_link_speed_gen() {
+        assert_eq!(parse_pcie_link_speed_gen("2.5 GT/s PCIe\n"), Some(1));
+        assert_eq!(parse_pcie_link_speed_gen("5.0 GT/s PCIe\n"), Some(2));
+        assert_eq!(parse_pcie_link_speed_gen("8.0 GT/s PCIe\n"), Some(3));
+        assert_eq!(parse_pcie_link_speed_gen("16.0 GT/s PCIe\n"), Some(4));
+        assert_eq!(parse_pcie_link_speed_gen("32.0 GT/s PCIe\n"), Some(5));
+        assert_eq!(parse_pcie_link_speed_gen("64.0 GT/s PCIe\n"), Some(6));
+        assert_eq!(parse_pcie_link_speed_gen("8.0 GT/s\n"), Some(3));
+        assert_eq!(parse_pcie_link_speed_gen("Unknown\n"), None);
+    }
+
+    #[test]
+    fn test_platform_pcie_lanes() {
+        assert_eq!(
+            platform_pcie_lanes("Intel(R) Xeon(R) Platinum 8480+", 2),
+            Some(160)
+        );
+        assert_eq!(
+            platform_pcie_lanes("Intel(R) Xeon(R) Gold 6338 CPU @ 2.00GHz", 2),
+            Some(128)
+        );
+        assert_eq!(
+            platform_pcie_lanes("AMD EPYC 9654 96-Core Processor", 2),
+            Some(160)
+        );
+        assert_eq!(
+            platform_pcie_lanes("AMD EPYC 7763 64-Core Processor", 1),
+            Some(128)
+        );
+        assert_eq!(platform_pcie_lanes("Apple M2 Max", 1), None);
+    }
+
+    #[test]
+    fn test_pair_gpus_with_rdma_nics() {
+        // Switch A (root port 00:01.0) hosts GPU0 and NIC0; switch B hosts GPU1 and NIC1.
+        // GPU3 sits alone on a root port on NUMA node 1, next to a non-RDMA NIC.
+        let gpus = vec![
+            gpu(
+                0,
+                path(&["0000:00:01.0", "0000:01:00.0", "0000:02:00.0"]),
+                0,
+            ),
+            gpu(
+                1,
+                path(&["0000:00:03.0", "0000:10:00.0", "0000:11:00.0"]),
+                0,
+            ),
+            gpu(3, path(&["0000:80:01.0"]), 1),
+        ];
+        let interfaces = vec![
+            nic(
+                "ibp3s0",
+                Some("mlx5_0"),
+                path(&["0000:00:01.0", "0000:01:00.0", "0000:02:04.0"]),
+                0,
+            ),
+            nic(
+                "ibp18s0",
+                Some("mlx5_1"),
+                path(&["0000:00:03.0", "0000:10:00.0", "0000:11:04.0"]),
+                0,
+            ),
+            nic("eno1", None, path(&["0000:80:01.0"]), 1),
+            nic("ibp160s0", Some("mlx5_2"), path(&["0000:a0:01.0"]), 1),
+        ];
+
+        let affinity = pair_gpus_with_rdma_nics(&gpus, &interfaces);
+
+        assert_eq!(
+            affinity.pairs,
+            vec![
+                GpuNicPair {
+                    interface: "ibp3s0".to_string(),
+                    rdma_device: "mlx5_0".to_string(),
+                    gpu_index: 0,
+                    locality: GpuNicLocality::PcieSwitch,
+                },
+                GpuNicPair {
+                    interface: "ibp18s0".to_string(),
+                    rdma_device: "mlx5_1".to_string(),
+                    gpu_index: 1,
+                    locality: GpuNicLocality::PcieSwitch,
+                },
+                GpuNicPair {
+                    interface: "ibp160s0".to_string(),
+                    rdma_device: "mlx5_2".to_string(),
+                    gpu_index: 3,
+                    locality: GpuNicLocality::NumaNode,
+                },
+            ]
+        );
+        assert_eq!(
+            find_gpus_without_local_rdma_nic(&gpus, &interfaces),
+            vec![3]
+        );
+    }
+}
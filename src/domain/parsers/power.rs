@@ -0,0 +1,110 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Power draw parsing functions
+
+use std::time::Duration;
+
+/// Parse the instantaneous reading from `ipmitool dcmi power reading`
+///
+/// # Arguments
+///
+/// * `output` - Output from `ipmitool dcmi power reading`
+///
+/// # Returns
+///
+/// Whole-system power draw in watts, or `None` if no reading is present.
+pub fn parse_dcmi_power_reading(output: &str) -> Option<f64> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim() != "Instantaneous power reading" {
+            return None;
+        }
+        value.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Compute average power from two RAPL energy counter samples
+///
+/// RAPL exposes a cumulative energy counter (`energy_uj`), not a power
+/// reading, so power is only known from the delta between two samples.
+/// The counter wraps at `max_energy_range_uj`.
+///
+/// # Arguments
+///
+/// * `first_uj` - First `energy_uj` sample
+/// * `second_uj` - Second `energy_uj` sample, taken `interval` later
+/// * `max_energy_range_uj` - Content of `max_energy_range_uj`
+/// * `interval` - Time between the two samples
+///
+/// # Returns
+///
+/// Average power over the interval in watts, or `None` for a zero interval.
+pub fn rapl_power_watts(
+    first_uj: u64,
+    second_uj: u64,
+    max_energy_range_uj: u64,
+    interval: Duration,
+) -> Option<f64> {
+    if interval.is_zero() {
+        return None;
+    }
+    let delta_uj = if second_uj >= first_uj {
+        second_uj - first_uj
+    } else {
+        max_energy_range_uj.saturating_sub(first_uj) + second_uj
+    };
+    Some(delta_uj as f64 / 1_000_000.0 / interval.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dcmi_power_reading() {
+        let output = r#"
+    Instantaneous power reading:                   412 Watts
+    Minimum during sampling period:                 98 Watts
+    Maximum during sampling period:               1890 Watts
+    Average power reading over sample period:      405 Watts
+    IPMI timestamp:                           Tue Mar 12 10:15:42 2024
+    Sampling period:                          00000001 Seconds.
+    Power reading state is:                   activated
+"#;
+        assert_eq!(parse_dcmi_power_reading(output), Some(412.0));
+        assert_eq!(
+            parse_dcmi_power_reading("Power reading state is: deactivated"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rapl_power_watts() {
+        let interval = Duration::from_millis(500);
+        // 60 J over 0.5 s
+        assert_eq!(
+            rapl_power_watts(1_000_000_000, 1_060_000_000, 262_143_328_850, interval),
+            Some(120.0)
+        );
+        // Counter wrapped between samples: 10 J before the wrap, 50 J after
+        assert_eq!(
+            rapl_power_watts(262_133_328_850, 50_000_000, 262_143_328_850, interval),
+            Some(120.0)
+        );
+        assert_eq!(rapl_power_watts(0, 1, 2, Duration::ZERO), None);
+    }
+}
@@ -0,0 +1,526 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Redfish resource parsing functions
+
+use super::common::UNKNOWN;
+use crate::domain::{
+    format_bytes_iec, BiosInfo, BmcNetwork, ChassisInfo, CpuInfo, FirmwareComponent, MemoryInfo,
+    PowerInfo, PowerSupply, SensorReading, SystemInfo, TpmInfo, WatchdogInfo,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Parse an expanded Redfish firmware inventory collection
+///
+/// # Arguments
+///
+/// * `collection` - JSON from `/redfish/v1/UpdateService/FirmwareInventory?$expand=.`
+///
+/// # Returns
+///
+/// Components of every expanded member. Members that are only `@odata.id`
+/// links (BMCs without `$expand` support) are skipped; fetch them with
+/// [`parse_redfish_member_links`] and [`parse_redfish_firmware_component`].
+pub fn parse_redfish_firmware_inventory(
+    collection: &str,
+) -> Result<Vec<FirmwareComponent>, String> {
+    let value: Value = serde_json::from_str(collection)
+        .map_err(|e| format!("Invalid Redfish firmware inventory: {e}"))?;
+
+    Ok(members(&value)
+        .iter()
+        .filter_map(firmware_component)
+        .collect())
+}
+
+/// Parse the member links of a Redfish collection
+///
+/// # Arguments
+///
+/// * `collection` - JSON of any Redfish collection
+///
+/// # Returns
+///
+/// The `@odata.id` path of each member.
+pub fn parse_redfish_member_links(collection: &str) -> Result<Vec<String>, String> {
+    let value: Value =
+        serde_json::from_str(collection).map_err(|e| format!("Invalid Redfish collection: {e}"))?;
+
+    Ok(members(&value)
+        .iter()
+        .filter_map(|member| member.get("@odata.id")?.as_str().map(str::to_string))
+        .collect())
+}
+
+/// Parse a single Redfish `SoftwareInventory` resource
+///
+/// # Arguments
+///
+/// * `resource` - JSON from `/redfish/v1/UpdateService/FirmwareInventory/<id>`
+pub fn parse_redfish_firmware_component(resource: &str) -> Result<FirmwareComponent, String> {
+    let value: Value = serde_json::from_str(resource)
+        .map_err(|e| format!("Invalid Redfish software inventory: {e}"))?;
+
+    firmware_component(&value).ok_or_else(|| "Software inventory has no version".to_string())
+}
+
+/// Parse identity fields of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+pub fn parse_redfish_system_info(system: &str) -> Result<SystemInfo, String> {
+    let value = parse_resource(system, "ComputerSystem")?;
+
+    Ok(SystemInfo {
+        uuid: string_or_unknown(&value, "UUID"),
+        serial: string_or_unknown(&value, "SerialNumber"),
+        product_name: string_or_unknown(&value, "Model"),
+        product_manufacturer: string_or_unknown(&value, "Manufacturer"),
+    })
+}
+
+/// Parse the BIOS version of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+pub fn parse_redfish_bios_info(system: &str) -> Result<BiosInfo, String> {
+    let value = parse_resource(system, "ComputerSystem")?;
+
+    Ok(BiosInfo {
+        vendor: string_or_unknown(&value, "Manufacturer"),
+        version: string_or_unknown(&value, "BiosVersion"),
+        release_date: UNKNOWN.to_string(),
+        firmware_version: UNKNOWN.to_string(),
+    })
+}
+
+/// Parse the `ProcessorSummary` of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+///
+/// # Returns
+///
+/// CPU model and topology; counts the BMC does not report are 0.
+pub fn parse_redfish_cpu_summary(system: &str) -> Result<CpuInfo, String> {
+    let value = parse_resource(system, "ComputerSystem")?;
+    let summary = value.get("ProcessorSummary").unwrap_or(&Value::Null);
+    let count = |key: &str| {
+        summary
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0)
+    };
+
+    let sockets = count("Count");
+    let cores = count("CoreCount");
+    let logical = count("LogicalProcessorCount");
+
+    Ok(CpuInfo {
+        model: string_or_unknown(summary, "Model"),
+        sockets,
+        cores: cores.checked_div(sockets).unwrap_or(0),
+        threads: logical.checked_div(cores).unwrap_or(0),
+        speed: UNKNOWN.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Parse the `MemorySummary` of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+pub fn parse_redfish_memory_summary(system: &str) -> Result<MemoryInfo, String> {
+    let value = parse_resource(system, "ComputerSystem")?;
+    let total_gib = value
+        .get("MemorySummary")
+        .and_then(|summary| summary.get("TotalSystemMemoryGiB"))
+        .and_then(Value::as_f64);
+
+    Ok(MemoryInfo {
+        total: total_gib
+            .map(|gib| format_bytes_iec((gib * 1024.0 * 1024.0 * 1024.0) as u64))
+            .unwrap_or_else(|| UNKNOWN.to_string()),
+        type_: UNKNOWN.to_string(),
+        speed: UNKNOWN.to_string(),
+        modules: Vec::new(),
+        transparent_hugepages: None,
+        hugepage_pools: Vec::new(),
+        nvdimm_namespaces: Vec::new(),
+        ecc_enabled: None,
+    })
+}
+
+/// Parse the host name a Redfish `ComputerSystem` resource reports
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+///
+/// # Returns
+///
+/// `None` when the BMC has not learned the host name (e.g., the OS never booted).
+pub fn parse_redfish_hostname(system: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(system).ok()?;
+    string(&value, "HostName")
+}
+
+/// Parse the `TrustedModules` of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+///
+/// # Returns
+///
+/// TPM presence and version; absent or unparseable modules mean no TPM.
+pub fn parse_redfish_tpm_info(system: &str) -> TpmInfo {
+    let value: Value = serde_json::from_str(system).unwrap_or(Value::Null);
+    let module = value
+        .get("TrustedModules")
+        .and_then(Value::as_array)
+        .and_then(|modules| {
+            modules.iter().find(|module| {
+                module.pointer("/Status/State").and_then(Value::as_str) != Some("Absent")
+            })
+        });
+
+    let Some(module) = module else {
+        return TpmInfo::default();
+    };
+    TpmInfo {
+        present: true,
+        version: module
+            .get("InterfaceType")
+            .and_then(Value::as_str)
+            .and_then(|interface| match interface {
+                "TPM1_2" => Some("1.2".to_string()),
+                "TPM2_0" => Some("2.0".to_string()),
+                _ => None,
+            }),
+        manufacturer: None,
+    }
+}
+
+/// Parse the `HostWatchdogTimer` of a Redfish `ComputerSystem` resource
+///
+/// # Arguments
+///
+/// * `system` - JSON from `/redfish/v1/Systems/<id>`
+///
+/// # Returns
+///
+/// `None` when the BMC does not describe a host watchdog. Redfish does not
+/// expose the timeout.
+pub fn parse_redfish_host_watchdog(system: &str) -> Option<WatchdogInfo> {
+    let value: Value = serde_json::from_str(system).ok()?;
+    let watchdog = value.get("HostWatchdogTimer")?;
+    Some(WatchdogInfo {
+        device: "IPMI".to_string(),
+        timeout_s: None,
+        active: watchdog
+            .get("FunctionEnabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+/// Parse a Redfish `Chassis` resource
+///
+/// # Arguments
+///
+/// * `chassis` - JSON from `/redfish/v1/Chassis/<id>`
+pub fn parse_redfish_chassis_info(chassis: &str) -> Result<ChassisInfo, String> {
+    let value = parse_resource(chassis, "Chassis")?;
+
+    Ok(ChassisInfo {
+        manufacturer: string_or_unknown(&value, "Manufacturer"),
+        type_: string_or_unknown(&value, "ChassisType"),
+        serial: string_or_unknown(&value, "SerialNumber"),
+        airflow: string(&value, "ThermalDirection").and_then(|direction| {
+            let airflow = match direction.as_str() {
+                "FrontToBack" => "front-to-rear",
+                "BackToFront" => "rear-to-front",
+                "Sealed" => "passive",
+                _ => return None,
+            };
+            Some(airflow.to_string())
+        }),
+    })
+}
+
+/// Parse power draw and power supplies from a Redfish `Power` resource
+///
+/// # Arguments
+///
+/// * `power` - JSON from `/redfish/v1/Chassis/<id>/Power`
+pub fn parse_redfish_power(power: &str) -> Result<PowerInfo, String> {
+    let value = parse_resource(power, "Power")?;
+
+    let power_draw_watts = array(&value, "PowerControl")
+        .iter()
+        .find_map(|control| control.get("PowerConsumedWatts")?.as_f64());
+    let power_supplies = array(&value, "PowerSupplies")
+        .iter()
+        .filter_map(|psu| {
+            Some(PowerSupply {
+                name: string(psu, "Name")?,
+                health: psu
+                    .get("Status")
+                    .and_then(|status| string(status, "Health")),
+                capacity_watts: psu.get("PowerCapacityWatts").and_then(Value::as_f64),
+            })
+        })
+        .collect();
+
+    Ok(PowerInfo {
+        power_draw_watts,
+        source: Some("redfish".to_string()),
+        cpu_package_watts: None,
+        power_supplies,
+    })
+}
+
+/// Parse temperature and fan readings from a Redfish `Thermal` resource
+///
+/// # Arguments
+///
+/// * `thermal` - JSON from `/redfish/v1/Chassis/<id>/Thermal`
+///
+/// # Returns
+///
+/// Every sensor with a current reading; absent sensors report `null`.
+pub fn parse_redfish_thermal(thermal: &str) -> Result<Vec<SensorReading>, String> {
+    let value = parse_resource(thermal, "Thermal")?;
+
+    let temperatures = array(&value, "Temperatures")
+        .into_iter()
+        .filter_map(|sensor| {
+            Some(SensorReading {
+                name: string(&sensor, "Name")?,
+                reading: sensor.get("ReadingCelsius")?.as_f64()?,
+                units: "Cel".to_string(),
+            })
+        });
+    let fans = array(&value, "Fans").into_iter().filter_map(|fan| {
+        Some(SensorReading {
+            // Redfish 2016 schemas name fans with FanName
+            name: string(&fan, "Name").or_else(|| string(&fan, "FanName"))?,
+            reading: fan.get("Reading")?.as_f64()?,
+            units: string(&fan, "ReadingUnits").unwrap_or_else(|| "RPM".to_string()),
+        })
+    });
+
+    Ok(temperatures.chain(fans).collect())
+}
+
+/// Parse the BMC address from a Redfish manager `EthernetInterface` resource
+///
+/// # Arguments
+///
+/// * `interface` - JSON from `/redfish/v1/Managers/<id>/EthernetInterfaces/<id>`
+pub fn parse_redfish_bmc_network(interface: &str) -> Result<BmcNetwork, String> {
+    let value = parse_resource(interface, "EthernetInterface")?;
+    let ipv4 = array(&value, "IPv4Addresses").into_iter().next();
+
+    Ok(BmcNetwork {
+        ip: ipv4
+            .as_ref()
+            .and_then(|address| string(address, "Address"))
+            .filter(|ip| ip != "0.0.0.0"),
+        mac: string(&value, "MACAddress").map(|mac| mac.to_lowercase()),
+        ip_source: ipv4
+            .as_ref()
+            .and_then(|address| string(address, "AddressOrigin"))
+            .map(|origin| origin.to_lowercase()),
+    })
+}
+
+/// Parse BIOS settings from a Redfish `Bios` resource
+///
+/// # Arguments
+///
+/// * `bios` - JSON from `/redfish/v1/Systems/<id>/Bios`
+///
+/// # Returns
+///
+/// Each attribute with its value as a string.
+pub fn parse_redfish_bios_attributes(bios: &str) -> Result<HashMap<String, String>, String> {
+    let value = parse_resource(bios, "Bios")?;
+    let attributes = value
+        .get("Attributes")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "Redfish Bios resource has no Attributes".to_string())?;
+
+    Ok(attributes
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect())
+}
+
+fn parse_resource(body: &str, kind: &str) -> Result<Value, String> {
+    serde_json::from_str(body).map_err(|e| format!("Invalid Redfish {kind}: {e}"))
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    let s = value.get(key)?.as_str()?.trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+fn string_or_unknown(value: &Value, key: &str) -> String {
+    string(value, key).unwrap_or_else(|| UNKNOWN.to_string())
+}
+
+fn array(value: &Value, key: &str) -> Vec<Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn members(collection: &Value) -> Vec<Value> {
+    array(collection, "Members")
+}
+
+fn firmware_component(resource: &Value) -> Option<FirmwareComponent> {
+    let version = resource.get("Version")?.as_str()?.trim();
+    let name = resource
+        .get("Name")
+        .or_else(|| resource.get("Id"))?
+        .as_str()?
+        .trim();
+
+    Some(FirmwareComponent {
+        name: name.to_string(),
+        version: version.to_string(),
+        updateable: resource
+            .get("Updateable")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed from a Dell iDRAC9 with `$expand=.`
+    const FIRMWARE_INVENTORY: &str = r##"{
+  "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory",
+  "@odata.type": "#SoftwareInventoryCollection.SoftwareInventoryCollection",
+  "Members": [
+    {
+      "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/Installed-159-2.19.1",
+      "Id": "Installed-159-2.19.1",
+      "Name": "BIOS",
+      "Updateable": true,
+      "Version": "2.19.1"
+    },
+    {
+      "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/Installed-25227-7.00.00.171",
+      "Id": "Installed-25227-7.00.00.171",
+      "Name": "Integrated Dell Remote Access Controller",
+      "Updateable": true,
+      "Version": "7.00.00.171"
+    },
+    {
+      "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/Installed-108255-28.39.10.02",
+      "Id": "Installed-108255-28.39.10.02",
+      "Name": "Mellanox ConnectX-7 Single Port VPI NDR OSFP Adapter",
+      "Updateable": true,
+      "Version": "28.39.10.02"
+    },
+    {
+      "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/Installed-0-2.2.0",
+      "Id": "Installed-0-2.2.0",
+      "Name": "System CPLD",
+      "Updateable": false,
+      "Version": "2.2.0"
+    },
+    {
+      "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/Installed-104843-00.1F.7E"
+    }
+  ]
+}"##;
+
+    #[test]
+    fn test_parse_redfish_firmware_inventory() {
+        let components = parse_redfish_firmware_inventory(FIRMWARE_INVENTORY).unwrap();
+
+        assert_eq!(components.len(), 4);
+        assert_eq!(
+            components[0],
+            FirmwareComponent {
+                name: "BIOS".to_string(),
+                version: "2.19.1".to_string(),
+                updateable: true,
+            }
+        );
+        assert_eq!(components[2].version, "28.39.10.02");
+        assert_eq!(components[3].name, "System CPLD");
+        assert!(!components[3].updateable);
+    }
+
+    #[test]
+    fn test_parse_redfish_member_links() {
+        let links = parse_redfish_member_links(FIRMWARE_INVENTORY).unwrap();
+        assert_eq!(links.len(), 5);
+        assert_eq!(
+            links[4],
+            "/redfish/v1/UpdateService/FirmwareInventory/Installed-104843-00.1F.7E"
+        );
+
+        let component = parse_redfish_firmware_component(
+            r#"{"Id": "Installed-104843-00.1F.7E", "Name": "PSU.Slot.1", "Version": "00.1F.7E"}"#,
+        )
+        .unwrap();
+        assert_eq!(component.name, "PSU.Slot.1");
+        assert!(!component.updateable);
+        assert!(parse_redfish_firmware_component("{}").is_err());
+    }
+
+    #[test]
+    fn test_rear_to_front_chassis_airflow_for_netbox() {
+        let chassis = r#"{
+  "@odata.id": "/redfish/v1/Chassis/1",
+  "ChassisType": "RackMount",
+  "Manufacturer": "Supermicro",
+  "SerialNumber": "C8170LK42A01234",
+  "ThermalDirection": "BackToFront"
+}"#;
+        let info = parse_redfish_chassis_info(chassis).unwrap();
+        assert_eq!(info.airflow.as_deref(), Some("rear-to-front"));
+        assert_eq!(info.netbox_airflow(), "rear-to-front");
+
+        // BMCs predating ThermalDirection fall back to the NetBox default
+        let info = parse_redfish_chassis_info(r#"{"ChassisType": "RackMount"}"#).unwrap();
+        assert_eq!(info.airflow, None);
+        assert_eq!(info.netbox_airflow(), "front-to-rear");
+    }
+}
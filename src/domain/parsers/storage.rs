@@ -16,8 +16,11 @@ limitations under the License.
 
 //! Storage information parsing functions
 
-use super::common::{clean_value, parse_size_to_bytes};
-use crate::domain::{StorageDevice, StorageType};
+use super::common::{clean_value, parse_size_to_bytes, UNKNOWN};
+use crate::domain::{
+    NvmeHealth, Partition, RaidVirtualDisk, SmartHealth, StorageDevice, StorageType,
+};
+use serde_json::Value;
 
 /// Parse sysfs size file (sectors to bytes)
 ///
@@ -52,6 +55,422 @@ pub fn parse_sysfs_rotational(content: &str) -> bool {
     content.trim() == "1"
 }
 
+/// Parse the active I/O scheduler from sysfs
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/block/{dev}/queue/scheduler` (e.g., "[mq-deadline] kyber none")
+///
+/// # Returns
+///
+/// The bracketed (active) scheduler, or `None` if none is marked active.
+pub fn parse_sysfs_scheduler(content: &str) -> Option<String> {
+    content
+        .split_whitespace()
+        .find_map(|s| s.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::to_string)
+}
+
+/// Parse a PCI mass storage controller class
+///
+/// # Arguments
+///
+/// * `content` - Content of `/sys/bus/pci/devices/{addr}/class` (e.g., "0x010400")
+///
+/// # Returns
+///
+/// Controller kind (e.g., "RAID", "NVMe"), or `None` if the class is not a
+/// mass storage controller.
+pub fn parse_pci_storage_class(content: &str) -> Option<&'static str> {
+    let class = content.trim().trim_start_matches("0x");
+    if class.len() < 4 || !class.starts_with("01") {
+        return None;
+    }
+
+    Some(match &class[2..4] {
+        "00" => "SCSI",
+        "01" => "IDE",
+        "04" => "RAID",
+        "06" => "SATA",
+        "07" => "SAS",
+        "08" => "NVMe",
+        _ => "Storage",
+    })
+}
+
+/// Parse rotation rate and form factor from `smartctl -i` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `smartctl -i /dev/{name}`
+///
+/// # Returns
+///
+/// Tuple of (rotation RPM, form factor). Solid state devices report
+/// "Solid State Device" as their rotation rate, which maps to `None`.
+pub fn parse_smartctl_rotation_info(output: &str) -> (Option<u32>, Option<String>) {
+    let mut rotation_rpm = None;
+    let mut form_factor = None;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Rotation Rate" => {
+                rotation_rpm = value
+                    .strip_suffix("rpm")
+                    .and_then(|rpm| rpm.trim().parse().ok());
+            }
+            "Form Factor" if !value.is_empty() => {
+                form_factor = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    (rotation_rpm, form_factor)
+}
+
+/// Parse the NVMe SMART/health log from `nvme smart-log -o json` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `nvme smart-log /dev/{name} -o json`
+///
+/// # Returns
+///
+/// Health log, or an error if the output is not a smart-log JSON object.
+pub fn parse_nvme_smart_log_json(output: &str) -> Result<NvmeHealth, String> {
+    let value: Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid nvme smart-log JSON: {e}"))?;
+
+    // Newer nvme-cli versions may wrap values as {"value": n}
+    let number = |key: &str| {
+        let field = value.get(key)?;
+        field
+            .as_u64()
+            .or_else(|| field.get("value").and_then(Value::as_u64))
+    };
+    let percent = |key: &str| number(key).and_then(|n| u8::try_from(n).ok());
+
+    let critical_warning = number("critical_warning")
+        .and_then(|n| u8::try_from(n).ok())
+        .ok_or_else(|| "nvme smart-log JSON has no critical_warning".to_string())?;
+
+    Ok(NvmeHealth {
+        critical_warning,
+        available_spare: percent("avail_spare"),
+        available_spare_threshold: percent("spare_thresh"),
+        percentage_used: percent("percent_used"),
+        data_units_written: number("data_units_written"),
+        data_units_read: number("data_units_read"),
+        media_errors: number("media_errors"),
+        temperature_c: number("temperature").and_then(kelvin_to_celsius),
+        warning_temperature_c: None,
+        critical_temperature_c: None,
+    })
+}
+
+/// ATA SMART attributes whose normalized value counts down from 100 as the
+/// SSD wears: Wear_Leveling_Count, SSD_Life_Left, Media_Wearout_Indicator
+const ATA_WEAR_ATTRIBUTE_IDS: [u64; 3] = [177, 231, 233];
+
+/// Parse drive identity and health from `smartctl -j -a` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `smartctl -j -a /dev/{name}`
+///
+/// # Returns
+///
+/// Serial, firmware, overall health and endurance used; fields smartctl does
+/// not report for the device are `None`.
+pub fn parse_smartctl_health_json(output: &str) -> Result<SmartHealth, String> {
+    let value: Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid smartctl JSON: {e}"))?;
+    let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let nvme_used = value
+        .pointer("/nvme_smart_health_information_log/percentage_used")
+        .and_then(Value::as_u64);
+    let scsi_used = value
+        .get("scsi_percentage_used_endurance_indicator")
+        .and_then(Value::as_u64);
+    let ata_used = value
+        .pointer("/ata_smart_attributes/table")
+        .and_then(Value::as_array)
+        .and_then(|table| {
+            table.iter().find_map(|attribute| {
+                let id = attribute.get("id").and_then(Value::as_u64)?;
+                if !ATA_WEAR_ATTRIBUTE_IDS.contains(&id) {
+                    return None;
+                }
+                let remaining = attribute.get("value").and_then(Value::as_u64)?;
+                Some(100u64.saturating_sub(remaining))
+            })
+        });
+
+    Ok(SmartHealth {
+        serial: text("serial_number"),
+        firmware: text("firmware_version"),
+        healthy: value
+            .pointer("/smart_status/passed")
+            .and_then(Value::as_bool),
+        wearout_percent: nvme_used
+            .or(scsi_used)
+            .or(ata_used)
+            .map(|used| used.min(u8::MAX as u64) as u8),
+    })
+}
+
+/// Whether a `smartctl` exit status comes with a usable report
+///
+/// The exit status is a bit mask. Bits 0 and 1 mean the command line could
+/// not be parsed or the device could not be opened. Bits 2-7 flag problems
+/// with the drive itself (failing, pre-fail attributes, logged errors) and
+/// still come with a complete report, so those drives must not be treated
+/// as unknown.
+pub fn smartctl_exit_has_report(exit_code: i32) -> bool {
+    exit_code & 0b11 == 0
+}
+
+/// Parse virtual disks from storcli JSON output
+///
+/// # Arguments
+///
+/// * `output` - Output from `storcli /call/vall show all j`
+///
+/// # Returns
+///
+/// One entry per virtual disk on every controller that answered successfully.
+pub fn parse_storcli_virtual_disks_json(output: &str) -> Result<Vec<RaidVirtualDisk>, String> {
+    let value: Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid storcli JSON: {e}"))?;
+    let controllers = value
+        .get("Controllers")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "Missing Controllers array in storcli output".to_string())?;
+
+    let mut disks = Vec::new();
+    for controller in controllers {
+        let Some(response) = controller.get("Response Data").and_then(Value::as_object) else {
+            continue;
+        };
+        let controller_id = controller
+            .pointer("/Command Status/Controller")
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as u32;
+
+        for (key, entries) in response {
+            // Virtual disk sections are keyed "/c0/v1"
+            let Some(virtual_drive) = key
+                .rsplit_once("/v")
+                .and_then(|(_, id)| id.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Some(raid_level) = entries
+                .pointer("/0/TYPE")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            let physical_disk_count = response
+                .get(&format!("PDs for VD {virtual_drive}"))
+                .and_then(Value::as_array)
+                .map_or(0, |drives| drives.len() as u32);
+            let os_device = response
+                .get(&format!("VD{virtual_drive} Properties"))
+                .and_then(|properties| properties.get("OS Drive Name"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+
+            disks.push(RaidVirtualDisk {
+                controller: controller_id,
+                virtual_drive,
+                raid_level,
+                physical_disk_count,
+                os_device,
+            });
+        }
+    }
+
+    disks.sort_by_key(|disk| (disk.controller, disk.virtual_drive));
+    Ok(disks)
+}
+
+/// Parse virtual disks from MegaCli output
+///
+/// MegaCli does not report the OS block device, so `os_device` is always `None`.
+///
+/// # Arguments
+///
+/// * `output` - Output from `MegaCli -LDInfo -Lall -aAll`
+pub fn parse_megacli_ldinfo(output: &str) -> Vec<RaidVirtualDisk> {
+    /// Fields of one "Virtual Drive:" block
+    #[derive(Default)]
+    struct Block {
+        disk: RaidVirtualDisk,
+        levels: Option<(u32, u32)>,
+        drives_per_span: u32,
+        span_depth: u32,
+    }
+
+    fn finish(block: Option<Block>, disks: &mut Vec<RaidVirtualDisk>) {
+        if let Some(mut block) = block {
+            if let Some((primary, secondary)) = block.levels {
+                block.disk.raid_level = megacli_raid_level(primary, secondary, block.span_depth);
+            }
+            block.disk.physical_disk_count = block.drives_per_span * block.span_depth.max(1);
+            disks.push(block.disk);
+        }
+    }
+
+    let leading_number = |text: &str| {
+        text.split_whitespace()
+            .next()
+            .and_then(|id| id.parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+
+    let mut disks = Vec::new();
+    let mut controller = 0;
+    let mut current: Option<Block> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("Adapter ") {
+            finish(current.take(), &mut disks);
+            controller = leading_number(rest);
+        } else if let Some(rest) = line.strip_prefix("Virtual Drive:") {
+            finish(current.take(), &mut disks);
+            current = Some(Block {
+                disk: RaidVirtualDisk {
+                    controller,
+                    virtual_drive: leading_number(rest),
+                    ..Default::default()
+                },
+                span_depth: 1,
+                ..Default::default()
+            });
+        } else if let (Some(block), Some((key, value))) = (current.as_mut(), line.split_once(':')) {
+            match key.trim() {
+                "RAID Level" => {
+                    // "Primary-1, Secondary-0, RAID Level Qualifier-0"
+                    let level = |prefix: &str| {
+                        value
+                            .split(',')
+                            .find_map(|part| part.trim().strip_prefix(prefix)?.parse().ok())
+                    };
+                    block.levels = level("Primary-").zip(level("Secondary-"));
+                }
+                "Number Of Drives" | "Number Of Drives per span" => {
+                    block.drives_per_span = value.trim().parse().unwrap_or(0);
+                }
+                "Span Depth" => block.span_depth = value.trim().parse().unwrap_or(1),
+                _ => {}
+            }
+        }
+    }
+    finish(current, &mut disks);
+
+    disks
+}
+
+/// Map MegaCli primary/secondary RAID levels to the conventional name
+fn megacli_raid_level(primary: u32, secondary: u32, span_depth: u32) -> String {
+    match (primary, secondary) {
+        (1, 3) => "RAID10".to_string(),
+        (1, _) if span_depth > 1 => "RAID10".to_string(),
+        (5, 3) => "RAID50".to_string(),
+        (6, 3) => "RAID60".to_string(),
+        (primary, _) => format!("RAID{primary}"),
+    }
+}
+
+/// Find the controller virtual disk backing a block device
+///
+/// Matches on the OS device name when the tool reports it, otherwise on the
+/// SCSI target ID, which a MegaRAID controller sets to the virtual drive
+/// number. A target ID shared by virtual disks on several controllers is
+/// ambiguous and matches nothing.
+///
+/// # Arguments
+///
+/// * `disks` - Virtual disks from storcli or MegaCli
+/// * `device_path` - Block device path (e.g., "/dev/sda")
+/// * `scsi_target` - SCSI target ID of the block device, if known
+pub fn find_raid_virtual_disk<'a>(
+    disks: &'a [RaidVirtualDisk],
+    device_path: &str,
+    scsi_target: Option<u32>,
+) -> Option<&'a RaidVirtualDisk> {
+    if let Some(disk) = disks
+        .iter()
+        .find(|disk| disk.os_device.as_deref() == Some(device_path))
+    {
+        return Some(disk);
+    }
+
+    let target = scsi_target?;
+    let mut candidates = disks
+        .iter()
+        .filter(|disk| disk.os_device.is_none() && disk.virtual_drive == target);
+    let disk = candidates.next()?;
+    candidates.next().is_none().then_some(disk)
+}
+
+/// Parse the composite temperature thresholds from `nvme id-ctrl -o json` output
+///
+/// # Arguments
+///
+/// * `output` - Output from `nvme id-ctrl /dev/{name} -o json`
+///
+/// # Returns
+///
+/// `(warning, critical)` thresholds in Celsius; a threshold the controller
+/// does not report (zero) is `None`.
+pub fn parse_nvme_id_ctrl_temperature_thresholds(
+    output: &str,
+) -> Result<(Option<i32>, Option<i32>), String> {
+    let value: Value =
+        serde_json::from_str(output).map_err(|e| format!("Invalid nvme id-ctrl JSON: {e}"))?;
+    let threshold = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(kelvin_to_celsius)
+    };
+
+    Ok((threshold("wctemp"), threshold("cctemp")))
+}
+
+/// Check whether an NVMe drive is running at or above its warning temperature
+///
+/// Falls back to the critical temperature threshold, then to the controller's
+/// own temperature critical-warning bit, when thresholds are not reported.
+pub fn is_nvme_overheating(health: &NvmeHealth) -> bool {
+    let threshold = health
+        .warning_temperature_c
+        .or(health.critical_temperature_c);
+
+    match (health.temperature_c, threshold) {
+        (Some(temperature), Some(threshold)) => temperature >= threshold,
+        _ => health.critical_warning & 0x02 != 0,
+    }
+}
+
+/// NVMe reports temperatures in Kelvin; zero means "not reported"
+fn kelvin_to_celsius(kelvin: u64) -> Option<i32> {
+    if kelvin == 0 {
+        return None;
+    }
+    i32::try_from(kelvin).ok().map(|k| k - 273)
+}
+
 /// Check if device name indicates a virtual device
 ///
 /// Virtual devices should be filtered from physical storage lists.
@@ -69,6 +488,72 @@ pub fn is_virtual_device(name: &str) -> bool {
         || name.starts_with("nbd")
 }
 
+/// Controller name for an NVMe namespace block device
+///
+/// # Arguments
+///
+/// * `name` - Block device name (e.g., "nvme0n1", or "nvme0c1n1" for a
+///   multipath path)
+///
+/// # Returns
+///
+/// * `Some("nvme0")` for namespaces, `None` for partitions, controllers and
+///   non-NVMe devices
+pub fn nvme_namespace_controller(name: &str) -> Option<&str> {
+    let rest = name.strip_prefix("nvme")?;
+    let controller_len = rest.find(|c: char| !c.is_ascii_digit())?;
+    if controller_len == 0 {
+        return None;
+    }
+    let mut tail = &rest[controller_len..];
+    if let Some(path) = tail.strip_prefix('c') {
+        tail = path.trim_start_matches(|c: char| c.is_ascii_digit());
+    }
+    let namespace = tail.strip_prefix('n')?;
+    if namespace.is_empty() || !namespace.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(&name[..4 + controller_len])
+}
+
+/// Build a partition from its sysfs attributes and check its alignment
+///
+/// A partition is misaligned when the kernel reports a nonzero
+/// `alignment_offset` or when its start is not a multiple of the device's
+/// physical block size.
+///
+/// # Arguments
+/// * `name` - Partition name (e.g., "sda1")
+/// * `start` - Content of `/sys/block/<dev>/<part>/start` (512-byte sectors)
+/// * `alignment_offset` - Content of `/sys/block/<dev>/<part>/alignment_offset`
+/// * `physical_block_size` - Content of `/sys/block/<dev>/queue/physical_block_size`
+///
+/// # Returns
+/// * `Some(Partition)` - Parsed partition
+/// * `None` - `start` is not a number
+pub fn parse_sysfs_partition(
+    name: &str,
+    start: &str,
+    alignment_offset: &str,
+    physical_block_size: &str,
+) -> Option<Partition> {
+    let start_sector: u64 = start.trim().parse().ok()?;
+    let alignment_offset: i64 = alignment_offset.trim().parse().unwrap_or(0);
+    let physical_block_size: u64 = physical_block_size.trim().parse().unwrap_or(512);
+
+    let start_aligned = physical_block_size == 0
+        || start_sector
+            .saturating_mul(512)
+            .is_multiple_of(physical_block_size);
+
+    Some(Partition {
+        name: name.to_string(),
+        start_sector,
+        alignment_offset,
+        misaligned: alignment_offset != 0 || !start_aligned,
+    })
+}
+
 /// Parse lsblk JSON output
 ///
 /// # Arguments
@@ -215,14 +700,14 @@ pub fn parse_macos_storage_info(
             current_device = Some(StorageDevice {
                 name: model.clone(),
                 type_: "ssd".to_string(),
-                size: "Unknown".to_string(),
+                size: UNKNOWN.to_string(),
                 model: format!("{model} (Apple Fabric)"),
                 ..Default::default()
             });
         } else if trimmed.starts_with("Size:") && current_device.is_some() {
             // Extract size information
             if let Some(ref mut device) = current_device {
-                let size_str = trimmed.split(':').nth(1).unwrap_or("Unknown").trim();
+                let size_str = trimmed.split(':').nth(1).unwrap_or(UNKNOWN).trim();
                 device.size = size_str.to_string();
             }
         }
@@ -255,3 +740,279 @@ pub fn calculate_total_storage_size(devices: &[StorageDevice]) -> f64 {
         .sum::<u64>() as f64
         / (1024.0 * 1024.0 * 1024.0 * 1024.0) // Convert to TB
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_storcli_virtual_disks_json() {
+        let output = r#"{
+            "Controllers": [{
+                "Command Status": {"Controller": 0, "Status": "Success"},
+                "Response Data": {
+                    "/c0/v0": [{"DG/VD": "0/0", "TYPE": "RAID1", "State": "Optl", "Size": "446.625 GB"}],
+                    "PDs for VD 0": [{"EID:Slt": "64:0"}, {"EID:Slt": "64:1"}],
+                    "VD0 Properties": {"Strip Size": "64 KB", "OS Drive Name": "/dev/sda"},
+                    "/c0/v1": [{"DG/VD": "1/1", "TYPE": "RAID10", "State": "Optl", "Size": "6.985 TB"}],
+                    "PDs for VD 1": [{"EID:Slt": "64:2"}, {"EID:Slt": "64:3"}, {"EID:Slt": "64:4"}, {"EID:Slt": "64:5"}],
+                    "VD1 Properties": {"Strip Size": "256 KB", "OS Drive Name": "/dev/sdb"}
+                }
+            }]
+        }"#;
+
+        let disks = parse_storcli_virtual_disks_json(output).unwrap();
+
+        assert_eq!(disks.len(), 2);
+        assert_eq!(disks[0].raid_level, "RAID1");
+        assert_eq!(disks[0].physical_disk_count, 2);
+        assert_eq!(disks[1].raid_level, "RAID10");
+        assert_eq!(disks[1].physical_disk_count, 4);
+        assert_eq!(
+            find_raid_virtual_disk(&disks, "/dev/sdb", None),
+            Some(&disks[1])
+        );
+        assert_eq!(find_raid_virtual_disk(&disks, "/dev/nvme0n1", None), None);
+    }
+
+    #[test]
+    fn test_parse_megacli_ldinfo() {
+        let output = "\
+Adapter 0 -- Virtual Drive Information:
+Virtual Drive: 0 (Target Id: 0)
+Name                :
+RAID Level          : Primary-1, Secondary-0, RAID Level Qualifier-0
+Size                : 446.625 GB
+State               : Optimal
+Number Of Drives    : 2
+Span Depth          : 1
+Virtual Drive: 1 (Target Id: 1)
+Name                :
+RAID Level          : Primary-1, Secondary-3, RAID Level Qualifier-0
+Size                : 6.985 TB
+State               : Optimal
+Number Of Drives per span:2
+Span Depth          : 2
+
+Exit Code: 0x00
+";
+
+        let disks = parse_megacli_ldinfo(output);
+
+        assert_eq!(disks.len(), 2);
+        assert_eq!(disks[0].raid_level, "RAID1");
+        assert_eq!(disks[0].physical_disk_count, 2);
+        assert_eq!(disks[1].raid_level, "RAID10");
+        assert_eq!(disks[1].physical_disk_count, 4);
+        assert_eq!(
+            find_raid_virtual_disk(&disks, "/dev/sdb", Some(1)),
+            Some(&disks[1])
+        );
+    }
+
+    #[test]
+    fn test_nvme_namespace_controller() {
+        assert_eq!(nvme_namespace_controller("nvme0n1"), Some("nvme0"));
+        assert_eq!(nvme_namespace_controller("nvme12n3"), Some("nvme12"));
+        assert_eq!(nvme_namespace_controller("nvme1c2n1"), Some("nvme1"));
+        assert_eq!(nvme_namespace_controller("nvme0n1p1"), None);
+        assert_eq!(nvme_namespace_controller("nvme0"), None);
+        assert_eq!(nvme_namespace_controller("sda"), None);
+    }
+
+    #[test]
+    fn test_parse_sysfs_scheduler() {
+        assert_eq!(
+            parse_sysfs_scheduler("[mq-deadline] kyber none\n"),
+            Some("mq-deadline".to_string())
+        );
+        assert_eq!(
+            parse_sysfs_scheduler("mq-deadline kyber [none]\n"),
+            Some("none".to_string())
+        );
+        assert_eq!(parse_sysfs_scheduler("none\n"), None);
+    }
+
+    #[test]
+    fn test_parse_smartctl_rotation_info_hdd() {
+        let output = "=== START OF INFORMATION SECTION ===
+Device Model:     ST8000NM0055-1RM112
+Serial Number:    ZA1234AB
+User Capacity:    8,001,563,222,016 bytes [8.00 TB]
+Rotation Rate:    7200 rpm
+Form Factor:      3.5 inches
+SATA Version is:  SATA 3.1, 6.0 Gb/s (current: 6.0 Gb/s)
+";
+        let (rpm, form_factor) = parse_smartctl_rotation_info(output);
+        assert_eq!(rpm, Some(7200));
+        assert_eq!(form_factor.as_deref(), Some("3.5 inches"));
+    }
+
+    #[test]
+    fn test_parse_smartctl_rotation_info_ssd() {
+        let output = "=== START OF INFORMATION SECTION ===
+Device Model:     Samsung SSD 870 EVO 1TB
+Rotation Rate:    Solid State Device
+Form Factor:      2.5 inches
+";
+        let (rpm, form_factor) = parse_smartctl_rotation_info(output);
+        assert_eq!(rpm, None);
+        assert_eq!(form_factor.as_deref(), Some("2.5 inches"));
+    }
+
+    #[test]
+    fn test_smartctl_exit_has_report() {
+        assert!(smartctl_exit_has_report(0));
+        // DISK FAILING and pre-fail attribute bits
+        assert!(smartctl_exit_has_report(0x08));
+        assert!(smartctl_exit_has_report(0x10 | 0x40));
+        // Command line error, device open failure
+        assert!(!smartctl_exit_has_report(0x01));
+        assert!(!smartctl_exit_has_report(0x02 | 0x08));
+    }
+
+    #[test]
+    fn test_parse_smartctl_health_json() {
+        let sata = r#"{
+  "device": {"name": "/dev/sda", "type": "sat", "protocol": "ATA"},
+  "model_name": "SAMSUNG MZ7LH960HAJR-00005",
+  "serial_number": "S45NNE0M812345",
+  "firmware_version": "HXT7404Q",
+  "smart_status": {"passed": true},
+  "ata_smart_attributes": {
+    "revision": 1,
+    "table": [
+      {"id": 5, "name": "Reallocated_Sector_Ct", "value": 100, "worst": 100, "thresh": 10},
+      {"id": 177, "name": "Wear_Leveling_Count", "value": 93, "worst": 93, "thresh": 5}
+    ]
+  }
+}"#;
+        assert_eq!(
+            parse_smartctl_health_json(sata).unwrap(),
+            SmartHealth {
+                serial: Some("S45NNE0M812345".to_string()),
+                firmware: Some("HXT7404Q".to_string()),
+                healthy: Some(true),
+                wearout_percent: Some(7),
+            }
+        );
+
+        let nvme = r#"{
+  "device": {"name": "/dev/nvme0", "type": "nvme", "protocol": "NVMe"},
+  "serial_number": "PHLJ123400AB4P0DGN",
+  "firmware_version": "VDV10184",
+  "smart_status": {"passed": false, "nvme": {"value": 4}},
+  "nvme_smart_health_information_log": {"critical_warning": 4, "percentage_used": 103}
+}"#;
+        let health = parse_smartctl_health_json(nvme).unwrap();
+        assert_eq!(health.healthy, Some(false));
+        assert_eq!(health.wearout_percent, Some(103));
+
+        // HDDs have no wear attributes
+        let hdd = r#"{"serial_number": "ZL2ABCDE", "smart_status": {"passed": true}}"#;
+        assert_eq!(
+            parse_smartctl_health_json(hdd).unwrap().wearout_percent,
+            None
+        );
+
+        assert!(parse_smartctl_health_json("").is_err());
+    }
+
+    #[test]
+    fn test_parse_nvme_smart_log_json() {
+        let output = r#"{
+  "critical_warning" : 4,
+  "temperature" : 318,
+  "avail_spare" : 3,
+  "spare_thresh" : 10,
+  "percent_used" : 104,
+  "endurance_grp_critical_warning_summary" : 0,
+  "data_units_read" : 912345678,
+  "data_units_written" : 1876543210,
+  "host_read_commands" : 20485013371,
+  "host_write_commands" : 41239849012,
+  "controller_busy_time" : 51337,
+  "power_cycles" : 112,
+  "power_on_hours" : 31284,
+  "unsafe_shutdowns" : 57,
+  "media_errors" : 12,
+  "num_err_log_entries" : 230
+}"#;
+
+        let health = parse_nvme_smart_log_json(output).unwrap();
+        assert_eq!(
+            health,
+            NvmeHealth {
+                critical_warning: 4,
+                available_spare: Some(3),
+                available_spare_threshold: Some(10),
+                percentage_used: Some(104),
+                data_units_written: Some(1876543210),
+                data_units_read: Some(912345678),
+                media_errors: Some(12),
+                temperature_c: Some(45),
+                warning_temperature_c: None,
+                critical_temperature_c: None,
+            }
+        );
+        // Bit 2: NVM subsystem reliability degraded
+        assert_ne!(health.critical_warning & 0x04, 0);
+
+        assert!(parse_nvme_smart_log_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_nvme_overheating_above_warning_threshold() {
+        let smart_log = r#"{
+  "critical_warning" : 0,
+  "temperature" : 351,
+  "avail_spare" : 100,
+  "spare_thresh" : 10,
+  "percent_used" : 3
+}"#;
+        let id_ctrl = r#"{
+  "vid" : 5197,
+  "mn" : "SAMSUNG MZQL23T8HCLS-00A07",
+  "wctemp" : 343,
+  "cctemp" : 356
+}"#;
+
+        let mut health = parse_nvme_smart_log_json(smart_log).unwrap();
+        let (warning, critical) = parse_nvme_id_ctrl_temperature_thresholds(id_ctrl).unwrap();
+        assert_eq!(warning, Some(70));
+        assert_eq!(critical, Some(83));
+        health.warning_temperature_c = warning;
+        health.critical_temperature_c = critical;
+
+        // 78C is past the 70C warning threshold but below critical
+        assert_eq!(health.temperature_c, Some(78));
+        assert!(is_nvme_overheating(&health));
+
+        health.temperature_c = Some(45);
+        assert!(!is_nvme_overheating(&health));
+    }
+
+    #[test]
+    fn test_parse_sysfs_partition_alignment() {
+        // Modern tools start the first partition at 1 MiB
+        let aligned = parse_sysfs_partition("nvme0n1p1", "2048\n", "0\n", "4096\n").unwrap();
+        assert_eq!(aligned.start_sector, 2048);
+        assert!(!aligned.misaligned);
+
+        // The kernel flags a nonzero alignment offset
+        let offset = parse_sysfs_partition("sda1", "2048\n", "3584\n", "4096\n").unwrap();
+        assert_eq!(offset.alignment_offset, 3584);
+        assert!(offset.misaligned);
+
+        // Legacy DOS layouts start at sector 63, off a 4K physical block
+        let dos = parse_sysfs_partition("sdb1", "63\n", "0\n", "4096\n").unwrap();
+        assert!(dos.misaligned);
+        assert!(
+            !parse_sysfs_partition("sdc1", "63\n", "0\n", "512\n")
+                .unwrap()
+                .misaligned
+        );
+
+        assert_eq!(parse_sysfs_partition("sdd1", "", "0", "512"), None);
+    }
+}
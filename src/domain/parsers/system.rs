@@ -16,19 +16,21 @@ limitations under the License.
 
 //! System information parsing functions
 
-use super::common::{clean_value, extract_dmidecode_value};
-use crate::domain::{BiosInfo, ChassisInfo, SystemInfo};
+use super::common::{clean_value, extract_dmidecode_value, is_unknown, UNKNOWN};
+use crate::domain::{
+    BiosInfo, BmcNetwork, ChassisInfo, SelEvent, SystemInfo, TimeSyncInfo, TpmInfo, WatchdogInfo,
+};
 
 /// Parse system information from dmidecode output
 pub fn parse_dmidecode_system_info(dmidecode_output: &str) -> Result<SystemInfo, String> {
     let uuid =
-        extract_dmidecode_value(dmidecode_output, "UUID").unwrap_or_else(|_| "Unknown".to_string());
+        extract_dmidecode_value(dmidecode_output, "UUID").unwrap_or_else(|_| UNKNOWN.to_string());
     let serial = extract_dmidecode_value(dmidecode_output, "Serial Number")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
     let product_name = extract_dmidecode_value(dmidecode_output, "Product Name")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
     let manufacturer = extract_dmidecode_value(dmidecode_output, "Manufacturer")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
 
     Ok(SystemInfo {
         uuid: clean_value(&uuid),
@@ -40,12 +42,12 @@ pub fn parse_dmidecode_system_info(dmidecode_output: &str) -> Result<SystemInfo,
 
 /// Parse BIOS information from dmidecode output
 pub fn parse_dmidecode_bios_info(dmidecode_output: &str) -> Result<BiosInfo, String> {
-    let vendor = extract_dmidecode_value(dmidecode_output, "Vendor")
-        .unwrap_or_else(|_| "Unknown".to_string());
+    let vendor =
+        extract_dmidecode_value(dmidecode_output, "Vendor").unwrap_or_else(|_| UNKNOWN.to_string());
     let version = extract_dmidecode_value(dmidecode_output, "Version")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
     let release_date = extract_dmidecode_value(dmidecode_output, "Release Date")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
 
     Ok(BiosInfo {
         vendor: clean_value(&vendor),
@@ -58,16 +60,17 @@ pub fn parse_dmidecode_bios_info(dmidecode_output: &str) -> Result<BiosInfo, Str
 /// Parse chassis information from dmidecode output
 pub fn parse_dmidecode_chassis_info(dmidecode_output: &str) -> Result<ChassisInfo, String> {
     let manufacturer = extract_dmidecode_value(dmidecode_output, "Manufacturer")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
     let type_ =
-        extract_dmidecode_value(dmidecode_output, "Type").unwrap_or_else(|_| "Unknown".to_string());
+        extract_dmidecode_value(dmidecode_output, "Type").unwrap_or_else(|_| UNKNOWN.to_string());
     let serial = extract_dmidecode_value(dmidecode_output, "Serial Number")
-        .unwrap_or_else(|_| "Unknown".to_string());
+        .unwrap_or_else(|_| UNKNOWN.to_string());
 
     Ok(ChassisInfo {
         manufacturer: clean_value(&manufacturer),
         type_: clean_value(&type_),
         serial: clean_value(&serial),
+        airflow: None,
     })
 }
 
@@ -75,3 +78,715 @@ pub fn parse_dmidecode_chassis_info(dmidecode_output: &str) -> Result<ChassisInf
 pub fn parse_hostname_output(hostname_output: &str) -> Result<String, String> {
     Ok(clean_value(hostname_output.trim()))
 }
+
+/// Check whether DMI-derived data is complete
+///
+/// Without privileges dmidecode cannot read the DMI tables, leaving fields
+/// as "Unknown".
+///
+/// # Arguments
+/// * `has_privileges` - Whether collection ran with elevated privileges
+/// * `system_info` - DMI system information
+///
+/// # Returns
+/// * `false` if collected without privileges or the system identity is unknown
+pub fn is_dmi_data_complete(has_privileges: bool, system_info: &SystemInfo) -> bool {
+    has_privileges && !is_unknown(&system_info.uuid) && !is_unknown(&system_info.serial)
+}
+
+/// Choose a canonical machine identifier
+///
+/// Candidates are tried in order: system UUID, chassis serial, motherboard
+/// serial, OS machine ID (`/etc/machine-id`), then hostname. Empty values and
+/// DMI placeholders such as "Unknown" or "To Be Filled By O.E.M." are skipped.
+///
+/// # Arguments
+/// * `system_uuid` - DMI system UUID
+/// * `chassis_serial` - DMI chassis serial number
+/// * `motherboard_serial` - DMI baseboard serial number
+/// * `os_machine_id` - OS machine ID, if readable
+/// * `hostname` - System hostname, the last resort
+///
+/// # Returns
+/// * Tuple of (machine ID, source name)
+pub fn select_machine_id(
+    system_uuid: &str,
+    chassis_serial: &str,
+    motherboard_serial: &str,
+    os_machine_id: Option<&str>,
+    hostname: &str,
+) -> (String, &'static str) {
+    [
+        (system_uuid, "system_uuid"),
+        (chassis_serial, "chassis_serial"),
+        (motherboard_serial, "motherboard_serial"),
+        (os_machine_id.unwrap_or_default(), "os_machine_id"),
+    ]
+    .into_iter()
+    .find(|(value, _)| !is_unknown(value))
+    .map(|(value, source)| (value.trim().to_string(), source))
+    .unwrap_or_else(|| (hostname.to_string(), "hostname"))
+}
+
+/// Derive the FQDN for an overridden hostname
+///
+/// When the override is not fully qualified, the domain of the detected FQDN
+/// is kept.
+///
+/// # Arguments
+/// * `detected_hostname` - Hostname detected on the system
+/// * `detected_fqdn` - FQDN detected on the system
+/// * `hostname` - Hostname to use instead
+pub fn override_fqdn(detected_hostname: &str, detected_fqdn: &str, hostname: &str) -> String {
+    let domain = detected_fqdn
+        .strip_prefix(detected_hostname)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .filter(|domain| !domain.is_empty());
+
+    match domain {
+        Some(domain) if !hostname.contains('.') => format!("{hostname}.{domain}"),
+        _ => hostname.to_string(),
+    }
+}
+
+/// Parse failed systemd units from `systemctl --failed --plain --no-legend`
+///
+/// # Arguments
+/// * `systemctl_output` - Output of the systemctl command
+///
+/// # Returns
+/// * Names of failed units (e.g., "nginx.service")
+pub fn parse_systemctl_failed_units(systemctl_output: &str) -> Vec<String> {
+    systemctl_output
+        .lines()
+        .filter_map(|line| {
+            line.split_whitespace()
+                .find(|token| *token != "●" && *token != "*")
+        })
+        .map(|unit| unit.to_string())
+        .collect()
+}
+
+/// Parse clock synchronization state from `chronyc tracking`
+///
+/// # Arguments
+/// * `tracking_output` - Output of `chronyc tracking`
+///
+/// # Returns
+/// * Time sync state; the offset is negative when the clock is behind NTP time
+pub fn parse_chronyc_tracking(tracking_output: &str) -> TimeSyncInfo {
+    let mut info = TimeSyncInfo::default();
+
+    for line in tracking_output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Reference ID" => {
+                // "A9FEA97B (169.254.169.123)" - prefer the name in parentheses
+                info.source = value
+                    .split_once('(')
+                    .map(|(_, name)| name.trim_end_matches(')').to_string())
+                    .or_else(|| Some(value.to_string()))
+                    .filter(|source| !source.is_empty());
+            }
+            "Stratum" => info.stratum = value.parse().ok(),
+            "System time" => {
+                // "0.000012345 seconds slow of NTP time"
+                let seconds: Option<f64> =
+                    value.split_whitespace().next().and_then(|v| v.parse().ok());
+                let sign = if value.contains("slow") { -1.0 } else { 1.0 };
+                info.offset_ms = seconds.map(|s| sign * s * 1000.0);
+            }
+            "Leap status" => info.synchronized = value != "Not synchronised",
+            _ => {}
+        }
+    }
+
+    // An unreachable server leaves stratum 0 while the leap status stays normal
+    if info.stratum == Some(0) {
+        info.synchronized = false;
+    }
+
+    info
+}
+
+/// Parse clock synchronization state from `timedatectl show-timesync`
+///
+/// # Arguments
+/// * `timesync_output` - Output of `timedatectl show-timesync` (key=value lines)
+///
+/// # Returns
+/// * Time sync state; synchronized once an NTP reply has been received
+pub fn parse_timedatectl_timesync(timesync_output: &str) -> TimeSyncInfo {
+    let mut info = TimeSyncInfo::default();
+
+    for line in timesync_output.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "ServerName" if !value.trim().is_empty() => {
+                info.source = Some(value.trim().to_string());
+            }
+            "NTPMessage" => {
+                // "{ Leap=0, Version=4, Mode=4, Stratum=2, ... }"
+                let fields = value.trim_matches(|c| c == '{' || c == '}' || c == ' ');
+                for field in fields.split(',') {
+                    match field.trim().split_once('=') {
+                        Some(("Leap", leap)) => info.synchronized = leap != "3",
+                        Some(("Stratum", stratum)) => info.stratum = stratum.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    info
+}
+
+/// Parse how the BMC obtained its address from `ipmitool lan print`
+///
+/// # Arguments
+/// * `lan_print_output` - Output of `ipmitool lan print`
+///
+/// # Returns
+/// * "dhcp", "static", "bios" or "other"; `None` when the field is absent
+pub fn parse_ipmitool_ip_source(lan_print_output: &str) -> Option<String> {
+    let value = lan_print_output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "IP Address Source").then(|| value.trim().to_lowercase())
+    })?;
+
+    let source = if value.starts_with("dhcp") {
+        "dhcp"
+    } else if value.starts_with("static") {
+        "static"
+    } else if value.starts_with("bios") {
+        "bios"
+    } else {
+        "other"
+    };
+    Some(source.to_string())
+}
+
+/// Parse the BMC address from `ipmitool lan print`
+///
+/// # Arguments
+/// * `lan_print_output` - Output of `ipmitool lan print`
+///
+/// # Returns
+/// * BMC address; an unconfigured address ("0.0.0.0") is `None`
+pub fn parse_ipmitool_lan_print(lan_print_output: &str) -> BmcNetwork {
+    let field = |name: &str| {
+        lan_print_output.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim().to_string())
+        })
+    };
+
+    BmcNetwork {
+        ip: field("IP Address").filter(|ip| !ip.is_empty() && ip != "0.0.0.0"),
+        mac: field("MAC Address").map(|mac| mac.to_lowercase()),
+        ip_source: parse_ipmitool_ip_source(lan_print_output),
+    }
+}
+
+/// Parse System Event Log entries from `ipmitool sel elist`
+///
+/// # Arguments
+/// * `elist_output` - Output of `ipmitool sel elist`
+///
+/// # Returns
+/// * SEL entries in log order; lines that are not entries are skipped
+pub fn parse_ipmitool_sel_elist(elist_output: &str) -> Vec<SelEvent> {
+    elist_output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            // Entries logged before the BMC clock is set read "Pre-Init" with an uptime counter
+            let timestamp =
+                (fields[1] != "Pre-Init").then(|| format!("{} {}", fields[1], fields[2]));
+            Some(SelEvent {
+                timestamp,
+                sensor: fields[3].to_string(),
+                description: fields[4..].join(" | "),
+            })
+        })
+        .collect()
+}
+
+/// Parse the BMC watchdog state from `ipmitool mc watchdog get`
+///
+/// # Arguments
+/// * `output` - Output of `ipmitool mc watchdog get`
+///
+/// # Returns
+/// * `Some(WatchdogInfo)` - Watchdog with device "IPMI"
+/// * `None` - Output has no timer state
+pub fn parse_ipmitool_watchdog_get(output: &str) -> Option<WatchdogInfo> {
+    let field = |key: &str| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim())
+        })
+    };
+
+    let state = field("Watchdog Timer Is")?;
+    // "300 sec" on older ipmitool, "300.0 sec" on newer
+    let timeout_s = field("Initial Countdown")
+        .and_then(|countdown| countdown.split_whitespace().next())
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .map(|seconds| seconds.round() as u32);
+
+    Some(WatchdogInfo {
+        device: "IPMI".to_string(),
+        timeout_s,
+        active: state.starts_with("Started"),
+    })
+}
+
+/// TCG vendor IDs of common TPM manufacturers
+const TPM_VENDORS: &[(&str, &str)] = &[
+    ("IFX", "Infineon"),
+    ("NTC", "Nuvoton"),
+    ("NTZ", "Nationz"),
+    ("STM", "STMicroelectronics"),
+    ("INTC", "Intel"),
+    ("AMD", "AMD"),
+    ("ATML", "Atmel"),
+    ("MSFT", "Microsoft"),
+];
+
+/// Build TPM information from sysfs and the DMI TPM Device record
+///
+/// # Arguments
+/// * `version_major` - Content of `/sys/class/tpm/tpm0/tpm_version_major`, if present
+/// * `dmidecode_output` - Output of `dmidecode -t 43` (empty when unavailable)
+///
+/// # Returns
+/// * TPM information; `present` is false when neither source reports a TPM
+pub fn parse_tpm_info(version_major: Option<&str>, dmidecode_output: &str) -> TpmInfo {
+    let sysfs_version = version_major.and_then(|major| match major.trim() {
+        "1" => Some("1.2".to_string()),
+        "2" => Some("2.0".to_string()),
+        _ => None,
+    });
+
+    let has_dmi_record = dmidecode_output.contains("TPM Device");
+    let dmi_field = |key: &str| {
+        extract_dmidecode_value(dmidecode_output, key)
+            .ok()
+            .filter(|value| !is_unknown(value))
+    };
+    let manufacturer = has_dmi_record
+        .then(|| dmi_field("Vendor ID"))
+        .flatten()
+        .map(|vendor_id| {
+            TPM_VENDORS
+                .iter()
+                .find(|(id, _)| *id == vendor_id)
+                .map_or(vendor_id.clone(), |(_, name)| name.to_string())
+        });
+
+    TpmInfo {
+        present: version_major.is_some() || has_dmi_record,
+        version: sysfs_version.or_else(|| {
+            has_dmi_record
+                .then(|| dmi_field("Specification Version"))
+                .flatten()
+        }),
+        manufacturer,
+    }
+}
+
+/// cgroup v1 reports "unlimited" as a page-aligned value near `i64::MAX`
+const CGROUP_V1_UNLIMITED: u64 = 1 << 62;
+
+/// Detect the container environment from `/proc/1/cgroup`
+///
+/// # Arguments
+/// * `cgroup_content` - Content of `/proc/1/cgroup`
+///
+/// # Returns
+/// * Container type ("docker", "kubernetes", "podman", "lxc" or "containerd"), if any
+pub fn parse_container_from_cgroup(cgroup_content: &str) -> Option<String> {
+    let markers = [
+        ("kubepods", "kubernetes"),
+        ("libpod", "podman"),
+        ("docker", "docker"),
+        ("lxc", "lxc"),
+        ("containerd", "containerd"),
+    ];
+
+    cgroup_content
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(|path| {
+            markers
+                .iter()
+                .find(|(marker, _)| path.contains(marker))
+                .map(|(_, name)| name.to_string())
+        })
+}
+
+/// Parse a cgroup v2 `cpu.max` file into a quota in cores
+///
+/// # Arguments
+/// * `cpu_max` - Content of `cpu.max`, e.g. "200000 100000" or "max 100000"
+///
+/// # Returns
+/// * Quota divided by period, or `None` when unlimited
+pub fn parse_cgroup_cpu_max(cpu_max: &str) -> Option<f64> {
+    let mut fields = cpu_max.split_whitespace();
+    let quota = fields.next()?.parse::<u64>().ok()?;
+    let period = fields.next()?.parse::<u64>().ok().filter(|p| *p > 0)?;
+    Some(quota as f64 / period as f64)
+}
+
+/// Parse the throttled period count from a cgroup `cpu.stat` file
+///
+/// # Arguments
+/// * `cpu_stat` - Content of `cpu.stat` (cgroup v1 or v2)
+///
+/// # Returns
+/// * Value of `nr_throttled`, if present
+pub fn parse_cgroup_throttled_periods(cpu_stat: &str) -> Option<u64> {
+    cpu_stat.lines().find_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        (key == "nr_throttled").then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Parse GPU device nodes from a cgroup v1 `devices.list` allowlist
+///
+/// # Arguments
+/// * `devices_list` - Content of `devices.list` (e.g., "c 195:0 rwm")
+///
+/// # Returns
+/// * NVIDIA GPU device nodes the cgroup may open. Unrestricted cgroups
+///   (`a *:* rwm`) grant every device and are not attributed any GPU.
+pub fn parse_cgroup_gpu_devices(devices_list: &str) -> Vec<String> {
+    let mut devices = Vec::new();
+
+    for line in devices_list.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some("c"), Some(numbers)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let Some((major, minor)) = numbers.split_once(':') else {
+            continue;
+        };
+        if major != NVIDIA_DEVICE_MAJOR {
+            continue;
+        }
+
+        let device = match minor {
+            "*" => "/dev/nvidia*".to_string(),
+            // 254 and 255 are nvidia-modeset and nvidiactl, not GPUs
+            _ => match minor.parse::<u32>() {
+                Ok(minor) if minor < 254 => format!("/dev/nvidia{minor}"),
+                _ => continue,
+            },
+        };
+        if !devices.contains(&device) {
+            devices.push(device);
+        }
+    }
+
+    devices
+}
+
+/// Character device major number of the NVIDIA driver's `/dev/nvidia*` nodes
+const NVIDIA_DEVICE_MAJOR: &str = "195";
+
+/// Parse a cgroup limit file such as `memory.max` or `memory.limit_in_bytes`
+///
+/// # Arguments
+/// * `content` - File content: a byte count, "max", or a v1 near-`i64::MAX` value
+///
+/// # Returns
+/// * Limit in bytes, or `None` when unlimited
+pub fn parse_cgroup_limit(content: &str) -> Option<u64> {
+    content
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|limit| *limit < CGROUP_V1_UNLIMITED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_fqdn() {
+        // The detected domain is kept for a bare hostname
+        assert_eq!(
+            override_fqdn("localhost", "localhost.lab.example.com", "gpu-node-07"),
+            "gpu-node-07.lab.example.com"
+        );
+        // A qualified override replaces the FQDN outright
+        assert_eq!(
+            override_fqdn("localhost", "localhost.lab.example.com", "gpu-node-07.dc2"),
+            "gpu-node-07.dc2"
+        );
+        // No detected domain to keep
+        assert_eq!(
+            override_fqdn("localhost", "localhost", "gpu-node-07"),
+            "gpu-node-07"
+        );
+    }
+
+    #[test]
+    fn test_parse_cgroup_gpu_devices() {
+        let devices_list = "c 1:3 rwm\nc 195:255 rw\nc 195:2 rw\nc 195:0 rw\nc 195:2 m\n";
+        assert_eq!(
+            parse_cgroup_gpu_devices(devices_list),
+            vec!["/dev/nvidia2".to_string(), "/dev/nvidia0".to_string()]
+        );
+        assert_eq!(
+            parse_cgroup_gpu_devices("c 195:* rwm\n"),
+            vec!["/dev/nvidia*".to_string()]
+        );
+        assert!(parse_cgroup_gpu_devices("a *:* rwm\n").is_empty());
+    }
+
+    #[test]
+    fn test_dmi_data_incomplete_without_privileges() {
+        // Non-root dmidecode prints a permission error and no DMI data
+        let system_info = parse_dmidecode_system_info(
+            "/sys/firmware/dmi/tables/smbios_entry_point: Permission denied\n",
+        )
+        .unwrap();
+        assert!(!is_dmi_data_complete(false, &system_info));
+
+        let output = "System Information\n\tManufacturer: Supermicro\n\tSerial Number: S123456\n\tUUID: 4c4c4544-0042-3510-8052-b4c04f4b4e32\n";
+        let system_info = parse_dmidecode_system_info(output).unwrap();
+        assert!(is_dmi_data_complete(true, &system_info));
+        assert!(!is_dmi_data_complete(false, &system_info));
+    }
+
+    #[test]
+    fn test_parse_systemctl_failed_units() {
+        let output = "\
+nvidia-persistenced.service loaded failed failed NVIDIA Persistence Daemon
+systemd-networkd-wait-online.service loaded failed failed Wait for Network to be Configured
+";
+        assert_eq!(
+            parse_systemctl_failed_units(output),
+            vec![
+                "nvidia-persistenced.service",
+                "systemd-networkd-wait-online.service"
+            ]
+        );
+        assert!(parse_systemctl_failed_units("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_chronyc_tracking_synchronized() {
+        let output = "\
+Reference ID    : A9FEA97B (169.254.169.123)
+Stratum         : 4
+Ref time (UTC)  : Wed Oct 14 09:12:44 2026
+System time     : 0.000012345 seconds slow of NTP time
+Last offset     : -0.000003210 seconds
+RMS offset      : 0.000011052 seconds
+Frequency       : 12.345 ppm fast
+Leap status     : Normal
+";
+        let info = parse_chronyc_tracking(output);
+        assert!(info.synchronized);
+        assert_eq!(info.source.as_deref(), Some("169.254.169.123"));
+        assert_eq!(info.stratum, Some(4));
+        let offset = info.offset_ms.unwrap();
+        assert!((offset - -0.012345).abs() < 1e-9);
+
+        let unsynced =
+            parse_chronyc_tracking("Stratum         : 0\nLeap status     : Not synchronised\n");
+        assert!(!unsynced.synchronized);
+    }
+
+    #[test]
+    fn test_select_machine_id_fallback_chain() {
+        assert_eq!(
+            select_machine_id("4c4c4544-0042", "CH123", "MB456", None, "node-1"),
+            ("4c4c4544-0042".to_string(), "system_uuid")
+        );
+        assert_eq!(
+            select_machine_id("Unknown", "CH123", "MB456", None, "node-1"),
+            ("CH123".to_string(), "chassis_serial")
+        );
+        assert_eq!(
+            select_machine_id("Unknown", "To Be Filled By O.E.M.", "MB456", None, "node-1"),
+            ("MB456".to_string(), "motherboard_serial")
+        );
+        assert_eq!(
+            select_machine_id(
+                "Unknown",
+                "Unknown",
+                "",
+                Some("b08dfa6083e7567a1921a715000001fb\n"),
+                "node-1"
+            ),
+            (
+                "b08dfa6083e7567a1921a715000001fb".to_string(),
+                "os_machine_id"
+            )
+        );
+        assert_eq!(
+            select_machine_id("Unknown", "Unknown", "Unknown", None, "node-1"),
+            ("node-1".to_string(), "hostname")
+        );
+    }
+
+    #[test]
+    fn test_parse_ipmitool_ip_source() {
+        let lan_print = "\
+Set in Progress         : Set Complete
+Auth Type Support       : NONE MD2 MD5 PASSWORD
+IP Address Source       : Static Address
+IP Address              : 10.0.12.34
+Subnet Mask             : 255.255.255.0
+MAC Address             : 3c:ec:ef:12:34:56
+Default Gateway IP      : 10.0.12.1
+";
+        assert_eq!(
+            parse_ipmitool_ip_source(lan_print).as_deref(),
+            Some("static")
+        );
+        assert_eq!(
+            parse_ipmitool_ip_source("IP Address Source       : DHCP Address\n").as_deref(),
+            Some("dhcp")
+        );
+        assert_eq!(parse_ipmitool_ip_source("IP Address : 10.0.12.34\n"), None);
+
+        assert_eq!(
+            parse_ipmitool_lan_print(lan_print),
+            BmcNetwork {
+                ip: Some("10.0.12.34".to_string()),
+                mac: Some("3c:ec:ef:12:34:56".to_string()),
+                ip_source: Some("static".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ipmitool_sel_elist() {
+        let elist = "\
+   1 | Pre-Init  |0000000004| System Event #0xff | Timestamp Clock Sync | Asserted
+   2 | 03/15/2024 | 10:22:01 | System Boot Initiated #0x5a | Initiated by power up | Asserted
+   3 | 03/15/2024 | 14:07:43 | Memory #0x87 | Correctable ECC | Asserted | DIMM_A1
+";
+        let events = parse_ipmitool_sel_elist(elist);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].timestamp, None);
+        assert_eq!(events[1].sensor, "System Boot Initiated #0x5a");
+        assert_eq!(
+            events[2],
+            SelEvent {
+                timestamp: Some("03/15/2024 14:07:43".to_string()),
+                sensor: "Memory #0x87".to_string(),
+                description: "Correctable ECC | Asserted | DIMM_A1".to_string(),
+            }
+        );
+
+        assert!(parse_ipmitool_sel_elist("SEL has no entries\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_ipmitool_watchdog_get() {
+        let output = "\
+Watchdog Timer Use:     SMS/OS (0x44)
+Watchdog Timer Is:      Started/Running
+Watchdog Timer Actions: Hard Reset (0x01)
+Pre-timeout interval:   0 seconds
+Timer Expiration Flags: 0x10
+Initial Countdown:      300.0 sec
+Present Countdown:      287.4 sec
+";
+        assert_eq!(
+            parse_ipmitool_watchdog_get(output),
+            Some(WatchdogInfo {
+                device: "IPMI".to_string(),
+                timeout_s: Some(300),
+                active: true,
+            })
+        );
+
+        let stopped = "Watchdog Timer Is:      Stopped\nInitial Countdown:      0 sec\n";
+        assert!(!parse_ipmitool_watchdog_get(stopped).unwrap().active);
+        assert_eq!(parse_ipmitool_watchdog_get("Could not open device\n"), None);
+    }
+
+    #[test]
+    fn test_parse_tpm_info() {
+        let dmidecode = "\
+Handle 0x0049, DMI type 43, 31 bytes
+TPM Device
+	Vendor ID: IFX
+	Specification Version: 2.0
+	Firmware Revision: 15.23
+	Description: TPM 2.0, ManufacturerID: IFX , Firmware Version: 0xF0017.0x2B
+	Characteristics:
+		Family configurable via platform software support
+	OEM-defined: 0x00000000
+";
+        assert_eq!(
+            parse_tpm_info(Some("2\n"), dmidecode),
+            TpmInfo {
+                present: true,
+                version: Some("2.0".to_string()),
+                manufacturer: Some("Infineon".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_tpm_info(Some("1\n"), ""),
+            TpmInfo {
+                present: true,
+                version: Some("1.2".to_string()),
+                manufacturer: None,
+            }
+        );
+        assert_eq!(parse_tpm_info(None, ""), TpmInfo::default());
+    }
+
+    #[test]
+    fn test_parse_container_from_cgroup() {
+        let docker = "12:memory:/docker/3f2a9c1b\n0::/docker/3f2a9c1b\n";
+        assert_eq!(
+            parse_container_from_cgroup(docker).as_deref(),
+            Some("docker")
+        );
+
+        let k8s = "0::/kubepods.slice/kubepods-burstable.slice/cri-containerd-ab12.scope\n";
+        assert_eq!(
+            parse_container_from_cgroup(k8s).as_deref(),
+            Some("kubernetes")
+        );
+
+        // Host init lives in the root cgroup
+        assert_eq!(parse_container_from_cgroup("0::/init.scope\n"), None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_limits() {
+        assert_eq!(parse_cgroup_cpu_max("200000 100000\n"), Some(2.0));
+        assert_eq!(parse_cgroup_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cgroup_limit("8589934592\n"), Some(8589934592));
+        assert_eq!(parse_cgroup_limit("max\n"), None);
+        assert_eq!(parse_cgroup_limit("9223372036854771712\n"), None);
+        assert_eq!(
+            parse_cgroup_throttled_periods(
+                "usage_usec 8123456\nnr_periods 5000\nnr_throttled 312\nthrottled_usec 91000\n"
+            ),
+            Some(312)
+        );
+    }
+}
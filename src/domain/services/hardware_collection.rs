@@ -15,14 +15,21 @@ limitations under the License.
 */
 
 use crate::domain::{
-    CpuTopology, HardwareInfo, HardwareReport, InterfaceIPs, PublishConfig, PublishError,
-    ReportConfig, ReportError, SystemSummary,
+    assign_gpu_cpu_affinity, calculate_schedulable_cores, count_mig_instances, count_nics,
+    count_pcie_lanes_in_use, find_configuration_deviations, format_bytes_iec, is_dmi_data_complete,
+    is_memory_running_at_rated_speed, pair_gpus_with_rdma_nics, parse_size_to_bytes,
+    platform_pcie_lanes, select_machine_id, Capabilities, CpuTopology, HardwareInfo,
+    HardwareReport, InterfaceIPs, OperationalInfo, ProgressCallback, PublishConfig, PublishError,
+    ReportConfig, ReportError, Subsystem, SubsystemProgress, SystemError, SystemSummary, TpmInfo,
+    REPORT_VERSION,
 };
 use crate::ports::{
     ConfigurationProvider, DataPublisher, HardwareReportingService, SystemInfoProvider,
 };
 use async_trait::async_trait;
+use regex::Regex;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 
 /// Parameters for creating a system summary
@@ -38,6 +45,26 @@ struct SystemSummaryParams<'a> {
     numa_topology: HashMap<String, crate::domain::NumaNode>,
     filesystems: Vec<String>,
     cpu: &'a crate::domain::CpuInfo,
+    has_privileges: bool,
+    count_virtual_nics: bool,
+    tpm: TpmInfo,
+    numa_balancing: Option<bool>,
+}
+
+/// Await `collection`, reporting when it starts and completes
+async fn track_progress<T>(
+    progress: Option<&ProgressCallback>,
+    subsystem: Subsystem,
+    collection: impl Future<Output = T>,
+) -> T {
+    if let Some(progress) = progress {
+        progress.report(SubsystemProgress::Started(subsystem));
+    }
+    let result = collection.await;
+    if let Some(progress) = progress {
+        progress.report(SubsystemProgress::Completed(subsystem));
+    }
+    result
 }
 
 /// Domain service that implements hardware report collection
@@ -74,14 +101,39 @@ impl HardwareCollectionService {
     }
 
     /// Collect all hardware information and create summary
-    async fn collect_hardware_info(&self) -> Result<(HardwareInfo, SystemSummary), ReportError> {
+    async fn collect_hardware_info(
+        &self,
+        config: &ReportConfig,
+    ) -> Result<(HardwareInfo, SystemSummary), ReportError> {
+        let progress = config.progress.as_ref();
+
         // Collect all hardware components concurrently
         let (cpu_result, memory_result, storage_result, gpu_result, network_result) = tokio::join!(
-            self.system_provider.get_cpu_info(),
-            self.system_provider.get_memory_info(),
-            self.system_provider.get_storage_info(),
-            self.system_provider.get_gpu_info(),
-            self.system_provider.get_network_info(),
+            track_progress(
+                progress,
+                Subsystem::Cpu,
+                self.system_provider.get_cpu_info()
+            ),
+            track_progress(
+                progress,
+                Subsystem::Memory,
+                self.system_provider.get_memory_info()
+            ),
+            track_progress(
+                progress,
+                Subsystem::Storage,
+                self.system_provider.get_storage_info()
+            ),
+            track_progress(
+                progress,
+                Subsystem::Gpus,
+                self.system_provider.get_gpu_info()
+            ),
+            track_progress(
+                progress,
+                Subsystem::Network,
+                self.system_provider.get_network_info()
+            ),
         );
 
         let cpu = cpu_result
@@ -97,11 +149,25 @@ impl HardwareCollectionService {
             ReportError::GenerationFailed(format!("Network collection failed: {e}"))
         })?;
 
-        let hardware = HardwareInfo {
+        // Informational only; neither should fail the report
+        let accelerators = self
+            .system_provider
+            .get_accelerators()
+            .await
+            .unwrap_or_default();
+        let unbound_pci_devices = self
+            .system_provider
+            .get_unbound_pci_devices()
+            .await
+            .unwrap_or_default();
+
+        let mut hardware = HardwareInfo {
             cpu: cpu.clone(),
             memory: memory.clone(),
             storage: storage.clone(),
             gpus: gpus.clone(),
+            accelerators,
+            unbound_pci_devices,
         };
 
         // Collect system metadata concurrently
@@ -112,14 +178,23 @@ impl HardwareCollectionService {
             motherboard_result,
             numa_result,
             filesystems_result,
-        ) = tokio::join!(
-            self.system_provider.get_system_info(),
-            self.system_provider.get_bios_info(),
-            self.system_provider.get_chassis_info(),
-            self.system_provider.get_motherboard_info(),
-            self.system_provider.get_numa_topology(),
-            self.system_provider.get_filesystems(),
-        );
+            privileges_result,
+            tpm_result,
+            numa_balancing_result,
+        ) = track_progress(progress, Subsystem::Platform, async {
+            tokio::join!(
+                self.system_provider.get_system_info(),
+                self.system_provider.get_bios_info(),
+                self.system_provider.get_chassis_info(),
+                self.system_provider.get_motherboard_info(),
+                self.system_provider.get_numa_topology(),
+                self.system_provider.get_filesystems(),
+                self.system_provider.has_required_privileges(),
+                self.system_provider.get_tpm_info(),
+                self.system_provider.get_numa_balancing(),
+            )
+        })
+        .await;
 
         let system_info = system_info_result.map_err(|e| {
             ReportError::GenerationFailed(format!("System info collection failed: {e}"))
@@ -134,6 +209,7 @@ impl HardwareCollectionService {
         })?;
         let numa_topology = numa_result
             .map_err(|e| ReportError::GenerationFailed(format!("NUMA collection failed: {e}")))?;
+        assign_gpu_cpu_affinity(&mut hardware.gpus.devices, &numa_topology);
         let filesystems = filesystems_result.map_err(|e| {
             ReportError::GenerationFailed(format!("Filesystem collection failed: {e}"))
         })?;
@@ -152,6 +228,10 @@ impl HardwareCollectionService {
                 numa_topology,
                 filesystems,
                 cpu: &cpu,
+                has_privileges: privileges_result.unwrap_or(false),
+                count_virtual_nics: config.count_virtual_nics,
+                tpm: tpm_result.unwrap_or_default(),
+                numa_balancing: numa_balancing_result.ok().flatten(),
             })
             .await?;
 
@@ -164,14 +244,30 @@ impl HardwareCollectionService {
         params: SystemSummaryParams<'_>,
     ) -> Result<SystemSummary, ReportError> {
         // Calculate CPU topology
+        let cpu_counts = params.cpu.counts();
+        let total_threads = cpu_counts.threads_total;
         let cpu_topology = CpuTopology {
-            total_cores: params.cpu.cores * params.cpu.sockets,
-            total_threads: params.cpu.cores * params.cpu.sockets * params.cpu.threads,
-            sockets: params.cpu.sockets,
-            cores_per_socket: params.cpu.cores,
-            threads_per_core: params.cpu.threads,
+            total_cores: cpu_counts.cores_total,
+            total_threads,
+            sockets: cpu_counts.sockets,
+            cores_per_socket: cpu_counts.cores_per_socket,
+            threads_per_core: cpu_counts.threads_per_core,
             numa_nodes: params.numa_topology.len() as u32,
             cpu_model: params.cpu.model.clone(),
+            isolated_cpus: params.cpu.isolated_cpus.clone(),
+            nohz_full_cpus: params.cpu.nohz_full_cpus.clone(),
+            schedulable_cores: calculate_schedulable_cores(
+                &params.cpu.online_cpus,
+                &params.cpu.isolated_cpus,
+                total_threads,
+            ),
+            offline_cpus: params.cpu.offline_cpus.clone(),
+            turbo_enabled: params.cpu.turbo_enabled,
+            smt_control: params.cpu.smt_control.clone(),
+            smt_active: params.cpu.smt_active,
+            cpufreq_driver: params.cpu.cpufreq_driver.clone(),
+            cpuidle_driver: params.cpu.cpuidle_driver.clone(),
+            logical_cpus: params.cpu.logical_cpus.clone(),
         };
 
         // Calculate total storage in TB
@@ -181,12 +277,20 @@ impl HardwareCollectionService {
         let cpu_summary = format!(
             "{} ({} Socket{}, {} Core{}/Socket, {} Thread{}/Core, {} NUMA Node{})",
             params.cpu.model,
-            params.cpu.sockets,
-            if params.cpu.sockets == 1 { "" } else { "s" },
-            params.cpu.cores,
-            if params.cpu.cores == 1 { "" } else { "s" },
-            params.cpu.threads,
-            if params.cpu.threads == 1 { "" } else { "s" },
+            cpu_counts.sockets,
+            if cpu_counts.sockets == 1 { "" } else { "s" },
+            cpu_counts.cores_per_socket,
+            if cpu_counts.cores_per_socket == 1 {
+                ""
+            } else {
+                "s"
+            },
+            cpu_counts.threads_per_core,
+            if cpu_counts.threads_per_core == 1 {
+                ""
+            } else {
+                "s"
+            },
             params.numa_topology.len(),
             if params.numa_topology.len() == 1 {
                 ""
@@ -198,31 +302,54 @@ impl HardwareCollectionService {
         // Create memory config string
         let memory_config = format!("{} @ {}", params.memory.type_, params.memory.speed);
 
+        let dmi_data_complete = is_dmi_data_complete(params.has_privileges, &params.system_info);
+
         Ok(SystemSummary {
             system_info: params.system_info,
             total_memory: params.memory.total.clone(),
             memory_config,
+            memory_running_at_rated_speed: is_memory_running_at_rated_speed(&params.memory.modules),
             total_storage: self.format_total_storage(&params.storage.devices),
             total_storage_tb,
             filesystems: params.filesystems,
             bios: params.bios,
             chassis: params.chassis,
             motherboard: params.motherboard,
+            dmi_data_complete,
             total_gpus: params.gpus.devices.len(),
-            total_nics: params.network.interfaces.len(),
+            total_mig_instances: count_mig_instances(&params.gpus.devices),
+            total_nics: count_nics(&params.network.interfaces, params.count_virtual_nics),
+            pcie_lanes_in_use: count_pcie_lanes_in_use(
+                &params.gpus.devices,
+                &params.network.interfaces,
+            ),
+            pcie_lanes_total: platform_pcie_lanes(&params.cpu.model, params.cpu.sockets),
             numa_topology: params.numa_topology,
             cpu_topology,
+            cpu_counts,
             cpu_summary,
+            tpm: params.tpm,
+            numa_balancing: params.numa_balancing,
         })
     }
 
-    /// Calculate total storage in TB
-    fn calculate_total_storage_tb(&self, devices: &[crate::domain::StorageDevice]) -> f64 {
+    /// Calculate total storage in bytes
+    fn calculate_total_storage_bytes(&self, devices: &[crate::domain::StorageDevice]) -> u64 {
         devices
             .iter()
-            .map(|device| self.parse_storage_size_to_bytes(&device.size))
-            .sum::<u64>() as f64
-            / (1024.0 * 1024.0 * 1024.0 * 1024.0) // Convert bytes to TB
+            .map(|device| {
+                if device.size_bytes > 0 {
+                    device.size_bytes
+                } else {
+                    parse_size_to_bytes(&device.size).unwrap_or(0)
+                }
+            })
+            .sum()
+    }
+
+    /// Calculate total storage in TB
+    fn calculate_total_storage_tb(&self, devices: &[crate::domain::StorageDevice]) -> f64 {
+        self.calculate_total_storage_bytes(devices) as f64 / (1024.0 * 1024.0 * 1024.0 * 1024.0)
     }
 
     /// Format total storage as human-readable string
@@ -231,33 +358,7 @@ impl HardwareCollectionService {
             return "No storage devices found".to_string();
         }
 
-        let total_tb = self.calculate_total_storage_tb(devices);
-        if total_tb >= 1.0 {
-            format!("{total_tb:.1} TB")
-        } else {
-            let total_gb = total_tb * 1024.0;
-            format!("{total_gb:.0} GB")
-        }
-    }
-
-    /// Parse storage size string to bytes (simplified version)
-    fn parse_storage_size_to_bytes(&self, size: &str) -> u64 {
-        // This is a simplified implementation - in the real implementation,
-        // we would use the more sophisticated parsing logic from the original code
-        if size.contains("TB") {
-            if let Some(num_str) = size.split_whitespace().next() {
-                if let Ok(num) = num_str.parse::<f64>() {
-                    return (num * 1024.0 * 1024.0 * 1024.0 * 1024.0) as u64;
-                }
-            }
-        } else if size.contains("GB") {
-            if let Some(num_str) = size.split_whitespace().next() {
-                if let Ok(num) = num_str.parse::<f64>() {
-                    return (num * 1024.0 * 1024.0 * 1024.0) as u64;
-                }
-            }
-        }
-        0
+        format_bytes_iec(self.calculate_total_storage_bytes(devices))
     }
 
     /// Get hostname and FQDN
@@ -282,10 +383,12 @@ impl HardwareCollectionService {
 
 #[async_trait]
 impl HardwareReportingService for HardwareCollectionService {
-    async fn generate_report(&self, _config: ReportConfig) -> Result<HardwareReport, ReportError> {
+    async fn generate_report(&self, config: ReportConfig) -> Result<HardwareReport, ReportError> {
         // Collect network identity and hardware info concurrently
-        let (network_result, hardware_result) =
-            tokio::join!(self.get_network_identity(), self.collect_hardware_info());
+        let (network_result, hardware_result) = tokio::join!(
+            self.get_network_identity(),
+            self.collect_hardware_info(&config)
+        );
 
         let (hostname, fqdn, os_ip) = network_result?;
         let (hardware, summary) = hardware_result?;
@@ -295,17 +398,220 @@ impl HardwareReportingService for HardwareCollectionService {
             ReportError::GenerationFailed(format!("Network collection failed: {e}"))
         })?;
 
-        let report = HardwareReport {
+        let data_interfaces = config
+            .data_interfaces
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| {
+                ReportError::ValidationFailed(format!("Invalid data interface pattern: {e}"))
+            })?;
+
+        let gpu_nic_affinity = (!hardware.gpus.devices.is_empty())
+            .then(|| pair_gpus_with_rdma_nics(&hardware.gpus.devices, &network.interfaces));
+
+        // Tool versions are informational, so failures are not fatal
+        let tool_versions = self
+            .system_provider
+            .get_tool_versions()
+            .await
+            .unwrap_or_default();
+
+        let os_machine_id = self.system_provider.get_os_machine_id().await.ok();
+        let (machine_id, machine_id_source) = select_machine_id(
+            &summary.system_info.uuid,
+            &summary.chassis.serial,
+            &summary.motherboard.serial,
+            os_machine_id.as_deref(),
+            &hostname,
+        );
+
+        // BIOS settings come from optional vendor tools, so failures are not fatal
+        let bios_settings = self
+            .system_provider
+            .get_bios_settings()
+            .await
+            .unwrap_or_default();
+
+        let boot_mode = self.system_provider.get_boot_mode().await.ok();
+        let time_sync = self.system_provider.get_time_sync().await.ok();
+        let watchdog = self.system_provider.get_watchdog().await.ok();
+        let power = self.system_provider.get_power_info().await.ok();
+        let bmc = self
+            .system_provider
+            .get_bmc_network()
+            .await
+            .unwrap_or_default();
+        let sensors = self.system_provider.get_sensors().await.unwrap_or_default();
+
+        // Providers without an OS (out-of-band) answer the OS-level probes
+        // below with `NotAvailable`, which skips them rather than failing
+        let operational = if config.collect_operational {
+            match self.system_provider.get_failed_units().await {
+                Ok(failed_units) => Some(OperationalInfo { failed_units }),
+                Err(SystemError::NotAvailable { resource }) => {
+                    log::warn!("Failed unit collection skipped: {resource} not available");
+                    None
+                }
+                Err(e) => {
+                    return Err(ReportError::GenerationFailed(format!(
+                        "Failed unit collection failed: {e}"
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+
+        // Needs a local BMC, so absence is not an error
+        let sel_recent_events = if config.collect_sel_events {
+            self.system_provider.get_sel_events().await.ok()
+        } else {
+            None
+        };
+
+        // The kernel log and container runtime are often unreadable in
+        // containers or without root, which should not fail the whole report
+        let kernel_hardware_errors = if config.collect_kernel_errors {
+            match self.system_provider.get_kernel_hardware_errors().await {
+                Ok(errors) => Some(errors),
+                Err(e) => {
+                    log::warn!("Kernel error collection skipped: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let container_support = if config.collect_container_runtime {
+            match self.system_provider.get_container_runtime().await {
+                Ok(runtime) => Some(runtime),
+                Err(e) => {
+                    log::warn!("Container runtime detection skipped: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let firmware_components = if config.redfish.is_some() {
+            self.system_provider
+                .get_firmware_inventory()
+                .await
+                .map_err(|e| {
+                    ReportError::GenerationFailed(format!("Firmware inventory failed: {e}"))
+                })?
+        } else {
+            Vec::new()
+        };
+
+        // Only available inside a container, so absence is not an error
+        let container_limits = self.system_provider.get_container_limits().await.ok();
+
+        // Device allowlists are only readable under cgroup v1, so absence is not an error
+        let gpu_cgroup_allocations = if config.collect_gpu_cgroup_allocations {
+            self.system_provider.get_gpu_cgroup_allocations().await.ok()
+        } else {
+            None
+        };
+
+        let cloud = if config.collect_cloud_metadata {
+            match self.system_provider.get_cloud_info().await {
+                Ok(cloud) => cloud,
+                Err(SystemError::NotAvailable { resource }) => {
+                    log::warn!("Cloud metadata collection skipped: {resource} not available");
+                    None
+                }
+                Err(e) => {
+                    return Err(ReportError::GenerationFailed(format!(
+                        "Cloud metadata collection failed: {e}"
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+
+        // Raw output is read last so it covers every command run above
+        let raw_sources = if config.include_raw {
+            Some(self.system_provider.get_raw_sources().await.map_err(|e| {
+                ReportError::GenerationFailed(format!("Raw source collection failed: {e}"))
+            })?)
+        } else {
+            None
+        };
+
+        let mut report = HardwareReport {
+            report_version: REPORT_VERSION,
             summary,
             hostname,
+            detected_hostname: None,
+            machine_id,
+            machine_id_source: machine_id_source.to_string(),
             fqdn,
             os_ip,
-            bmc_ip: None,  // Would be populated by BMC detection logic
-            bmc_mac: None, // Would be populated by BMC detection logic
+            bmc_ip: bmc.ip,
+            bmc_mac: bmc.mac,
+            bmc_ip_source: bmc.ip_source,
             hardware,
             network,
+            gpu_nic_affinity,
+            tool_versions,
+            bios_settings,
+            firmware_components,
+            boot_mode,
+            operational,
+            kernel_hardware_errors,
+            sel_recent_events,
+            configuration_deviations: Vec::new(),
+            time_sync,
+            watchdog,
+            power,
+            sensors,
+            container_support,
+            container_limits,
+            gpu_cgroup_allocations,
+            cloud,
+            raw_sources,
         };
 
+        if config.collect_xid_errors {
+            match self.system_provider.get_xid_errors().await {
+                Ok(xid_errors) => report.hardware.gpus.recent_xid_errors = Some(xid_errors),
+                Err(e) => log::warn!("Xid error collection skipped: {e}"),
+            }
+        }
+
+        if config.collect_partitions {
+            let mut partitions = match self.system_provider.get_partitions().await {
+                Ok(partitions) => partitions,
+                Err(SystemError::NotAvailable { resource }) => {
+                    log::warn!("Partition collection skipped: {resource} not available");
+                    HashMap::new()
+                }
+                Err(e) => {
+                    return Err(ReportError::GenerationFailed(format!(
+                        "Partition collection failed: {e}"
+                    )))
+                }
+            };
+            for device in &mut report.hardware.storage.devices {
+                device.partitions = partitions.remove(&device.name).unwrap_or_default();
+            }
+        }
+
+        if let Some(hostname) = &config.hostname_override {
+            report.apply_hostname_override(hostname);
+        }
+
+        report.configuration_deviations = find_configuration_deviations(
+            &report,
+            &config.configuration_profile,
+            data_interfaces.as_ref(),
+        );
+
         Ok(report)
     }
 
@@ -332,4 +638,190 @@ impl HardwareReportingService for HardwareCollectionService {
             .await
             .map_err(|e| ReportError::GenerationFailed(format!("Privilege check failed: {e}")))
     }
+
+    async fn probe_capabilities(&self) -> Result<Capabilities, ReportError> {
+        self.system_provider
+            .probe_capabilities()
+            .await
+            .map_err(|e| ReportError::GenerationFailed(format!("Capability probe failed: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::SimpleConfigurationProvider;
+    use crate::domain::{
+        BiosInfo, ChassisInfo, CpuInfo, GpuInfo, MemoryInfo, MotherboardInfo, NetworkInfo,
+        NumaNode, StorageInfo, SystemInfo,
+    };
+    use std::sync::Mutex;
+
+    /// Provider answering the required probes from the checked-in sample report
+    struct SampleSystemInfoProvider {
+        report: HardwareReport,
+    }
+
+    #[async_trait]
+    impl SystemInfoProvider for SampleSystemInfoProvider {
+        async fn get_cpu_info(&self) -> Result<CpuInfo, SystemError> {
+            Ok(self.report.hardware.cpu.clone())
+        }
+
+        async fn get_memory_info(&self) -> Result<MemoryInfo, SystemError> {
+            Ok(self.report.hardware.memory.clone())
+        }
+
+        async fn get_storage_info(&self) -> Result<StorageInfo, SystemError> {
+            Ok(self.report.hardware.storage.clone())
+        }
+
+        async fn get_gpu_info(&self) -> Result<GpuInfo, SystemError> {
+            Ok(self.report.hardware.gpus.clone())
+        }
+
+        async fn get_network_info(&self) -> Result<NetworkInfo, SystemError> {
+            Ok(self.report.network.clone())
+        }
+
+        async fn get_bios_info(&self) -> Result<BiosInfo, SystemError> {
+            Ok(self.report.summary.bios.clone())
+        }
+
+        async fn get_chassis_info(&self) -> Result<ChassisInfo, SystemError> {
+            Ok(self.report.summary.chassis.clone())
+        }
+
+        async fn get_motherboard_info(&self) -> Result<MotherboardInfo, SystemError> {
+            Ok(self.report.summary.motherboard.clone())
+        }
+
+        async fn get_system_info(&self) -> Result<SystemInfo, SystemError> {
+            Ok(self.report.summary.system_info.clone())
+        }
+
+        async fn get_numa_topology(&self) -> Result<HashMap<String, NumaNode>, SystemError> {
+            Ok(self.report.summary.numa_topology.clone())
+        }
+
+        async fn get_hostname(&self) -> Result<String, SystemError> {
+            Ok(self.report.hostname.clone())
+        }
+
+        async fn get_fqdn(&self) -> Result<String, SystemError> {
+            Ok(self.report.fqdn.clone())
+        }
+
+        async fn get_filesystems(&self) -> Result<Vec<String>, SystemError> {
+            Ok(self.report.summary.filesystems.clone())
+        }
+
+        async fn has_required_privileges(&self) -> Result<bool, SystemError> {
+            Ok(true)
+        }
+
+        async fn get_missing_dependencies(&self) -> Result<Vec<String>, SystemError> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Publisher for services that are never asked to publish
+    struct UnusedDataPublisher;
+
+    #[async_trait]
+    impl DataPublisher for UnusedDataPublisher {
+        async fn publish(
+            &self,
+            _report: &HardwareReport,
+            _config: &PublishConfig,
+        ) -> Result<(), PublishError> {
+            unreachable!("tests do not publish")
+        }
+
+        async fn test_connectivity(&self, _config: &PublishConfig) -> Result<bool, PublishError> {
+            unreachable!("tests do not publish")
+        }
+    }
+
+    fn sample_service(config: &ReportConfig) -> HardwareCollectionService {
+        HardwareCollectionService::new(
+            Arc::new(SampleSystemInfoProvider {
+                report: crate::test_support::sample_server_info().into(),
+            }),
+            Arc::new(UnusedDataPublisher),
+            Arc::new(SimpleConfigurationProvider::new(config.clone())),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_validator_rejects_report_with_missing_gpus() {
+        let config = ReportConfig::default();
+        let service = sample_service(&config);
+
+        let expect_eight_gpus = |report: &HardwareReport| {
+            let found = report.summary.total_gpus;
+            if found == 8 {
+                Ok(())
+            } else {
+                Err(vec![format!("expected 8 GPUs, found {found}")])
+            }
+        };
+
+        match service
+            .generate_and_validate(config, &expect_eight_gpus)
+            .await
+        {
+            Err(ReportError::Rejected(violations)) => {
+                assert_eq!(violations, vec!["expected 8 GPUs, found 1".to_string()]);
+            }
+            other => panic!("expected rejection, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_callback_reports_each_subsystem() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let config = ReportConfig {
+            progress: Some(ProgressCallback::new(move |event| {
+                recorded.lock().unwrap().push(event)
+            })),
+            ..Default::default()
+        };
+        let service = sample_service(&config);
+
+        service.generate_report(config).await.unwrap();
+
+        let events = events.lock().unwrap();
+        let started: Vec<Subsystem> = events
+            .iter()
+            .filter_map(|event| match event {
+                SubsystemProgress::Started(subsystem) => Some(*subsystem),
+                SubsystemProgress::Completed(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            started,
+            vec![
+                Subsystem::Cpu,
+                Subsystem::Memory,
+                Subsystem::Storage,
+                Subsystem::Gpus,
+                Subsystem::Network,
+                Subsystem::Platform,
+            ]
+        );
+        for subsystem in started {
+            let start = events
+                .iter()
+                .position(|e| *e == SubsystemProgress::Started(subsystem))
+                .unwrap();
+            let end = events
+                .iter()
+                .position(|e| *e == SubsystemProgress::Completed(subsystem))
+                .unwrap();
+            assert!(start < end, "{subsystem} completed before it started");
+        }
+        assert_eq!(events.len(), 12);
+    }
 }
@@ -62,8 +62,12 @@ limitations under the License.
 // New Ports and Adapters Architecture
 pub mod adapters;
 pub mod container;
+pub mod diff;
 pub mod domain;
 pub mod ports;
+pub mod schema;
+#[cfg(test)]
+mod test_support;
 
 // Re-export public API - specific exports to avoid conflicts with legacy types
 // Only export new types that don't conflict with legacy compatibility layer
@@ -72,11 +76,16 @@ pub use adapters::{
     MacOSSystemInfoProvider, UnixCommandExecutor,
 };
 pub use container::{ContainerConfig, ContainerConfigBuilder, ServiceContainer};
-pub use domain::{PublishConfig, PublishError, ReportConfig, ReportError};
+pub use diff::{ComponentChange, DiffOptions, FieldChange, HardwareDiff};
+pub use domain::{
+    Capabilities, ProgressCallback, PublishConfig, PublishError, ReportConfig, ReportError,
+    Subsystem, SubsystemProgress,
+};
 pub use ports::{
     CommandExecutor, ConfigurationProvider, DataPublisher, FileRepository,
     HardwareReportingService, OutputFormat, SystemInfoProvider,
 };
+pub use schema::json_schema;
 
 // Re-export domain entities under a namespace to avoid conflicts
 pub use domain::HardwareReport as NewHardwareReport;
@@ -88,9 +97,15 @@ pub mod new_domain {
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
-use std::process::Command;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// Upper bound for a single `smartctl -a` call; a hung drive must not stall collection
+const SMARTCTL_TIMEOUT: Duration = Duration::from_secs(10);
 
 lazy_static! {
     static ref STORAGE_SIZE_RE: Regex = Regex::new(r"(\d+(?:\.\d+)?)(B|K|M|G|T)").unwrap();
@@ -98,7 +113,7 @@ lazy_static! {
 }
 
 /// CPU topology information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuTopology {
     pub total_cores: u32,
     pub total_threads: u32,
@@ -110,7 +125,7 @@ pub struct CpuTopology {
 }
 
 /// Motherboard information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MotherboardInfo {
     pub manufacturer: String,
     pub product_name: String,
@@ -121,7 +136,7 @@ pub struct MotherboardInfo {
     pub type_: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub uuid: String,
     pub serial: String,
@@ -130,7 +145,7 @@ pub struct SystemInfo {
 }
 
 /// Summary of key system components
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemSummary {
     /// System information
     pub system_info: SystemInfo,
@@ -163,7 +178,7 @@ pub struct SystemSummary {
 }
 
 /// BIOS information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiosInfo {
     pub vendor: String,
     pub version: String,
@@ -172,30 +187,81 @@ pub struct BiosInfo {
 }
 
 /// Chassis information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChassisInfo {
     pub manufacturer: String,
     pub type_: String,
     pub serial: String,
 }
 
+/// Options for [`ServerInfo::collect_with`]
+#[derive(Debug, Clone, Default)]
+pub struct CollectOptions {
+    /// Skip slow per-device probes (smartctl); the fields they populate stay `None`
+    pub fast: bool,
+    /// Pattern selecting the data interfaces whose MTUs must agree; a
+    /// mismatch is recorded in `configuration_deviations`
+    pub data_interfaces: Option<Regex>,
+}
+
 /// Represents the overall server information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerInfo {
+    /// Report format version, see `domain::REPORT_VERSION` (0 for reports
+    /// written before versioning)
+    #[serde(default)]
+    pub report_version: u32,
     /// System summary
     pub summary: SystemSummary,
     /// Other fields remain the same
     pub hostname: String,
+    /// Hostname detected on the system when it was replaced by an override
+    #[serde(default)]
+    pub detected_hostname: Option<String>,
+    /// Canonical machine identifier, see `domain::select_machine_id` for the priority order
+    #[serde(default)]
+    pub machine_id: String,
+    /// Source of `machine_id` (e.g., "system_uuid", "chassis_serial", "hostname")
+    #[serde(default)]
+    pub machine_id_source: String,
     pub fqdn: String,
     pub os_ip: Vec<InterfaceIPs>,
     pub bmc_ip: Option<String>,
     pub bmc_mac: Option<String>,
+    /// How the BMC obtained its IP address ("dhcp" or "static")
+    #[serde(default)]
+    pub bmc_ip_source: Option<String>,
     pub hardware: HardwareInfo,
     pub network: NetworkInfo,
+    /// Health findings, see `domain::HardwareReport::configuration_deviations`
+    #[serde(default)]
+    pub configuration_deviations: Vec<domain::Deviation>,
 }
 
+/// Static subset of `ServerInfo` suitable for committed baselines
+///
+/// Components carry only the fields `ServerInfo::diff` treats as
+/// non-volatile, sorted so probe order does not matter.
+#[derive(Debug, Serialize)]
+pub struct StableServerInfo<'a> {
+    pub system_info: &'a SystemInfo,
+    pub bios: &'a BiosInfo,
+    pub chassis: &'a ChassisInfo,
+    pub motherboard: &'a MotherboardInfo,
+    /// CPU model, socket, core and thread counts
+    pub cpu: StableFields,
+    pub memory_modules: Vec<StableFields>,
+    pub storage_devices: Vec<StableFields>,
+    pub gpus: Vec<StableFields>,
+    /// Network interface identity, without addresses or link state
+    pub interfaces: Vec<StableFields>,
+}
+
+/// Non-volatile fields of one component, keyed by field name
+pub type StableFields = BTreeMap<&'static str, String>;
+
 /// Contains detailed hardware information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardwareInfo {
     /// CPU information.
     pub cpu: CpuInfo,
@@ -208,13 +274,19 @@ pub struct HardwareInfo {
 }
 
 /// Represents CPU information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuInfo {
     /// CPU model name.
     pub model: String,
     /// Number of cores per socket.
+    ///
+    /// Deprecated naming, kept for compatibility; prefer
+    /// `summary.cpu_topology.cores_per_socket` and `total_cores`.
     pub cores: u32,
     /// Number of threads per core.
+    ///
+    /// Deprecated naming, kept for compatibility; prefer
+    /// `summary.cpu_topology.threads_per_core` and `total_threads`.
     pub threads: u32,
     /// Number of sockets.
     pub sockets: u32,
@@ -223,7 +295,7 @@ pub struct CpuInfo {
 }
 
 /// Represents memory information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryInfo {
     /// Total memory size.
     pub total: String,
@@ -236,7 +308,7 @@ pub struct MemoryInfo {
 }
 
 /// Represents a memory module.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryModule {
     /// Size of the memory module.
     pub size: String,
@@ -250,17 +322,20 @@ pub struct MemoryModule {
     pub manufacturer: String,
     /// Serial number of the memory module.
     pub serial: String,
+    /// Configured (actual) speed, which may be below the rated `speed`.
+    #[serde(default)]
+    pub configured_speed: Option<String>,
 }
 
 /// Represents storage information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageInfo {
     /// List of storage devices.
     pub devices: Vec<StorageDevice>,
 }
 
 /// Represents a storage device.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageDevice {
     /// Device name.
     pub name: String,
@@ -270,17 +345,35 @@ pub struct StorageDevice {
     pub size: String,
     /// Device model.
     pub model: String,
+    /// Serial number, from smartctl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    /// Firmware revision, from smartctl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware: Option<String>,
+    /// Overall SMART self-assessment passed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_healthy: Option<bool>,
+    /// Rated endurance used in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wearout_percent: Option<u8>,
+    /// NVMe controller owning this namespace (e.g., "nvme0")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nvme_controller: Option<String>,
+    /// Model of the NVMe controller, from sysfs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller_model: Option<String>,
 }
 
 /// Represents GPU information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuInfo {
     /// List of GPU devices.
     pub devices: Vec<GpuDevice>,
 }
 
 /// Represents a GPU device.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuDevice {
     /// GPU index
     pub index: u32,
@@ -299,7 +392,7 @@ pub struct GpuDevice {
 }
 
 /// Represents a NUMA node
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NumaNode {
     /// Node ID
     pub id: i32,
@@ -314,7 +407,7 @@ pub struct NumaNode {
 }
 
 /// Represents a device attached to a NUMA node
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NumaDevice {
     /// Device type (GPU, NIC, etc.)
     pub type_: String,
@@ -325,7 +418,7 @@ pub struct NumaDevice {
 }
 
 /// Represents network information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInfo {
     /// List of network interfaces.
     pub interfaces: Vec<NetworkInterface>,
@@ -334,7 +427,7 @@ pub struct NetworkInfo {
 }
 
 /// Represents a network interface.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInterface {
     /// Interface name.
     pub name: String,
@@ -352,17 +445,20 @@ pub struct NetworkInterface {
     pub model: String,
     pub pci_id: String,
     pub numa_node: Option<i32>,
+    /// Maximum transmission unit in bytes (0 when unknown).
+    #[serde(default)]
+    pub mtu: u32,
 }
 
 /// Represents Infiniband information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfinibandInfo {
     /// List of Infiniband interfaces.
     pub interfaces: Vec<IbInterface>,
 }
 
 /// Represents an Infiniband interface.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IbInterface {
     /// Interface name.
     pub name: String,
@@ -372,6 +468,12 @@ pub struct IbInterface {
     pub state: String,
     /// Interface rate.
     pub rate: String,
+    /// Link layer (e.g., "InfiniBand" or "Ethernet" for RoCE).
+    #[serde(default)]
+    pub link_layer: Option<String>,
+    /// Active MTU in bytes.
+    #[serde(default)]
+    pub active_mtu: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -381,7 +483,7 @@ pub struct NumaInfo {
 
 pub mod posting;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceIPs {
     pub interface: String,
     pub ip_addresses: Vec<String>,
@@ -393,7 +495,103 @@ pub struct InterfaceIPs {
 #[allow(clippy::manual_map)]
 #[allow(clippy::format_in_format_args)]
 #[allow(clippy::needless_borrows_for_generic_args)]
+#[allow(clippy::type_complexity)]
 impl ServerInfo {
+    /// Load a report previously written as TOML (e.g. `<serial>_hardware_report.toml`)
+    ///
+    /// Fields added to the report since the file was written take their
+    /// defaults; unknown fields are ignored.
+    pub fn from_toml_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let report: Self = toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        report.warn_if_newer(path);
+        Ok(report)
+    }
+
+    /// Load a report previously written as JSON, see [`ServerInfo::from_toml_file`]
+    pub fn from_json_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let report: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        report.warn_if_newer(path);
+        Ok(report)
+    }
+
+    /// Check whether this report uses a newer schema than this library understands
+    ///
+    /// # Returns
+    /// * `Some(String)` - Compatibility warning message
+    /// * `None` - Report schema is supported
+    pub fn compatibility_warning(&self) -> Option<String> {
+        (self.report_version > domain::REPORT_VERSION).then(|| {
+            format!(
+                "Report version {} is newer than supported version {}; some fields may be ignored",
+                self.report_version,
+                domain::REPORT_VERSION
+            )
+        })
+    }
+
+    /// A newer schema is loaded with a warning rather than rejected
+    fn warn_if_newer(&self, path: &Path) {
+        if let Some(warning) = self.compatibility_warning() {
+            log::warn!("{}: {warning}", path.display());
+        }
+    }
+
+    /// Replace the collected hostname and FQDN with an intended hostname,
+    /// preserving the detected hostname in `detected_hostname`
+    pub fn apply_hostname_override(&mut self, hostname: &str) {
+        self.fqdn = domain::override_fqdn(&self.hostname, &self.fqdn, hostname);
+        let detected = std::mem::replace(&mut self.hostname, hostname.to_string());
+        self.detected_hostname = Some(detected);
+    }
+
+    /// Static subset of the report that only changes with the hardware
+    ///
+    /// Covers DMI identity plus the non-volatile CPU, memory, storage, GPU
+    /// and NIC fields used by [`ServerInfo::diff`]. IP addresses, link
+    /// speeds, CPU clock, device names and SMART state are left out.
+    pub fn stable_subset(&self) -> StableServerInfo<'_> {
+        fn project<T>(components: &[T], fields: fn(&T) -> Vec<diff::Field>) -> Vec<StableFields> {
+            let mut projected: Vec<StableFields> = components
+                .iter()
+                .map(|component| diff::stable_fields(fields(component)))
+                .collect();
+            projected.sort();
+            projected
+        }
+
+        StableServerInfo {
+            system_info: &self.summary.system_info,
+            bios: &self.summary.bios,
+            chassis: &self.summary.chassis,
+            motherboard: &self.summary.motherboard,
+            cpu: diff::stable_fields(diff::cpu_fields(&self.hardware.cpu)),
+            memory_modules: project(&self.hardware.memory.modules, diff::memory_fields),
+            storage_devices: project(&self.hardware.storage.devices, diff::storage_fields),
+            gpus: project(&self.hardware.gpus.devices, diff::gpu_fields),
+            interfaces: project(&self.network.interfaces, diff::nic_fields),
+        }
+    }
+
+    /// Stable fingerprint of the installed hardware, hashed from `stable_subset`
+    pub fn hardware_fingerprint(&self) -> String {
+        // serde_json output is deterministic here since the subset only contains ordered maps
+        let canonical = serde_json::to_vec(&self.stable_subset()).unwrap_or_default();
+
+        // FNV-1a, so the value is stable across builds and Rust versions
+        let hash = canonical
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}")
+    }
+
     /// Checks for required system dependencies and returns any missing ones
     fn check_dependencies() -> Result<Vec<&'static str>, Box<dyn Error>> {
         let required_packages = if cfg!(target_os = "macos") {
@@ -654,19 +852,20 @@ impl ServerInfo {
 
         let size_str = size.replace(" ", "").to_uppercase();
 
-        // Handle both Linux format (123G) and macOS format (123 GB)
-        let re = Regex::new(r"(\d+(?:\.\d+)?)\s*(B|KB?|MB?|GB?|TB?|BYTES?)$")?;
+        // Handle Linux format (123G), macOS format (123 GB) and IEC units (123 GiB, 123Gi)
+        let re = Regex::new(r"(\d+(?:\.\d+)?)\s*(B|[KMGTP]I?B?|BYTES?)$")?;
 
         if let Some(caps) = re.captures(&size_str) {
             let value: f64 = caps[1].parse()?;
             let unit = &caps[2];
 
-            let multiplier = match unit {
-                "B" | "BYTES" => 1_u64,
-                "K" | "KB" => 1024_u64,
-                "M" | "MB" => 1024_u64 * 1024,
-                "G" | "GB" => 1024_u64 * 1024 * 1024,
-                "T" | "TB" => 1024_u64 * 1024 * 1024 * 1024,
+            let multiplier = match &unit[..1] {
+                "B" => 1_u64,
+                "K" => 1024_u64,
+                "M" => 1024_u64 * 1024,
+                "G" => 1024_u64 * 1024 * 1024,
+                "T" => 1024_u64 * 1024 * 1024 * 1024,
+                "P" => 1024_u64 * 1024 * 1024 * 1024 * 1024,
                 _ => return Err(format!("Unknown storage unit: {unit}").into()),
             };
 
@@ -817,16 +1016,8 @@ impl ServerInfo {
                 match parts[0].trim() {
                     "Vendor" => vendor = value.to_string(),
                     "Device" => device = value.to_string(),
-                    "SVendor" => {
-                        if vendor.is_empty() {
-                            vendor = value.to_string()
-                        }
-                    }
-                    "SDevice" => {
-                        if device.is_empty() {
-                            device = value.to_string()
-                        }
-                    }
+                    "SVendor" if vendor.is_empty() => vendor = value.to_string(),
+                    "SDevice" if device.is_empty() => device = value.to_string(),
                     _ => {}
                 }
             }
@@ -1123,6 +1314,11 @@ impl ServerInfo {
 
     /// Collects all server information
     pub fn collect() -> Result<Self, Box<dyn Error>> {
+        Self::collect_with(&CollectOptions::default())
+    }
+
+    /// Collects all server information with explicit options
+    pub fn collect_with(options: &CollectOptions) -> Result<Self, Box<dyn Error>> {
         // Check dependencies first and warn about missing packages
         let missing_packages = Self::check_dependencies()?;
 
@@ -1153,24 +1349,53 @@ impl ServerInfo {
 
         let hostname = Self::get_hostname()?;
         let fqdn = Self::get_fqdn()?;
-        let hardware = Self::collect_hardware_info()?;
+        let hardware = Self::collect_hardware_info(options)?;
         let network = Self::collect_network_info()?;
         let system_info = Self::get_system_info()?;
-        let (bmc_ip, bmc_mac) = Self::collect_ipmi_info()?;
+        let (bmc_ip, bmc_mac, bmc_ip_source) = Self::collect_ipmi_info()?;
         let os_ip = Self::collect_ip_addresses()?;
 
         let summary = Self::generate_summary(&hardware, &network, &system_info)?;
+        let os_machine_id = if cfg!(target_os = "macos") {
+            // The hardware UUID is already reported as the system UUID on macOS
+            None
+        } else {
+            std::fs::read_to_string("/etc/machine-id").ok()
+        };
+        let (machine_id, machine_id_source) = domain::select_machine_id(
+            &summary.system_info.uuid,
+            &summary.chassis.serial,
+            &summary.motherboard.serial,
+            os_machine_id.as_deref(),
+            &hostname,
+        );
 
-        Ok(ServerInfo {
+        let mut server_info = ServerInfo {
+            report_version: domain::REPORT_VERSION,
             summary,
             hostname,
+            detected_hostname: None,
+            machine_id,
+            machine_id_source: machine_id_source.to_string(),
             fqdn,
             os_ip,
             bmc_ip,
             bmc_mac,
+            bmc_ip_source,
             hardware,
             network,
-        })
+            configuration_deviations: Vec::new(),
+        };
+
+        // Same health checks as the service; the profile settings are not
+        // collected here, so only the health checks can fire
+        let report: domain::HardwareReport = server_info.clone().into();
+        server_info.configuration_deviations = domain::find_configuration_deviations(
+            &report,
+            &domain::ConfigurationProfile::default(),
+            options.data_interfaces.as_ref(),
+        );
+        Ok(server_info)
     }
 
     /// Calculates total storage in terabytes
@@ -1209,17 +1434,7 @@ impl ServerInfo {
             }
         }
 
-        if total_bytes >= 1024 * 1024 * 1024 * 1024 {
-            Ok(format!(
-                "{:.1} TB",
-                total_bytes as f64 / (1024.0 * 1024.0 * 1024.0 * 1024.0)
-            ))
-        } else {
-            Ok(format!(
-                "{:.1} GB",
-                total_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
-            ))
-        }
+        Ok(domain::format_bytes_iec(total_bytes))
     }
 
     /// Gets filesystem information
@@ -1478,11 +1693,7 @@ impl ServerInfo {
     fn get_cpu_topology_macos() -> Result<CpuTopology, Box<dyn Error>> {
         let physical_cores = Self::get_macos_cpu_cores().unwrap_or(0);
         let logical_cores = Self::get_macos_logical_cpu_cores().unwrap_or(physical_cores);
-        let threads_per_core = if physical_cores > 0 {
-            logical_cores / physical_cores
-        } else {
-            1
-        };
+        let threads_per_core = logical_cores.checked_div(physical_cores).unwrap_or(1);
 
         // Get CPU model from system_profiler
         let mut cpu_model = "Unknown".to_string();
@@ -1653,7 +1864,11 @@ impl ServerInfo {
             chassis,
             motherboard,
             total_gpus: hardware.gpus.devices.len(),
-            total_nics: network.interfaces.len(),
+            total_nics: network
+                .interfaces
+                .iter()
+                .filter(|nic| !domain::is_container_interface(&nic.name))
+                .count(),
             numa_topology: Self::collect_numa_topology()?,
             cpu_topology,
             cpu_summary,
@@ -1661,11 +1876,11 @@ impl ServerInfo {
     }
 
     /// Collects detailed hardware information.
-    fn collect_hardware_info() -> Result<HardwareInfo, Box<dyn Error>> {
+    fn collect_hardware_info(options: &CollectOptions) -> Result<HardwareInfo, Box<dyn Error>> {
         Ok(HardwareInfo {
             cpu: Self::collect_cpu_info()?,
             memory: Self::collect_memory_info()?,
-            storage: Self::collect_storage_info()?,
+            storage: Self::collect_storage_info(options)?,
             gpus: Self::collect_gpu_info()?,
         })
     }
@@ -1965,6 +2180,7 @@ impl ServerInfo {
                 location: "System Memory".to_string(),
                 manufacturer: manufacturer.clone(),
                 serial: "N/A".to_string(),
+                configured_speed: None,
             }]
         } else {
             // Check for traditional DIMM slots (Intel Macs)
@@ -1988,6 +2204,7 @@ impl ServerInfo {
                         location: current_slot.clone(),
                         manufacturer: "Unknown".to_string(),
                         serial: "Unknown".to_string(),
+                        configured_speed: None,
                     });
                 } else if let Some(ref mut module) = current_module {
                     if trimmed.starts_with("Size:") {
@@ -2091,8 +2308,7 @@ impl ServerInfo {
             .output()?;
         let memsize_str = String::from_utf8(output.stdout)?;
         if let Ok(bytes) = memsize_str.trim().parse::<u64>() {
-            let gb = bytes as f64 / (1024.0 * 1024.0 * 1024.0);
-            Ok(format!("{gb:.1}G"))
+            Ok(domain::format_bytes_iec(bytes))
         } else {
             Ok("Unknown".to_string())
         }
@@ -2110,8 +2326,7 @@ impl ServerInfo {
                             let parts: Vec<&str> = line.split_whitespace().collect();
                             if parts.len() >= 2 {
                                 let kb: u64 = parts[1].parse().unwrap_or(0);
-                                let gb = kb as f64 / 1024.0 / 1024.0;
-                                return Ok(format!("{gb:.1}G"));
+                                return Ok(domain::format_bytes_iec(kb * 1024));
                             }
                         }
                     }
@@ -2142,6 +2357,11 @@ impl ServerInfo {
         let location = Self::extract_dmidecode_value(text, "Locator")?;
         let manufacturer = Self::extract_dmidecode_value(text, "Manufacturer")?;
         let serial = Self::extract_dmidecode_value(text, "Serial Number")?;
+        // Older dmidecode versions call it "Configured Clock Speed"
+        let configured_speed = ["Configured Memory Speed", "Configured Clock Speed"]
+            .iter()
+            .find_map(|key| Self::extract_dmidecode_value(text, key).ok())
+            .filter(|speed| !domain::is_unknown(speed));
 
         Ok(MemoryModule {
             size,
@@ -2150,15 +2370,16 @@ impl ServerInfo {
             location,
             manufacturer,
             serial,
+            configured_speed,
         })
     }
 
     /// Collects storage information using platform-specific commands.
-    fn collect_storage_info() -> Result<StorageInfo, Box<dyn Error>> {
+    fn collect_storage_info(options: &CollectOptions) -> Result<StorageInfo, Box<dyn Error>> {
         if cfg!(target_os = "macos") {
             Self::collect_storage_info_macos()
         } else {
-            Self::collect_storage_info_linux()
+            Self::collect_storage_info_linux(options)
         }
     }
 
@@ -2236,6 +2457,12 @@ impl ServerInfo {
                             type_: medium_type.to_lowercase(),
                             size: capacity,
                             model: format!("{device_name} ({protocol})"),
+                            serial: None,
+                            firmware: None,
+                            smart_healthy: None,
+                            wearout_percent: None,
+                            nvme_controller: None,
+                            controller_model: None,
                         });
                     }
                 } else {
@@ -2305,6 +2532,12 @@ impl ServerInfo {
                                         },
                                         size: total_size,
                                         model: format!("{device_name} ({device_location})"),
+                                        serial: None,
+                                        firmware: None,
+                                        smart_healthy: None,
+                                        wearout_percent: None,
+                                        nvme_controller: None,
+                                        controller_model: None,
                                     });
                                 }
                             }
@@ -2318,7 +2551,7 @@ impl ServerInfo {
     }
 
     /// Collects storage information on Linux using lsblk
-    fn collect_storage_info_linux() -> Result<StorageInfo, Box<dyn Error>> {
+    fn collect_storage_info_linux(options: &CollectOptions) -> Result<StorageInfo, Box<dyn Error>> {
         let output = match Command::new("lsblk")
             .args(&["-J", "-o", "NAME,TYPE,SIZE,MODEL"])
             .output()
@@ -2335,14 +2568,35 @@ impl ServerInfo {
         let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
         let mut devices = Vec::new();
 
+        // Only top-level disks count; partitions are nested under their disk and
+        // NVMe controllers are character devices, so each namespace is counted once
         if let Some(blockdevices) = json["blockdevices"].as_array() {
             for device in blockdevices {
                 if device["type"].as_str() == Some("disk") {
+                    let name = device["name"].as_str().unwrap_or("").to_string();
+                    let health = if options.fast {
+                        domain::SmartHealth::default()
+                    } else {
+                        Self::collect_smart_health(&name).unwrap_or_default()
+                    };
+                    let nvme_controller = domain::nvme_namespace_controller(&name);
+                    let controller_model = nvme_controller.and_then(|controller| {
+                        std::fs::read_to_string(format!("/sys/class/nvme/{controller}/model"))
+                            .ok()
+                            .map(|model| model.trim().to_string())
+                            .filter(|model| !model.is_empty())
+                    });
                     devices.push(StorageDevice {
-                        name: device["name"].as_str().unwrap_or("").to_string(),
+                        nvme_controller: nvme_controller.map(str::to_string),
+                        controller_model,
                         type_: device["type"].as_str().unwrap_or("").to_string(),
                         size: device["size"].as_str().unwrap_or("").to_string(),
                         model: device["model"].as_str().unwrap_or("").to_string(),
+                        serial: health.serial,
+                        firmware: health.firmware,
+                        smart_healthy: health.healthy,
+                        wearout_percent: health.wearout_percent,
+                        name,
                     });
                 }
             }
@@ -2351,6 +2605,65 @@ impl ServerInfo {
         Ok(StorageInfo { devices })
     }
 
+    /// Reads serial, firmware and health for a disk with smartctl
+    ///
+    /// Returns `None` when smartctl is missing, times out, or could not open
+    /// the device. Failing drives make smartctl exit non-zero but still
+    /// produce a full report, which is parsed.
+    fn collect_smart_health(name: &str) -> Option<domain::SmartHealth> {
+        let output = Self::output_with_timeout(
+            Command::new("smartctl").args(&["-j", "-a", &format!("/dev/{name}")]),
+            SMARTCTL_TIMEOUT,
+        )?;
+        if !output
+            .status
+            .code()
+            .is_some_and(domain::smartctl_exit_has_report)
+        {
+            return None;
+        }
+        domain::parse_smartctl_health_json(&String::from_utf8_lossy(&output.stdout)).ok()
+    }
+
+    /// Runs a command, killing it if it has not exited within `timeout`
+    ///
+    /// Returns `None` when the command cannot be started or times out.
+    /// stderr is discarded.
+    fn output_with_timeout(command: &mut Command, timeout: Duration) -> Option<Output> {
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        // Drain stdout on a thread so a large report cannot fill the pipe
+        let mut stdout = child.stdout.take()?;
+        let reader = std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = stdout.read_to_end(&mut buffer);
+            buffer
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            match child.try_wait().ok()? {
+                Some(status) => break status,
+                None if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return None;
+                }
+                None => std::thread::sleep(Duration::from_millis(20)),
+            }
+        };
+
+        Some(Output {
+            status,
+            stdout: reader.join().ok()?,
+            stderr: Vec::new(),
+        })
+    }
+
     /// Collects GPU information using platform-specific commands.
     fn collect_gpu_info() -> Result<GpuInfo, Box<dyn Error>> {
         if cfg!(target_os = "macos") {
@@ -2542,9 +2855,48 @@ impl ServerInfo {
             }
         }
 
+        if devices.is_empty() {
+            devices = Self::collect_intel_gpus();
+        }
+
         Ok(GpuInfo { devices })
     }
 
+    /// Collects Intel discrete GPUs using xpu-smi, falling back to an lspci scan
+    /// for cards the tool does not report
+    fn collect_intel_gpus() -> Vec<GpuDevice> {
+        let mut found = Command::new("xpu-smi")
+            .args(&["discovery", "-j"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                domain::parse_xpu_smi_discovery(&String::from_utf8_lossy(&output.stdout)).ok()
+            })
+            .unwrap_or_default();
+
+        if let Ok(output) = Command::new("lspci")
+            .args(&["-D", "-nn", "-d", "8086:"])
+            .output()
+        {
+            let scanned = domain::parse_lspci_intel_gpus(&String::from_utf8_lossy(&output.stdout));
+            domain::merge_gpu_devices(&mut found, scanned);
+        }
+
+        found
+            .into_iter()
+            .map(|device| GpuDevice {
+                numa_node: device.pci_bus_id.as_deref().and_then(Self::get_numa_node),
+                index: device.index,
+                name: device.name,
+                uuid: device.uuid,
+                memory: device.memory,
+                pci_id: device.pci_id,
+                vendor: device.vendor,
+            })
+            .collect()
+    }
+
     /// Collects network information, including Infiniband if available.
     fn collect_network_info() -> Result<NetworkInfo, Box<dyn Error>> {
         if cfg!(target_os = "macos") {
@@ -2630,6 +2982,7 @@ impl ServerInfo {
                         model: model.to_string(),
                         pci_id: pci_id.to_string(),
                         numa_node: None,
+                        mtu: 0,
                     });
                 }
 
@@ -2697,6 +3050,7 @@ impl ServerInfo {
                     model: model.to_string(),
                     pci_id: pci_id.to_string(),
                     numa_node: None,
+                    mtu: 0,
                 });
             } else if let Some(ref mut interface) = current_interface {
                 if trimmed.starts_with("Type:") {
@@ -2966,6 +3320,7 @@ impl ServerInfo {
                         model,
                         pci_id,
                         numa_node,
+                        mtu: iface["mtu"].as_u64().unwrap_or(0) as u32,
                     });
                 }
             }
@@ -2984,21 +3339,7 @@ impl ServerInfo {
         match output {
             Ok(output) => {
                 let output_str = String::from_utf8(output.stdout)?;
-                let mut interfaces = Vec::new();
-
-                // Parse ibstat output.
-                let re = Regex::new(
-                    r"CA '([^']+)'\n(?:\s+.+\n)*?\s+Port (\d+):\n(?:\s+.+\n)*?\s+State:\s+(\S+)\s+(?:\S+)\n(?:\s+.+\n)*?\s+Rate:\s+(\S+)",
-                )?;
-
-                for cap in re.captures_iter(&output_str) {
-                    interfaces.push(IbInterface {
-                        name: cap[1].to_string(),
-                        port: cap[2].parse()?,
-                        state: cap[3].to_string(),
-                        rate: cap[4].to_string(),
-                    });
-                }
+                let interfaces = Self::parse_ibstat(&output_str)?;
 
                 if interfaces.is_empty() {
                     Ok(None)
@@ -3010,11 +3351,61 @@ impl ServerInfo {
         }
     }
 
-    /// Collects BMC IP and MAC addresses by parsing 'ipmitool' output.
-    fn collect_ipmi_info() -> Result<(Option<String>, Option<String>), Box<dyn Error>> {
+    /// Parses 'ibstat' output into one interface per CA port.
+    ///
+    /// Ports without a state or rate are skipped; the link layer and active
+    /// MTU are optional since not every ibstat version prints them.
+    fn parse_ibstat(output: &str) -> Result<Vec<IbInterface>, Box<dyn Error>> {
+        let ca_re = Regex::new(r"^CA '([^']+)'")?;
+        let port_re = Regex::new(r"^\s+Port (\d+):")?;
+        let field_re = Regex::new(r"^\s+(State|Rate|Link layer|Active MTU):\s+(\S+)")?;
+
+        let mut interfaces = Vec::new();
+        let mut ca_name: Option<String> = None;
+        let mut port: Option<IbInterface> = None;
+        let mut flush = |port: Option<IbInterface>| {
+            if let Some(port) = port.filter(|p| !p.state.is_empty() && !p.rate.is_empty()) {
+                interfaces.push(port);
+            }
+        };
+
+        for line in output.lines() {
+            if let Some(cap) = ca_re.captures(line) {
+                flush(port.take());
+                ca_name = Some(cap[1].to_string());
+            } else if let Some(cap) = port_re.captures(line) {
+                flush(port.take());
+                if let Some(name) = &ca_name {
+                    port = Some(IbInterface {
+                        name: name.clone(),
+                        port: cap[1].parse()?,
+                        state: String::new(),
+                        rate: String::new(),
+                        link_layer: None,
+                        active_mtu: None,
+                    });
+                }
+            } else if let (Some(port), Some(cap)) = (port.as_mut(), field_re.captures(line)) {
+                let value = cap[2].to_string();
+                match &cap[1] {
+                    "State" => port.state = value,
+                    "Rate" => port.rate = value,
+                    "Link layer" => port.link_layer = Some(value),
+                    _ => port.active_mtu = Some(value),
+                }
+            }
+        }
+        flush(port);
+
+        Ok(interfaces)
+    }
+
+    /// Collects BMC IP, MAC and IP address source by parsing 'ipmitool' output.
+    fn collect_ipmi_info(
+    ) -> Result<(Option<String>, Option<String>, Option<String>), Box<dyn Error>> {
         if cfg!(target_os = "macos") {
             // IPMI is not typically available on macOS
-            return Ok((None, None));
+            return Ok((None, None, None));
         }
 
         let output = Command::new("ipmitool").args(&["lan", "print"]).output();
@@ -3032,13 +3423,40 @@ impl ServerInfo {
                     .captures(&output_str)
                     .map(|cap| cap[1].trim().to_string());
 
-                Ok((ip, mac))
+                let ip_source = domain::parse_ipmitool_ip_source(&output_str);
+
+                Ok((ip, mac, ip_source))
             }
-            Err(_) => Ok((None, None)),
+            Err(_) => Ok((None, None, None)),
         }
     }
 }
 
+/// Render a report in the Prometheus text exposition format
+///
+/// Write the output to a `.prom` file in node_exporter's textfile collector
+/// directory. Metric names and labels match [`export_openmetrics`].
+///
+/// # Arguments
+/// * `report` - Collected server information
+///
+/// # Returns
+/// * Metric families with `# HELP` and `# TYPE` headers
+pub fn export_prometheus(report: &ServerInfo) -> String {
+    adapters::to_prometheus(&report.clone().into())
+}
+
+/// Render a report in the OpenMetrics text exposition format
+///
+/// # Arguments
+/// * `report` - Collected server information
+///
+/// # Returns
+/// * Metric families with `# UNIT` metadata, ending with `# EOF`
+pub fn export_openmetrics(report: &ServerInfo) -> String {
+    adapters::to_openmetrics(&report.clone().into())
+}
+
 // Legacy compatibility will be handled by keeping the old ServerInfo struct
 // and implementing From traits for conversion between old and new types
 
@@ -3145,3 +3563,120 @@ pub async fn validate_system() -> Result<(Vec<String>, bool), Box<dyn Error>> {
     let has_privileges = container.check_privileges().await?;
     Ok((missing_deps, has_privileges))
 }
+
+/// Probe what can be collected on this host without collecting any data
+///
+/// # Returns
+/// * `Ok(Capabilities)` - Active adapter, privilege status and per-subsystem availability
+/// * `Err(Box<dyn Error>)` - Error occurred while probing
+pub async fn probe_capabilities() -> Result<Capabilities, Box<dyn Error>> {
+    let container = ServiceContainer::with_defaults();
+    container.probe_capabilities().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_future_report_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut report = test_support::sample_server_info();
+        assert!(report.compatibility_warning().is_none());
+        report.report_version = domain::REPORT_VERSION + 1;
+
+        // A newer schema is loaded with a warning rather than rejected
+        let json_path = temp_dir.path().join("future_report.json");
+        std::fs::write(&json_path, serde_json::to_string(&report).unwrap()).unwrap();
+        let loaded = ServerInfo::from_json_file(&json_path).unwrap();
+        assert_eq!(loaded.hostname, report.hostname);
+        assert!(loaded.compatibility_warning().is_some());
+
+        let toml_path = temp_dir.path().join("future_report.toml");
+        std::fs::write(&toml_path, toml::to_string(&report).unwrap()).unwrap();
+        let loaded = ServerInfo::from_toml_file(&toml_path).unwrap();
+        assert!(loaded.compatibility_warning().is_some());
+    }
+
+    #[test]
+    fn test_parse_ibstat_with_and_without_link_layer() {
+        // mlx5_0 is from a current ibstat, mlx4_0 from one predating the
+        // "Link layer" and "Active MTU" lines
+        let output = "\
+CA 'mlx5_0'
+\tCA type: MT4129
+\tNumber of ports: 1
+\tFirmware version: 28.39.1002
+\tHardware version: 0
+\tNode GUID: 0xa088c20300e1a2b4
+\tSystem image GUID: 0xa088c20300e1a2b4
+\tPort 1:
+\t\tState: Active
+\t\tPhysical state: LinkUp
+\t\tRate: 400
+\t\tBase lid: 12
+\t\tLMC: 0
+\t\tSM lid: 1
+\t\tCapability mask: 0xa751e848
+\t\tPort GUID: 0xa088c20300e1a2b4
+\t\tLink layer: InfiniBand
+\t\tActive MTU: 4096
+CA 'mlx4_0'
+\tCA type: MT4099
+\tNumber of ports: 2
+\tFirmware version: 2.42.5000
+\tHardware version: 1
+\tNode GUID: 0x0002c90300a1b2c0
+\tSystem image GUID: 0x0002c90300a1b2c3
+\tPort 1:
+\t\tState: Down
+\t\tPhysical state: Polling
+\t\tRate: 10
+\t\tBase lid: 0
+\t\tLMC: 0
+\t\tSM lid: 0
+\t\tCapability mask: 0x02514868
+\t\tPort GUID: 0x0002c90300a1b2c1
+\tPort 2:
+\t\tState: Initializing
+\t\tPhysical state: LinkUp
+\t\tRate: 56
+\t\tBase lid: 0
+\t\tLMC: 0
+\t\tSM lid: 0
+\t\tCapability mask: 0x02514868
+\t\tPort GUID: 0x0002c90300a1b2c2
+";
+
+        let interfaces = ServerInfo::parse_ibstat(output).unwrap();
+        let ports: Vec<_> = interfaces
+            .iter()
+            .map(|ib| {
+                (
+                    ib.name.as_str(),
+                    ib.port,
+                    ib.state.as_str(),
+                    ib.rate.as_str(),
+                    ib.link_layer.as_deref(),
+                    ib.active_mtu.as_deref(),
+                )
+            })
+            .collect();
+        // "Physical state" never overwrites the logical state
+        assert_eq!(
+            ports,
+            vec![
+                (
+                    "mlx5_0",
+                    1,
+                    "Active",
+                    "400",
+                    Some("InfiniBand"),
+                    Some("4096")
+                ),
+                ("mlx4_0", 1, "Down", "10", None, None),
+                ("mlx4_0", 2, "Initializing", "56", None, None),
+            ]
+        );
+    }
+}
@@ -14,7 +14,9 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::domain::{HardwareReport, PublishConfig, PublishError, ReportConfig, ReportError};
+use crate::domain::{
+    Capabilities, HardwareReport, PublishConfig, PublishError, ReportConfig, ReportError,
+};
 use async_trait::async_trait;
 
 /// Primary port - Main interface offered by the hardware reporting domain
@@ -33,6 +35,26 @@ pub trait HardwareReportingService: Send + Sync {
     /// * `Err(ReportError)` - Error occurred during collection
     async fn generate_report(&self, config: ReportConfig) -> Result<HardwareReport, ReportError>;
 
+    /// Generate a hardware report and check it with a caller-supplied validator
+    ///
+    /// # Arguments
+    /// * `config` - Configuration options for report generation
+    /// * `validator` - Returns the list of violations when the report is unacceptable
+    ///
+    /// # Returns
+    /// * `Ok(HardwareReport)` - Report that passed the validator
+    /// * `Err(ReportError::Rejected)` - Violations reported by the validator
+    /// * `Err(ReportError)` - Error occurred during collection
+    async fn generate_and_validate(
+        &self,
+        config: ReportConfig,
+        validator: &(dyn for<'r> Fn(&'r HardwareReport) -> Result<(), Vec<String>> + Send + Sync),
+    ) -> Result<HardwareReport, ReportError> {
+        let report = self.generate_report(config).await?;
+        validator(&report).map_err(ReportError::Rejected)?;
+        Ok(report)
+    }
+
     /// Publish a hardware report to a remote endpoint
     ///
     /// # Arguments
@@ -61,6 +83,18 @@ pub trait HardwareReportingService: Send + Sync {
     /// * `Ok(bool)` - true if sufficient privileges, false otherwise
     /// * `Err(ReportError)` - Error occurred during privilege check
     async fn check_privileges(&self) -> Result<bool, ReportError>;
+
+    /// Report what can be collected on this host without collecting data
+    ///
+    /// # Returns
+    /// * `Ok(Capabilities)` - Adapter, privilege status and subsystem availability
+    /// * `Err(ReportError)` - Error occurred while probing, or the service
+    ///   does not support probing
+    async fn probe_capabilities(&self) -> Result<Capabilities, ReportError> {
+        Err(ReportError::GenerationFailed(
+            "capability probe not supported".to_string(),
+        ))
+    }
 }
 
 /// Primary port - System monitoring interface for real-time hardware monitoring
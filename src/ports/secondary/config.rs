@@ -77,4 +77,8 @@ pub enum OutputFormat {
     /// Both JSON and TOML
     #[default]
     Both,
+    /// OpenMetrics text exposition
+    OpenMetrics,
+    /// Markdown summary for wiki and runbook pages
+    Markdown,
 }
@@ -77,6 +77,33 @@ pub trait FileRepository: Send + Sync {
     /// * `Err(PublishError)` - Error occurred during save
     async fn save_toml(&self, report: &HardwareReport, path: &Path) -> Result<(), PublishError>;
 
+    /// Save hardware report to a file in OpenMetrics text format
+    ///
+    /// # Arguments
+    /// * `report` - The hardware report to save
+    /// * `path` - File path to save to
+    ///
+    /// # Returns
+    /// * `Ok(())` - Report successfully saved
+    /// * `Err(PublishError)` - Error occurred during save
+    async fn save_openmetrics(
+        &self,
+        report: &HardwareReport,
+        path: &Path,
+    ) -> Result<(), PublishError>;
+
+    /// Save hardware report to a file as a Markdown summary
+    ///
+    /// # Arguments
+    /// * `report` - The hardware report to save
+    /// * `path` - File path to save to
+    ///
+    /// # Returns
+    /// * `Ok(())` - Report successfully saved
+    /// * `Err(PublishError)` - Error occurred during save
+    async fn save_markdown(&self, report: &HardwareReport, path: &Path)
+        -> Result<(), PublishError>;
+
     /// Load hardware report from a JSON file
     ///
     /// # Arguments
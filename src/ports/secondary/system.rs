@@ -15,8 +15,11 @@ limitations under the License.
 */
 
 use crate::domain::{
-    BiosInfo, ChassisInfo, CpuInfo, GpuInfo, MemoryInfo, MotherboardInfo, NetworkInfo, NumaNode,
-    StorageInfo, SystemError, SystemInfo,
+    Accelerator, BiosInfo, BmcNetwork, Capabilities, ChassisInfo, CloudInfo, ContainerLimits,
+    ContainerRuntimeInfo, CpuInfo, FirmwareComponent, GpuCgroupAllocation, GpuInfo, KernelError,
+    MemoryInfo, MotherboardInfo, NetworkInfo, NumaNode, Partition, PciDevice, PowerInfo, SelEvent,
+    SensorReading, StorageInfo, SystemError, SystemInfo, TimeSyncInfo, TpmInfo, WatchdogInfo,
+    XidError,
 };
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -25,6 +28,11 @@ use std::collections::HashMap;
 ///
 /// This interface abstracts platform-specific system information collection.
 /// Different adapters can implement this for Linux, macOS, Windows, etc.
+///
+/// Methods after `get_missing_dependencies` are optional capabilities. Their
+/// default implementations report the data as absent (empty, `None` or
+/// `SystemError::NotAvailable`), so adapters only override what their
+/// platform supports.
 #[async_trait]
 pub trait SystemInfoProvider: Send + Sync {
     /// Collect CPU information
@@ -131,4 +139,251 @@ pub trait SystemInfoProvider: Send + Sync {
     /// * `Ok(Vec<String>)` - List of missing commands/tools
     /// * `Err(SystemError)` - Error checking dependencies
     async fn get_missing_dependencies(&self) -> Result<Vec<String>, SystemError>;
+
+    /// Get versions of the external tools used for collection
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, String>)` - Tool name to version mapping
+    /// * `Err(SystemError)` - Error checking tool versions
+    async fn get_tool_versions(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    /// Get failed service units
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Failed unit names (empty without systemd)
+    /// * `Err(SystemError)` - Error listing failed units
+    async fn get_failed_units(&self) -> Result<Vec<String>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get firmware boot mode
+    ///
+    /// # Returns
+    /// * `Ok(String)` - "UEFI" or "Legacy"
+    /// * `Err(SystemError)` - Error detecting boot mode
+    async fn get_boot_mode(&self) -> Result<String, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "boot mode".to_string(),
+        })
+    }
+
+    /// Get Trusted Platform Module presence and version
+    ///
+    /// # Returns
+    /// * `Ok(TpmInfo)` - TPM information (`present: false` when there is none)
+    /// * `Err(SystemError)` - TPM presence cannot be determined
+    async fn get_tpm_info(&self) -> Result<TpmInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "TPM".to_string(),
+        })
+    }
+
+    /// Get whether the kernel's automatic NUMA balancing is enabled
+    ///
+    /// # Returns
+    /// * `Ok(Some(bool))` - Setting from `/proc/sys/kernel/numa_balancing`
+    /// * `Ok(None)` - The kernel does not expose the setting
+    /// * `Err(SystemError)` - Error reading the setting
+    async fn get_numa_balancing(&self) -> Result<Option<bool>, SystemError> {
+        Ok(None)
+    }
+
+    /// Get key BIOS settings under vendor-neutral names
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, String>)` - Normalized settings (empty when no vendor tool is available)
+    /// * `Err(SystemError)` - Error reading BIOS settings
+    async fn get_bios_settings(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    /// Get the OS machine identifier (e.g., `/etc/machine-id`)
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Machine ID assigned by the OS
+    /// * `Err(SystemError)` - No machine ID is available
+    async fn get_os_machine_id(&self) -> Result<String, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "OS machine ID".to_string(),
+        })
+    }
+
+    /// Get partitions and their alignment for each block device
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, Vec<Partition>>)` - Partitions keyed by parent device name
+    /// * `Err(SystemError)` - Error reading partition information
+    async fn get_partitions(&self) -> Result<HashMap<String, Vec<Partition>>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    /// Get recent NVIDIA Xid errors from the kernel log
+    ///
+    /// # Returns
+    /// * `Ok(Vec<XidError>)` - Xid errors grouped by GPU and code
+    /// * `Err(SystemError)` - Error reading the kernel log
+    async fn get_xid_errors(&self) -> Result<Vec<XidError>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get MCE, PCIe AER, disk I/O and thermal errors from the kernel log
+    ///
+    /// # Returns
+    /// * `Ok(Vec<KernelError>)` - Hardware errors in log order
+    /// * `Err(SystemError)` - Error reading the kernel log
+    async fn get_kernel_hardware_errors(&self) -> Result<Vec<KernelError>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get non-GPU accelerators such as FPGAs and inference NPUs
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Accelerator>)` - Accelerators found on the PCI bus
+    /// * `Err(SystemError)` - Error enumerating PCI devices
+    async fn get_accelerators(&self) -> Result<Vec<Accelerator>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get GPUs, NICs, storage controllers and accelerators with no driver bound
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PciDevice>)` - Devices present on the bus but unclaimed
+    /// * `Err(SystemError)` - Error enumerating PCI devices
+    async fn get_unbound_pci_devices(&self) -> Result<Vec<PciDevice>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Detect the container runtime and NVIDIA container toolkit
+    ///
+    /// # Returns
+    /// * `Ok(ContainerRuntimeInfo)` - Runtime in use and toolkit presence
+    /// * `Err(SystemError)` - Error probing container support
+    async fn get_container_runtime(&self) -> Result<ContainerRuntimeInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "container runtime".to_string(),
+        })
+    }
+
+    /// Get cgroup CPU and memory limits when running inside a container
+    ///
+    /// # Returns
+    /// * `Ok(ContainerLimits)` - Container type and its cgroup limits
+    /// * `Err(SystemError)` - Not running in a container
+    async fn get_container_limits(&self) -> Result<ContainerLimits, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "container limits".to_string(),
+        })
+    }
+
+    /// Attribute GPU device nodes to cgroups from their device allowlists
+    ///
+    /// # Returns
+    /// * `Ok(Vec<GpuCgroupAllocation>)` - cgroups granted at least one GPU
+    /// * `Err(SystemError)` - No readable device allowlists (e.g., cgroup v2)
+    async fn get_gpu_cgroup_allocations(&self) -> Result<Vec<GpuCgroupAllocation>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get the BMC network address
+    ///
+    /// # Returns
+    /// * `Ok(BmcNetwork)` - BMC IP and MAC address
+    /// * `Err(SystemError)` - No reachable BMC
+    async fn get_bmc_network(&self) -> Result<BmcNetwork, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "BMC network".to_string(),
+        })
+    }
+
+    /// Get the most recent entries from the BMC System Event Log
+    ///
+    /// # Returns
+    /// * `Ok(Vec<SelEvent>)` - Recent SEL entries, oldest first
+    /// * `Err(SystemError)` - No reachable BMC
+    async fn get_sel_events(&self) -> Result<Vec<SelEvent>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get temperature and fan readings from the BMC
+    ///
+    /// # Returns
+    /// * `Ok(Vec<SensorReading>)` - Current sensor readings
+    /// * `Err(SystemError)` - Sensors not readable from this adapter
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get the BMC firmware inventory over Redfish
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FirmwareComponent>)` - BIOS, BMC, NIC, drive, PSU and CPLD firmware
+    /// * `Err(SystemError)` - Redfish is not configured or the BMC query failed
+    async fn get_firmware_inventory(&self) -> Result<Vec<FirmwareComponent>, SystemError> {
+        Ok(Vec::new())
+    }
+
+    /// Get cloud instance metadata from the provider metadata endpoint
+    ///
+    /// # Returns
+    /// * `Ok(Some(CloudInfo))` - Provider, instance type, region and zone
+    /// * `Ok(None)` - Not running on a supported cloud
+    /// * `Err(SystemError)` - Error setting up the metadata query
+    async fn get_cloud_info(&self) -> Result<Option<CloudInfo>, SystemError> {
+        Ok(None)
+    }
+
+    /// Get clock synchronization state
+    ///
+    /// # Returns
+    /// * `Ok(TimeSyncInfo)` - Sync state from the running time daemon
+    /// * `Err(SystemError)` - No time daemon could be queried
+    async fn get_time_sync(&self) -> Result<TimeSyncInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "time synchronization".to_string(),
+        })
+    }
+
+    /// Get the configured watchdog timer
+    ///
+    /// # Returns
+    /// * `Ok(WatchdogInfo)` - The running watchdog, or the first one found
+    /// * `Err(SystemError)` - No watchdog is present
+    async fn get_watchdog(&self) -> Result<WatchdogInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "watchdog".to_string(),
+        })
+    }
+
+    /// Get instantaneous power draw
+    ///
+    /// # Returns
+    /// * `Ok(PowerInfo)` - Power draw and the source it was read from
+    /// * `Err(SystemError)` - No power source could be read
+    async fn get_power_info(&self) -> Result<PowerInfo, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "power reading".to_string(),
+        })
+    }
+
+    /// Get raw stdout of the commands run so far, keyed by command line
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, String>)` - Recorded output (empty unless capture is enabled)
+    /// * `Err(SystemError)` - Error reading recorded output
+    async fn get_raw_sources(&self) -> Result<HashMap<String, String>, SystemError> {
+        Ok(HashMap::new())
+    }
+
+    /// Probe which subsystems can be collected, without collecting any data
+    ///
+    /// # Returns
+    /// * `Ok(Capabilities)` - Adapter, privilege status and subsystem availability
+    /// * `Err(SystemError)` - Error probing capabilities
+    async fn probe_capabilities(&self) -> Result<Capabilities, SystemError> {
+        Err(SystemError::NotAvailable {
+            resource: "capability probe".to_string(),
+        })
+    }
 }
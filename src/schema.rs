@@ -0,0 +1,826 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! JSON Schema for the report and a validator for serialized reports
+//!
+//! The schema is derived from the report's `Deserialize` impls rather than
+//! written by hand: a tracing deserializer walks every struct, field, option,
+//! sequence and map once and records the shape it was asked for. It therefore
+//! cannot drift from the types, and validating a serialized report against it
+//! catches a field being serialized in a shape the types don't describe.
+//!
+//! Which fields are required is probed the same way: each struct is replayed
+//! with one field left out at a time, and a field is required only if its
+//! `Deserialize` impl rejects the omission, so `#[serde(default)]` fields stay
+//! optional just as they are when a report is loaded.
+
+use crate::ServerInfo;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema (draft 2020-12) describing a serialized [`ServerInfo`]
+///
+/// Each struct is a definition under `$defs`, `Option` fields accept `null`,
+/// fields that may be omitted when loading are not required, unit enums are
+/// a string `enum` of their serialized variant names, and unknown properties
+/// are rejected. `$id` carries the
+/// crate version so consumers can pin to the schema they validated against.
+///
+/// # Returns
+/// * Pretty-printed schema document
+pub fn json_schema() -> String {
+    serde_json::to_string_pretty(&server_info_schema()).expect("schema is valid JSON")
+}
+
+/// [`json_schema`] as a JSON value
+pub(crate) fn server_info_schema() -> Value {
+    schema_for::<ServerInfo>("ServerInfo")
+}
+
+/// Trace the JSON Schema of any type whose `Deserialize` impl the tracer supports
+fn schema_for<'de, T: Deserialize<'de>>(title: &str) -> Value {
+    let mut context = TraceContext::default();
+    let mut root = Value::Null;
+    let trace = |context: &mut TraceContext, schema: &mut Value| {
+        T::deserialize(Tracer { schema, context }).map(drop)
+    };
+    let unsupported =
+        |e: TraceError| format!("{title} uses a shape the schema tracer does not support: {e}");
+
+    // Shapes first, so unsupported ones fail with their own message
+    trace(&mut context, &mut root).unwrap_or_else(|e| panic!("{}", unsupported(e)));
+
+    // Probe until every struct has had each field left out once
+    context.probing = true;
+    loop {
+        context.progressed = false;
+        match trace(&mut context, &mut Value::Null) {
+            Ok(()) if !context.progressed => break,
+            Ok(()) | Err(TraceError::Probed) => {}
+            Err(e) => panic!("{}", unsupported(e)),
+        }
+    }
+
+    // Trace again now that the required fields are known
+    context.probing = false;
+    trace(&mut context, &mut root).unwrap_or_else(|e| panic!("{}", unsupported(e)));
+    let definitions = context.definitions;
+
+    let mut schema = json!({
+        "$schema": SCHEMA_DIALECT,
+        "$id": format!("urn:hardware_report:{title}:{}", env!("CARGO_PKG_VERSION")),
+        "title": title,
+    });
+    if let (Value::Object(schema), Value::Object(root)) = (&mut schema, root) {
+        schema.extend(root);
+        schema.insert("$defs".to_string(), Value::Object(definitions));
+    }
+    schema
+}
+
+/// Check a serialized report against the report schema
+///
+/// # Arguments
+/// * `report` - Report as produced by `serde_json::to_value`
+///
+/// # Returns
+/// * One message per violation, each prefixed with the JSON pointer of the
+///   offending value
+pub fn validate_report_json(report: &Value) -> Result<(), Vec<String>> {
+    let schema = server_info_schema();
+    let mut errors = Vec::new();
+    validate(&schema, &schema, report, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Serialize a report and check it against the report schema
+///
+/// # Arguments
+/// * `report` - Collected server information
+///
+/// # Returns
+/// * One message per violation, see [`validate_report_json`]
+pub fn validate_report(report: &ServerInfo) -> Result<(), Vec<String>> {
+    let value = serde_json::to_value(report).map_err(|e| vec![e.to_string()])?;
+    validate_report_json(&value)
+}
+
+fn validate(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let location = if path.is_empty() { "/" } else { path };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match reference
+            .strip_prefix("#/$defs/")
+            .and_then(|name| root["$defs"].get(name))
+        {
+            Some(target) => validate(root, target, value, path, errors),
+            None => errors.push(format!("{location}: unresolved reference {reference}")),
+        }
+        return;
+    }
+
+    if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+        let matched = alternatives.iter().any(|alternative| {
+            let mut scratch = Vec::new();
+            validate(root, alternative, value, path, &mut scratch);
+            scratch.is_empty()
+        });
+        if !matched {
+            // Report against the non-null alternative, which is what a
+            // present value was meant to match
+            match alternatives
+                .iter()
+                .find(|alternative| alternative.get("type") != Some(&json!("null")))
+            {
+                Some(alternative) => validate(root, alternative, value, path, errors),
+                None => errors.push(format!("{location}: matches no alternative")),
+            }
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        let matches = match expected {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "string" => value.is_string(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!(
+                "{location}: expected {expected}, found {}",
+                json_type(value)
+            ));
+            return;
+        }
+    }
+
+    if let (Some(minimum), Some(number)) = (
+        schema.get("minimum").and_then(Value::as_f64),
+        value.as_f64(),
+    ) {
+        if number < minimum {
+            errors.push(format!("{location}: {number} is below minimum {minimum}"));
+        }
+    }
+
+    if let (Some(allowed), false) = (
+        schema.get("enum").and_then(Value::as_array),
+        value.is_null(),
+    ) {
+        if !allowed.contains(value) {
+            errors.push(format!("{location}: {value} is not one of {allowed:?}"));
+        }
+    }
+
+    if let (Some(items), Some(elements)) = (schema.get("items"), value.as_array()) {
+        for (index, element) in elements.iter().enumerate() {
+            validate(root, items, element, &format!("{path}/{index}"), errors);
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    errors.push(format!("{location}: missing required property '{name}'"));
+                }
+            }
+        }
+        for (name, field) in object {
+            let field_path = format!("{path}/{name}");
+            match (
+                properties.and_then(|p| p.get(name)),
+                schema.get("additionalProperties"),
+            ) {
+                (Some(property), _) => validate(root, property, field, &field_path, errors),
+                (None, Some(Value::Bool(false))) => {
+                    errors.push(format!("{location}: unexpected property '{name}'"))
+                }
+                (None, Some(additional)) if additional.is_object() => {
+                    validate(root, additional, field, &field_path, errors)
+                }
+                (None, _) => {}
+            }
+        }
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Error raised while tracing
+#[derive(Debug)]
+enum TraceError {
+    /// A struct rejected a missing field
+    MissingField(&'static str),
+    /// A probe finished and was recorded; the trace restarts
+    Probed,
+    Custom(String),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::MissingField(field) => write!(f, "missing field `{field}`"),
+            TraceError::Probed => write!(f, "probe recorded"),
+            TraceError::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl de::Error for TraceError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TraceError::Custom(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        TraceError::MissingField(field)
+    }
+}
+
+/// State shared by every tracer over one type
+#[derive(Default)]
+struct TraceContext {
+    definitions: Map<String, Value>,
+    /// Whether structs are being probed for required fields rather than traced
+    probing: bool,
+    /// Per struct, the index of the next field to leave out
+    cursors: HashMap<&'static str, usize>,
+    /// Per struct, the fields its `Deserialize` impl cannot do without
+    required: HashMap<&'static str, HashSet<&'static str>>,
+    /// Whether the current probing pass advanced any cursor
+    progressed: bool,
+}
+
+/// Deserializer that records the requested shape into `schema` instead of
+/// producing data, handing visitors placeholder values
+struct Tracer<'a> {
+    schema: &'a mut Value,
+    context: &'a mut TraceContext,
+}
+
+impl Tracer<'_> {
+    fn primitive<'de, V: Visitor<'de>>(
+        self,
+        schema: Value,
+        visit: impl FnOnce(V) -> Result<V::Value, TraceError>,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        *self.schema = schema;
+        visit(visitor)
+    }
+}
+
+macro_rules! trace_integers {
+    ($($method:ident => $visit:ident($zero:expr), $schema:tt;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.primitive(json!($schema), |v| v.$visit($zero), visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Tracer<'_> {
+    type Error = TraceError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.primitive(json!({}), |v| v.visit_unit(), visitor)
+    }
+
+    trace_integers! {
+        deserialize_i8 => visit_i64(0), {"type": "integer"};
+        deserialize_i16 => visit_i64(0), {"type": "integer"};
+        deserialize_i32 => visit_i64(0), {"type": "integer"};
+        deserialize_i64 => visit_i64(0), {"type": "integer"};
+        deserialize_u8 => visit_u64(0), {"type": "integer", "minimum": 0};
+        deserialize_u16 => visit_u64(0), {"type": "integer", "minimum": 0};
+        deserialize_u32 => visit_u64(0), {"type": "integer", "minimum": 0};
+        deserialize_u64 => visit_u64(0), {"type": "integer", "minimum": 0};
+        deserialize_f32 => visit_f64(0.0), {"type": "number"};
+        deserialize_f64 => visit_f64(0.0), {"type": "number"};
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.primitive(json!({"type": "boolean"}), |v| v.visit_bool(false), visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.primitive(json!({"type": "string"}), |v| v.visit_char(' '), visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.primitive(json!({"type": "string"}), |v| v.visit_str(""), visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let schema = json!({"type": "array", "items": {"type": "integer", "minimum": 0}});
+        self.primitive(schema, |v| v.visit_bytes(&[]), visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut inner = Value::Null;
+        let value = visitor.visit_some(Tracer {
+            schema: &mut inner,
+            context: self.context,
+        })?;
+        *self.schema = json!({"anyOf": [inner, {"type": "null"}]});
+        Ok(value)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.primitive(json!({"type": "null"}), |v| v.visit_unit(), visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let (schema, value, mut slots) = trace_seq(self, 1, visitor)?;
+        *schema = json!({"type": "array", "items": slots.pop().unwrap_or(json!({}))});
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (schema, value, slots) = trace_seq(self, len, visitor)?;
+        *schema = json!({
+            "type": "array",
+            "prefixItems": slots,
+            "minItems": len,
+            "maxItems": len,
+        });
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut map = TraceMap {
+            key_done: false,
+            value: Value::Null,
+            context: self.context,
+        };
+        let value = visitor.visit_map(&mut map)?;
+        *self.schema = json!({"type": "object", "additionalProperties": map.value});
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let Tracer { schema, context } = self;
+        if context.probing {
+            return probe_struct(name, fields, visitor, context);
+        }
+        *schema = json!({"$ref": format!("#/$defs/{name}")});
+
+        let mut seq = TraceSeq {
+            slots: Vec::new(),
+            remaining: fields.len(),
+            context,
+        };
+        let value = visitor.visit_seq(&mut seq)?;
+        // Aliases are listed in `fields` but never read, so names and
+        // schemas would no longer line up
+        if seq.slots.len() != fields.len() {
+            return Err(de::Error::custom(format!("{name} has aliased fields")));
+        }
+
+        let required_fields = seq.context.required.get(name);
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for (field, field_schema) in fields.iter().zip(seq.slots) {
+            if required_fields.is_some_and(|required| required.contains(field)) {
+                required.push(json!(field));
+            }
+            properties.insert(field.to_string(), field_schema);
+        }
+        seq.context.definitions.insert(
+            name.to_string(),
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            }),
+        );
+        Ok(value)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.schema = json!({"type": "string", "enum": variants});
+        visitor.visit_enum(TraceEnum {
+            variant: variants.first().copied().unwrap_or_default(),
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// Feed a struct every field but the next one under probe, recording whether
+/// its `Deserialize` impl accepts the omission
+fn probe_struct<'de, V: Visitor<'de>>(
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+    context: &mut TraceContext,
+) -> Result<V::Value, TraceError> {
+    let omitted = fields
+        .get(context.cursors.get(name).copied().unwrap_or_default())
+        .copied();
+
+    let mut map = ProbeMap {
+        keys: fields
+            .iter()
+            .copied()
+            .filter(|field| Some(*field) != omitted)
+            .collect(),
+        next: 0,
+        context,
+    };
+    let result = visitor.visit_map(&mut map);
+
+    // A nested probe failing first leaves this one undecided for the next pass
+    let context = map.context;
+    let result = match result {
+        Err(TraceError::MissingField(field)) if Some(field) == omitted => {
+            context.required.entry(name).or_default().insert(field);
+            Err(TraceError::Probed)
+        }
+        Err(TraceError::Probed) => return Err(TraceError::Probed),
+        result => result,
+    };
+    if omitted.is_some() {
+        *context.cursors.entry(name).or_default() += 1;
+        context.progressed = true;
+    }
+    result
+}
+
+/// Visit `len` traced elements, returning the tracer's slot and each element's schema
+fn trace_seq<'a, 'de, V: Visitor<'de>>(
+    tracer: Tracer<'a>,
+    len: usize,
+    visitor: V,
+) -> Result<(&'a mut Value, V::Value, Vec<Value>), TraceError> {
+    let mut seq = TraceSeq {
+        slots: Vec::new(),
+        remaining: len,
+        context: tracer.context,
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    Ok((tracer.schema, value, seq.slots))
+}
+
+/// Sequence yielding `remaining` traced elements, one schema slot each
+struct TraceSeq<'a> {
+    slots: Vec<Value>,
+    remaining: usize,
+    context: &'a mut TraceContext,
+}
+
+impl<'de> SeqAccess<'de> for TraceSeq<'_> {
+    type Error = TraceError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        let mut slot = Value::Null;
+        let value = seed.deserialize(Tracer {
+            schema: &mut slot,
+            context: self.context,
+        })?;
+        self.slots.push(slot);
+        Ok(Some(value))
+    }
+}
+
+/// Map yielding a single traced entry; only the value shape is recorded
+struct TraceMap<'a> {
+    key_done: bool,
+    value: Value,
+    context: &'a mut TraceContext,
+}
+
+impl<'de> MapAccess<'de> for TraceMap<'_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if self.key_done {
+            return Ok(None);
+        }
+        self.key_done = true;
+        let mut key = Value::Null;
+        seed.deserialize(Tracer {
+            schema: &mut key,
+            context: self.context,
+        })
+        .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(Tracer {
+            schema: &mut self.value,
+            context: self.context,
+        })
+    }
+}
+
+/// Struct body naming each of `keys` once, with traced placeholder values
+struct ProbeMap<'a> {
+    keys: Vec<&'static str>,
+    next: usize,
+    context: &'a mut TraceContext,
+}
+
+impl<'de> MapAccess<'de> for ProbeMap<'_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(key) = self.keys.get(self.next) else {
+            return Ok(None);
+        };
+        self.next += 1;
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(Tracer {
+            schema: &mut Value::Null,
+            context: self.context,
+        })
+    }
+}
+
+/// Enum access selecting the first variant; only unit variants are supported
+struct TraceEnum {
+    variant: &'static str,
+}
+
+impl<'de> EnumAccess<'de> for TraceEnum {
+    type Error = TraceError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TraceEnum {
+    type Error = TraceError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        _seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        Err(de::Error::custom(
+            "data-carrying enum variants are not supported",
+        ))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom(
+            "data-carrying enum variants are not supported",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom(
+            "data-carrying enum variants are not supported",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> Value {
+        serde_json::to_value(crate::test_support::sample_server_info()).unwrap()
+    }
+
+    #[test]
+    fn test_schema_describes_report() {
+        let schema = server_info_schema();
+        assert_eq!(schema["$ref"], "#/$defs/ServerInfo");
+        assert_eq!(
+            schema["$defs"]["ServerInfo"]["properties"]["hostname"]["type"],
+            "string"
+        );
+        assert_eq!(
+            schema["$defs"]["ServerInfo"]["properties"]["detected_hostname"]["anyOf"][1]["type"],
+            "null"
+        );
+        assert!(validate_report_json(&sample_report()).is_ok());
+    }
+
+    #[test]
+    fn test_schema_accepts_pre_versioning_sample() {
+        // The checked-in sample predates `report_version` and `machine_id`,
+        // which load with their defaults and so must not be required
+        let raw: Value =
+            serde_json::from_str(include_str!("../MYQQGPTJ6J_hardware_report.json")).unwrap();
+        assert!(raw.get("report_version").is_none());
+        assert_eq!(validate_report_json(&raw), Ok(()));
+    }
+
+    #[test]
+    fn test_schema_nullable_options_and_enums() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        #[serde(rename_all = "kebab-case")]
+        enum Airflow {
+            FrontToRear,
+            RearToFront,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Chassis {
+            serial: String,
+            airflow: Airflow,
+            slots: Option<u32>,
+            tags: Vec<String>,
+            #[serde(default)]
+            rack: String,
+        }
+
+        let schema = schema_for::<Chassis>("Chassis");
+        let chassis = &schema["$defs"]["Chassis"];
+        assert_eq!(
+            chassis["properties"]["airflow"],
+            json!({"type": "string", "enum": ["front-to-rear", "rear-to-front"]})
+        );
+        assert_eq!(
+            chassis["properties"]["slots"],
+            json!({"anyOf": [{"type": "integer", "minimum": 0}, {"type": "null"}]})
+        );
+        assert_eq!(
+            chassis["properties"]["tags"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(chassis["required"], json!(["serial", "airflow", "tags"]));
+        assert_eq!(
+            schema["$id"],
+            format!("urn:hardware_report:Chassis:{}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Renamed has aliased fields")]
+    fn test_schema_rejects_aliased_fields() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Renamed {
+            #[serde(alias = "old_name")]
+            name: String,
+            size: u64,
+        }
+
+        schema_for::<Renamed>("Renamed");
+    }
+
+    #[test]
+    fn test_schema_violations_rejected() {
+        let mut report = sample_report();
+        report["hostname"] = json!(42);
+        report["summary"]["cpu_topology"]["total_cores"] = json!("many");
+        report["hardware"]["memory"]
+            .as_object_mut()
+            .unwrap()
+            .remove("total");
+        report["network"]["interfaces"][0]["unexpected"] = json!(true);
+
+        let errors = validate_report_json(&report).unwrap_err();
+        assert!(errors.contains(&"/hostname: expected string, found integer".to_string()));
+        assert!(errors.contains(
+            &"/summary/cpu_topology/total_cores: expected integer, found string".to_string()
+        ));
+        assert!(errors.contains(&"/hardware/memory: missing required property 'total'".to_string()));
+        assert!(
+            errors.contains(&"/network/interfaces/0: unexpected property 'unexpected'".to_string())
+        );
+        assert_eq!(errors.len(), 4);
+    }
+}
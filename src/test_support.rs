@@ -0,0 +1,24 @@
+/*
+Copyright 2024 San Francisco Compute Company
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Fixtures shared by unit tests across modules
+
+use crate::ServerInfo;
+
+/// Load the checked-in sample report
+pub(crate) fn sample_server_info() -> ServerInfo {
+    serde_json::from_str(include_str!("../MYQQGPTJ6J_hardware_report.json")).unwrap()
+}